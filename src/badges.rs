@@ -0,0 +1,68 @@
+//! Get the badges of a crate, to paste into a readme that is not generated
+//!
+//! The crates.io, docs.rs and license badges point to the pages of the crate, so they are only
+//! added for published crates. The license badge of an unpublished crate, and the MSRV badge, are
+//! static badges from shields.io.
+
+use cargo_readme::Manifest;
+
+/// Get the badges of the crate of `manifest`
+///
+/// The badges defined in `Cargo.toml` come after the crates.io, docs.rs, license and MSRV badges.
+pub fn badges(manifest: &Manifest) -> Vec<String> {
+    let name = &manifest.name;
+    let mut badges = Vec::new();
+
+    if manifest.publish {
+        badges.push(format!(
+            "[![Crates.io](https://img.shields.io/crates/v/{0}.svg)](https://crates.io/crates/{0})",
+            name
+        ));
+        badges.push(format!(
+            "[![Docs.rs](https://docs.rs/{0}/badge.svg)](https://docs.rs/{0})",
+            name
+        ));
+    }
+
+    match manifest.license {
+        Some(_) if manifest.publish => badges.push(format!(
+            "[![License](https://img.shields.io/crates/l/{0}.svg)](https://crates.io/crates/{0})",
+            name
+        )),
+        Some(ref license) => badges.push(format!(
+            "![License](https://img.shields.io/badge/license-{}-blue.svg)",
+            badge_text(license)
+        )),
+        _ => {}
+    }
+
+    if let Some(ref rust_version) = manifest.rust_version {
+        badges.push(format!(
+            "![MSRV](https://img.shields.io/badge/MSRV-{}-blue.svg)",
+            badge_text(rust_version)
+        ));
+    }
+
+    badges.extend(manifest.badges.iter().cloned());
+    badges
+}
+
+/// Escape `text` for the URL of a static shields.io badge, where `-` separates the label, the
+/// message and the color
+fn badge_text(text: &str) -> String {
+    text.replace('-', "--")
+        .replace('_', "__")
+        .replace(' ', "%20")
+        .replace('/', "%2F")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::badge_text;
+
+    #[test]
+    fn escape_badge_text() {
+        assert_eq!(badge_text("MIT OR Apache-2.0"), "MIT%20OR%20Apache--2.0");
+        assert_eq!(badge_text("MIT/Apache_2"), "MIT%2FApache__2");
+    }
+}
@@ -178,10 +178,35 @@ pub fn maintenance(attrs: Attrs) -> String {
     )
 }
 
-fn percent_encode(input: &str) -> pe::PercentEncode {
+pub fn keyword(keyword: &str) -> String {
+    format!(
+        "[![{keyword}](https://img.shields.io/badge/keyword-{badge}-blue.svg)]\
+         (https://crates.io/keywords/{url})",
+        keyword = keyword,
+        badge = shields_escape(keyword),
+        url = keyword
+    )
+}
+
+pub fn category(category: &str) -> String {
+    format!(
+        "[![{category}](https://img.shields.io/badge/category-{badge}-blue.svg)]\
+         (https://crates.io/categories/{url})",
+        category = category,
+        badge = shields_escape(category),
+        url = category
+    )
+}
+
+fn percent_encode(input: &str) -> pe::PercentEncode<'_> {
     pe::utf8_percent_encode(input, pe::NON_ALPHANUMERIC)
 }
 
+/// Escape text for the static badge path of shields.io, where `-` and `_` are separators
+fn shields_escape(input: &str) -> String {
+    input.replace('-', "--").replace('_', "__")
+}
+
 fn badge_service_short_name(service: &str) -> &'static str {
     match service {
         "github" => "gh",
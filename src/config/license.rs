@@ -0,0 +1,77 @@
+//! Detect the license type from a license file
+
+use std::fs;
+use std::path::Path;
+
+use crate::log;
+
+/// Known license texts, matched in order against the license file contents
+///
+/// Each entry is the SPDX identifier followed by phrases that must all be present in the file.
+const KNOWN_LICENSES: &[(&str, &[&str])] = &[
+    ("MIT", &["Permission is hereby granted, free of charge"]),
+    ("Apache-2.0", &["Apache License", "Version 2.0"]),
+    (
+        "LGPL-3.0",
+        &["GNU LESSER GENERAL PUBLIC LICENSE", "Version 3"],
+    ),
+    (
+        "LGPL-2.1",
+        &["GNU LESSER GENERAL PUBLIC LICENSE", "Version 2.1"],
+    ),
+    (
+        "AGPL-3.0",
+        &["GNU AFFERO GENERAL PUBLIC LICENSE", "Version 3"],
+    ),
+    ("GPL-3.0", &["GNU GENERAL PUBLIC LICENSE", "Version 3"]),
+    ("GPL-2.0", &["GNU GENERAL PUBLIC LICENSE", "Version 2"]),
+    ("MPL-2.0", &["Mozilla Public License", "2.0"]),
+    ("ISC", &["ISC License"]),
+    ("Unlicense", &["This is free and unencumbered software"]),
+];
+
+/// Try to detect the license type of the given file
+///
+/// Returns `None` if the file could not be read or the license is not recognized.
+pub fn detect(license_file: &Path) -> Option<&'static str> {
+    log::read(license_file);
+    let content = fs::read_to_string(license_file).ok()?;
+    detect_from_str(&content)
+}
+
+fn detect_from_str(content: &str) -> Option<&'static str> {
+    KNOWN_LICENSES
+        .iter()
+        .find(|(_, phrases)| phrases.iter().all(|phrase| content.contains(phrase)))
+        .map(|(name, _)| *name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::detect_from_str;
+
+    #[test]
+    fn detect_mit() {
+        let content = "MIT License\n\nPermission is hereby granted, free of charge, to any person";
+        assert_eq!(Some("MIT"), detect_from_str(content));
+    }
+
+    #[test]
+    fn detect_apache() {
+        let content = "                                 Apache License\n\
+                       Version 2.0, January 2004";
+        assert_eq!(Some("Apache-2.0"), detect_from_str(content));
+    }
+
+    #[test]
+    fn detect_lgpl_before_gpl() {
+        let content = "GNU LESSER GENERAL PUBLIC LICENSE\nVersion 3, 29 June 2007\n\
+                       GNU GENERAL PUBLIC LICENSE";
+        assert_eq!(Some("LGPL-3.0"), detect_from_str(content));
+    }
+
+    #[test]
+    fn detect_unknown() {
+        assert_eq!(None, detect_from_str("All rights reserved."));
+    }
+}
@@ -0,0 +1,46 @@
+//! Settings of the checks of `cargo readme lint`, set in `[package.metadata.readme.lint]`
+
+use serde::Deserialize;
+
+/// How a problem found in the readme is reported
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Reported, and the check fails
+    Error,
+    /// Reported, without failing the check
+    Warning,
+    /// Not reported
+    Allow,
+}
+
+/// Settings of the checks of the readme
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct LintConfig {
+    /// Headings of the sections the readme must have, like `Usage` or `License`
+    pub required_sections: Vec<String>,
+    /// Severity of a missing required section
+    pub missing_section: Severity,
+    /// Severity of a relative link to a file that does not exist
+    pub broken_link: Severity,
+    /// Severity of a heading found several times
+    pub duplicate_heading: Severity,
+    /// Severity of a code block without language
+    pub fence_without_language: Severity,
+    /// Severity of an empty readme
+    pub empty: Severity,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        LintConfig {
+            required_sections: Vec::new(),
+            missing_section: Severity::Error,
+            broken_link: Severity::Error,
+            duplicate_heading: Severity::Warning,
+            fence_without_language: Severity::Warning,
+            empty: Severity::Error,
+        }
+    }
+}
@@ -6,79 +6,366 @@ use std::fs::File;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 
-use toml;
-
 use super::badges;
+use super::license;
+use super::lint::LintConfig;
+use super::profile::Profile;
+use super::project;
+use crate::log;
+use crate::readme::DiagramBlocks;
+use crate::Error;
 
 /// Try to get manifest info from Cargo.toml
-pub fn get_manifest(project_root: &Path) -> Result<Manifest, String> {
-    let mut cargo_toml = File::open(project_root.join("Cargo.toml"))
-        .map_err(|e| format!("Could not read Cargo.toml: {}", e))?;
+pub fn get_manifest(project_root: &Path) -> Result<Manifest, Error> {
+    let path = project_root.join("Cargo.toml");
+    log::read(&path);
+    let mut cargo_toml = File::open(&path).map_err(|e| Error::io(&path, e))?;
 
     let buf = {
         let mut buf = String::new();
         cargo_toml
             .read_to_string(&mut buf)
-            .map_err(|e| format!("{}", e))?;
+            .map_err(|e| Error::io(&path, e))?;
         buf
     };
 
-    let cargo_toml: CargoToml = toml::from_str(&buf).map_err(|e| format!("{}", e))?;
+    parse(&buf, Some(project_root))
+}
+
+/// Get the manifest info from the text of Cargo.toml, without reading any file
+///
+/// The files of the crate are not looked for: a crate without `[lib]` section is taken for a
+/// library unless it has `[[bin]]` sections, and the license file is labelled with the
+/// `license-label` of `[package.metadata.readme]`, or its name.
+pub fn parse_manifest(cargo_toml: &str) -> Result<Manifest, Error> {
+    parse(cargo_toml, None)
+}
+
+/// Parse the text of the Cargo.toml of `project_root`, whose files are looked for when it is given
+fn parse(buf: &str, project_root: Option<&Path>) -> Result<Manifest, Error> {
+    let path = project_root.map_or(PathBuf::from("Cargo.toml"), |root| root.join("Cargo.toml"));
+    let parse_error = |e: toml::de::Error| {
+        Error::ManifestParse(match project_root {
+            Some(project_root) => parse_error(project_root, e),
+            None => format!("Could not parse Cargo.toml: {}", e.to_string().trim_end()),
+        })
+    };
+
+    let sections: CargoTomlSections = toml::from_str(buf).map_err(parse_error)?;
+    if sections.package.is_none() {
+        if sections.workspace.is_some() {
+            return Err(Error::ManifestParse(format!(
+                "`{}` is a virtual manifest, use `--workspace` to generate the README of every \
+                 member or `--package NAME` to select one",
+                path.to_string_lossy()
+            )));
+        }
+        return Err(Error::ManifestParse(
+            "Missing `[package]` section in Cargo.toml".to_owned(),
+        ));
+    }
+
+    let cargo_toml: CargoToml = toml::from_str(buf).map_err(parse_error)?;
 
-    let manifest = Manifest::new(cargo_toml);
+    let manifest = Manifest::new(project_root, cargo_toml);
 
     Ok(manifest)
 }
 
+/// Get the message of an error parsing the `Cargo.toml` of `project_root`
+///
+/// The message of `toml` tells the line of the error, like `TOML parse error at line 3`.
+pub(crate) fn parse_error(project_root: &Path, error: toml::de::Error) -> String {
+    format!(
+        "Could not parse '{}': {}",
+        project_root.join("Cargo.toml").to_string_lossy(),
+        error.to_string().trim_end()
+    )
+}
+
+/// Information about the crate, read from its `Cargo.toml`
+///
+/// More fields may be added, so the manifest is built with [`get_manifest`], [`parse_manifest`] or
+/// [`Manifest::with_name`].
+///
+/// ```
+/// use cargo_readme::{parse_manifest, ManifestLib};
+///
+/// let cargo = parse_manifest(
+///     r#"
+/// [package]
+/// name = "tool"
+/// version = "1.0.0"
+///
+/// [[bin]]
+/// name = "tool"
+/// "#,
+/// )?;
+/// let bins: Vec<&ManifestLib> = cargo.bin.iter().collect();
+/// assert_eq!(bins[0].name.as_deref(), Some("tool"));
+/// # Ok::<(), cargo_readme::Error>(())
+/// ```
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct Manifest {
     pub name: String,
+    pub description: Option<String>,
+    /// License of the crate, from the `license` field, or the label of the `license-file`, like
+    /// `MIT`
     pub license: Option<String>,
+    /// Path of the `license-file`, which the license links to, when there is no `license` field
+    pub license_file: Option<String>,
     pub lib: Option<ManifestLib>,
     pub bin: Vec<ManifestLib>,
+    pub example: Vec<ManifestLib>,
+    /// Alternative file to read the docs from, set in `[package.metadata.readme]`
+    pub entrypoint: Option<PathBuf>,
+    /// Directory of a facade crate to read the docs from, set in `[package.metadata.readme]`
+    pub facade: Option<PathBuf>,
+    /// Whether the library is a procedural macro crate
+    pub proc_macro: bool,
+    /// Whether the crate has a library target, either declared or at `src/lib.rs`
+    pub has_lib: bool,
     pub badges: Vec<String>,
+    pub keywords: Vec<String>,
+    pub categories: Vec<String>,
     pub version: String,
+    /// Minimum supported Rust version, from the `rust-version` field
+    pub rust_version: Option<String>,
+    /// Whether the crate can be published, `false` when `publish = false` or `publish = []`
+    pub publish: bool,
+    pub repository: Option<String>,
+    /// Branch of the repository that relative links point to, set in `[package.metadata.readme]`
+    pub repository_branch: Option<String>,
+    /// Headings of the sections to remove from the readme, set in `[package.metadata.readme]`
+    pub exclude_sections: Vec<String>,
+    /// What to do with the mermaid diagrams and the display math, set in
+    /// `[package.metadata.readme]`
+    pub diagrams: Option<DiagramBlocks>,
+    /// Settings of the checks of the readme, set in `[package.metadata.readme.lint]`
+    pub lint: LintConfig,
+    /// Generation profiles by name, set in `[package.metadata.readme.profiles]`
+    pub profiles: BTreeMap<String, Profile>,
 }
 
 impl Manifest {
-    fn new(cargo_toml: CargoToml) -> Manifest {
+    /// Manifest of a crate read without `Cargo.toml`, knowing only its name
+    pub fn with_name(name: &str) -> Manifest {
+        Manifest {
+            name: name.to_owned(),
+            description: None,
+            license: None,
+            license_file: None,
+            lib: None,
+            bin: Vec::new(),
+            example: Vec::new(),
+            entrypoint: None,
+            facade: None,
+            proc_macro: false,
+            has_lib: true,
+            badges: Vec::new(),
+            keywords: Vec::new(),
+            categories: Vec::new(),
+            version: String::new(),
+            rust_version: None,
+            publish: true,
+            repository: None,
+            repository_branch: None,
+            exclude_sections: Vec::new(),
+            diagrams: None,
+            lint: LintConfig::default(),
+            profiles: BTreeMap::new(),
+        }
+    }
+
+    /// Get the license as written in the readme, a link to the `license_file` when there is one,
+    /// like `[MIT](LICENSE)`
+    pub fn license_link(&self) -> Option<String> {
+        let license = self.license.as_ref()?;
+        match self.license_file {
+            Some(ref license_file) => Some(format!("[{}]({})", license, license_file)),
+            None => Some(license.clone()),
+        }
+    }
+
+    fn new(project_root: Option<&Path>, cargo_toml: CargoToml) -> Manifest {
+        let has_lib = cargo_toml.lib.is_some()
+            || match project_root {
+                Some(project_root) => project_root.join("src/lib.rs").is_file(),
+                None => cargo_toml.bin.is_none(),
+            };
+        let proc_macro = cargo_toml
+            .lib
+            .as_ref()
+            .and_then(|lib| lib.proc_macro)
+            .unwrap_or(false);
+        let readme_config = cargo_toml.package.readme_config();
+        let entrypoint = readme_config
+            .and_then(|r| r.entrypoint.as_ref())
+            .map(PathBuf::from);
+        let facade = readme_config
+            .and_then(|r| r.facade.as_ref())
+            .map(PathBuf::from);
+        let repository_branch = readme_config.and_then(|r| r.repository_branch.clone());
+        let exclude_sections = readme_config
+            .and_then(|r| r.exclude_sections.clone())
+            .unwrap_or_default();
+        let diagrams = readme_config.and_then(|r| r.diagrams);
+        let lint = readme_config
+            .and_then(|r| r.lint.clone())
+            .unwrap_or_default();
+        let profiles = readme_config
+            .and_then(|r| r.profiles.clone())
+            .unwrap_or_default();
+        let license_file = match cargo_toml.package.license {
+            Some(_) => None,
+            None => cargo_toml.package.license_file.clone(),
+        };
+        let license = cargo_toml
+            .package
+            .license
+            .clone()
+            .or_else(|| license_label(project_root, &cargo_toml.package));
+        let bin = cargo_toml
+            .bin
+            .map(|bin_vec| {
+                bin_vec
+                    .into_iter()
+                    .map(|bin| {
+                        let default_path = default_bin_path(
+                            project_root,
+                            &cargo_toml.package.name,
+                            bin.name.as_deref(),
+                        );
+                        ManifestLib::from_cargo_toml(bin, || default_path)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let example = cargo_toml
+            .example
+            .map(|example_vec| {
+                example_vec
+                    .into_iter()
+                    .map(|example| {
+                        let default_path =
+                            default_example_path(project_root, example.name.as_deref());
+                        ManifestLib::from_cargo_toml(example, || default_path)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
         Manifest {
             name: cargo_toml.package.name,
-            license: cargo_toml.package.license,
-            lib: cargo_toml.lib.map(|lib| ManifestLib::from_cargo_toml(lib)),
-            bin: cargo_toml
-                .bin
-                .map(|bin_vec| {
-                    bin_vec
-                        .into_iter()
-                        .map(|bin| ManifestLib::from_cargo_toml(bin))
-                        .collect()
-                })
-                .unwrap_or_default(),
-            badges: cargo_toml
-                .badges
-                .map(|b| process_badges(b))
-                .unwrap_or_default(),
+            description: cargo_toml.package.description,
+            license,
+            license_file,
+            lib: cargo_toml
+                .lib
+                .map(|lib| ManifestLib::from_cargo_toml(lib, || PathBuf::from("src/lib.rs"))),
+            bin,
+            example,
+            entrypoint,
+            facade,
+            proc_macro,
+            has_lib,
+            badges: cargo_toml.badges.map(process_badges).unwrap_or_default(),
+            keywords: cargo_toml.package.keywords.unwrap_or_default(),
+            categories: cargo_toml.package.categories.unwrap_or_default(),
             version: cargo_toml.package.version,
+            // an inherited version, like `rust-version.workspace = true`, is not known here
+            rust_version: cargo_toml
+                .package
+                .rust_version
+                .as_ref()
+                .and_then(toml::Value::as_str)
+                .map(str::to_owned),
+            publish: match cargo_toml.package.publish {
+                Some(CargoTomlPublish::Enabled(enabled)) => enabled,
+                Some(CargoTomlPublish::Registries(registries)) => !registries.is_empty(),
+                None => true,
+            },
+            repository_branch,
+            repository: cargo_toml.package.repository,
+            exclude_sections,
+            diagrams,
+            lint,
+            profiles,
         }
     }
 }
 
+/// A target of the crate, its library, a binary or an example
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct ManifestLib {
+    pub name: Option<String>,
     pub path: PathBuf,
     pub doc: bool,
 }
 
 impl ManifestLib {
-    fn from_cargo_toml(lib: CargoTomlLib) -> Self {
+    fn from_cargo_toml<F: FnOnce() -> PathBuf>(lib: CargoTomlLib, default_path: F) -> Self {
         ManifestLib {
-            path: PathBuf::from(lib.path),
+            name: lib.name,
+            path: lib.path.map(PathBuf::from).unwrap_or_else(default_path),
             doc: lib.doc.unwrap_or(true),
         }
     }
 }
 
+/// Infer the path of a `[[bin]]` target that does not declare one, the same way cargo does
+fn default_bin_path(
+    project_root: Option<&Path>,
+    package_name: &str,
+    name: Option<&str>,
+) -> PathBuf {
+    match name {
+        Some(name) => discovered_path(project_root, project::bin_paths(package_name, name)),
+        None => PathBuf::from("src/main.rs"),
+    }
+}
+
+/// Infer the path of an `[[example]]` target that does not declare one, the same way cargo does
+fn default_example_path(project_root: Option<&Path>, name: Option<&str>) -> PathBuf {
+    discovered_path(
+        project_root,
+        project::example_paths(name.unwrap_or_default()),
+    )
+}
+
+/// Get the first of the `paths` of a target found in `project_root`, or the first one when none is
+/// found, or the files of the crate are not looked for
+fn discovered_path(project_root: Option<&Path>, mut paths: Vec<PathBuf>) -> PathBuf {
+    match paths
+        .iter()
+        .position(|path| project_root.is_some_and(|root| root.join(path).is_file()))
+    {
+        Some(index) => paths.swap_remove(index),
+        None => paths.swap_remove(0),
+    }
+}
+
+/// Get the label of the license from the `license-file` field
+///
+/// The license type is detected from the file contents, unless a label is set in
+/// `[package.metadata.readme]`. If neither is available, the file name is used as label.
+fn license_label(project_root: Option<&Path>, package: &CargoTomlPackage) -> Option<String> {
+    let license_file = package.license_file.as_ref()?;
+    let label = package
+        .readme_config()
+        .and_then(|r| r.license_label.clone())
+        .or_else(|| {
+            project_root
+                .and_then(|root| license::detect(&root.join(license_file)))
+                .map(str::to_owned)
+        })
+        .unwrap_or_else(|| license_file.clone());
+
+    Some(label)
+}
+
 fn process_badges(badges: BTreeMap<String, BTreeMap<String, String>>) -> Vec<String> {
     let mut b: Vec<(u16, _)> = badges
         .into_iter()
@@ -97,20 +384,28 @@ fn process_badges(badges: BTreeMap<String, BTreeMap<String, String>>) -> Vec<Str
                 Some((8, badges::is_it_maintained_open_issues(attrs)))
             }
             "maintenance" => Some((9, badges::maintenance(attrs))),
-            _ => return None,
+            _ => None,
         })
         .collect();
 
-    b.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    b.sort_unstable_by_key(|(order, _)| *order);
     b.into_iter().map(|(_, badge)| badge).collect()
 }
 
+/// Cargo.toml top level sections, used to detect virtual manifests
+#[derive(Clone, Deserialize)]
+struct CargoTomlSections {
+    pub package: Option<toml::Value>,
+    pub workspace: Option<toml::Value>,
+}
+
 /// Cargo.toml crate information
 #[derive(Clone, Deserialize)]
 struct CargoToml {
     pub package: CargoTomlPackage,
     pub lib: Option<CargoTomlLib>,
     pub bin: Option<Vec<CargoTomlLib>>,
+    pub example: Option<Vec<CargoTomlLib>>,
     pub badges: Option<BTreeMap<String, BTreeMap<String, String>>>,
 }
 
@@ -118,13 +413,61 @@ struct CargoToml {
 #[derive(Clone, Deserialize)]
 struct CargoTomlPackage {
     pub name: String,
+    pub description: Option<String>,
     pub license: Option<String>,
+    #[serde(rename = "license-file")]
+    pub license_file: Option<String>,
     pub version: String,
+    #[serde(rename = "rust-version")]
+    pub rust_version: Option<toml::Value>,
+    pub repository: Option<String>,
+    pub keywords: Option<Vec<String>>,
+    pub categories: Option<Vec<String>>,
+    pub publish: Option<CargoTomlPublish>,
+    pub metadata: Option<CargoTomlMetadata>,
+}
+
+/// Cargo.toml `publish` field, either a boolean or a list of registries
+#[derive(Clone, Deserialize)]
+#[serde(untagged)]
+enum CargoTomlPublish {
+    Enabled(bool),
+    Registries(Vec<String>),
+}
+
+impl CargoTomlPackage {
+    /// Get the `[package.metadata.readme]` section, if present
+    fn readme_config(&self) -> Option<&CargoTomlReadme> {
+        self.metadata.as_ref().and_then(|m| m.readme.as_ref())
+    }
+}
+
+/// Cargo.toml crate package metadata
+#[derive(Clone, Deserialize)]
+struct CargoTomlMetadata {
+    pub readme: Option<CargoTomlReadme>,
+}
+
+/// Cargo.toml `[package.metadata.readme]` configuration
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct CargoTomlReadme {
+    pub license_label: Option<String>,
+    pub entrypoint: Option<String>,
+    pub facade: Option<String>,
+    pub repository_branch: Option<String>,
+    pub exclude_sections: Option<Vec<String>>,
+    pub diagrams: Option<DiagramBlocks>,
+    pub lint: Option<LintConfig>,
+    pub profiles: Option<BTreeMap<String, Profile>>,
 }
 
 /// Cargo.toml crate lib information
 #[derive(Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 struct CargoTomlLib {
-    pub path: String,
+    pub name: Option<String>,
+    pub path: Option<String>,
+    pub proc_macro: Option<bool>,
     pub doc: Option<bool>,
 }
@@ -1,6 +1,14 @@
-mod badges;
+pub mod badges;
+mod license;
+mod lint;
 mod manifest;
+mod profile;
 pub mod project;
+mod workspace;
 
+pub use self::lint::{LintConfig, Severity};
 pub use self::manifest::get_manifest;
-pub use self::manifest::Manifest;
+pub use self::manifest::parse_manifest;
+pub use self::manifest::{Manifest, ManifestLib};
+pub use self::profile::Profile;
+pub use self::workspace::get_workspace;
@@ -0,0 +1,20 @@
+//! Named generation profiles, set in `[package.metadata.readme.profiles.NAME]`
+
+use serde::Deserialize;
+
+/// Settings of a generation profile, like the README for GitHub or the page of a website
+///
+/// The options not covered by a setting of their own are given as command line arguments, in
+/// `args`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct Profile {
+    /// Template file, like `--template`
+    pub template: Option<String>,
+    /// Output file, like `--output`
+    pub output: Option<String>,
+    /// Format of the output, like `--output-format`
+    pub output_format: Option<String>,
+    /// Other command line arguments, like `["--no-badges", "--front-matter", "yaml"]`
+    pub args: Vec<String>,
+}
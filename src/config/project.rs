@@ -1,15 +1,19 @@
 use std::env;
 use std::path::{Path, PathBuf};
 
-use crate::config::manifest::{Manifest, ManifestLib};
+use crate::config::manifest::{get_manifest, Manifest, ManifestLib};
+use crate::config::workspace::get_workspace;
+use crate::log;
+use crate::Error;
 
-/// Get the project root from given path or defaults to current directory
+/// Get the project root from given path or defaults to the project of the current directory
 ///
 /// The given path is appended to the current directory if is a relative path, otherwise it is used
-/// as is. If no path is given, the current directory is used.
+/// as is. If no path is given, the nearest directory with a `Cargo.toml` file, starting from the
+/// current directory and going up, is used, like cargo does when run from a subdirectory.
 /// A `Cargo.toml` file must be present is the root directory.
-pub fn get_root(given_root: Option<&str>) -> Result<PathBuf, String> {
-    let current_dir = env::current_dir().map_err(|e| format!("{}", e))?;
+pub fn get_root(given_root: Option<&str>) -> Result<PathBuf, Error> {
+    let current_dir = env::current_dir().map_err(|e| Error::io(".", e))?;
     let root = match given_root {
         Some(root) => {
             let root = Path::new(root);
@@ -19,55 +23,176 @@ pub fn get_root(given_root: Option<&str>) -> Result<PathBuf, String> {
                 current_dir.join(root)
             }
         }
-        None => current_dir,
+        None => match current_dir
+            .ancestors()
+            .find(|dir| dir.join("Cargo.toml").is_file())
+        {
+            Some(root) => {
+                if root != current_dir {
+                    log::verbose(format_args!(
+                        "Using the project root '{}'",
+                        root.to_string_lossy()
+                    ));
+                }
+                root.to_path_buf()
+            }
+            None => current_dir,
+        },
     };
 
     if !root.join("Cargo.toml").is_file() {
-        return Err(format!(
+        return Err(Error::Usage(format!(
             "`{:?}` does not look like a Rust/Cargo project",
             root
-        ));
+        )));
     }
 
     Ok(root)
 }
 
+/// Find the root of the git repository containing `project_root`
+///
+/// Returns `None` if no parent directory contains a `.git` directory or file.
+pub fn find_repository_root(project_root: &Path) -> Option<PathBuf> {
+    let project_root = project_root.canonicalize().ok()?;
+    project_root
+        .ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Find the directory of the workspace member named `name`
+pub fn find_package(workspace_root: &Path, name: &str) -> Result<PathBuf, Error> {
+    let workspace = get_workspace(workspace_root)?;
+
+    for member in workspace.members {
+        if get_manifest(&member)?.name == name {
+            return Ok(member);
+        }
+    }
+
+    Err(Error::Workspace(format!(
+        "No package named `{}` found in the workspace",
+        name
+    )))
+}
+
+/// Get the path of the source file from which the doc comments of the project are extracted
+///
+/// The `input` file is used when it is given, relative to `project_root`. Otherwise, if `bin` or
+/// `example` is given, the source file of that target is used, and by default the entrypoint of
+/// the crate, as found by [`find_entrypoint`].
+pub fn source_path(
+    project_root: &Path,
+    input: Option<&str>,
+    bin: Option<&str>,
+    example: Option<&str>,
+) -> Result<PathBuf, Error> {
+    if let Some(input) = input {
+        return Ok(project_root.join(input));
+    }
+
+    let manifest = get_manifest(project_root)?;
+    match (bin, example) {
+        (Some(bin), _) => find_bin(project_root, &manifest, bin),
+        (None, Some(example)) => find_example(project_root, &manifest, example),
+        (None, None) => {
+            // the entrypoint is relative to the project root when it comes from the `[lib]` or
+            // `[[bin]]` sections, and already joined to it otherwise
+            let entrypoint = find_entrypoint(project_root, &manifest)?;
+            match entrypoint.starts_with(project_root) {
+                true => Ok(entrypoint),
+                false => Ok(project_root.join(entrypoint)),
+            }
+        }
+    }
+}
+
+/// Get the directory of the modules declared in the module file `path`
+///
+/// The modules of `src/foo.rs` are in `src/foo/`, while the modules of `src/foo/mod.rs` are next
+/// to it.
+pub fn modules_dir(path: &Path) -> PathBuf {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    match path.file_stem() {
+        Some(stem) if stem != "mod" => dir.join(stem),
+        _ => dir.to_path_buf(),
+    }
+}
+
 /// Find the default entrypoiny to read the doc comments from
 ///
 /// Try to read entrypoint in the following order:
+/// - `entrypoint` defined in the `[package.metadata.readme]` section of Cargo.toml
+/// - entrypoint of the `facade` crate defined in the `[package.metadata.readme]` section
+/// - file defined in the `[lib]` section of Cargo.toml
 /// - src/lib.rs
 /// - src/main.rs
-/// - file defined in the `[lib]` section of Cargo.toml
 /// - file defined in the `[[bin]]` section of Cargo.toml, if there is only one
 ///   - if there is more than one `[[bin]]`, an error is returned
-pub fn find_entrypoint(current_dir: &Path, manifest: &Manifest) -> Result<PathBuf, String> {
-    // try lib.rs
-    let lib_rs = current_dir.join("src/lib.rs");
-    if lib_rs.exists() {
-        return Ok(lib_rs);
+pub fn find_entrypoint(current_dir: &Path, manifest: &Manifest) -> Result<PathBuf, Error> {
+    // try entrypoint configured in `Cargo.toml`
+    if let Some(ref entrypoint) = manifest.entrypoint {
+        return Ok(current_dir.join(entrypoint));
     }
 
-    // try main.rs
-    let main_rs = current_dir.join("src/main.rs");
-    if main_rs.exists() {
-        return Ok(main_rs);
+    // try facade crate configured in `Cargo.toml`
+    if let Some(ref facade) = manifest.facade {
+        let facade_dir = current_dir.join(facade);
+        let facade_manifest = get_manifest(&facade_dir).map_err(|e| {
+            Error::Entrypoint(format!(
+                "Could not read facade crate '{}': {}",
+                facade.display(),
+                e
+            ))
+        })?;
+        if facade_manifest.facade.is_some() {
+            return Err(Error::Entrypoint(format!(
+                "Facade crate '{}' cannot declare a facade itself",
+                facade.display()
+            )));
+        }
+        let entrypoint = find_entrypoint(&facade_dir, &facade_manifest)?;
+        return Ok(facade_dir.join(entrypoint));
+    }
+
+    // proc-macro crates usually document their API elsewhere
+    if manifest.proc_macro {
+        log::warn(format_args!(
+            "`{}` is a proc-macro crate, consider setting `facade` or `entrypoint` in \
+             `[package.metadata.readme]`",
+            manifest.name
+        ));
     }
 
     // try lib defined in `Cargo.toml`
     if let Some(ManifestLib {
         path: ref lib,
         doc: true,
+        ..
     }) = manifest.lib
     {
         return Ok(lib.to_path_buf());
     }
 
+    // try lib.rs
+    let lib_rs = current_dir.join("src/lib.rs");
+    if lib_rs.exists() {
+        return Ok(lib_rs);
+    }
+
+    // try main.rs
+    let main_rs = current_dir.join("src/main.rs");
+    if main_rs.exists() {
+        return Ok(main_rs);
+    }
+
     // try bin defined in `Cargo.toml`
-    if manifest.bin.len() > 0 {
+    if !manifest.bin.is_empty() {
         let mut bin_list: Vec<_> = manifest
             .bin
             .iter()
-            .filter(|b| b.doc == true)
+            .filter(|b| b.doc)
             .map(|b| b.path.clone())
             .collect();
 
@@ -77,7 +202,10 @@ pub fn find_entrypoint(current_dir: &Path, manifest: &Manifest) -> Result<PathBu
                 .map(|p| p.to_string_lossy())
                 .collect::<Vec<_>>()
                 .join(", ");
-            return Err(format!("Multiple binaries found, choose one: [{}]", paths));
+            return Err(Error::Entrypoint(format!(
+                "Multiple binaries found, choose one: [{}]",
+                paths
+            )));
         }
 
         if let Some(bin) = bin_list.pop() {
@@ -86,5 +214,128 @@ pub fn find_entrypoint(current_dir: &Path, manifest: &Manifest) -> Result<PathBu
     }
 
     // if no entrypoint is found, return an error
-    Err("No entrypoint found".to_owned())
+    Err(Error::Entrypoint("No entrypoint found".to_owned()))
+}
+
+/// Find the source files of all the binary targets, along with their names
+///
+/// Binaries declared in the `[[bin]]` section of Cargo.toml come first, followed by the ones
+/// cargo discovers automatically in src/main.rs and src/bin/. Binaries with `doc = false` are
+/// left out.
+pub fn find_bins(current_dir: &Path, manifest: &Manifest) -> Vec<(String, PathBuf)> {
+    let mut bins: Vec<(String, PathBuf)> = Vec::new();
+    let mut excluded: Vec<PathBuf> = Vec::new();
+
+    for bin in &manifest.bin {
+        let path = current_dir.join(&bin.path);
+        let name = bin.name.clone().unwrap_or_else(|| manifest.name.clone());
+        if bin.doc {
+            bins.push((name, path));
+        } else {
+            excluded.push(path);
+        }
+    }
+
+    let mut discovered = Vec::new();
+    let main_rs = current_dir.join("src/main.rs");
+    if main_rs.is_file() {
+        discovered.push((manifest.name.clone(), main_rs));
+    }
+
+    if let Ok(entries) = std::fs::read_dir(current_dir.join("src/bin")) {
+        let mut entries: Vec<PathBuf> = entries.filter_map(|e| e.ok().map(|e| e.path())).collect();
+        entries.sort();
+
+        for entry in entries {
+            let (name, path) = if entry.is_dir() {
+                (entry.file_name(), entry.join("main.rs"))
+            } else if entry.extension().is_some_and(|ext| ext == "rs") {
+                (entry.file_stem(), entry.clone())
+            } else {
+                continue;
+            };
+
+            if let Some(name) = name.and_then(|n| n.to_str()) {
+                if path.is_file() {
+                    discovered.push((name.to_owned(), path));
+                }
+            }
+        }
+    }
+
+    for (name, path) in discovered {
+        let known = bins.iter().any(|(n, p)| *n == name || *p == path) || excluded.contains(&path);
+        if !known {
+            bins.push((name, path));
+        }
+    }
+
+    bins
+}
+
+/// Find the source file of the binary target with the given name
+///
+/// Binaries declared in the `[[bin]]` section of Cargo.toml are searched first, then the ones
+/// cargo discovers automatically:
+/// - src/main.rs, if `name` is the package name
+/// - src/bin/`name`.rs
+/// - src/bin/`name`/main.rs
+pub fn find_bin(current_dir: &Path, manifest: &Manifest, name: &str) -> Result<PathBuf, Error> {
+    if let Some(bin) = manifest
+        .bin
+        .iter()
+        .find(|b| b.name.as_deref() == Some(name))
+    {
+        return Ok(current_dir.join(&bin.path));
+    }
+
+    bin_paths(&manifest.name, name)
+        .into_iter()
+        .map(|path| current_dir.join(path))
+        .find(|p| p.is_file())
+        .ok_or_else(|| Error::Entrypoint(format!("No binary target named `{}` found", name)))
+}
+
+/// Find the source file of the example target with the given name
+///
+/// Examples declared in the `[[example]]` section of Cargo.toml are searched first, then the ones
+/// cargo discovers automatically:
+/// - examples/`name`.rs
+/// - examples/`name`/main.rs
+pub fn find_example(current_dir: &Path, manifest: &Manifest, name: &str) -> Result<PathBuf, Error> {
+    if let Some(example) = manifest
+        .example
+        .iter()
+        .find(|e| e.name.as_deref() == Some(name))
+    {
+        return Ok(current_dir.join(&example.path));
+    }
+
+    example_paths(name)
+        .into_iter()
+        .map(|path| current_dir.join(path))
+        .find(|p| p.is_file())
+        .ok_or_else(|| Error::Entrypoint(format!("No example target named `{}` found", name)))
+}
+
+/// Get the paths where cargo discovers the binary target `name` of the package `package_name`,
+/// relative to the project root, in the order they are looked for
+pub(crate) fn bin_paths(package_name: &str, name: &str) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if name == package_name {
+        paths.push(PathBuf::from("src/main.rs"));
+    }
+    paths.push(PathBuf::from(format!("src/bin/{}.rs", name)));
+    paths.push(PathBuf::from(format!("src/bin/{}/main.rs", name)));
+
+    paths
+}
+
+/// Get the paths where cargo discovers the example target `name`, relative to the project root,
+/// in the order they are looked for
+pub(crate) fn example_paths(name: &str) -> Vec<PathBuf> {
+    vec![
+        PathBuf::from(format!("examples/{}.rs", name)),
+        PathBuf::from(format!("examples/{}/main.rs", name)),
+    ]
 }
@@ -0,0 +1,137 @@
+//! Read workspace information from `Cargo.toml`
+
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::log;
+use crate::Error;
+
+/// Try to get workspace info from Cargo.toml
+///
+/// An error is returned if the manifest has no `[workspace]` section.
+pub fn get_workspace(project_root: &Path) -> Result<Workspace, Error> {
+    let path = project_root.join("Cargo.toml");
+    log::read(&path);
+    let buf = fs::read_to_string(&path).map_err(|e| Error::io(&path, e))?;
+
+    let cargo_toml: CargoToml = toml::from_str(&buf)
+        .map_err(|e| Error::ManifestParse(super::manifest::parse_error(project_root, e)))?;
+
+    let has_package = cargo_toml.package.is_some();
+    match cargo_toml.workspace {
+        Some(workspace) => Workspace::new(project_root, has_package, workspace),
+        None => Err(Error::Workspace(format!(
+            "`{}` is not a workspace root, there is no `[workspace]` section in `Cargo.toml`",
+            project_root.to_string_lossy()
+        ))),
+    }
+}
+
+#[derive(Debug)]
+pub struct Workspace {
+    /// Directories of the workspace members
+    pub members: Vec<PathBuf>,
+    /// Do not generate a readme for crates with `publish = false`
+    pub skip_unpublished: bool,
+    /// Template used for crates with `publish = false`
+    pub unpublished_template: Option<PathBuf>,
+}
+
+impl Workspace {
+    fn new(
+        project_root: &Path,
+        has_package: bool,
+        workspace: CargoTomlWorkspace,
+    ) -> Result<Workspace, Error> {
+        let exclude: Vec<PathBuf> = workspace
+            .exclude
+            .unwrap_or_default()
+            .iter()
+            .map(|e| project_root.join(e))
+            .collect();
+
+        let mut members = Vec::new();
+        // the root package is a member of its own workspace
+        if has_package {
+            members.push(project_root.to_path_buf());
+        }
+        for member in workspace.members.unwrap_or_default() {
+            for dir in expand_member(project_root, &member)? {
+                if !exclude.contains(&dir) && !members.contains(&dir) {
+                    members.push(dir);
+                }
+            }
+        }
+
+        let config = workspace.metadata.and_then(|m| m.readme);
+
+        Ok(Workspace {
+            members,
+            skip_unpublished: config
+                .as_ref()
+                .and_then(|c| c.skip_unpublished)
+                .unwrap_or(false),
+            unpublished_template: config
+                .and_then(|c| c.unpublished_template)
+                .map(PathBuf::from),
+        })
+    }
+}
+
+/// Expand a workspace member path, where a path component may be a `*` wildcard
+fn expand_member(project_root: &Path, member: &str) -> Result<Vec<PathBuf>, Error> {
+    let mut dirs = vec![project_root.to_path_buf()];
+
+    for component in member.split('/').filter(|c| !c.is_empty() && *c != ".") {
+        let mut expanded = Vec::new();
+        for dir in dirs {
+            if component == "*" {
+                let entries = fs::read_dir(&dir).map_err(|e| Error::io(&dir, e))?;
+                let mut children: Vec<PathBuf> = entries
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| path.is_dir())
+                    .collect();
+                children.sort();
+                expanded.extend(children);
+            } else {
+                expanded.push(dir.join(component));
+            }
+        }
+        dirs = expanded;
+    }
+
+    Ok(dirs
+        .into_iter()
+        .filter(|dir| dir.join("Cargo.toml").is_file())
+        .collect())
+}
+
+/// Cargo.toml workspace information
+#[derive(Clone, Deserialize)]
+struct CargoToml {
+    pub package: Option<toml::Value>,
+    pub workspace: Option<CargoTomlWorkspace>,
+}
+
+/// Cargo.toml `[workspace]` section
+#[derive(Clone, Deserialize)]
+struct CargoTomlWorkspace {
+    pub members: Option<Vec<String>>,
+    pub exclude: Option<Vec<String>>,
+    pub metadata: Option<CargoTomlWorkspaceMetadata>,
+}
+
+/// Cargo.toml workspace metadata
+#[derive(Clone, Deserialize)]
+struct CargoTomlWorkspaceMetadata {
+    pub readme: Option<CargoTomlWorkspaceReadme>,
+}
+
+/// Cargo.toml `[workspace.metadata.readme]` configuration
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct CargoTomlWorkspaceReadme {
+    pub skip_unpublished: Option<bool>,
+    pub unpublished_template: Option<String>,
+}
@@ -0,0 +1,204 @@
+//! Show the differences between the existing output file and the generated one
+//!
+//! The differences are shown as a unified diff, like `diff -u`, with the removed lines in red and
+//! the added lines in green when colors are enabled.
+
+/// Number of unchanged lines shown around the changes
+const CONTEXT: usize = 3;
+
+/// What happens to a line going from the old text to the new one
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Edit {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Get the unified diff from `old`, named `old_name`, to `new`, named `new_name`
+///
+/// An empty string is returned when the lines are the same, like when only the line endings differ.
+pub fn unified_diff(old: &str, new: &str, old_name: &str, new_name: &str, color: bool) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let edits = edits(&old, &new);
+
+    let mut diff = String::new();
+    for hunk in hunks(&edits) {
+        if diff.is_empty() {
+            diff.push_str(&paint(&format!("--- {}", old_name), "1", color));
+            diff.push('\n');
+            diff.push_str(&paint(&format!("+++ {}", new_name), "1", color));
+            diff.push('\n');
+        }
+
+        let (_, old_start, new_start) = hunk[0];
+        let old_len = hunk.iter().filter(|e| e.0 != Edit::Insert).count();
+        let new_len = hunk.iter().filter(|e| e.0 != Edit::Delete).count();
+        let header = format!(
+            "@@ -{} +{} @@",
+            range(old_start, old_len),
+            range(new_start, new_len)
+        );
+        diff.push_str(&paint(&header, "36", color));
+        diff.push('\n');
+
+        for &(edit, i, j) in hunk {
+            let line = match edit {
+                Edit::Equal => format!(" {}", old[i]),
+                Edit::Delete => paint(&format!("-{}", old[i]), "31", color),
+                Edit::Insert => paint(&format!("+{}", new[j]), "32", color),
+            };
+            diff.push_str(&line);
+            diff.push('\n');
+        }
+    }
+
+    diff
+}
+
+/// Get the edits turning `old` into `new`, with the index of their line in both texts
+///
+/// The edits keep the longest common subsequence of lines, and the common prefix and suffix are
+/// matched first to keep the table small.
+fn edits(old: &[&str], new: &[&str]) -> Vec<(Edit, usize, usize)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    // lcs[i][j] is the length of the longest common subsequence of old_mid[i..] and new_mid[j..]
+    let mut lcs = vec![vec![0usize; new_mid.len() + 1]; old_mid.len() + 1];
+    for i in (0..old_mid.len()).rev() {
+        for j in (0..new_mid.len()).rev() {
+            lcs[i][j] = match old_mid[i] == new_mid[j] {
+                true => lcs[i + 1][j + 1] + 1,
+                false => lcs[i + 1][j].max(lcs[i][j + 1]),
+            };
+        }
+    }
+
+    let mut edits: Vec<(Edit, usize, usize)> = (0..prefix).map(|i| (Edit::Equal, i, i)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < old_mid.len() || j < new_mid.len() {
+        let edit = if i < old_mid.len() && j < new_mid.len() && old_mid[i] == new_mid[j] {
+            Edit::Equal
+        } else if j == new_mid.len() || (i < old_mid.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            Edit::Delete
+        } else {
+            Edit::Insert
+        };
+        edits.push((edit, prefix + i, prefix + j));
+        if edit != Edit::Insert {
+            i += 1;
+        }
+        if edit != Edit::Delete {
+            j += 1;
+        }
+    }
+    let (old_end, new_end) = (prefix + old_mid.len(), prefix + new_mid.len());
+    edits.extend((0..suffix).map(|k| (Edit::Equal, old_end + k, new_end + k)));
+
+    edits
+}
+
+/// Group the changes of `edits` into hunks, with `CONTEXT` unchanged lines around them
+///
+/// Changes closer than twice the context are shown in the same hunk.
+fn hunks(edits: &[(Edit, usize, usize)]) -> Vec<&[(Edit, usize, usize)]> {
+    let changes: Vec<usize> = (0..edits.len())
+        .filter(|&k| edits[k].0 != Edit::Equal)
+        .collect();
+
+    let mut hunks = Vec::new();
+    let mut k = 0;
+    while k < changes.len() {
+        let start = changes[k].saturating_sub(CONTEXT);
+        while k + 1 < changes.len() && changes[k + 1] - changes[k] <= 2 * CONTEXT {
+            k += 1;
+        }
+        let end = (changes[k] + CONTEXT + 1).min(edits.len());
+        hunks.push(&edits[start..end]);
+        k += 1;
+    }
+
+    hunks
+}
+
+/// Format the range of a hunk, starting at the 0-based `start` line, like `3,4`
+///
+/// An empty range starts at the line before it, like `diff -u` does.
+fn range(start: usize, len: usize) -> String {
+    match len {
+        0 => format!("{},0", start),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, len),
+    }
+}
+
+/// Color `text` with the ANSI escape `code`, when `color` is set
+fn paint(text: &str, code: &str, color: bool) -> String {
+    match color {
+        true => format!("\x1b[{}m{}\x1b[0m", code, text),
+        false => text.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::unified_diff;
+
+    #[test]
+    fn diff_changed_lines() {
+        let old = "# title\nintro\na\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\n";
+        let new = "# title\nintro\na\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\n";
+
+        let expected = "\
+--- README.md
++++ README.md (generated)
+@@ -1,7 +1,7 @@
+ # title
+ intro
+ a
+-b
++B
+ c
+ d
+ e
+@@ -11,3 +11,4 @@
+ i
+ j
+ k
++l
+";
+
+        assert_eq!(
+            unified_diff(old, new, "README.md", "README.md (generated)", false),
+            expected
+        );
+    }
+
+    #[test]
+    fn diff_empty_file() {
+        let expected = "--- a\n+++ b\n@@ -0,0 +1,2 @@\n+x\n+y\n";
+
+        assert_eq!(unified_diff("", "x\ny\n", "a", "b", false), expected);
+    }
+
+    #[test]
+    fn diff_same_lines() {
+        assert_eq!(unified_diff("a\r\nb\r\n", "a\nb\n", "a", "b", false), "");
+    }
+
+    #[test]
+    fn diff_with_colors() {
+        let expected = "\x1b[1m--- a\x1b[0m\n\x1b[1m+++ b\x1b[0m\n\x1b[36m@@ -1 +1 @@\x1b[0m\n\
+                        \x1b[31m-x\x1b[0m\n\x1b[32m+y\x1b[0m\n";
+
+        assert_eq!(unified_diff("x\n", "y\n", "a", "b", true), expected);
+    }
+}
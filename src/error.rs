@@ -0,0 +1,325 @@
+//! Errors of the generation of the readme
+//!
+//! The library returns an `Error` whose variant tells the kind of failure, so it can be matched
+//! on, and whose message is the one shown by `cargo readme`. Its kind, and the file and line it
+//! is about, are also given apart from the message, like for the JSON diagnostics.
+
+use std::error;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Error of the generation of the readme
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// A file could not be read, like the source or the template, or the input when it has no path
+    Io {
+        path: Option<PathBuf>,
+        source: io::Error,
+    },
+    /// A file could not be written, or stdout when it has no path
+    Write {
+        path: Option<PathBuf>,
+        source: io::Error,
+    },
+    /// `Cargo.toml` could not be parsed, or is not the manifest of a package
+    ManifestParse(String),
+    /// The template uses `{{license}}` but the crate has no license
+    MissingLicense,
+    /// The template uses `{{badges}}` but the crate has no badges
+    MissingBadges,
+    /// The template has no `{{readme}}` tag
+    TemplateMissingReadmeTag,
+    /// The relative links are made absolute but the crate has no repository
+    MissingRepository,
+    /// The options cannot be used together, or miss a value
+    Usage(String),
+    /// The markers of the generated content, of the table of contents or of the readme regions
+    /// are missing or out of order, in the file at `path` when it is known
+    Marker {
+        path: Option<PathBuf>,
+        message: String,
+    },
+    /// A file included with `#![doc = include_str!("...")]` could not be read
+    Include { path: PathBuf, source: io::Error },
+    /// The file of a module declared in `dir` could not be found
+    Module { name: String, dir: PathBuf },
+    /// The section with the given heading, kept with `--only-section`, is not in the docs
+    MissingSection(String),
+    /// The file the docs are read from could not be chosen, like with several binaries
+    Entrypoint(String),
+    /// The workspace or one of its packages could not be found
+    Workspace(String),
+    /// The docs could not be read with `cargo rustdoc`
+    Rustdoc(String),
+    /// The file at `path` differs from the result, from `line` on
+    Outdated {
+        path: PathBuf,
+        line: usize,
+        existing_lines: usize,
+        generated_lines: usize,
+    },
+    /// The readme has problems with the error severity
+    Lint { problems: usize },
+    /// The file at `path` was not generated by cargo-readme, and is not replaced
+    NotGenerated(PathBuf),
+    /// The file at `path` already exists, and is not replaced
+    Exists(PathBuf),
+    /// The git hook could not be installed or removed
+    Hook(String),
+}
+
+impl Error {
+    /// Create the error of reading the file at `path`
+    pub fn io<P: Into<PathBuf>>(path: P, source: io::Error) -> Self {
+        Error::Io {
+            path: Some(path.into()),
+            source,
+        }
+    }
+
+    /// Create the error of writing the file at `path`
+    pub fn write<P: Into<PathBuf>>(path: P, source: io::Error) -> Self {
+        Error::Write {
+            path: Some(path.into()),
+            source,
+        }
+    }
+
+    /// Create the error of the missing or misplaced markers described by `message`
+    pub(crate) fn marker(message: String) -> Self {
+        Error::Marker {
+            path: None,
+            message,
+        }
+    }
+
+    /// Get the kind of the error, like `io`, `manifest` or `outdated-readme`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::Io { .. } | Error::Write { .. } => "io",
+            Error::ManifestParse(_) | Error::MissingRepository => "manifest",
+            Error::MissingLicense | Error::MissingBadges | Error::TemplateMissingReadmeTag => {
+                "template"
+            }
+            Error::Usage(_) => "usage",
+            Error::Marker { .. } => "marker",
+            Error::Include { .. } | Error::Module { .. } | Error::MissingSection(_) => "docs",
+            Error::Entrypoint(_) => "entrypoint",
+            Error::Workspace(_) => "workspace",
+            Error::Rustdoc(_) => "rustdoc",
+            Error::Outdated { .. } => "outdated-readme",
+            Error::Lint { .. } => "lint",
+            Error::NotGenerated(_) | Error::Exists(_) => "overwrite",
+            Error::Hook(_) => "hook",
+        }
+    }
+
+    /// Get the file the error is about, when known
+    pub fn file(&self) -> Option<&Path> {
+        match self {
+            Error::Io { path, .. } | Error::Write { path, .. } | Error::Marker { path, .. } => {
+                path.as_deref()
+            }
+            Error::Include { path, .. }
+            | Error::Outdated { path, .. }
+            | Error::NotGenerated(path)
+            | Error::Exists(path) => Some(path),
+            Error::ManifestParse(_)
+            | Error::MissingLicense
+            | Error::MissingBadges
+            | Error::TemplateMissingReadmeTag
+            | Error::MissingRepository
+            | Error::Usage(_)
+            | Error::Module { .. }
+            | Error::MissingSection(_)
+            | Error::Entrypoint(_)
+            | Error::Workspace(_)
+            | Error::Rustdoc(_)
+            | Error::Lint { .. }
+            | Error::Hook(_) => None,
+        }
+    }
+
+    /// Get the line of the file the error is about, starting at 1, when known
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Error::Outdated { line, .. } => Some(*line),
+            Error::Io { .. }
+            | Error::Write { .. }
+            | Error::ManifestParse(_)
+            | Error::MissingLicense
+            | Error::MissingBadges
+            | Error::TemplateMissingReadmeTag
+            | Error::MissingRepository
+            | Error::Usage(_)
+            | Error::Marker { .. }
+            | Error::Include { .. }
+            | Error::Module { .. }
+            | Error::MissingSection(_)
+            | Error::Entrypoint(_)
+            | Error::Workspace(_)
+            | Error::Rustdoc(_)
+            | Error::Lint { .. }
+            | Error::NotGenerated(_)
+            | Error::Exists(_)
+            | Error::Hook(_) => None,
+        }
+    }
+
+    /// Tell that the markers of this error are in the file at `path`
+    pub fn in_file<P: Into<PathBuf>>(self, path: P) -> Self {
+        match self {
+            Error::Marker { message, .. } => Error::Marker {
+                path: Some(path.into()),
+                message,
+            },
+            error => error,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io {
+                path: Some(path),
+                source,
+            } => write!(f, "Could not read '{}': {}", path.to_string_lossy(), source),
+            Error::Io { path: None, source } => write!(f, "Could not read the input: {}", source),
+            Error::Write {
+                path: Some(path),
+                source,
+            } => write!(
+                f,
+                "Could not write '{}': {}",
+                path.to_string_lossy(),
+                source
+            ),
+            Error::Write { path: None, source } => {
+                write!(f, "Could not write to stdout: {}", source)
+            }
+            Error::ManifestParse(message)
+            | Error::Usage(message)
+            | Error::Entrypoint(message)
+            | Error::Workspace(message)
+            | Error::Rustdoc(message)
+            | Error::Hook(message) => f.write_str(message),
+            Error::MissingLicense => {
+                f.write_str("`{{license}}` was found in template but no license was provided")
+            }
+            Error::MissingBadges => {
+                f.write_str("`{{badges}}` was found in template but no badges were provided")
+            }
+            Error::TemplateMissingReadmeTag => f.write_str("Missing `{{readme}}` in template"),
+            Error::MissingRepository => f.write_str(
+                "Cannot rewrite relative links, `package.repository` is not set in Cargo.toml",
+            ),
+            Error::Marker {
+                path: Some(path),
+                message,
+            } => write!(f, "{} in '{}'", message, path.to_string_lossy()),
+            Error::Marker {
+                path: None,
+                message,
+            } => f.write_str(message),
+            Error::MissingSection(heading) => {
+                write!(f, "Section `{}` not found in the docs", heading)
+            }
+            Error::Include { path, source } => write!(
+                f,
+                "Could not read included file '{}': {}",
+                path.to_string_lossy(),
+                source
+            ),
+            Error::Module { name, dir } => write!(
+                f,
+                "Could not find the file of module `{}` in '{}'",
+                name,
+                dir.to_string_lossy()
+            ),
+            Error::Outdated {
+                path,
+                line,
+                existing_lines,
+                generated_lines,
+            } => write!(
+                f,
+                "'{}' is not up to date, the first difference is at line {} ({} lines on disk, \
+                 {} lines generated)",
+                path.to_string_lossy(),
+                line,
+                existing_lines,
+                generated_lines
+            ),
+            Error::Lint { problems: 1 } => f.write_str("The README has 1 problem"),
+            Error::Lint { problems } => write!(f, "The README has {} problems", problems),
+            Error::NotGenerated(path) => write!(
+                f,
+                "'{}' was not generated by cargo-readme, use `--force` to replace it",
+                path.to_string_lossy()
+            ),
+            Error::Exists(path) => write!(
+                f,
+                "'{}' already exists, use `--force` to replace it",
+                path.to_string_lossy()
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. }
+            | Error::Write { source, .. }
+            | Error::Include { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+    use std::error::Error as _;
+    use std::io;
+
+    #[test]
+    fn io_error_message_and_source() {
+        let error = Error::io(
+            "README.tpl",
+            io::Error::new(io::ErrorKind::NotFound, "not found"),
+        );
+
+        assert_eq!(error.to_string(), "Could not read 'README.tpl': not found");
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn outdated_error_kind_file_and_line() {
+        let error = Error::Outdated {
+            path: "README.md".into(),
+            line: 3,
+            existing_lines: 4,
+            generated_lines: 5,
+        };
+
+        assert_eq!(error.kind(), "outdated-readme");
+        assert_eq!(error.file(), Some(std::path::Path::new("README.md")));
+        assert_eq!(error.line(), Some(3));
+    }
+
+    #[test]
+    fn marker_error_in_file() {
+        let error = Error::marker("Missing `<!-- cargo-readme start -->` marker".to_owned())
+            .in_file("README.md");
+
+        assert!(matches!(error, Error::Marker { path: Some(_), .. }));
+        assert_eq!(
+            error.to_string(),
+            "Missing `<!-- cargo-readme start -->` marker in 'README.md'"
+        );
+    }
+}
@@ -1,115 +1,538 @@
-use std::fs::File;
-use std::io::{ErrorKind, Write};
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, ErrorKind, Write};
 use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use cargo_readme::get_manifest;
 use cargo_readme::project;
+use cargo_readme::{convert_line_endings, AssetCopy, Error, LineEndings};
 
-const DEFAULT_TEMPLATE: &'static str = "README.tpl";
+use crate::messages;
+use crate::watch;
+
+pub const DEFAULT_TEMPLATE: &str = "README.tpl";
+/// Comment written at the top of the output files, telling they can be replaced
+pub const GENERATED_MARKER: &str = "<!-- generated by cargo-readme -->";
+/// Comment written at the top of the reStructuredText output files
+pub const RST_GENERATED_MARKER: &str = ".. generated by cargo-readme";
+/// Comment written at the top of the AsciiDoc output files
+pub const ASCIIDOC_GENERATED_MARKER: &str = "// generated by cargo-readme";
+/// Comment written at the top of the man page output files
+pub const MAN_GENERATED_MARKER: &str = ".\\\" generated by cargo-readme";
+/// Comment written at the top of the Org output files
+pub const ORG_GENERATED_MARKER: &str = "# generated by cargo-readme";
 
 /// Get the project root from given path or defaults to current directory
 ///
 /// The given path is appended to the current directory if is a relative path, otherwise it is used
 /// as is. If no path is given, the current directory is used.
-/// A `Cargo.toml` file must be present is the root directory.
-pub fn get_project_root(given_root: Option<&str>) -> Result<PathBuf, String> {
+/// A `Cargo.toml` file must be present is the root directory, unless `no_cargo_toml` is set.
+pub fn get_project_root(given_root: Option<&str>, no_cargo_toml: bool) -> Result<PathBuf, Error> {
+    if no_cargo_toml {
+        let current_dir = env::current_dir().map_err(|e| Error::io(".", e))?;
+        return Ok(given_root.map_or(current_dir.clone(), |root| current_dir.join(root)));
+    }
+
     project::get_root(given_root)
 }
 
-/// Get the source file from which the doc comments will be extracted
-pub fn get_source(project_root: &Path, input: Option<&str>) -> Result<File, String> {
-    match input {
-        Some(input) => {
-            let input = project_root.join(input);
-            File::open(&input)
-                .map_err(|e| format!("Could not open file '{}': {}", input.to_string_lossy(), e))
-        }
-        None => find_entrypoint(&project_root),
-    }
+/// Open the source file from which the doc comments will be extracted
+pub fn open_source(path: &Path) -> Result<File, Error> {
+    watch::record(path);
+    File::open(path).map_err(|e| Error::io(path, e))
+}
+
+/// Destination where the result will be output to
+pub enum Dest {
+    Stdout,
+    /// File written once the result is ready, replacing its content
+    ///
+    /// Unless `force` is set, an existing file that was not generated by cargo-readme is only
+    /// replaced when its content is the same as the result.
+    File {
+        path: PathBuf,
+        force: bool,
+    },
+    /// Existing file, whose content between the cargo-readme markers is replaced
+    InPlace(PathBuf),
+    /// Existing file, only compared with the result, or with the file updated between the
+    /// cargo-readme markers when `in_place` is set
+    Check {
+        path: PathBuf,
+        in_place: bool,
+        compare: Compare,
+    },
+    /// Output file, or stdout when `None`, only reported as the destination of the result, like
+    /// with `--dry-run`
+    DryRun {
+        path: Option<PathBuf>,
+        in_place: bool,
+    },
+}
+
+/// How the result is compared with the output file, instead of being written
+#[derive(Clone, Copy)]
+pub struct Compare {
+    /// Fail when the output file is not up to date, like with `--check`
+    pub fail: bool,
+    /// Colorize the differences
+    pub color: bool,
 }
 
-/// Get the destination file where the result will be output to
-pub fn get_dest(project_root: &Path, output: Option<&str>) -> Result<Option<File>, String> {
-    match output {
-        Some(filename) => {
+/// Get the destination where the result will be output to
+///
+/// The output file is only written once the result is ready, and defaults to `README.md` with
+/// `in_place`. With `compare`, the output file, which also defaults to `README.md`, is never
+/// written, and neither is it with `dry_run`.
+pub fn get_dest(
+    project_root: &Path,
+    output: Option<&str>,
+    in_place: bool,
+    compare: Option<Compare>,
+    dry_run: bool,
+    force: bool,
+) -> Result<Dest, Error> {
+    match (output, compare) {
+        (Some(crate::STDIO), Some(_)) => Err(Error::Usage(
+            "`--check` and `--diff` cannot compare with stdout".to_owned(),
+        )),
+        (Some(crate::STDIO), None) if in_place => {
+            Err(Error::Usage("`--in-place` cannot update stdout".to_owned()))
+        }
+        (_, Some(compare)) => {
+            let path = project_root.join(output.unwrap_or(crate::DEFAULT_OUTPUT));
+            messages::verbose(format_args!("Comparing with '{}'", path.to_string_lossy()));
+            Ok(Dest::Check {
+                path,
+                in_place,
+                compare,
+            })
+        }
+        (Some(crate::STDIO), None) | (None, None) if dry_run && !in_place => Ok(Dest::DryRun {
+            path: None,
+            in_place,
+        }),
+        _ if dry_run => Ok(Dest::DryRun {
+            path: Some(project_root.join(output.unwrap_or(crate::DEFAULT_OUTPUT))),
+            in_place,
+        }),
+        _ if in_place => {
+            let path = project_root.join(output.unwrap_or(crate::DEFAULT_OUTPUT));
+            messages::verbose(format_args!(
+                "Updating '{}' in place",
+                path.to_string_lossy()
+            ));
+            Ok(Dest::InPlace(path))
+        }
+        (Some(crate::STDIO), None) | (None, None) => Ok(Dest::Stdout),
+        (Some(filename), None) => {
             let output = project_root.join(filename);
-            File::create(&output).map(|f| Some(f)).map_err(|e| {
-                format!(
-                    "Could not create output file '{}': {}",
-                    output.to_string_lossy(),
-                    e
-                )
+            messages::verbose(format_args!("Writing to '{}'", output.to_string_lossy()));
+            Ok(Dest::File {
+                path: output,
+                force,
             })
         }
-        None => Ok(None),
     }
 }
 
+/// Tell if `readme` can be replaced, as it is empty, was generated by cargo-readme or has its
+/// markers, like `<!-- cargo-readme start -->` or `<!-- cargo-readme manual -->`
+///
+/// The table of contents markers, put by `cargo readme toc` in any markdown file, do not count.
+/// The JSON documents of `--output-format json`, and the READMEs starting with a front matter,
+/// are recognized by their `generator` field.
+fn is_generated(readme: &str) -> bool {
+    readme.trim().is_empty()
+        || readme.contains(GENERATED_MARKER)
+        || readme.starts_with(RST_GENERATED_MARKER)
+        || readme.starts_with(ASCIIDOC_GENERATED_MARKER)
+        || readme.starts_with(MAN_GENERATED_MARKER)
+        || readme.starts_with(ORG_GENERATED_MARKER)
+        || readme.contains("\"generator\": \"cargo-readme ")
+        || readme.contains("<meta name=\"generator\" content=\"cargo-readme ")
+        || front_matter(readme).any(|field| {
+            field == "generator: \"cargo-readme\"" || field == "generator = \"cargo-readme\""
+        })
+        || readme.matches("<!-- cargo-readme ").count()
+            > readme.matches("<!-- cargo-readme toc ").count()
+}
+
+/// Get the fields of the front matter block at the top of `readme`, between `---` or `+++` lines
+fn front_matter(readme: &str) -> impl Iterator<Item = &str> {
+    let mut lines = readme.lines().map(str::trim_end);
+    let delimiter = lines.next().filter(|line| *line == "---" || *line == "+++");
+    let closed = delimiter.is_some_and(|delimiter| lines.clone().any(|line| line == delimiter));
+
+    lines
+        .take_while(move |line| Some(*line) != delimiter)
+        .filter(move |_| closed)
+}
+
+/// Get the date of the front matter of `readme`, like `2024-01-31`
+pub fn front_matter_date(readme: &str) -> Option<&str> {
+    front_matter(readme).find_map(|field| {
+        field
+            .strip_prefix("date: ")
+            .or_else(|| field.strip_prefix("date = "))
+    })
+}
+
+/// Check if `date` is a date like `2024-01-31`
+pub fn is_date(date: &str) -> bool {
+    let parts: Vec<&str> = date.split('-').collect();
+
+    parts.iter().map(|part| part.len()).eq([4, 2, 2])
+        && parts
+            .iter()
+            .all(|part| part.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Get the current date, or the date of `SOURCE_DATE_EPOCH`, like `2024-01-31`
+pub fn today() -> String {
+    source_date().unwrap_or_else(|| {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        epoch_date(seconds)
+    })
+}
+
+/// Get the date of `SOURCE_DATE_EPOCH`, when it is set, like for reproducible builds
+pub fn source_date() -> Option<String> {
+    env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.trim().parse::<u64>().ok())
+        .map(epoch_date)
+}
+
+/// Get the date of the time `seconds` after 1970-01-01, like `2024-01-31`
+fn epoch_date(seconds: u64) -> String {
+    let (year, month, day) = civil_date(seconds / 86_400);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Get the year, month and day of the day `days` after 1970-01-01
+///
+/// This is the `civil_from_days` algorithm of Howard Hinnant.
+fn civil_date(days: u64) -> (u64, u64, u64) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + (month <= 2) as u64;
+
+    (year, month, day)
+}
+
+/// Get the content of the existing output file, to carry its manual sections over
+///
+/// With `in_place`, only the content between the cargo-readme markers is returned.
+pub fn get_existing_readme(
+    project_root: &Path,
+    output: Option<&str>,
+    in_place: bool,
+    check: bool,
+) -> Result<Option<String>, Error> {
+    let output = match output {
+        Some(crate::STDIO) => return Ok(None),
+        Some(output) => project_root.join(output),
+        None if in_place || check => project_root.join(crate::DEFAULT_OUTPUT),
+        None => return Ok(None),
+    };
+
+    let existing = match fs::read_to_string(&output) {
+        Ok(existing) => existing,
+        // there is nothing to carry over from a new file
+        Err(ref e) if e.kind() == ErrorKind::NotFound && !in_place => return Ok(None),
+        Err(e) => return Err(Error::io(output, e)),
+    };
+
+    if in_place {
+        let content = cargo_readme::marked_content(&existing).map_err(|e| e.in_file(&output))?;
+        return Ok(Some(content.to_owned()));
+    }
+
+    Ok(Some(existing))
+}
+
 /// Get the template file that will be used to render the output
+///
+/// A `template` given explicitly must exist, while the default template `README.tpl` is optional:
+/// when it is not found, `None` is returned and the README is generated without template.
 pub fn get_template_file(
     project_root: &Path,
     template: Option<&str>,
-) -> Result<Option<File>, String> {
-    match template {
-        // template path was given, try to read it
-        Some(template) => {
-            let template = project_root.join(template);
-            File::open(&template).map(|f| Some(f)).map_err(|e| {
-                format!(
-                    "Could not open template file '{}': {}",
-                    template.to_string_lossy(),
-                    e
-                )
-            })
+) -> Result<Option<File>, Error> {
+    let (template, explicit) = match template {
+        Some(template) => (project_root.join(template), true),
+        None => (project_root.join(DEFAULT_TEMPLATE), false),
+    };
+
+    watch::record(&template);
+    match File::open(&template) {
+        Ok(file) => {
+            messages::verbose(format_args!(
+                "Using the template '{}'",
+                template.to_string_lossy()
+            ));
+            Ok(Some(file))
         }
-        // try to read the defautl template file
-        None => {
-            let template = project_root.join(DEFAULT_TEMPLATE);
-            match File::open(&template) {
-                Ok(file) => Ok(Some(file)),
-                // do not generate an error on file not found
-                Err(ref e) if e.kind() != ErrorKind::NotFound => {
-                    return Err(format!(
-                        "Could not open template file '{}': {}",
-                        DEFAULT_TEMPLATE, e
-                    ))
-                }
-                // default template not found, return `None`
-                _ => Ok(None),
+        Err(ref e) if e.kind() == ErrorKind::NotFound => {
+            if explicit {
+                return Err(Error::Usage(format!(
+                    "The template file '{}' does not exist",
+                    template.to_string_lossy()
+                )));
             }
+            messages::trace(format_args!(
+                "No template found at '{}'",
+                template.to_string_lossy()
+            ));
+            Ok(None)
         }
+        Err(e) => Err(Error::io(template, e)),
     }
 }
 
-/// Write result to output, either stdout or destination file
-pub fn write_output(dest: &mut Option<File>, readme: String) -> Result<(), String> {
-    match dest.as_mut() {
-        Some(dest) => {
-            let mut bytes = readme.into_bytes();
-            // Append new line at end of file to match behavior of `cargo readme > README.md`
-            bytes.push(b'\n');
+/// Write result to output, either stdout or destination file, or compare it with the destination
+/// file
+///
+/// The output ends with a single new line, to match behavior of `cargo readme > README.md`. The
+/// output file is written with the given `line_endings`, while stdout always uses `\n`, and starts
+/// with the `marker` comment when it is given, unless it is updated in place. When
+/// comparing, the differences with the destination file are output as a unified diff, and with
+/// `--check` an error is returned if the destination file is not up to date. With a dry run, only
+/// where the result would be written, and whether it would change the file, is output.
+///
+/// With `backup`, the previous content of the destination file is saved next to it, to the file
+/// named after it with the `backup` suffix, before it is replaced. The `asset_copies` are only made
+/// when the result is written to a file, never when comparing nor with a dry run.
+pub fn write_output(
+    dest: &mut Dest,
+    readme: String,
+    asset_copies: &[AssetCopy],
+    line_endings: LineEndings,
+    marker: Option<&str>,
+    backup: Option<&str>,
+) -> Result<(), Error> {
+    let readme = format!("{}\n", readme.trim_end_matches(['\r', '\n']));
+    let marked = |readme: &str| match marker {
+        Some(marker) => format!("{}\n{}", marker, readme),
+        None => readme.to_owned(),
+    };
+
+    match dest {
+        Dest::File { path, force } => {
+            let marked_readme = marked(&readme);
+            // replacing the whole file would lose its hand-written content, unless it is what
+            // `cargo readme > README.md` wrote before the marker was added
+            if !*force {
+                if let Ok(existing) = fs::read_to_string(&path) {
+                    let same = existing == convert_line_endings(&readme, line_endings)
+                        || existing == convert_line_endings(&marked_readme, line_endings);
+                    if !same && !is_generated(&existing) {
+                        return Err(Error::NotGenerated(path.clone()));
+                    }
+                }
+            }
+            write_file(
+                path,
+                &convert_line_endings(&marked_readme, line_endings),
+                backup,
+            )?;
+            copy_assets(asset_copies)?;
+        }
+        Dest::InPlace(path) => {
+            let existing = fs::read_to_string(&path).map_err(|e| Error::io(&*path, e))?;
+            let updated = cargo_readme::update_between_markers(&existing, &readme)
+                .map_err(|e| e.in_file(&*path))?;
 
-            dest.write_all(&mut bytes)
-                .map(|_| ())
-                .map_err(|e| format!("Could not write to output file: {}", e))?;
+            write_file(path, &convert_line_endings(&updated, line_endings), backup)?;
+            copy_assets(asset_copies)?;
         }
-        None => println!("{}", readme),
+        Dest::Check {
+            path,
+            in_place,
+            compare,
+        } => {
+            let existing = fs::read_to_string(&path).map_err(|e| Error::io(&*path, e))?;
+            let expected = match in_place {
+                true => cargo_readme::update_between_markers(&existing, &readme)
+                    .map_err(|e| e.in_file(&*path))?,
+                // files written before the marker was added are still up to date
+                false if marker.is_some_and(|m| existing.starts_with(m)) => marked(&readme),
+                false => readme,
+            };
+            let expected = convert_line_endings(&expected, line_endings);
+
+            if existing == expected {
+                return Ok(());
+            }
+            let name = path.to_string_lossy();
+            let diff = crate::diff::unified_diff(
+                &existing,
+                &expected,
+                &name,
+                &format!("{} (generated)", name),
+                compare.color,
+            );
+            match io::stdout().write_all(diff.as_bytes()) {
+                Err(ref e) if e.kind() == ErrorKind::BrokenPipe => {}
+                result => result.map_err(|source| Error::Write { path: None, source })?,
+            }
+            if compare.fail {
+                let (line, existing_lines, generated_lines) =
+                    first_difference(&existing, &expected);
+                return Err(Error::Outdated {
+                    path: path.clone(),
+                    line,
+                    existing_lines,
+                    generated_lines,
+                });
+            }
+        }
+        Dest::DryRun { path: None, .. } => {
+            println!("Would write {} lines to stdout", readme.lines().count());
+        }
+        Dest::DryRun {
+            path: Some(path),
+            in_place,
+        } => {
+            let name = path.to_string_lossy();
+            let existing = match fs::read_to_string(&path) {
+                Ok(existing) => existing,
+                Err(ref e) if e.kind() == ErrorKind::NotFound && !*in_place => {
+                    println!(
+                        "Would create '{}' with {} lines",
+                        name,
+                        readme.lines().count()
+                    );
+                    return Ok(());
+                }
+                Err(e) => return Err(Error::io(&*path, e)),
+            };
+            let expected = match in_place {
+                true => cargo_readme::update_between_markers(&existing, &readme)
+                    .map_err(|e| e.in_file(&*path))?,
+                false => marked(&readme),
+            };
+            let expected = convert_line_endings(&expected, line_endings);
+
+            match existing == expected {
+                true => println!("Would leave '{}' unchanged, it is up to date", name),
+                false => println!(
+                    "Would update '{}', {}",
+                    name,
+                    diff_summary(&existing, &expected)
+                ),
+            }
+        }
+        Dest::Stdout => match io::stdout().write_all(readme.as_bytes()) {
+            // the reader is gone, like with `cargo readme | head`
+            Err(ref e) if e.kind() == ErrorKind::BrokenPipe => {}
+            result => result.map_err(|source| Error::Write { path: None, source })?,
+        },
+    }
+
+    Ok(())
+}
+
+/// Copy the images the written readme links to next to it
+fn copy_assets(asset_copies: &[AssetCopy]) -> Result<(), Error> {
+    for asset in asset_copies {
+        asset.copy().map_err(|e| Error::write(&asset.to, e))?;
     }
 
     Ok(())
 }
 
-/// Find the default entrypoiny to read the doc comments from
+/// Write `content` to the file at `path`
 ///
-/// Try to read entrypoint in the following order:
-/// - src/lib.rs
-/// - src/main.rs
-/// - file defined in the `[lib]` section of Cargo.toml
-/// - file defined in the `[[bin]]` section of Cargo.toml, if there is only one
-///   - if there is more than one `[[bin]]`, an error is returned
-pub fn find_entrypoint(current_dir: &Path) -> Result<File, String> {
+/// The content is written to a temporary file next to it, then renamed to `path`, so an
+/// interrupted write never leaves a truncated file behind. The file is left untouched, keeping its
+/// modification time, when it already has the content. Otherwise, with `backup`, the file is first
+/// copied to the file named after it with the `backup` suffix.
+pub fn write_file(path: &Path, content: &str, backup: Option<&str>) -> Result<(), Error> {
+    if fs::read(path).is_ok_and(|existing| existing == content.as_bytes()) {
+        messages::verbose(format_args!(
+            "Not writing '{}', it is up to date",
+            path.to_string_lossy()
+        ));
+        return Ok(());
+    }
+
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    if let Some(suffix) = backup.filter(|_| path.is_file()) {
+        let backup = path.with_file_name(format!("{}{}", file_name, suffix));
+        fs::copy(path, &backup).map_err(|e| Error::write(&backup, e))?;
+        messages::verbose(format_args!(
+            "Saved the previous content to '{}'",
+            backup.to_string_lossy()
+        ));
+    }
+
+    let temp = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+
+    let result = fs::write(&temp, content).and_then(|_| fs::rename(&temp, path));
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp);
+        return Err(Error::write(path, e));
+    }
+
+    Ok(())
+}
+
+/// Summarize the difference between the `existing` file and the `expected` one
+fn diff_summary(existing: &str, expected: &str) -> String {
+    let (line, existing_lines, expected_lines) = first_difference(existing, expected);
+
+    format!(
+        "the first difference is at line {} ({} lines on disk, {} lines generated)",
+        line, existing_lines, expected_lines
+    )
+}
+
+/// Get the line of the first difference between the `existing` file and the `expected` one,
+/// starting at 1, along with their numbers of lines
+fn first_difference(existing: &str, expected: &str) -> (usize, usize, usize) {
+    let existing: Vec<&str> = existing.lines().collect();
+    let expected: Vec<&str> = expected.lines().collect();
+    let first = existing
+        .iter()
+        .zip(&expected)
+        .position(|(a, b)| a != b)
+        .unwrap_or(existing.len().min(expected.len()));
+
+    (first + 1, existing.len(), expected.len())
+}
+
+/// Find the source files of all the binary targets, along with their names
+pub fn find_bins(current_dir: &Path) -> Result<Vec<(String, PathBuf)>, Error> {
     let manifest = get_manifest(current_dir)?;
-    let entrypoint = project::find_entrypoint(current_dir, &manifest)?;
+    let bins = project::find_bins(current_dir, &manifest);
 
-    File::open(current_dir.join(entrypoint)).map_err(|e| format!("{}", e))
+    if bins.is_empty() {
+        return Err(Error::Entrypoint("No binary targets found".to_owned()));
+    }
+
+    Ok(bins)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::civil_date;
+
+    #[test]
+    fn dates() {
+        assert_eq!(civil_date(0), (1970, 1, 1));
+        assert_eq!(civil_date(11_016), (2000, 2, 29));
+        assert_eq!(civil_date(19_753), (2024, 1, 31));
+    }
 }
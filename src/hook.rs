@@ -0,0 +1,170 @@
+//! Install a git pre-commit hook keeping the readme up to date
+//!
+//! The hook runs `cargo readme --check`, so commits with a stale readme are refused, or
+//! regenerates the readme and adds it to the commit. The hook is tagged with a comment, so only
+//! the hooks installed by cargo-readme are replaced or removed.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use cargo_readme::Error;
+
+use crate::messages;
+
+/// Comment tagging the hooks installed by cargo-readme
+const HOOK_MARKER: &str = "# Installed by cargo-readme";
+
+/// Write the pre-commit hook of the git repository containing `project_root`
+///
+/// With `regenerate`, the hook writes the readme and adds it to the commit instead of checking
+/// it. An existing hook not installed by cargo-readme is only replaced when `force` is set.
+pub fn install_hook(project_root: &Path, regenerate: bool, force: bool) -> Result<(), Error> {
+    let (hook, prefix) = git_paths(project_root)?;
+
+    if let Ok(existing) = fs::read_to_string(&hook) {
+        if !existing.contains(HOOK_MARKER) && !force {
+            return Err(Error::Hook(format!(
+                "'{}' already exists and was not installed by cargo-readme, use `--force` to \
+                 replace it",
+                hook.to_string_lossy()
+            )));
+        }
+    }
+
+    if let Some(dir) = hook.parent() {
+        fs::create_dir_all(dir).map_err(|e| Error::write(dir, e))?;
+    }
+    fs::write(&hook, hook_script(&prefix, regenerate)).map_err(|e| Error::write(&hook, e))?;
+    make_executable(&hook)?;
+    messages::info(format_args!("Installed '{}'", hook.to_string_lossy()));
+
+    Ok(())
+}
+
+/// Remove the pre-commit hook of the git repository containing `project_root`
+///
+/// Only a hook installed by cargo-readme is removed.
+pub fn uninstall_hook(project_root: &Path) -> Result<(), Error> {
+    let (hook, _) = git_paths(project_root)?;
+
+    match fs::read_to_string(&hook) {
+        Ok(existing) if existing.contains(HOOK_MARKER) => {
+            fs::remove_file(&hook).map_err(|e| Error::write(&hook, e))?;
+            messages::info(format_args!("Removed '{}'", hook.to_string_lossy()));
+            Ok(())
+        }
+        Ok(_) => Err(Error::Hook(format!(
+            "'{}' was not installed by cargo-readme, remove it by hand",
+            hook.to_string_lossy()
+        ))),
+        Err(_) => Err(Error::Hook(format!(
+            "'{}' does not exist",
+            hook.to_string_lossy()
+        ))),
+    }
+}
+
+/// Get the path of the pre-commit hook, and the path of `project_root` in the repository, like
+/// `crates/my-crate/`
+///
+/// git is asked for them, to follow the `core.hooksPath` setting and the worktrees.
+fn git_paths(project_root: &Path) -> Result<(PathBuf, String), Error> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(project_root)
+        .args([
+            "rev-parse",
+            "--show-prefix",
+            "--git-path",
+            "hooks/pre-commit",
+        ])
+        .output()
+        .map_err(|e| Error::Hook(format!("Could not run `git`: {}", e)))?;
+    if !output.status.success() {
+        return Err(Error::Hook(format!(
+            "'{}' is not in a git repository:\n{}",
+            project_root.to_string_lossy(),
+            String::from_utf8_lossy(&output.stderr).trim_end()
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    let prefix = lines.next().unwrap_or_default().to_owned();
+    let hook = lines
+        .next()
+        .ok_or_else(|| Error::Hook("Could not find the git hooks directory".to_owned()))?;
+
+    // the hook path is relative to the directory git was run in
+    Ok((project_root.join(hook), prefix))
+}
+
+/// Get the hook script, run from the root of the repository, for the crate at `prefix`
+fn hook_script(prefix: &str, regenerate: bool) -> String {
+    let root = prefix.trim_end_matches('/');
+    let project_root = match root {
+        "" => String::new(),
+        root => format!(" --project-root {}", quote(root)),
+    };
+
+    let command = match regenerate {
+        true => {
+            let readme = Path::new(root).join("README.md");
+            format!(
+                "cargo readme{} --output README.md && git add {}",
+                project_root,
+                quote(&readme.to_string_lossy())
+            )
+        }
+        false => format!("cargo readme{} --check", project_root),
+    };
+
+    format!(
+        "#!/bin/sh\n{}, remove with `cargo readme uninstall-hook`\n{}\n",
+        HOOK_MARKER, command
+    )
+}
+
+/// Quote `value` for the shell
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Let the hook be run by git
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<(), Error> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).map_err(|e| Error::write(path, e))
+}
+
+/// Let the hook be run by git, which needs nothing more outside unix
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<(), Error> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hook_script;
+
+    #[test]
+    fn check_hook() {
+        assert_eq!(
+            hook_script("", false),
+            "#!/bin/sh\n# Installed by cargo-readme, remove with `cargo readme uninstall-hook`\n\
+             cargo readme --check\n"
+        );
+    }
+
+    #[test]
+    fn regenerate_hook_in_subdirectory() {
+        assert_eq!(
+            hook_script("crates/my-crate/", true),
+            "#!/bin/sh\n# Installed by cargo-readme, remove with `cargo readme uninstall-hook`\n\
+             cargo readme --project-root 'crates/my-crate' --output README.md && \
+             git add 'crates/my-crate/README.md'\n"
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Set up a crate to generate its readme
+//!
+//! A starter `README.tpl` is written next to `Cargo.toml`, with the badges, the docs and the
+//! license. The `[package.metadata.readme]` section, listing the settings that can be changed, can
+//! also be added to `Cargo.toml`.
+
+use std::fs;
+use std::path::Path;
+
+use cargo_readme::Error;
+
+use crate::messages;
+
+/// Header of the section of `Cargo.toml` with the settings of the readme
+const METADATA_HEADER: &str = "[package.metadata.readme]";
+
+/// Settings of `[package.metadata.readme]`, commented out with their default value or an example
+const METADATA: &str = "\
+[package.metadata.readme]
+# File to read the docs from
+# entrypoint = \"src/lib.rs\"
+# Directory of a facade crate to read the docs from
+# facade = \"../my-crate-facade\"
+# Branch that relative links point to, with `--absolute-links`
+# repository-branch = \"main\"
+# Headings of the sections removed from the readme
+# exclude-sections = [\"Development\"]
+# Label of the license set with `license-file`
+# license-label = \"Proprietary\"
+";
+
+/// Write a starter template to `template`, in `project_root`, and add `[package.metadata.readme]`
+/// to `Cargo.toml` when `metadata` is set
+///
+/// An existing template is only replaced when `force` is set.
+pub fn init(project_root: &Path, template: &str, metadata: bool, force: bool) -> Result<(), Error> {
+    let manifest = cargo_readme::get_manifest(project_root)?;
+
+    let template_path = project_root.join(template);
+    if template_path.exists() && !force {
+        return Err(Error::Exists(template_path));
+    }
+    let content = starter_template(
+        manifest.publish,
+        !manifest.badges.is_empty(),
+        manifest.license.is_some(),
+    );
+    fs::write(&template_path, content).map_err(|e| Error::write(&template_path, e))?;
+    messages::info(format_args!(
+        "Created '{}'",
+        template_path.to_string_lossy()
+    ));
+
+    if metadata {
+        add_metadata(&project_root.join("Cargo.toml"))?;
+    }
+
+    Ok(())
+}
+
+/// Get a starter template
+///
+/// The crates.io and docs.rs badges are only added for published crates, and `{{badges}}` and
+/// `{{license}}` only when `Cargo.toml` has badges and a license, as they are required then.
+fn starter_template(publish: bool, has_badges: bool, has_license: bool) -> String {
+    let mut badges = Vec::new();
+    if publish {
+        badges.push("[![Crates.io](https://img.shields.io/crates/v/{{crate}}.svg)](https://crates.io/crates/{{crate}})");
+        badges.push("[![Docs.rs](https://docs.rs/{{crate}}/badge.svg)](https://docs.rs/{{crate}})");
+    }
+    if has_badges {
+        badges.push("{{badges}}");
+    }
+
+    let mut template = String::new();
+    if !badges.is_empty() {
+        template.push_str(&badges.join("\n"));
+        template.push_str("\n\n");
+    }
+    template.push_str("# {{crate}}\n\n{{readme}}\n");
+    if has_license {
+        template.push_str("\n## License\n\n{{license}}\n");
+    }
+
+    template
+}
+
+/// Append the `[package.metadata.readme]` section to the `Cargo.toml` at `path`, unless it is
+/// already there
+fn add_metadata(path: &Path) -> Result<(), Error> {
+    let manifest = fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+
+    if manifest.lines().any(|line| line.trim() == METADATA_HEADER) {
+        messages::info(format_args!(
+            "'{}' already has a `{}` section",
+            path.to_string_lossy(),
+            METADATA_HEADER
+        ));
+        return Ok(());
+    }
+
+    let manifest = format!("{}\n\n{}", manifest.trim_end(), METADATA);
+    fs::write(path, manifest).map_err(|e| Error::write(path, e))?;
+    messages::info(format_args!(
+        "Added `{}` to '{}'",
+        METADATA_HEADER,
+        path.to_string_lossy()
+    ));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::starter_template;
+
+    #[test]
+    fn template_for_published_crate() {
+        let expected = "\
+[![Crates.io](https://img.shields.io/crates/v/{{crate}}.svg)](https://crates.io/crates/{{crate}})
+[![Docs.rs](https://docs.rs/{{crate}}/badge.svg)](https://docs.rs/{{crate}})
+{{badges}}
+
+# {{crate}}
+
+{{readme}}
+
+## License
+
+{{license}}
+";
+
+        assert_eq!(starter_template(true, true, true), expected);
+    }
+
+    #[test]
+    fn template_for_unpublished_crate() {
+        assert_eq!(
+            starter_template(false, false, false),
+            "# {{crate}}\n\n{{readme}}\n"
+        );
+    }
+}
@@ -31,6 +31,10 @@
 //! And that's it! Your `README.md` is populated with the contents of the doc comments from your
 //! `lib.rs` (or `main.rs`).
 //!
+//! Like other cargo subcommands, `cargo readme` can be run from any subdirectory of the crate. The
+//! paths of the source, the template and the output are relative to the directory of
+//! `Cargo.toml`, not to the current directory.
+//!
 //! # Usage
 //!
 //! Let's take the following rust doc:
@@ -75,9 +79,20 @@
 //! - a badge was created from the one defined in the `[badges]` section of `Cargo.toml`
 //! - the crate name ("my-crate") was added
 //! - "# Examples" heading became "## Examples"
-//! - code block became "```rust"
+//! - code block became "```rust", or another info string given with `--fence-lang`
 //! - hidden line `# assert_eq!(4, sum2(2, 2));` was removed
 //!
+//! When the docs already start with a title, like `# My Crate`, use `--title-from-docs` to output
+//! it as the title of the README, instead of the crate name. A first heading with the crate name,
+//! like `# my-crate`, is never duplicated by the title, unless `--always-add-title` is given.
+//!
+//! Attributes like `no_run` or `ignore` are dropped from the code blocks. Use
+//! `--doctest-attributes keep` to output them as "```rust,no_run", or
+//! `--doctest-attributes comment` to write them in an HTML comment before the code block.
+//!
+//! Tabs inside code blocks are kept as they are. Use `--expand-tabs 4` to replace them with
+//! spaces, up to tab stops every 4 columns, so the code looks the same everywhere.
+//!
 //! `cargo-readme` also supports multiline doc comments `/*! */` (but you cannot mix styles):
 //!
 //! ~~~ignore
@@ -96,6 +111,247 @@
 //! */
 //! ~~~
 //!
+//! Doc attributes like `#![doc = "..."]` and `#![doc = r#"..."#]` are also read, in addition to
+//! doc comments. Files included with `#![doc = include_str!("../docs/intro.md")]` are read
+//! relative to the source file, and their relative links are rewritten to point to the same files
+//! from the README.
+//!
+//! Docs behind `#![cfg_attr(docsrs, doc = "...")]` are read when the predicate holds. The `doc`
+//! cfg is always active, others can be activated with `--cfg`, like
+//! `cargo readme --cfg docsrs --cfg 'feature="serde"'`.
+//!
+//! Intra-doc links, like ``[`Item`]`` or `[text](crate::module::Item)`, only work in rustdoc. Use
+//! `--intra-doc-links docs-rs` to point them to the documentation on docs.rs, or
+//! `--intra-doc-links strip` to keep only their text.
+//!
+//! Relative links, like `[guide](docs/guide.md)`, can be rewritten with `--absolute-links` into
+//! URLs to the `repository` set in `Cargo.toml`, so they also work on crates.io. They point to the
+//! `HEAD` branch, unless `repository-branch` is set in `[package.metadata.readme]`.
+//!
+//! The README shown on crates.io is sanitized and highlighted more strictly than on GitHub. With
+//! `--crates-io`, the HTML elements crates.io removes, like `<svg>`, are removed with their
+//! content, the tags like `<picture>` are removed keeping their content, and the info strings of
+//! the code blocks are reduced to a language crates.io highlights. Without a `repository`, the
+//! relative links, which crates.io cannot resolve, are replaced by their text.
+//!
+//! Images linked relative to the source file, like `![diagram](assets/diagram.svg)` in
+//! `src/lib.rs`, are rewritten to be relative to the README instead. With `--copy-assets`, the
+//! images are copied next to the README when it is written to a file, like to `out/assets/` with
+//! `-o out/README.md`, and their links are left as is.
+//!
+//! When the docs are spread across modules, `--include-modules` follows the `pub mod name;`
+//! declarations and appends the docs of each public module under a heading with its path.
+//!
+//! To generate the README of a module instead of the crate root, use
+//! `--entrypoint src/some/module.rs`. Its modules are then looked for in `src/some/module/`, and
+//! the modules with a `#[path = "..."]` attribute are followed too.
+//!
+//! The docs of several files can be concatenated, in order, by giving `--input` multiple times or a
+//! comma separated list, like `--input src/lib.rs,src/readme_extra.rs`.
+//!
+//! With `--input -`, the source is read from stdin, like in editor integrations. Add
+//! `--no-cargo-toml --crate-name NAME`, or `--no-manifest`, when there is no `Cargo.toml` to read
+//! the crate from, like for a script or the output of a code generator. The license is then given
+//! with `--license`, like `--license MIT`, which also replaces the one of `Cargo.toml` otherwise.
+//!
+//! Docs generated by macros, like `#![doc = concat!(...)]`, cannot be read from the source file.
+//! With `--backend rustdoc-json`, the crate docs are read from the JSON output of
+//! `cargo +nightly rustdoc` instead, exactly as rustdoc sees them. This requires the nightly
+//! toolchain.
+//!
+//! Sections that belong in rustdoc but not in the README, like "Implementation notes", can be
+//! removed with `--exclude-section HEADING`, or with `exclude-sections = ["Implementation notes"]`
+//! in `[package.metadata.readme]`. The section is removed up to the next heading of the same or a
+//! higher level.
+//!
+//! To generate other documents from the same docs, like a website snippet, use
+//! `--only-section HEADING` to output the content of a single section.
+//!
+//! Footnotes, like `text[^1]` with the definition `[^1]: Note.`, are kept as they are, even when
+//! their definition is in a removed section. For renderers without footnotes, use
+//! `--inline-footnotes` to turn them into inline text in parentheses.
+//!
+//! rustdoc renders the straight quotes, `--`, `---` and `...` of the docs as curly quotes,
+//! dashes and ellipses. Use `--smart-punctuation` for the README to read the same, the code
+//! blocks, code spans and link targets being left as they are.
+//!
+//! GitHub renders emoji shortcodes, like `:crab:`, but crates.io and docs.rs show them as written.
+//! Use `--emoji unicode` to write the emoji themselves, or `--emoji shortcodes` to go the other
+//! way. Unknown shortcodes, code blocks and code spans are left as they are.
+//!
+//! Links are kept as they are by default. Use `--link-style inline` to turn the reference links,
+//! like `[text][label]`, into inline links, or `--link-style reference` to turn the links into
+//! numbered reference links, like `[text][1]`, whose definitions are collected at the end.
+//!
+//! Raw HTML, like `<div class="warning">`, is passed through as is. Use `--sanitize-html strip` to
+//! remove the HTML tags, or `--sanitize-html escape` to show them as text.
+//!
+//! Mermaid diagrams, in ```` ```mermaid ```` code blocks, and display math, in ```` ```math ````
+//! code blocks or between `$$` lines, are passed through as is. Use `--diagrams github` to turn
+//! the `$$` blocks into ```` ```math ```` code blocks, or `--diagrams image` to replace the blocks
+//! by linked images, for renderers like crates.io. The mode can also be set with
+//! `diagrams = "image"` in `[package.metadata.readme]`.
+//!
+//! With `--github-alerts`, the `<div class="warning">` blocks and the blockquotes starting with
+//! `**Note:**` or `**Warning:**` are converted into GitHub alerts, like `> [!WARNING]`.
+//!
+//! Source files and templates with Windows line endings are read as usual. The output file uses
+//! `\n` line endings, use `--line-endings crlf` or `--line-endings native` to change them.
+//!
+//! The output always ends with a single new line. For markdown linters,
+//! `--strip-trailing-whitespace` removes the trailing whitespace of every line, and
+//! `--collapse-blank-lines` collapses the runs of blank lines. `--normalize-markdown` rewrites the
+//! syntax of the output in a single style, whatever the style of the doc comments: `#` headings,
+//! `---` thematic breaks, `-` and `1.` list markers, backtick fences, and `*` and `**` emphasis.
+//! For style guides asking for wrapped markdown, `--wrap 100` reflows the paragraphs, list items
+//! and block quotes at 100 columns, leaving the code blocks, tables and headings as they are.
+//!
+//! Content meant for rustdoc only, like links to the modules, can be left out of the README by
+//! putting it between the `<!-- cargo-readme skip-start -->` and `<!-- cargo-readme skip-end -->`
+//! markers, or the `<!-- cargo-readme docs-only-start -->` and
+//! `<!-- cargo-readme docs-only-end -->` markers. rustdoc does not show these HTML comments.
+//!
+//! Content meant for the README only, like badges, is hidden from rustdoc in an HTML comment
+//! opened by a `<!-- cargo-readme readme-only-start` line and closed by a
+//! `cargo-readme readme-only-end -->` line. The README shows the content between these lines.
+//!
+//! To keep hand-written content in the README, add the `<!-- cargo-readme start -->` and
+//! `<!-- cargo-readme end -->` markers to it and use `--in-place`. Only the content between the
+//! markers is replaced.
+//!
+//! The files written with `--output` start with a `<!-- generated by cargo-readme -->` comment.
+//! An existing file without this comment, or the cargo-readme markers, is not replaced, so a
+//! hand-written README is not lost by accident, unless it is the same as the generated one,
+//! like a README written with `cargo readme > README.md`. Use `--force` to replace it anyway.
+//! With `--backup`, the previous README is saved to `README.md.bak` before it is replaced, or to
+//! the file named with the suffix given, like `--backup=.orig`.
+//!
+//! With `--footer`, the README ends with a comment telling the version of cargo-readme and the
+//! hash of the source file, like `<!-- cargo-readme 3.3.1 source-hash fnv1a-64:... -->`, so
+//! `--check` also fails when the source changed without changing the docs.
+//!
+//! To check the README for problems, run `cargo readme lint`. It reports an empty README, missing
+//! required sections, relative links to files that do not exist, duplicate headings and code
+//! blocks without language. The checks are set in `[package.metadata.readme.lint]`, each as
+//! `error`, `warning` or `allow`:
+//!
+//! ```toml
+//! [package.metadata.readme.lint]
+//! required-sections = ["Usage", "License"]
+//! missing-section = "error"
+//! broken-link = "error"
+//! duplicate-heading = "warning"
+//! fence-without-language = "warning"
+//! empty = "error"
+//! ```
+//!
+//! To use the README as a page of a static site generator, like Hugo, Jekyll or Zola, add
+//! `--front-matter yaml` or `--front-matter toml`. The README then starts with a front matter
+//! block, with the crate name as title, its description, a date and its keywords as tags. The date
+//! of the existing README is kept, so `--check` does not fail the next day. A new README is dated
+//! with `--date`, or else the current date, or the one of `SOURCE_DATE_EPOCH` when it is set.
+//!
+//! For other tools, like static site generators, `--output-format json` outputs a JSON document
+//! instead of the markdown: the crate information, the values of the template variables, the
+//! sections of the README with their heading level, and the code blocks with their language and
+//! attributes, along with the markdown itself.
+//!
+//! For Sphinx based sites, `--output-format rst` converts the README to reStructuredText: the
+//! headings are underlined, the code blocks become `code-block` directives, the badges become
+//! `image` directives, the tables `list-table` directives, the GitHub alerts admonitions and the
+//! links anonymous hyperlinks. For Antora or Asciidoctor based sites, `--output-format asciidoc`
+//! converts it to AsciiDoc, to write a `README.adoc`: the code blocks become `source` blocks, the
+//! tables `|===` table blocks and the GitHub alerts, like `> [!NOTE]`, become admonitions.
+//!
+//! To publish a simple landing page from the same docs, `--output-format html` renders the README
+//! as a standalone HTML page, like `index.html`, with its stylesheet inline and the Rust code
+//! blocks highlighted.
+//!
+//! Binary crates can also get their man page from the same docs, with `--output-format man`: the
+//! headings, like `Synopsis` and `Options`, become the sections of the page, and the list items
+//! starting with a code span, like ``- `--verbose`: print more``, the tagged paragraphs of the
+//! options. The page is dated with `--date`, or the date of `SOURCE_DATE_EPOCH` when it is set, or
+//! else with the version of the crate, so it only changes along with the docs.
+//!
+//! With `--output-format text`, the markdown syntax is stripped and the code blocks are indented,
+//! for the long description of `--help`, or the `README.txt` of a release archive.
+//!
+//! With `--output-format mdbook`, the output is a chapter to drop into the `src/` directory of an
+//! mdBook: its headings start at level 1, the hidden lines and the attributes of the doc tests are
+//! kept for `mdbook test`, the intra-doc links point to docs.rs, and the relative links to the
+//! `repository` set in `Cargo.toml`.
+//!
+//! For projects whose docs live in Org files and Emacs, `--output-format org` converts the README
+//! to Org, to write a `README.org`: the headings start with `*`, the code blocks become
+//! `#+BEGIN_SRC rust` blocks and the links become Org links.
+//!
+//! To push the docs into a Confluence wiki from CI, `--output-format confluence` converts the
+//! README to Confluence wiki markup: the headings become `h1.` to `h6.`, the code blocks `{code}`
+//! macros, the GitHub alerts `{info}`, `{tip}`, `{note}` or `{warning}` macros, and the links
+//! `[text|url]`.
+//!
+//! To generate several files, like the README for GitHub and a page of the website, set named
+//! profiles in `Cargo.toml`, each with its template, output file, output format and other
+//! arguments:
+//!
+//! ```toml
+//! [package.metadata.readme.profiles.website]
+//! template = "WEBSITE.tpl"
+//! output = "docs/index.md"
+//! args = ["--front-matter", "yaml", "--absolute-links"]
+//! ```
+//!
+//! Then run `cargo readme --profile website`, or `cargo readme --all-profiles` to generate every
+//! profile. The arguments given on the command line override the ones of the profile.
+//!
+//! To follow the documentation of the crate over time, run `cargo readme stats`. It counts the
+//! lines of doc comments, the code blocks and how many are `ignore`d, the headings, the links and
+//! the words.
+//!
+//! In CI, use `--check` to make sure the README is up to date. The README is generated in memory
+//! and compared with the output file, and the command fails if they differ, showing the
+//! differences as a unified diff. Use `--diff` to only show the differences. Use `--dry-run` to
+//! only tell where the README would be written, and whether it would change the file.
+//!
+//! The differences, errors and warnings are colorized when written to a terminal, unless
+//! `NO_COLOR` is set. Use `--color always` or `--color never` to choose.
+//!
+//! To check the README before each commit, run `cargo readme install-hook`. It installs a git
+//! pre-commit hook running `cargo readme --check`, or regenerating the README and adding it to the
+//! commit with `--regenerate`. Remove it with `cargo readme uninstall-hook`.
+//!
+//! To complete the command line, load the script output by `cargo readme completions <SHELL>`,
+//! where the shell is `bash`, `elvish`, `fish`, `powershell` or `zsh`.
+//!
+//! For a README that is not generated, `cargo readme badges` outputs the badges of the crate, to
+//! paste into it: the crates.io, docs.rs and license badges of published crates, the MSRV badge
+//! when `rust-version` is set, and the badges defined in `Cargo.toml`.
+//!
+//! Other markdown files, like `CONTRIBUTING.md`, can get a table of contents with
+//! `cargo readme toc CONTRIBUTING.md`. It lists the level 2 and 3 headings, which
+//! `--min-level` and `--max-level` change, linking to the anchors GitHub gives them, or GitLab and
+//! rustdoc with `--anchor-style gitlab` or `--anchor-style rustdoc`. With
+//! `--in-place`, the table of contents replaces the content between the
+//! `<!-- cargo-readme toc start -->` and `<!-- cargo-readme toc end -->` markers of the file.
+//!
+//! While writing the docs, use `--watch` to regenerate the README each time one of the files it
+//! was generated from changes: the source file and its modules, the files included with
+//! `include_str!`, `Cargo.toml` and the template.
+//!
+//! To find out where the README comes from, use `-v` to list the files read and written, like the
+//! source file, `Cargo.toml` and the template, on stderr. Use `-vv` to also list the
+//! transformations applied to the docs. In scripts, use `--quiet` to leave out the warnings, like
+//! the one about falling back to `package.description`. For editors and CI, use
+//! `--message-format json` to write the warnings and errors as JSON objects, one per line, with
+//! their level, kind, message, and the file and line they are about when known.
+//!
+//! The command exits with code `2` when `--check` finds the README out of date, `3` when
+//! `Cargo.toml`, the template or the arguments are invalid, and `1` on the other errors.
+//!
+//! Sections edited by hand can also be tagged as manual, like
+//! `## Contributing <!-- cargo-readme manual -->`. When writing to an existing file, manual
+//! sections are carried over verbatim, replacing the generated section with the same heading.
+//!
 //! If you have additional information that does not fit in doc comments, you can use a template.
 //! Just create a file called `README.tpl` in the same directory as `Cargo.toml` with the following
 //! content:
@@ -141,10 +397,103 @@
 //!
 //! By default, `README.tpl` will be used as the template, but you can override it using the
 //! `--template` to choose a different template or `--no-template` to disable it.
+//!
+//! To start with a template, run `cargo readme init`. It writes a `README.tpl` with the badges, the
+//! docs and the license, and with `--metadata` also adds a `[package.metadata.readme]` section
+//! listing the settings to `Cargo.toml`.
+//!
+//! # Library
+//!
+//! `cargo readme` is a thin command line over the `cargo_readme` library, which has the whole
+//! pipeline: finding the source of the crate, extracting and processing the docs, and rendering the
+//! template. Build scripts, xtask crates and other tools can generate the README with
+//! `ReadmeBuilder`, which reads the entrypoint of the crate and its `README.tpl` by default:
+//!
+//! ```no_run
+//! use cargo_readme::ReadmeBuilder;
+//!
+//! let root = env!("CARGO_MANIFEST_DIR");
+//! let readme = ReadmeBuilder::new(root)
+//!     .configure(|options| options.github_alerts = true)
+//!     .license(false)
+//!     .generate()
+//!     .unwrap();
+//! std::fs::write(format!("{}/README.md", root), readme.text).unwrap();
+//! ```
+//!
+//! The generated `Readme` also lists the images to copy next to it with `copy_assets`, which are
+//! made with `AssetCopy::copy` once the README is written, and the warnings about the docs, which
+//! the library never prints. Nor does it read the clock or the environment: the date of the front
+//! matter and of the man page is `ReadmeOptions::date`. Its other messages, like the files that are
+//! read, go to the logger set with `log::set_logger`, if any, which `cargo readme` sets to write
+//! them to stderr with `-v` and `-vv`.
+//!
+//! The options are the fields of `ReadmeOptions`, changed with `configure`. As more options may be
+//! added, `ReadmeOptions` is not built field by field, but from `ReadmeOptions::default()`, given
+//! to `ReadmeBuilder::options` once changed.
+//!
+//! The source, the template and the manifest can also be set, from files or from text, like
+//! `ReadmeBuilder::new(root).source_path("src/main.rs").template("{{readme}}")`.
+//!
+//! To generate the README without touching the filesystem, like in tests or in a web service,
+//! `generate_readme_from_str` takes the text of the source, of `Cargo.toml` and of the template.
+//! The files included with `include_str!` and the modules are not read then.
+//!
+//! Custom rewrites, like shortening the company links or filtering words, are closures registered
+//! with `ReadmeBuilder::line_hook`, run on each line of the extracted docs, or with
+//! `before_template` and `after_template`, run on the whole README. They are also set on
+//! `ReadmeOptions::hooks`.
+//!
+//! The failures are returned as an `Error`, whose variant tells what went wrong, like
+//! `Error::ManifestParse` or `Error::TemplateMissingReadmeTag`, and whose message is the one
+//! `cargo readme` shows.
+//!
+//! Only the command line itself is left to the binary: reading the arguments, finding the output
+//! file and telling whether it can be replaced, and comparing the README with `--check`.
 
 mod config;
+mod error;
+pub mod log;
 mod readme;
 
 pub use config::get_manifest;
+pub use config::get_workspace;
+pub use config::parse_manifest;
 pub use config::project;
+pub use config::LintConfig;
+pub use config::Manifest;
+pub use config::ManifestLib;
+pub use config::Profile;
+pub use config::Severity;
+pub use error::Error;
+pub use readme::convert_line_endings;
+pub use readme::docs_stats;
+#[allow(deprecated)]
 pub use readme::generate_readme;
+pub use readme::generate_readme_bins;
+pub use readme::generate_readme_from_str;
+pub use readme::generate_readme_with_options;
+pub use readme::lint_readme;
+pub use readme::marked_content;
+pub use readme::table_of_contents;
+pub use readme::update_between_markers;
+pub use readme::update_table_of_contents;
+pub use readme::AnchorStyle;
+pub use readme::AssetCopy;
+pub use readme::Backend;
+pub use readme::DiagramBlocks;
+pub use readme::DocStats;
+pub use readme::DoctestAttributes;
+pub use readme::Emoji;
+pub use readme::FrontMatter;
+pub use readme::HiddenLines;
+pub use readme::Hooks;
+pub use readme::IntraDocLinks;
+pub use readme::LineEndings;
+pub use readme::LinkStyle;
+pub use readme::LintProblem;
+pub use readme::OutputFormat;
+pub use readme::Readme;
+pub use readme::ReadmeBuilder;
+pub use readme::ReadmeOptions;
+pub use readme::SanitizeHtml;
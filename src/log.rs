@@ -0,0 +1,84 @@
+//! Messages of the library about the generation of the readme
+//!
+//! The library writes nothing itself: its messages, like the files that are read and the
+//! transformations applied to the docs, go to the logger set with [`set_logger`], which tells
+//! whether and how they are shown. Without a logger, they are dropped. The logger is also told
+//! the paths of the files that are read, so the command line can watch them.
+
+use std::fmt;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Level of a message
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum Level {
+    /// Something likely to be wrong, like a proc-macro crate read for its docs
+    Warning,
+    /// The files that are read
+    Verbose,
+    /// The transformations applied to the docs
+    Trace,
+}
+
+/// Receiver of the messages of the library
+///
+/// ```
+/// use std::fmt;
+/// use cargo_readme::log::{self, Level, Logger};
+///
+/// struct Warnings;
+///
+/// impl Logger for Warnings {
+///     fn log(&self, level: Level, message: fmt::Arguments) {
+///         if level == Level::Warning {
+///             eprintln!("warning: {}", message);
+///         }
+///     }
+/// }
+///
+/// log::set_logger(Box::new(Warnings));
+/// ```
+pub trait Logger: Send + Sync {
+    /// Handle the `message` of `level`
+    fn log(&self, level: Level, message: fmt::Arguments);
+
+    /// Handle the `path` of a file read to generate the readme, like a module or `Cargo.toml`
+    fn read(&self, _path: &Path) {}
+}
+
+static LOGGER: OnceLock<Box<dyn Logger>> = OnceLock::new();
+
+/// Set the logger receiving the messages of the library, returning `false` when one was already
+/// set, as it can only be set once
+pub fn set_logger(logger: Box<dyn Logger>) -> bool {
+    LOGGER.set(logger).is_ok()
+}
+
+/// Send `message` to the logger as a warning
+pub(crate) fn warn(message: fmt::Arguments) {
+    log(Level::Warning, message);
+}
+
+/// Send `message` to the logger, as a file that is read
+pub(crate) fn verbose(message: fmt::Arguments) {
+    log(Level::Verbose, message);
+}
+
+/// Send `message` to the logger, as a transformation applied to the docs
+pub(crate) fn trace(message: fmt::Arguments) {
+    log(Level::Trace, message);
+}
+
+/// Send the `path` of a file that is read to the logger
+pub(crate) fn read(path: &Path) {
+    if let Some(logger) = LOGGER.get() {
+        logger.read(path);
+    }
+}
+
+/// Send the `message` of `level` to the logger, if any
+fn log(level: Level, message: fmt::Arguments) {
+    if let Some(logger) = LOGGER.get() {
+        logger.log(level, message);
+    }
+}
@@ -1,24 +1,110 @@
 //! Generate README.md from doc comments.
 
-use clap::Parser;
+use clap::{CommandFactory, Parser};
+use clap_complete::Shell;
+use std::env;
+use std::fs::{self, File};
 use std::io;
-use std::io::Write;
+use std::io::{IsTerminal, Read};
+use std::iter;
+use std::path::Path;
 
+use cargo_readme::{
+    AnchorStyle, Backend, DiagramBlocks, DoctestAttributes, Emoji, Error, FrontMatter, HiddenLines,
+    IntraDocLinks, LineEndings, LinkStyle, OutputFormat, Readme, ReadmeOptions, SanitizeHtml,
+};
+
+mod badges;
+mod diff;
 mod helper;
+mod hook;
+mod init;
+mod messages;
+mod watch;
+
+const DEFAULT_OUTPUT: &str = "README.md";
+/// Input or output standing for stdin or stdout
+const STDIO: &str = "-";
+
+/// Exit code of the errors other than the ones below
+const EXIT_ERROR: i32 = 1;
+/// Exit code of `--check` when the readme is not up to date
+const EXIT_OUTDATED: i32 = 2;
+/// Exit code of the errors in `Cargo.toml`, the template or the arguments
+const EXIT_CONFIG: i32 = 3;
 
 fn main() {
-    let args = Args::parse();
+    let args = match Args::try_parse() {
+        Ok(args) => args,
+        // the help and the version are not errors
+        Err(e) if !e.use_stderr() => e.exit(),
+        Err(e) => {
+            let _ = e.print();
+            std::process::exit(EXIT_CONFIG);
+        }
+    };
+    let Command::Readme(ref readme_args) = args.command;
+    cargo_readme::log::set_logger(Box::new(messages::Stderr));
+    messages::set_verbosity(match readme_args.verbose {
+        _ if readme_args.quiet => messages::Verbosity::Quiet,
+        0 => messages::Verbosity::Normal,
+        1 => messages::Verbosity::Verbose,
+        _ => messages::Verbosity::Trace,
+    });
+    messages::set_message_format(match readme_args.message_format.as_deref() {
+        Some("json") => messages::MessageFormat::Json,
+        _ => messages::MessageFormat::Human,
+    });
+    messages::set_color(use_color(readme_args, io::stderr().is_terminal()));
+
     let result = match &args.command {
+        Command::Readme(ReadmeArgs {
+            command: Some(ReadmeCommand::Init(init_args)),
+            ..
+        }) => init(init_args),
+        Command::Readme(ReadmeArgs {
+            command: Some(ReadmeCommand::InstallHook(hook_args)),
+            ..
+        }) => install_hook(hook_args),
+        Command::Readme(ReadmeArgs {
+            command: Some(ReadmeCommand::UninstallHook(hook_args)),
+            ..
+        }) => uninstall_hook(hook_args),
+        Command::Readme(ReadmeArgs {
+            command: Some(ReadmeCommand::Completions(completions_args)),
+            ..
+        }) => print_completions(completions_args),
+        Command::Readme(ReadmeArgs {
+            command: Some(ReadmeCommand::Badges(badges_args)),
+            ..
+        }) => print_badges(badges_args),
+        Command::Readme(ReadmeArgs {
+            command: Some(ReadmeCommand::Toc(toc_args)),
+            ..
+        }) => toc(toc_args),
+        Command::Readme(
+            readme_args @ ReadmeArgs {
+                command: Some(ReadmeCommand::Lint(lint_args)),
+                ..
+            },
+        ) => lint(readme_args, lint_args),
+        Command::Readme(
+            readme_args @ ReadmeArgs {
+                command: Some(ReadmeCommand::Stats),
+                ..
+            },
+        ) => print_stats(readme_args),
+        Command::Readme(readme_args)
+            if readme_args.profile.is_some() || readme_args.all_profiles =>
+        {
+            execute_profiles(readme_args)
+        }
+        Command::Readme(readme_args) if readme_args.watch => watch(readme_args),
         Command::Readme(readme_args) => execute(readme_args),
     };
-    match result {
-        Err(e) => {
-            io::stderr()
-                .write_fmt(format_args!("Error: {}\n", e))
-                .expect("An error occurred while trying to show an error message");
-            std::process::exit(1);
-        }
-        _ => {}
+    if let Err(e) = result {
+        messages::error(&e);
+        std::process::exit(exit_code(&e));
     }
 }
 /// The command line interface for setting up a Bottlerocket TestSys cluster and running tests.
@@ -39,6 +125,9 @@ enum Command {
 #[derive(Debug, Parser)]
 #[clap(author, version, about)]
 struct ReadmeArgs {
+    #[clap(subcommand)]
+    command: Option<ReadmeCommand>,
+
     /// Do not prepend badges line.
     /// By default, badges defined in Cargo.toml are prepended to the output.
     /// Ignored when using a template.
@@ -51,6 +140,16 @@ struct ReadmeArgs {
     #[clap(long)]
     no_indent_headings: bool,
 
+    /// Number of levels to shift headings by.
+    /// By default, headings are shifted by 1 ('#' becomes '##'). Greater values demote the
+    /// headings further and negative values promote them, to fit an existing README structure.
+    #[clap(
+        long,
+        allow_hyphen_values = true,
+        conflicts_with = "no_indent_headings"
+    )]
+    heading_offset: Option<isize>,
+
     /// Do not append license line.
     /// By default, the license defined in `Cargo.toml` will be prepended to the output.
     /// Ignored when using a template.
@@ -59,7 +158,7 @@ struct ReadmeArgs {
 
     /// Ignore template file when generating README.
     /// Only useful to ignore default template `README.tpl`.
-    #[clap(long)]
+    #[clap(long, conflicts_with = "template")]
     no_template: bool,
 
     /// Do not prepend title line.
@@ -67,19 +166,223 @@ struct ReadmeArgs {
     #[clap(long)]
     no_title: bool,
 
+    /// Prepend the title even when the docs already start with a heading with the crate name.
+    /// By default, that heading is replaced by the title, so it is not duplicated.
+    #[clap(long, conflicts_with = "no_title")]
+    always_add_title: bool,
+
+    /// Use the first heading of the docs as title, when the docs start with a `# Heading`.
+    /// The heading is output as is, instead of being indented below a '# crate-name' title.
+    #[clap(long, conflicts_with = "all_bins")]
+    title_from_docs: bool,
+
+    /// Keep the hidden lines of doc tests, like `# use std::fmt;`, in the output.
+    /// By default, hidden lines are removed, as in rustdoc.
+    #[clap(long)]
+    show_hidden_lines: bool,
+
+    /// Remove the `#` mark of the hidden lines kept by `--show-hidden-lines`,
+    /// so the code blocks are complete and compilable.
+    #[clap(long, requires = "show_hidden_lines")]
+    strip_hidden_marks: bool,
+
+    /// Info string of the rust code blocks in the output.
+    /// By default, doc tests are output as ```rust. An empty value outputs a bare fence, and
+    /// other values, like `rust,ignore`, are used as is.
+    #[clap(long, value_name = "LANG")]
+    fence_lang: Option<String>,
+
+    /// Keep the doc test attributes telling how a code block is run, like `no_run` or `ignore`.
+    /// With `keep`, they are added to the info string, like ```rust,no_run. With `comment`, they
+    /// are written in an HTML comment before the code block, like `<!-- no_run -->`. By default,
+    /// they are dropped.
+    #[clap(long, value_name = "MODE", value_parser = ["drop", "keep", "comment"])]
+    doctest_attributes: Option<String>,
+
+    /// Expand the tabs of the code blocks to spaces, with tab stops every N columns.
+    /// The alignment of the code is kept. The tabs outside code blocks are left as they are.
+    #[clap(long, value_name = "N", value_parser = clap::value_parser!(u8).range(1..))]
+    expand_tabs: Option<u8>,
+
+    /// Convert rustdoc admonitions into GitHub alerts.
+    /// The `<div class="warning">` blocks become `> [!WARNING]` alerts, and the blockquotes starting
+    /// with `**Note:**` or `**Warning:**` become `> [!NOTE]` or `> [!WARNING]` alerts.
+    #[clap(long)]
+    github_alerts: bool,
+
+    /// Sanitize the raw HTML of the doc comments, like `<div class="warning">`.
+    /// With `strip`, the HTML tags and comments are removed, keeping the text between tags. With
+    /// `escape`, they are escaped to be shown as text. By default, HTML is passed through as is.
+    #[clap(long, value_name = "MODE", value_parser = ["passthrough", "strip", "escape"])]
+    sanitize_html: Option<String>,
+
+    /// Rewrite the mermaid diagrams and the display math, in ```mermaid and ```math code blocks or
+    /// between `$$` lines. With `github`, the `$$` blocks become ```math code blocks, rendered by
+    /// GitHub. With `image`, the blocks are replaced by linked images, rendered by mermaid.ink and
+    /// CodeCogs, for crates.io and other renderers. Defaults to `diagrams` in
+    /// `[package.metadata.readme]`, or to `passthrough`, keeping them as they are.
+    #[clap(long, value_name = "MODE", value_parser = ["passthrough", "github", "image"])]
+    diagrams: Option<String>,
+
+    /// Rewrite the intra-doc links, like [`Item`] or [text](crate::module::Item).
+    /// With `docs-rs`, the links point to the documentation of the item on docs.rs. With `strip`,
+    /// the links are removed and only their text is kept.
+    #[clap(long, value_name = "MODE", value_parser = ["docs-rs", "strip"])]
+    intra_doc_links: Option<String>,
+
+    /// Remove the intra-doc links that cannot be resolved, keeping their text.
+    /// Links like [`Item`] are turned into `Item`, so they do not show as broken links. Links
+    /// cannot be resolved without `--intra-doc-links docs-rs`, or when the crate is not published.
+    #[clap(long)]
+    strip_unresolved_links: bool,
+
+    /// Rewrite the relative links into absolute URLs to the `repository` set in `Cargo.toml`.
+    /// The links point to the `HEAD` branch, or to `repository-branch` if set in
+    /// `[package.metadata.readme]`, so they work from crates.io too.
+    #[clap(long)]
+    absolute_links: bool,
+
+    /// Rewrite the docs for crates.io, so the README looks right on the crate page too.
+    /// The HTML elements crates.io removes, like `<svg>`, are removed with their content, and the
+    /// tags like `<picture>` keeping it. The info strings of the code blocks are reduced to a
+    /// language crates.io highlights. Without a `repository` in `Cargo.toml`, the relative links
+    /// are removed, keeping their text.
+    #[clap(long)]
+    crates_io: bool,
+
+    /// Copy the images linked relative to the source file next to the README, when it is written,
+    /// to the same path from the directory of the output file.
+    /// By default, the links to these images are rewritten to be relative to the README instead.
+    #[clap(long)]
+    copy_assets: bool,
+
+    /// Append the docs of the public modules to the output.
+    /// The modules declared with `pub mod name;` are followed recursively from the source file,
+    /// and the docs of each module are placed under a heading with the module path.
+    #[clap(long, conflicts_with = "all_bins")]
+    include_modules: bool,
+
     /// File to read from.
-    /// If not provided, will try to use `src/lib.rs`, then `src/main.rs`. If neither file
-    /// could be found, will look into `Cargo.toml` for a `[lib]`, then for a single `[[bin]]`.
+    /// If not provided, will use the `[lib]` path from `Cargo.toml`, then try `src/lib.rs` and
+    /// `src/main.rs`. If none could be found, will look into `Cargo.toml` for a single `[[bin]]`.
     /// If multiple binaries are found, an error will be returned.
-    #[clap(long, short = 'i')]
-    input: Option<String>,
+    /// Can be given multiple times, or as a comma separated list, like
+    /// `--input src/lib.rs,src/readme_extra.rs`, to concatenate the docs of the files in order.
+    /// Use `-` as the first input to read from stdin.
+    #[clap(long, short = 'i', value_name = "FILE", value_delimiter = ',')]
+    input: Vec<String>,
+
+    /// Binary target to read from.
+    /// The source file of the binary is resolved from `Cargo.toml`, or from the default
+    /// `src/main.rs` and `src/bin/` locations.
+    #[clap(long, conflicts_with = "input")]
+    bin: Option<String>,
+
+    /// Example target to read from.
+    /// The source file of the example is resolved from `Cargo.toml`, or from the default
+    /// `examples/` location.
+    #[clap(long, conflicts_with_all = ["input", "bin"])]
+    example: Option<String>,
+
+    /// Merge the docs of every binary target into the output.
+    /// The docs of each binary are placed under a heading with the binary name.
+    #[clap(long, conflicts_with_all = ["input", "bin", "example"])]
+    all_bins: bool,
+
+    /// Module file to read from, instead of the crate root.
+    /// Unlike `--input`, the file is read as a module, like `src/some/module.rs`, so the modules
+    /// it declares are looked for in `src/some/module/` by `--include-modules`.
+    #[clap(long, value_name = "FILE", conflicts_with_all = ["input", "bin", "example", "all_bins"])]
+    entrypoint: Option<String>,
 
-    /// File to write to. If not provided, will output to stdout.
+    /// Where to read the doc comments from.
+    /// By default, the doc comments are parsed from the source file. With `rustdoc-json`, the
+    /// crate docs are read from the JSON output of `cargo +nightly rustdoc`, so the docs generated
+    /// by macros are captured too. Only the library target, or the binary given by `--bin`, can
+    /// be read with `rustdoc-json`.
+    #[clap(long, value_name = "BACKEND", value_parser = ["source", "rustdoc-json"])]
+    backend: Option<String>,
+
+    /// File to write to. If not provided, or if `-`, will output to stdout.
     #[clap(long, short = 'o')]
     output: Option<String>,
 
+    /// Update the output file in place, between marker comments.
+    /// Only the content between `<!-- cargo-readme start -->` and `<!-- cargo-readme end -->` is
+    /// replaced, leaving the rest of the file untouched. The output file defaults to `README.md`.
+    #[clap(long)]
+    in_place: bool,
+
+    /// Check that the output file is up to date, without writing it.
+    /// The README is generated in memory and compared with the output file, which defaults to
+    /// `README.md`. If they differ, the differences are shown as a unified diff and the command
+    /// fails, so CI can catch a stale README.
+    #[clap(long)]
+    check: bool,
+
+    /// Show the differences between the output file and the generated README, without writing it.
+    /// Like `--check`, but the command succeeds when they differ.
+    #[clap(long, conflicts_with = "check")]
+    diff: bool,
+
+    /// Regenerate the README each time one of the files it was generated from changes, like the
+    /// source files, `Cargo.toml` or the template, until the command is interrupted.
+    #[clap(long, conflicts_with_all = ["check", "diff", "workspace"])]
+    watch: bool,
+
+    /// Generate the README without writing it, and tell where it would be written and whether it
+    /// would change the output file.
+    #[clap(long, conflicts_with_all = ["check", "diff", "watch"])]
+    dry_run: bool,
+
+    /// Save the previous content of the output file before replacing it, to the file named after
+    /// it with SUFFIX, `.bak` by default, like `README.md.bak`.
+    #[clap(
+        long,
+        value_name = "SUFFIX",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = ".bak"
+    )]
+    backup: Option<String>,
+
+    /// Replace the output file even if it was not generated by cargo-readme.
+    /// By default, an existing output file without the `<!-- generated by cargo-readme -->`
+    /// comment written at its top, or the cargo-readme markers, is not replaced, unless its
+    /// content is the same as the generated README.
+    #[clap(long)]
+    force: bool,
+
+    /// Report the files read and written to stderr.
+    /// Use `-vv` to report the transformations applied to the docs too.
+    #[clap(long, short = 'v', action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Do not report the warnings, like the crate falling back to `package.description`.
+    /// Errors are still reported.
+    #[clap(long, short = 'q', conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Write the warnings and errors to stderr as JSON objects, one per line, with their level,
+    /// kind, message, and the file and line they are about when known.
+    #[clap(long, value_name = "FORMAT", value_parser = ["human", "json"])]
+    message_format: Option<String>,
+
+    /// When to colorize the differences shown by `--check` and `--diff`, and the labels of the
+    /// errors and warnings, like the problems found by `lint`.
+    /// With `auto`, the default, they are colorized when written to a terminal and `NO_COLOR` is
+    /// not set.
+    #[clap(long, value_name = "WHEN", value_parser = ["auto", "always", "never"])]
+    color: Option<String>,
+
+    /// Line endings of the output file.
+    /// With `native`, the line endings of the platform are used, `crlf` on Windows and `lf`
+    /// elsewhere. Defaults to `lf`.
+    #[clap(long, value_name = "STYLE", value_parser = ["lf", "crlf", "native"])]
+    line_endings: Option<String>,
+
     /// Directory to be set as project root (where `Cargo.toml` is)
-    /// Defaults to the current directory.
+    /// Defaults to the nearest directory with a `Cargo.toml`, from the current directory up.
     #[clap(long = "project-root", short = 'r')]
     root: Option<String>,
 
@@ -87,41 +390,1106 @@ struct ReadmeArgs {
     /// Default behavior is to use `README.tpl` if it exists.
     #[clap(long, short = 't')]
     template: Option<String>,
+
+    /// Generate the README of every member of the workspace.
+    /// Each README is written to `README.md` in the member directory, unless `--output` is given.
+    /// Crates with `publish = false` are skipped when `skip-unpublished` is set in
+    /// `[workspace.metadata.readme]`, or rendered with `unpublished-template` if it is set.
+    #[clap(long, conflicts_with_all = ["input", "bin", "example"])]
+    workspace: bool,
+
+    /// Generate the README with the settings of the profile set in
+    /// `[package.metadata.readme.profiles.NAME]`, like its template, output file and format.
+    /// The options given on the command line take precedence over the ones of the profile.
+    #[clap(long, value_name = "NAME", conflicts_with_all = ["workspace", "watch"])]
+    profile: Option<String>,
+
+    /// Generate the README of every profile set in `[package.metadata.readme.profiles]`.
+    #[clap(long, conflicts_with_all = ["profile", "workspace", "watch"])]
+    all_profiles: bool,
+
+    /// Cfg option considered active when reading `#![cfg_attr(..., doc = "...")]` attributes.
+    /// Can be given multiple times, like `--cfg docsrs --cfg 'feature="serde"'`. The `doc` option
+    /// is always active.
+    #[clap(long = "cfg", value_name = "CFG")]
+    cfgs: Vec<String>,
+
+    /// Heading of a section to remove from the output, along with its content.
+    /// Can be given multiple times. Headings are matched ignoring case, and the sections listed in
+    /// `exclude-sections` in `[package.metadata.readme]` are removed too.
+    #[clap(long = "exclude-section", value_name = "HEADING")]
+    exclude_sections: Vec<String>,
+
+    /// Heading of the only section to output, like `Usage`.
+    /// The content of the section is output without the heading itself, up to the next heading
+    /// of the same or a higher level, to generate other documents from the same docs.
+    #[clap(
+        long,
+        value_name = "HEADING",
+        conflicts_with_all = ["all_bins", "include_modules"]
+    )]
+    only_section: Option<String>,
+
+    /// Turn the footnotes into inline text in parentheses.
+    /// A reference like `text[^1]`, with the definition `[^1]: Note.`, becomes `text (Note.)`, for
+    /// renderers that do not support footnotes.
+    #[clap(long)]
+    inline_footnotes: bool,
+
+    /// Convert the punctuation of the prose into its typographic form, like rustdoc does.
+    /// The straight quotes become curly quotes, `--` an en dash, `---` an em dash and `...` an
+    /// ellipsis. The code blocks, code spans and link targets are left as they are.
+    #[clap(long)]
+    smart_punctuation: bool,
+
+    /// Convert the emoji of the prose.
+    /// With `unicode`, the GitHub shortcodes, like `:crab:`, become Unicode emoji, so they render
+    /// on crates.io too. With `shortcodes`, the Unicode emoji become shortcodes. Unknown shortcodes,
+    /// code blocks and code spans are left as they are.
+    #[clap(long, value_name = "MODE", value_parser = ["unicode", "shortcodes"])]
+    emoji: Option<String>,
+
+    /// Style of the links of the output.
+    /// With `inline`, the reference links, like `[text][label]`, are turned into inline links, like
+    /// `[text](url)`. With `reference`, the links are turned into numbered reference links whose
+    /// definitions are collected at the end of the output. By default, they are kept as they are.
+    #[clap(long, value_name = "STYLE", value_parser = ["keep", "inline", "reference"])]
+    link_style: Option<String>,
+
+    /// Format of the output. Defaults to `markdown`.
+    /// With `json`, the output is a JSON document with the crate information, the values of the
+    /// template variables, the sections and the code blocks of the README, and its markdown.
+    /// With `rst`, the README is converted to reStructuredText, for Sphinx based sites, and with
+    /// `asciidoc`, to AsciiDoc, like `README.adoc`, for Antora or Asciidoctor based sites.
+    /// With `html`, the output is a standalone HTML page, like `index.html`, with the Rust code
+    /// blocks highlighted. With `man`, the output is a roff man page, for binary crates whose
+    /// docs have sections like `NAME`, `SYNOPSIS` and `OPTIONS`. With `text`, the markdown syntax
+    /// is stripped, for `--help` long descriptions or a `README.txt`; as plain text has no
+    /// comment to tell it was generated, replacing an existing file needs `--force`. With
+    /// `mdbook`, the output is a chapter of an mdBook, like `book/src/intro.md`: the headings
+    /// start at level 1, the hidden lines and the attributes of the doc tests are kept for
+    /// `mdbook test`, unless set otherwise, the intra-doc links point to docs.rs and the relative
+    /// links to the `repository`. With `org`, the README is converted to Org, like `README.org`,
+    /// for the docs kept with Emacs. With `confluence`, the README is converted to Confluence wiki
+    /// markup, to push the docs into a wiki page from CI; like plain text, the wiki markup has no
+    /// comments, so replacing an existing file needs `--force`.
+    #[clap(
+        long,
+        value_name = "FORMAT",
+        value_parser = [
+            "markdown", "json", "rst", "asciidoc", "html", "man", "text", "mdbook", "org",
+            "confluence"
+        ]
+    )]
+    output_format: Option<String>,
+
+    /// Prepend a front matter block, with the title, description, date and tags of the crate, so
+    /// the README can be used as a page of a static site generator, like Hugo, Jekyll or Zola.
+    /// The date of the existing output file is kept, otherwise it is the current date, or the one
+    /// of `SOURCE_DATE_EPOCH` when it is set.
+    #[clap(long, value_name = "FORMAT", value_parser = ["yaml", "toml"])]
+    front_matter: Option<String>,
+
+    /// Date of the front matter or of the man page, like `2024-01-31`.
+    /// The man page is dated with the one of `SOURCE_DATE_EPOCH` when it is set, or else with the
+    /// version of the crate.
+    #[clap(long, value_name = "DATE")]
+    date: Option<String>,
+
+    /// Normalize the markdown syntax of the output, whatever the style of the doc comments.
+    /// The headings are written with `#`, the thematic breaks `---`, the list markers `-` and `1.`,
+    /// the code fences with backticks, and the emphasis with `*` and `**`.
+    #[clap(long)]
+    normalize_markdown: bool,
+
+    /// Wrap the prose paragraphs of the output at N columns.
+    /// The lines of each paragraph, list item or block quote are joined and broken again before
+    /// the column. The code blocks, tables, headings and HTML are not wrapped, nor the words longer
+    /// than N, like URLs.
+    #[clap(long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    wrap: Option<u16>,
+
+    /// Remove the trailing whitespace of every line of the output.
+    /// Hard line breaks made of trailing spaces are kept as a backslash at the end of the line.
+    #[clap(long)]
+    strip_trailing_whitespace: bool,
+
+    /// Collapse the runs of blank lines of the output into a single blank line.
+    /// Blank lines inside code blocks are kept.
+    #[clap(long)]
+    collapse_blank_lines: bool,
+
+    /// Append a comment with the version of cargo-readme and the hash of the source file, like
+    /// `<!-- cargo-readme 3.3.1 source-hash fnv1a-64:... -->`.
+    /// The output changes with the source, even when the docs stay the same.
+    #[clap(long)]
+    footer: bool,
+
+    /// Name of the crate, used as title instead of the `name` set in `Cargo.toml`.
+    #[clap(long, value_name = "NAME")]
+    crate_name: Option<String>,
+
+    /// License of the crate, appended to the output instead of the `license` set in `Cargo.toml`.
+    #[clap(long, value_name = "LICENSE")]
+    license: Option<String>,
+
+    /// Do not read `Cargo.toml`, like for a source read from stdin with `--input -`, or a script
+    /// outside a Cargo project. Also available as `--no-manifest`.
+    /// The crate is only known by `--crate-name` and `--license`, so there are no badges or
+    /// version.
+    #[clap(
+        long,
+        alias = "no-manifest",
+        requires_all = ["crate_name", "input"],
+        conflicts_with_all = ["workspace", "package", "all_bins"],
+    )]
+    no_cargo_toml: bool,
+
+    /// Workspace member to generate the README for.
+    /// The member is looked up in the workspace at the project root.
+    #[clap(long, short = 'p', conflicts_with = "workspace")]
+    package: Option<String>,
+}
+
+impl ReadmeArgs {
+    // Gives the arguments of the profile `profile_args` overridden by these ones, the flags set in
+    // either being set
+    fn with_profile(&self, profile_args: ReadmeArgs) -> ReadmeArgs {
+        // every field is listed, so a new argument cannot be left out of the profiles
+        let ReadmeArgs {
+            command: _,
+            no_badges,
+            no_indent_headings,
+            heading_offset,
+            no_license,
+            no_template,
+            no_title,
+            always_add_title,
+            title_from_docs,
+            show_hidden_lines,
+            strip_hidden_marks,
+            fence_lang,
+            doctest_attributes,
+            expand_tabs,
+            github_alerts,
+            sanitize_html,
+            diagrams,
+            intra_doc_links,
+            strip_unresolved_links,
+            absolute_links,
+            crates_io,
+            copy_assets,
+            include_modules,
+            input,
+            bin,
+            example,
+            all_bins,
+            entrypoint,
+            backend,
+            output,
+            in_place,
+            check,
+            diff,
+            watch,
+            dry_run,
+            backup,
+            force,
+            verbose,
+            quiet,
+            message_format,
+            color,
+            line_endings,
+            root,
+            template,
+            workspace,
+            profile: _,
+            all_profiles: _,
+            cfgs,
+            exclude_sections,
+            only_section,
+            inline_footnotes,
+            smart_punctuation,
+            emoji,
+            link_style,
+            output_format,
+            front_matter,
+            date,
+            normalize_markdown,
+            wrap,
+            strip_trailing_whitespace,
+            collapse_blank_lines,
+            footer,
+            crate_name,
+            license,
+            no_cargo_toml,
+            package,
+        } = profile_args;
+
+        ReadmeArgs {
+            command: None,
+            no_badges: self.no_badges || no_badges,
+            no_indent_headings: self.no_indent_headings || no_indent_headings,
+            heading_offset: self.heading_offset.or(heading_offset),
+            no_license: self.no_license || no_license,
+            no_template: self.no_template || no_template,
+            no_title: self.no_title || no_title,
+            always_add_title: self.always_add_title || always_add_title,
+            title_from_docs: self.title_from_docs || title_from_docs,
+            show_hidden_lines: self.show_hidden_lines || show_hidden_lines,
+            strip_hidden_marks: self.strip_hidden_marks || strip_hidden_marks,
+            fence_lang: self.fence_lang.clone().or(fence_lang),
+            doctest_attributes: self.doctest_attributes.clone().or(doctest_attributes),
+            expand_tabs: self.expand_tabs.or(expand_tabs),
+            github_alerts: self.github_alerts || github_alerts,
+            sanitize_html: self.sanitize_html.clone().or(sanitize_html),
+            diagrams: self.diagrams.clone().or(diagrams),
+            intra_doc_links: self.intra_doc_links.clone().or(intra_doc_links),
+            strip_unresolved_links: self.strip_unresolved_links || strip_unresolved_links,
+            absolute_links: self.absolute_links || absolute_links,
+            crates_io: self.crates_io || crates_io,
+            copy_assets: self.copy_assets || copy_assets,
+            include_modules: self.include_modules || include_modules,
+            input: match self.input.is_empty() {
+                true => input,
+                false => self.input.clone(),
+            },
+            bin: self.bin.clone().or(bin),
+            example: self.example.clone().or(example),
+            all_bins: self.all_bins || all_bins,
+            entrypoint: self.entrypoint.clone().or(entrypoint),
+            backend: self.backend.clone().or(backend),
+            output: self.output.clone().or(output),
+            in_place: self.in_place || in_place,
+            check: self.check || check,
+            diff: self.diff || diff,
+            watch: self.watch || watch,
+            dry_run: self.dry_run || dry_run,
+            backup: self.backup.clone().or(backup),
+            force: self.force || force,
+            verbose: self.verbose.max(verbose),
+            quiet: self.quiet || quiet,
+            message_format: self.message_format.clone().or(message_format),
+            color: self.color.clone().or(color),
+            line_endings: self.line_endings.clone().or(line_endings),
+            root: self.root.clone().or(root),
+            template: self.template.clone().or(template),
+            workspace: self.workspace || workspace,
+            profile: self.profile.clone(),
+            all_profiles: self.all_profiles,
+            cfgs: match self.cfgs.is_empty() {
+                true => cfgs,
+                false => self.cfgs.clone(),
+            },
+            exclude_sections: match self.exclude_sections.is_empty() {
+                true => exclude_sections,
+                false => self.exclude_sections.clone(),
+            },
+            only_section: self.only_section.clone().or(only_section),
+            inline_footnotes: self.inline_footnotes || inline_footnotes,
+            smart_punctuation: self.smart_punctuation || smart_punctuation,
+            emoji: self.emoji.clone().or(emoji),
+            link_style: self.link_style.clone().or(link_style),
+            output_format: self.output_format.clone().or(output_format),
+            front_matter: self.front_matter.clone().or(front_matter),
+            date: self.date.clone().or(date),
+            normalize_markdown: self.normalize_markdown || normalize_markdown,
+            wrap: self.wrap.or(wrap),
+            strip_trailing_whitespace: self.strip_trailing_whitespace || strip_trailing_whitespace,
+            collapse_blank_lines: self.collapse_blank_lines || collapse_blank_lines,
+            footer: self.footer || footer,
+            crate_name: self.crate_name.clone().or(crate_name),
+            license: self.license.clone().or(license),
+            no_cargo_toml: self.no_cargo_toml || no_cargo_toml,
+            package: self.package.clone().or(package),
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+enum ReadmeCommand {
+    /// Set up the crate to generate its README, with a starter template
+    Init(InitArgs),
+    /// Install a git pre-commit hook checking that the README is up to date
+    InstallHook(InstallHookArgs),
+    /// Remove the git pre-commit hook installed by `install-hook`
+    UninstallHook(UninstallHookArgs),
+    /// Output the completion script of a shell
+    Completions(CompletionsArgs),
+    /// Check the generated README for problems, like broken links or missing sections
+    Lint(LintArgs),
+    /// Output statistics about the doc comments, like the number of code blocks or links.
+    /// The doc comments are read with the options given before `stats`.
+    Stats,
+    /// Output the badges of the crate, to paste into a README that is not generated
+    Badges(BadgesArgs),
+    /// Output the table of contents of a markdown file, or update it in the file
+    Toc(TocArgs),
+}
+
+/// Set up the crate to generate its README, with a starter template
+#[derive(Debug, Parser)]
+struct InitArgs {
+    /// Directory to be set as project root (where `Cargo.toml` is)
+    /// Defaults to the nearest directory with a `Cargo.toml`, from the current directory up.
+    #[clap(long = "project-root", short = 'r')]
+    root: Option<String>,
+
+    /// Template file to write.
+    /// Defaults to `README.tpl`, the template used by default.
+    #[clap(long, short = 't')]
+    template: Option<String>,
+
+    /// Also add a `[package.metadata.readme]` section to `Cargo.toml`, listing the settings.
+    #[clap(long)]
+    metadata: bool,
+
+    /// Replace the template file if it already exists.
+    #[clap(long)]
+    force: bool,
+}
+
+/// Install a git pre-commit hook checking that the README is up to date
+#[derive(Debug, Parser)]
+struct InstallHookArgs {
+    /// Directory to be set as project root (where `Cargo.toml` is)
+    /// Defaults to the nearest directory with a `Cargo.toml`, from the current directory up.
+    #[clap(long = "project-root", short = 'r')]
+    root: Option<String>,
+
+    /// Regenerate the README and add it to the commit, instead of refusing the commit.
+    #[clap(long)]
+    regenerate: bool,
+
+    /// Replace the pre-commit hook if it exists and was not installed by cargo-readme.
+    #[clap(long)]
+    force: bool,
+}
+
+/// Remove the git pre-commit hook installed by `install-hook`
+#[derive(Debug, Parser)]
+struct UninstallHookArgs {
+    /// Directory to be set as project root (where `Cargo.toml` is)
+    /// Defaults to the nearest directory with a `Cargo.toml`, from the current directory up.
+    #[clap(long = "project-root", short = 'r')]
+    root: Option<String>,
+}
+
+/// Output the completion script of a shell
+#[derive(Debug, Parser)]
+struct CompletionsArgs {
+    /// Shell to complete the command line of.
+    #[clap(value_enum)]
+    shell: Shell,
+}
+
+/// Output the badges of the crate, to paste into a README that is not generated
+#[derive(Debug, Parser)]
+struct BadgesArgs {
+    /// Directory to be set as project root (where `Cargo.toml` is)
+    /// Defaults to the nearest directory with a `Cargo.toml`, from the current directory up.
+    #[clap(long = "project-root", short = 'r')]
+    root: Option<String>,
+}
+
+/// Output the table of contents of a markdown file, or update it in the file
+///
+/// The table of contents is a list of links to the headings, with the anchors given by GitHub,
+/// GitLab or rustdoc.
+#[derive(Debug, Parser)]
+struct TocArgs {
+    /// Markdown file to get the table of contents of, like `CONTRIBUTING.md`.
+    file: String,
+
+    /// Replace the table of contents between the `<!-- cargo-readme toc start -->` and
+    /// `<!-- cargo-readme toc end -->` markers of the file, instead of writing it to stdout.
+    #[clap(long, short = 'i')]
+    in_place: bool,
+
+    /// Smallest level of the headings to list. Defaults to 2, as the level 1 heading is usually
+    /// the title of the file.
+    #[clap(long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..=6))]
+    min_level: u8,
+
+    /// Largest level of the headings to list.
+    #[clap(long, default_value_t = 3, value_parser = clap::value_parser!(u8).range(1..=6))]
+    max_level: u8,
+
+    /// Platform whose rules give the anchors of the headings. `github` is also right for
+    /// crates.io, and `rustdoc` for mdBook. Defaults to `github`.
+    #[clap(long, value_name = "STYLE", value_parser = ["github", "gitlab", "rustdoc"])]
+    anchor_style: Option<String>,
+}
+
+/// Check the generated README for problems, like broken links or missing sections
+///
+/// The README is generated with the options given before `lint`. The checks are set in
+/// `[package.metadata.readme.lint]`.
+#[derive(Debug, Parser)]
+struct LintArgs {
+    /// Fail on the warnings too.
+    #[clap(long)]
+    deny_warnings: bool,
 }
 
 // Takes the arguments matches from clap and outputs the result, either to stdout of a file
-fn execute(args: &ReadmeArgs) -> Result<(), String> {
+fn execute(args: &ReadmeArgs) -> Result<(), Error> {
     // get project root
-    let project_root = helper::get_project_root(args.root.as_deref())?;
+    let mut project_root = helper::get_project_root(args.root.as_deref(), args.no_cargo_toml)?;
 
-    // get source file
-    let mut source = helper::get_source(&project_root, args.input.as_deref())?;
+    if let Some(ref package) = args.package {
+        project_root = cargo_readme::project::find_package(&project_root, package)?;
+    }
+    messages::verbose(format_args!(
+        "Using the project root '{}'",
+        project_root.to_string_lossy()
+    ));
 
-    // get destination file
-    let mut dest = helper::get_dest(&project_root, args.output.as_deref())?;
+    if args.workspace {
+        return execute_workspace(args, &project_root);
+    }
+
+    // get destination file, after reading the existing one
+    let existing_readme = helper::get_existing_readme(
+        &project_root,
+        args.output.as_deref(),
+        args.in_place,
+        compare(args).is_some(),
+    )?;
+    let dest = helper::get_dest(
+        &project_root,
+        args.output.as_deref(),
+        args.in_place,
+        compare(args),
+        args.dry_run,
+        args.force,
+    )?;
 
     // get template file
-    let mut template_file = if args.no_template {
+    let template_file = if args.no_template {
+        None
+    } else {
+        helper::get_template_file(&project_root, args.template.as_deref())?
+    };
+
+    execute_package(args, &project_root, dest, template_file, existing_readme)
+}
+
+// Writes a starter template, and the readme settings when asked for
+fn init(args: &InitArgs) -> Result<(), Error> {
+    let project_root = helper::get_project_root(args.root.as_deref(), false)?;
+    let template = args.template.as_deref().unwrap_or(helper::DEFAULT_TEMPLATE);
+
+    init::init(&project_root, template, args.metadata, args.force)
+}
+
+// Installs the pre-commit hook of the repository of the project
+fn install_hook(args: &InstallHookArgs) -> Result<(), Error> {
+    let project_root = helper::get_project_root(args.root.as_deref(), false)?;
+
+    hook::install_hook(&project_root, args.regenerate, args.force)
+}
+
+// Removes the pre-commit hook of the repository of the project
+fn uninstall_hook(args: &UninstallHookArgs) -> Result<(), Error> {
+    let project_root = helper::get_project_root(args.root.as_deref(), false)?;
+
+    hook::uninstall_hook(&project_root)
+}
+
+// Checks the generated readme, failing when problems with the error severity are found
+fn lint(args: &ReadmeArgs, lint_args: &LintArgs) -> Result<(), Error> {
+    let mut project_root = helper::get_project_root(args.root.as_deref(), args.no_cargo_toml)?;
+    if let Some(ref package) = args.package {
+        project_root = cargo_readme::project::find_package(&project_root, package)?;
+    }
+
+    let config = match args.no_cargo_toml {
+        true => cargo_readme::LintConfig::default(),
+        false => cargo_readme::get_manifest(&project_root)?.lint,
+    };
+    let template_file = if args.no_template {
         None
     } else {
         helper::get_template_file(&project_root, args.template.as_deref())?
     };
+    let readme = generate_package(args, &project_root, template_file, None)?;
+
+    let mut errors = 0;
+    for problem in cargo_readme::lint_readme(&project_root, &readme.text, &config) {
+        match problem.severity {
+            cargo_readme::Severity::Warning if !lint_args.deny_warnings => {
+                messages::problem(&problem, true)
+            }
+            _ => {
+                errors += 1;
+                messages::problem(&problem, false);
+            }
+        }
+    }
+
+    match errors {
+        0 => Ok(()),
+        problems => Err(Error::Lint { problems }),
+    }
+}
+
+// Writes the badges of the crate to stdout, one per line
+fn print_badges(args: &BadgesArgs) -> Result<(), Error> {
+    let project_root = helper::get_project_root(args.root.as_deref(), false)?;
+    let manifest = cargo_readme::get_manifest(&project_root)?;
+
+    for badge in badges::badges(&manifest) {
+        println!("{}", badge);
+    }
+
+    Ok(())
+}
+
+// Writes the table of contents of a markdown file to stdout, or between its toc markers
+fn toc(args: &TocArgs) -> Result<(), Error> {
+    if args.min_level > args.max_level {
+        return Err(Error::Usage(format!(
+            "`--min-level` {} is larger than `--max-level` {}",
+            args.min_level, args.max_level
+        )));
+    }
+
+    let path = Path::new(&args.file);
+    let markdown = fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+    let style = match args.anchor_style.as_deref() {
+        Some("gitlab") => AnchorStyle::Gitlab,
+        Some("rustdoc") => AnchorStyle::Rustdoc,
+        _ => AnchorStyle::Github,
+    };
+    let toc = cargo_readme::table_of_contents(
+        &markdown,
+        args.min_level as usize,
+        args.max_level as usize,
+        style,
+    );
+
+    if args.in_place {
+        let updated =
+            cargo_readme::update_table_of_contents(&markdown, &toc).map_err(|e| e.in_file(path))?;
+        helper::write_file(path, &updated, None)
+    } else {
+        print!("{}", toc);
+        Ok(())
+    }
+}
+
+// Writes statistics about the doc comments of the crate to stdout
+fn print_stats(args: &ReadmeArgs) -> Result<(), Error> {
+    if args.all_bins {
+        return Err(Error::Usage(
+            "`stats` reads a single target, it cannot be used with `--all-bins`".to_owned(),
+        ));
+    }
+
+    let mut project_root = helper::get_project_root(args.root.as_deref(), args.no_cargo_toml)?;
+    if let Some(ref package) = args.package {
+        project_root = cargo_readme::project::find_package(&project_root, package)?;
+    }
+
+    let mut options = readme_options(args, &project_root, None)?;
+    let stats = if args.input.first().is_some_and(|input| input == STDIO) {
+        cargo_readme::docs_stats(&project_root, &mut io::stdin(), &options)?
+    } else {
+        let mut source = open_source(args, &project_root, &mut options)?;
+        cargo_readme::docs_stats(&project_root, &mut source, &options)?
+    };
+
+    println!("Doc lines:   {}", stats.doc_lines);
+    println!(
+        "Code blocks: {} ({} ignored)",
+        stats.code_blocks, stats.ignored_code_blocks
+    );
+    println!("Headings:    {}", stats.headings);
+    println!("Links:       {}", stats.links);
+    println!("Words:       {}", stats.words);
+
+    Ok(())
+}
+
+// Writes the completion script of the shell to stdout
+fn print_completions(args: &CompletionsArgs) -> Result<(), Error> {
+    clap_complete::generate(
+        args.shell,
+        &mut Args::command(),
+        "cargo-readme",
+        &mut io::stdout(),
+    );
+
+    Ok(())
+}
+
+// Regenerates the readme each time the files it is generated from change
+fn watch(args: &ReadmeArgs) -> Result<(), Error> {
+    if args.input.iter().any(|input| input == STDIO) {
+        return Err(Error::Usage(
+            "`--watch` cannot read the source from stdin".to_owned(),
+        ));
+    }
+
+    let mut project_root = helper::get_project_root(args.root.as_deref(), args.no_cargo_toml)?;
+    if let Some(ref package) = args.package {
+        project_root = cargo_readme::project::find_package(&project_root, package)?;
+    }
+
+    // the files read by each generation are watched, along with `Cargo.toml` and the template,
+    // which may be created or fixed after a failed generation
+    let template = args.template.as_deref().unwrap_or(helper::DEFAULT_TEMPLATE);
+    let paths = [project_root.join("Cargo.toml"), project_root.join(template)];
 
-    let add_title = !args.no_title;
-    let add_badges = !args.no_badges;
-    let add_license = !args.no_license;
-    let indent_headings = !args.no_indent_headings;
+    watch::watch(&paths, || execute(args))
+}
+
+// Gives the exit code of an error, from its variant
+fn exit_code(error: &Error) -> i32 {
+    match error {
+        Error::Outdated { .. } => EXIT_OUTDATED,
+        Error::ManifestParse(_)
+        | Error::MissingLicense
+        | Error::MissingBadges
+        | Error::TemplateMissingReadmeTag
+        | Error::MissingRepository
+        | Error::Usage(_)
+        | Error::Workspace(_) => EXIT_CONFIG,
+        Error::Io { .. }
+        | Error::Write { .. }
+        | Error::Marker { .. }
+        | Error::Include { .. }
+        | Error::Module { .. }
+        | Error::MissingSection(_)
+        | Error::Entrypoint(_)
+        | Error::Rustdoc(_)
+        | Error::Lint { .. }
+        | Error::NotGenerated(_)
+        | Error::Exists(_)
+        | Error::Hook(_) => EXIT_ERROR,
+        // the variants added to the library later
+        _ => EXIT_ERROR,
+    }
+}
+
+// Tells how the result is compared with the output file, with `--check` or `--diff`
+fn compare(args: &ReadmeArgs) -> Option<helper::Compare> {
+    if !args.check && !args.diff {
+        return None;
+    }
+
+    Some(helper::Compare {
+        fail: args.check,
+        color: use_color(args, io::stdout().is_terminal()),
+    })
+}
+
+// Tells if the output is colorized, with `--color`, or by default when it is written to a
+// terminal and `NO_COLOR` is not set
+fn use_color(args: &ReadmeArgs, terminal: bool) -> bool {
+    match args.color.as_deref() {
+        Some("always") => true,
+        Some("never") => false,
+        _ => terminal && env::var_os("NO_COLOR").is_none(),
+    }
+}
+
+// Generates the readme of the profiles given with `--profile` or `--all-profiles`, the arguments
+// of the command line overriding the ones of each profile
+fn execute_profiles(args: &ReadmeArgs) -> Result<(), Error> {
+    let mut project_root = helper::get_project_root(args.root.as_deref(), false)?;
+    if let Some(ref package) = args.package {
+        project_root = cargo_readme::project::find_package(&project_root, package)?;
+    }
+    let mut profiles = cargo_readme::get_manifest(&project_root)?.profiles;
+
+    let profiles = match args.profile {
+        Some(ref name) => {
+            let profile = profiles.remove(name).ok_or_else(|| {
+                Error::Usage(format!(
+                    "No profile named `{}` in `[package.metadata.readme.profiles]`",
+                    name
+                ))
+            })?;
+            vec![(name.clone(), profile)]
+        }
+        None if profiles.is_empty() => {
+            return Err(Error::Usage(
+                "No profile set in `[package.metadata.readme.profiles]`".to_owned(),
+            ))
+        }
+        None => profiles.into_iter().collect(),
+    };
+
+    for (name, profile) in profiles {
+        messages::verbose(format_args!("Generating the profile `{}`", name));
+
+        let mut profile_args = ReadmeArgs::try_parse_from(
+            iter::once("readme".to_owned()).chain(profile.args.iter().cloned()),
+        )
+        .map_err(|e| {
+            Error::Usage(format!(
+                "`args` of the profile `{}` are not valid: {}",
+                name,
+                e.to_string().trim_end()
+            ))
+        })?;
+        profile_args.template = profile.template.or(profile_args.template);
+        profile_args.output = profile.output.or(profile_args.output);
+        profile_args.output_format = profile.output_format.or(profile_args.output_format);
+        let profile_args = args.with_profile(profile_args);
+
+        execute(&profile_args)?;
+    }
+
+    Ok(())
+}
+
+// Generates the readme of every workspace member, writing each one next to its `Cargo.toml`
+fn execute_workspace(args: &ReadmeArgs, workspace_root: &Path) -> Result<(), Error> {
+    if args.output.as_deref() == Some(STDIO) {
+        return Err(Error::Usage(
+            "`--workspace` writes a README for every member, not to stdout".to_owned(),
+        ));
+    }
+
+    let workspace = cargo_readme::get_workspace(workspace_root)?;
+
+    for member in &workspace.members {
+        let manifest = cargo_readme::get_manifest(member)?;
+
+        let mut template_file = None;
+        if !manifest.publish {
+            if workspace.skip_unpublished {
+                messages::info(format_args!(
+                    "Skipping `{}`: crate is not published",
+                    manifest.name
+                ));
+                continue;
+            }
+            match workspace.unpublished_template {
+                Some(ref template) => {
+                    template_file = helper::get_template_file(
+                        workspace_root,
+                        Some(&template.to_string_lossy()),
+                    )?;
+                }
+                None => messages::warn(format_args!(
+                    "`{}` is not published, set `skip-unpublished` or `unpublished-template` in \
+                     `[workspace.metadata.readme]` to handle it",
+                    manifest.name
+                )),
+            }
+        }
+
+        if template_file.is_none() && !args.no_template {
+            template_file = helper::get_template_file(member, args.template.as_deref())?;
+        }
+
+        let output = args.output.as_deref().unwrap_or(DEFAULT_OUTPUT);
+        let existing_readme = helper::get_existing_readme(
+            member,
+            Some(output),
+            args.in_place,
+            compare(args).is_some(),
+        )?;
+        let dest = helper::get_dest(
+            member,
+            Some(output),
+            args.in_place,
+            compare(args),
+            args.dry_run,
+            args.force,
+        )?;
+
+        execute_package(args, member, dest, template_file, existing_readme)?;
+    }
+
+    Ok(())
+}
+
+// Generates the readme of a single package and writes it to `dest`
+fn execute_package(
+    args: &ReadmeArgs,
+    project_root: &Path,
+    mut dest: helper::Dest,
+    template_file: Option<File>,
+    existing_readme: Option<String>,
+) -> Result<(), Error> {
+    let readme = generate_package(args, project_root, template_file, existing_readme)?;
+
+    let line_endings = match args.line_endings.as_deref() {
+        Some("crlf") => LineEndings::Crlf,
+        Some("native") => LineEndings::Native,
+        _ => LineEndings::Lf,
+    };
+
+    // the JSON document, the HTML page and the front matter tell it was generated in their
+    // `generator` field, the front matter must be at the top of the file, and the plain text and
+    // the Confluence wiki markup have no comments
+    let marker = match (args.output_format.as_deref(), &args.front_matter) {
+        (Some("json" | "html" | "text" | "confluence"), _) | (_, Some(_)) => None,
+        (Some("rst"), _) => Some(helper::RST_GENERATED_MARKER),
+        (Some("asciidoc"), _) => Some(helper::ASCIIDOC_GENERATED_MARKER),
+        (Some("man"), _) => Some(helper::MAN_GENERATED_MARKER),
+        (Some("org"), _) => Some(helper::ORG_GENERATED_MARKER),
+        _ => Some(helper::GENERATED_MARKER),
+    };
+
+    helper::write_output(
+        &mut dest,
+        readme.text,
+        &readme.asset_copies,
+        line_endings,
+        marker,
+        args.backup.as_deref(),
+    )
+}
+
+// Generates the readme of a single package, with the options given
+fn generate_package(
+    args: &ReadmeArgs,
+    project_root: &Path,
+    mut template_file: Option<File>,
+    existing_readme: Option<String>,
+) -> Result<Readme, Error> {
+    let mut options = readme_options(args, project_root, existing_readme)?;
 
     // generate output
-    let readme = cargo_readme::generate_readme(
-        &project_root,
-        &mut source,
-        template_file.as_mut(),
-        add_title,
-        add_badges,
-        add_license,
-        indent_headings,
-    )?;
+    let readme = if args.all_bins {
+        // get the source files of every binary
+        let sources = helper::find_bins(project_root)?;
+
+        cargo_readme::generate_readme_bins(project_root, sources, template_file.as_mut(), &options)?
+    } else if args.input.first().is_some_and(|input| input == STDIO) {
+        // there is no source directory to resolve the included files and modules from
+        messages::verbose(format_args!("Reading the source from stdin"));
+        let mut source: Box<dyn Read> = Box::new(io::stdin());
+        let mut template = template_file.map(|file| Box::new(file) as Box<dyn Read>);
+
+        cargo_readme::generate_readme_with_options(
+            project_root,
+            &mut source,
+            template.as_mut(),
+            &options,
+        )?
+    } else {
+        let mut source = open_source(args, project_root, &mut options)?;
+
+        cargo_readme::generate_readme_with_options(
+            project_root,
+            &mut source,
+            template_file.as_mut(),
+            &options,
+        )?
+    };
+
+    for warning in &readme.warnings {
+        messages::warn(format_args!("{}", warning));
+    }
+
+    Ok(readme)
+}
+
+// Gets the options of the readme from the arguments
+fn readme_options(
+    args: &ReadmeArgs,
+    project_root: &Path,
+    existing_readme: Option<String>,
+) -> Result<ReadmeOptions, Error> {
+    let backend = match args.backend.as_deref() {
+        Some("rustdoc-json") => {
+            if !args.input.is_empty()
+                || args.example.is_some()
+                || args.entrypoint.is_some()
+                || args.all_bins
+            {
+                return Err(Error::Usage(
+                    "`--backend rustdoc-json` can only read the library target or a `--bin` target"
+                        .to_owned(),
+                ));
+            }
+            Backend::RustdocJson {
+                bin: args.bin.clone(),
+            }
+        }
+        _ => Backend::Source,
+    };
+
+    if args.input.iter().skip(1).any(|input| input == STDIO) {
+        return Err(Error::Usage(format!(
+            "Only the first `--input` can be `{}`, to read from stdin",
+            STDIO
+        )));
+    }
+
+    // an mdBook chapter keeps the doc tests runnable, unless set otherwise
+    let mdbook = args.output_format.as_deref() == Some("mdbook");
+
+    if let Some(date) = args.date.as_deref().filter(|date| !helper::is_date(date)) {
+        return Err(Error::Usage(format!(
+            "`--date` must be a date like `2024-01-31`, not `{}`",
+            date
+        )));
+    }
+    // the date of the existing page is kept, so it does not change every day
+    let date = args
+        .date
+        .clone()
+        .or_else(|| {
+            existing_readme
+                .as_deref()
+                .and_then(helper::front_matter_date)
+                .map(str::to_owned)
+        })
+        .or_else(|| match args.front_matter {
+            Some(_) => Some(helper::today()),
+            // the man page is dated with the version instead, so it only changes with the docs
+            None => helper::source_date(),
+        });
+
+    let mut options = ReadmeOptions::default();
+    options.backend = backend;
+    options.add_title = !args.no_title;
+    options.always_add_title = args.always_add_title;
+    options.title_from_docs = args.title_from_docs;
+    options.add_badges = !args.no_badges;
+    options.add_license = !args.no_license;
+    options.heading_offset = args
+        .heading_offset
+        .unwrap_or(!args.no_indent_headings as isize);
+    options.hidden_lines = match (args.show_hidden_lines, args.strip_hidden_marks) {
+        (true, true) => HiddenLines::Strip,
+        (true, false) => HiddenLines::Show,
+        // mdBook hides the lines itself, and `mdbook test` needs them
+        _ if mdbook => HiddenLines::Show,
+        _ => HiddenLines::Hide,
+    };
+    options.fence_lang = args.fence_lang.clone().unwrap_or_else(|| "rust".to_owned());
+    options.doctest_attributes = match args.doctest_attributes.as_deref() {
+        Some("keep") => DoctestAttributes::Keep,
+        Some("comment") => DoctestAttributes::Comment,
+        Some("drop") => DoctestAttributes::Drop,
+        _ if mdbook => DoctestAttributes::Keep,
+        _ => DoctestAttributes::Drop,
+    };
+    options.expand_tabs = args.expand_tabs.map(usize::from);
+    options.github_alerts = args.github_alerts;
+    options.sanitize_html = match args.sanitize_html.as_deref() {
+        Some("strip") => SanitizeHtml::Strip,
+        Some("escape") => SanitizeHtml::Escape,
+        _ => SanitizeHtml::Passthrough,
+    };
+    options.diagrams = match args.diagrams.as_deref() {
+        Some("passthrough") => Some(DiagramBlocks::Passthrough),
+        Some("github") => Some(DiagramBlocks::Github),
+        Some("image") => Some(DiagramBlocks::Image),
+        _ => None,
+    };
+    options.intra_doc_links = match args.intra_doc_links.as_deref() {
+        Some("docs-rs") => IntraDocLinks::DocsRs,
+        Some("strip") => IntraDocLinks::Strip,
+        _ if mdbook => IntraDocLinks::DocsRs,
+        _ => IntraDocLinks::Keep,
+    };
+    options.strip_unresolved_links = args.strip_unresolved_links;
+    options.absolute_links = args.absolute_links;
+    options.crates_io = args.crates_io;
+    options.copy_assets = args.copy_assets;
+    // the images are rebased on, and copied next to, the output file
+    options.output_dir = args
+        .output
+        .as_deref()
+        .filter(|output| *output != STDIO)
+        .and_then(|output| project_root.join(output).parent().map(Path::to_path_buf));
+    options.include_modules = args.include_modules;
+    options.exclude_sections = args.exclude_sections.clone();
+    options.only_section = args.only_section.clone();
+    options.inline_footnotes = args.inline_footnotes;
+    options.smart_punctuation = args.smart_punctuation;
+    options.emoji = match args.emoji.as_deref() {
+        Some("unicode") => Emoji::Unicode,
+        Some("shortcodes") => Emoji::Shortcodes,
+        _ => Emoji::Keep,
+    };
+    options.link_style = match args.link_style.as_deref() {
+        Some("inline") => LinkStyle::Inline,
+        Some("reference") => LinkStyle::Reference,
+        _ => LinkStyle::Keep,
+    };
+    options.output_format = match args.output_format.as_deref() {
+        Some(format) if format != "markdown" && (args.footer || args.front_matter.is_some()) => {
+            return Err(Error::Usage(format!(
+                "`--footer` and `--front-matter` cannot be used with `--output-format {}`",
+                format
+            )))
+        }
+        Some("json") => OutputFormat::Json,
+        Some("rst") => OutputFormat::Rst,
+        Some("asciidoc") => OutputFormat::Asciidoc,
+        Some("html") => OutputFormat::Html,
+        Some("man") if !args.no_cargo_toml && helper::find_bins(project_root).is_err() => {
+            return Err(Error::Usage(
+                "`--output-format man` needs a crate with a binary target".to_owned(),
+            ))
+        }
+        Some("man") => OutputFormat::Man,
+        Some("text") => OutputFormat::Text,
+        Some("mdbook") => OutputFormat::Mdbook,
+        Some("org") => OutputFormat::Org,
+        Some("confluence") => OutputFormat::Confluence,
+        _ => OutputFormat::Markdown,
+    };
+    options.front_matter = match args.front_matter.as_deref() {
+        Some("yaml") => Some(FrontMatter::Yaml),
+        Some("toml") => Some(FrontMatter::Toml),
+        _ => None,
+    };
+    options.date = date;
+    options.normalize_markdown = args.normalize_markdown;
+    options.wrap = args.wrap.map(usize::from);
+    options.strip_trailing_whitespace = args.strip_trailing_whitespace;
+    options.collapse_blank_lines = args.collapse_blank_lines;
+    options.footer = args.footer;
+    options.crate_name = args.crate_name.clone();
+    options.license = args.license.clone();
+    options.no_cargo_toml = args.no_cargo_toml;
+    options.existing_readme = existing_readme;
+    options.extra_sources = args
+        .input
+        .iter()
+        .skip(1)
+        .map(|input| project_root.join(input))
+        .collect();
+    options.cfgs = args.cfgs.clone();
+
+    Ok(options)
+}
+
+// Opens the source file of the crate, given by the arguments, and sets its directories in `options`
+fn open_source(
+    args: &ReadmeArgs,
+    project_root: &Path,
+    options: &mut ReadmeOptions,
+) -> Result<File, Error> {
+    let source_path = match args.entrypoint {
+        Some(ref entrypoint) => {
+            let source_path = project_root.join(entrypoint);
+            options.modules_dir = Some(cargo_readme::project::modules_dir(&source_path));
+            source_path
+        }
+        None => cargo_readme::project::source_path(
+            project_root,
+            args.input.first().map(String::as_str),
+            args.bin.as_deref(),
+            args.example.as_deref(),
+        )?,
+    };
+    messages::verbose(format_args!(
+        "Reading the source '{}'",
+        source_path.to_string_lossy()
+    ));
+    options.source_dir = source_path.parent().map(Path::to_path_buf);
 
-    helper::write_output(&mut dest, readme)
+    helper::open_source(&source_path)
 }
@@ -0,0 +1,242 @@
+//! Messages written to stderr while generating the readme
+//!
+//! With `-v`, the files that are read, like the source file, `Cargo.toml` and the template, are
+//! reported. With `-vv`, the transformations applied to the docs are reported too. With `-q`,
+//! the warnings are left out, and only the errors are reported. The messages of the library are
+//! written along with the ones of the command line, by the logger set at startup.
+//!
+//! With `--message-format json`, the warnings and errors are written as JSON objects, one per line,
+//! with their level, kind, message, and the file and line they are about when known. The other
+//! messages are written as JSON objects too, of level `info` or `trace`. Otherwise,
+//! with colors enabled, the `Error` and `Warning` labels are written in red and yellow.
+
+use std::fmt;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use cargo_readme::log::{Level, Logger};
+use cargo_readme::{Error, LintProblem};
+
+use crate::watch;
+
+/// How much is written to stderr
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub enum Verbosity {
+    /// Nothing, with `-q`
+    Quiet,
+    /// Only the warnings
+    #[default]
+    Normal,
+    /// The files that are read and written too, with `-v`
+    Verbose,
+    /// The transformations applied to the docs too, with `-vv`
+    Trace,
+}
+
+/// How the warnings and errors are written
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MessageFormat {
+    /// As text, like `Warning: ...`
+    #[default]
+    Human,
+    /// As JSON objects, one per line
+    Json,
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+static JSON: AtomicBool = AtomicBool::new(false);
+static COLOR: AtomicBool = AtomicBool::new(false);
+
+/// Set how much is written to stderr
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+/// Get how much is written to stderr
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        v if v == Verbosity::Trace as u8 => Verbosity::Trace,
+        v if v == Verbosity::Verbose as u8 => Verbosity::Verbose,
+        v if v == Verbosity::Normal as u8 => Verbosity::Normal,
+        _ => Verbosity::Quiet,
+    }
+}
+
+/// Set how the warnings and errors are written
+pub fn set_message_format(format: MessageFormat) {
+    JSON.store(format == MessageFormat::Json, Ordering::Relaxed);
+}
+
+/// Set whether the labels of the warnings and errors are colorized
+pub fn set_color(color: bool) {
+    COLOR.store(color, Ordering::Relaxed);
+}
+
+/// Write `message` to stderr as a warning, unless with `-q`
+pub fn warn(message: fmt::Arguments) {
+    if verbosity() >= Verbosity::Normal {
+        match JSON.load(Ordering::Relaxed) {
+            true => eprintln!(
+                "{}",
+                diagnostic("warning", "warning", &message.to_string(), None, None)
+            ),
+            false => eprintln!("{}: {}", label("Warning", "1;33"), message),
+        }
+    }
+}
+
+/// Write `error` to stderr
+pub fn error(error: &Error) {
+    match JSON.load(Ordering::Relaxed) {
+        true => eprintln!(
+            "{}",
+            diagnostic(
+                "error",
+                error.kind(),
+                &error.to_string(),
+                error.file(),
+                error.line()
+            )
+        ),
+        false => eprintln!("{}: {}", label("Error", "1;31"), error),
+    }
+}
+
+/// Write the lint `problem` to stderr, as an error or, with `warning`, as a warning unless with
+/// `-q`
+pub fn problem(problem: &LintProblem, warning: bool) {
+    if warning && verbosity() < Verbosity::Normal {
+        return;
+    }
+
+    let (level, text) = match warning {
+        true => ("warning", label("Warning", "1;33")),
+        false => ("error", label("Error", "1;31")),
+    };
+    match JSON.load(Ordering::Relaxed) {
+        true => eprintln!(
+            "{}",
+            diagnostic(level, "lint", &problem.message, None, problem.line)
+        ),
+        false => eprintln!("{}: {}", text, problem.message),
+    }
+}
+
+/// Write `message` to stderr, unless with `-q`
+pub fn info(message: fmt::Arguments) {
+    if verbosity() >= Verbosity::Normal {
+        note("info", "", message);
+    }
+}
+
+/// Write `message` to stderr, with `-v`
+pub fn verbose(message: fmt::Arguments) {
+    if verbosity() >= Verbosity::Verbose {
+        note("info", "Info: ", message);
+    }
+}
+
+/// Write `message` to stderr, with `-vv`
+pub fn trace(message: fmt::Arguments) {
+    if verbosity() >= Verbosity::Trace {
+        note("trace", "Trace: ", message);
+    }
+}
+
+/// Write `message` to stderr after `prefix`, or as a JSON object of `level` with
+/// `--message-format json`, so the JSON messages are one per line only
+fn note(level: &str, prefix: &str, message: fmt::Arguments) {
+    match JSON.load(Ordering::Relaxed) {
+        true => eprintln!(
+            "{}",
+            diagnostic(level, level, &message.to_string(), None, None)
+        ),
+        false => eprintln!("{}{}", prefix, message),
+    }
+}
+
+/// Logger writing the messages of the library to stderr, like the ones of the command line, and
+/// recording the files it reads for `--watch`
+pub struct Stderr;
+
+impl Logger for Stderr {
+    fn log(&self, level: Level, message: fmt::Arguments) {
+        match level {
+            Level::Warning => warn(message),
+            Level::Verbose => verbose(message),
+            Level::Trace => trace(message),
+        }
+    }
+
+    fn read(&self, path: &Path) {
+        watch::record(path);
+    }
+}
+
+/// Color the `label` of a message with the ANSI escape `code`, when colors are enabled
+fn label(label: &str, code: &str) -> String {
+    match COLOR.load(Ordering::Relaxed) {
+        true => format!("\x1b[{}m{}\x1b[0m", code, label),
+        false => label.to_owned(),
+    }
+}
+
+/// Get the JSON object of a warning or an error, with its `kind` and the `file` and `line` it is
+/// about
+fn diagnostic(
+    level: &str,
+    kind: &str,
+    message: &str,
+    file: Option<&Path>,
+    line: Option<usize>,
+) -> String {
+    serde_json::json!({
+        "level": level,
+        "kind": kind,
+        "message": message,
+        "file": file.map(|file| file.to_string_lossy()),
+        "line": line,
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::diagnostic;
+    use cargo_readme::Error;
+
+    #[test]
+    fn diagnostic_with_file_and_line() {
+        let error = Error::Outdated {
+            path: "README.md".into(),
+            line: 3,
+            existing_lines: 4,
+            generated_lines: 5,
+        };
+        let message = error.to_string();
+
+        assert_eq!(
+            diagnostic("error", error.kind(), &message, error.file(), error.line()),
+            format!(
+                r#"{{"file":"README.md","kind":"outdated-readme","level":"error","line":3,"message":"{}"}}"#,
+                message
+            )
+        );
+    }
+
+    #[test]
+    fn diagnostic_without_file() {
+        let error = Error::Usage("`--in-place` cannot update stdout".to_owned());
+
+        assert_eq!(
+            diagnostic(
+                "error",
+                error.kind(),
+                &error.to_string(),
+                error.file(),
+                error.line()
+            ),
+            r#"{"file":null,"kind":"usage","level":"error","line":null,"message":"`--in-place` cannot update stdout"}"#
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Convert rustdoc admonitions into GitHub alerts
+//!
+//! Rustdoc renders `<div class="warning">` blocks as warnings, and blockquotes starting with
+//! `**Note:**` or `**Warning:**` are a common way to write notes. GitHub renders both better as
+//! alerts, like `> [!WARNING]`.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::fences::fenced;
+
+lazy_static! {
+    // Is this the start of a rustdoc warning block?
+    static ref RE_WARNING_START: Regex = Regex::new(r#"^\s*<div\s+class\s*=\s*["']warning["']\s*>\s*$"#).unwrap();
+    // Is this the end of a rustdoc warning block?
+    static ref RE_WARNING_END: Regex = Regex::new(r"^\s*</div>\s*$").unwrap();
+    // Is this the first line of a blockquote starting with a bold admonition, like `**Note:**`?
+    static ref RE_ADMONITION: Regex = Regex::new(r"^(?P<indent>\s*)>\s*\*\*(?i:(?P<kind>note|tip|important|warning|caution))(?::\*\*|\*\*:)\s*(?P<text>.*)$").unwrap();
+}
+
+/// Convert the warning blocks and the blockquoted admonitions into GitHub alerts
+pub fn github_alerts(lines: Vec<String>) -> Vec<String> {
+    // the start line of the current warning block, and its content
+    let mut warning: Option<(String, Vec<String>)> = None;
+    let mut result: Vec<String> = Vec::new();
+
+    for (kind, line) in fenced(lines) {
+        if !kind.is_code() {
+            if let Some((_, ref mut content)) = warning {
+                if RE_WARNING_END.is_match(&line) {
+                    result.extend(alert("WARNING", content));
+                    warning = None;
+                } else {
+                    content.push(line);
+                }
+                continue;
+            }
+            if RE_WARNING_START.is_match(&line) {
+                warning = Some((line, Vec::new()));
+                continue;
+            }
+            let starts_blockquote = !result
+                .last()
+                .is_some_and(|l| l.trim_start().starts_with('>'));
+            if let Some(cap) = RE_ADMONITION.captures(&line).filter(|_| starts_blockquote) {
+                let indent = &cap["indent"];
+                result.push(format!("{}> [!{}]", indent, cap["kind"].to_uppercase()));
+                if !cap["text"].is_empty() {
+                    result.push(format!("{}> {}", indent, &cap["text"]));
+                }
+                continue;
+            }
+        }
+
+        match warning {
+            Some((_, ref mut content)) => content.push(line),
+            None => result.push(line),
+        }
+    }
+
+    // a warning block that is not closed is left as is
+    if let Some((start, content)) = warning {
+        result.push(start);
+        result.extend(content);
+    }
+
+    result
+}
+
+/// Format `content` as a GitHub alert of the given `kind`
+fn alert(kind: &str, content: &[String]) -> Vec<String> {
+    let start = content.iter().position(|l| !l.trim().is_empty());
+    let end = content.iter().rposition(|l| !l.trim().is_empty());
+
+    let mut lines = vec![format!("> [!{}]", kind)];
+    if let (Some(start), Some(end)) = (start, end) {
+        lines.extend(content[start..=end].iter().map(|l| {
+            if l.trim().is_empty() {
+                ">".to_owned()
+            } else {
+                format!("> {}", l)
+            }
+        }));
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::github_alerts;
+
+    fn alerts(lines: &[&str]) -> Vec<String> {
+        github_alerts(lines.iter().map(|l| l.to_string()).collect())
+    }
+
+    #[test]
+    fn convert_warning_block() {
+        let input = &[
+            "Intro",
+            "",
+            "<div class=\"warning\">",
+            "",
+            "Be careful.",
+            "",
+            "```",
+            "danger();",
+            "```",
+            "",
+            "</div>",
+        ];
+
+        assert_eq!(
+            alerts(input),
+            [
+                "Intro",
+                "",
+                "> [!WARNING]",
+                "> Be careful.",
+                ">",
+                "> ```",
+                "> danger();",
+                "> ```",
+            ]
+        );
+    }
+
+    #[test]
+    fn convert_blockquoted_admonitions() {
+        let input = &[
+            "> **Note:** This is a note",
+            "> on two lines.",
+            "",
+            "> **Warning**: This is a warning.",
+            "",
+            "> Quote",
+            "> **Note:** not at the start of the blockquote.",
+        ];
+
+        assert_eq!(
+            alerts(input),
+            [
+                "> [!NOTE]",
+                "> This is a note",
+                "> on two lines.",
+                "",
+                "> [!WARNING]",
+                "> This is a warning.",
+                "",
+                "> Quote",
+                "> **Note:** not at the start of the blockquote.",
+            ]
+        );
+    }
+
+    #[test]
+    fn keep_code_blocks() {
+        let input = &["```", "<div class=\"warning\">", "> **Note:** code", "```"];
+
+        assert_eq!(alerts(input), input);
+    }
+
+    #[test]
+    fn keep_unclosed_warning_block() {
+        let input = &["<div class=\"warning\">", "", "Not closed."];
+
+        assert_eq!(alerts(input), input);
+    }
+}
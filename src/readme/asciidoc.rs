@@ -0,0 +1,238 @@
+//! Convert the processed readme from markdown to AsciiDoc
+//!
+//! The headings start with `=` repeated by level, the code blocks become `source` blocks, the
+//! GitHub alerts, like `> [!NOTE]`, become admonition blocks and the other quotes become quote
+//! blocks. An image alone in its paragraph becomes a block image, and a paragraph of badges a line
+//! of inline images. The tables become `|===` table blocks, and the links and other images inline
+//! macros.
+
+use super::markdown::{comment, image_paragraph, join_blocks, parse, Block, Inline};
+
+/// Convert the markdown `readme` to AsciiDoc
+pub fn markdown_to_asciidoc(readme: &str) -> String {
+    blocks(&parse(readme), 0).join("\n").trim().to_owned()
+}
+
+/// Convert the markdown `blocks`, in lists nested `depth` deep, to the lines of AsciiDoc
+fn blocks(blocks: &[Block], depth: usize) -> Vec<String> {
+    join_blocks(blocks.iter().map(|b| block(b, depth)), false)
+}
+
+/// Convert a markdown block, in lists nested `depth` deep, to the lines of AsciiDoc
+fn block(block: &Block, depth: usize) -> Vec<String> {
+    match block {
+        Block::Heading { level, text } => vec![format!("{} {}", "=".repeat(*level), inline(text))],
+        Block::Paragraph(text) => match image_paragraph(text).as_deref() {
+            Some([image]) => vec![match image.link {
+                None => format!("image::{}[{}]", image.source, image.alt),
+                Some(link) => format!("image::{}[{},link={}]", image.source, image.alt, link),
+            }],
+            // the badges are kept together in a paragraph of inline images
+            Some(images) => {
+                let images: Vec<String> = images
+                    .iter()
+                    .map(|image| match image.link {
+                        None => format!("image:{}[{}]", image.source, image.alt),
+                        Some(link) => {
+                            format!("image:{}[{},link={}]", image.source, image.alt, link)
+                        }
+                    })
+                    .collect();
+                vec![images.join(" ")]
+            }
+            None => lines(&inline(text)),
+        },
+        Block::Code { language, code } => {
+            // the delimiter is longer than any line of dashes in the code
+            let dashes = code
+                .lines()
+                .filter(|l| !l.is_empty() && l.trim_end().chars().all(|c| c == '-'))
+                .map(|l| l.trim_end().len() + 1)
+                .max()
+                .unwrap_or(0)
+                .max(4);
+            let delimiter = "-".repeat(dashes);
+            let mut lines = vec![
+                match language {
+                    Some(language) => format!("[source,{}]", language),
+                    None => "[source]".to_owned(),
+                },
+                delimiter.clone(),
+            ];
+            lines.extend(self::lines(code));
+            lines.push(delimiter);
+            lines
+        }
+        Block::Quote { alert, blocks } => {
+            let (kind, delimiter) = match alert {
+                Some(alert) => (format!("[{}]", alert.name()), "===="),
+                None => ("[quote]".to_owned(), "____"),
+            };
+            let mut lines = vec![kind, delimiter.to_owned()];
+            lines.extend(self::blocks(blocks, 0));
+            lines.push(delimiter.to_owned());
+            lines
+        }
+        // the items are marked with `*` or `.` repeated by depth, and their other blocks than the
+        // nested lists are attached to them with `+`
+        Block::List { start, items, .. } => {
+            let marker = match start {
+                Some(_) => ".",
+                None => "*",
+            }
+            .repeat(depth + 1);
+            let mut lines = Vec::new();
+            for item in items {
+                for (i, item_block) in item.iter().enumerate() {
+                    let mut item_lines = self::block(item_block, depth + 1);
+                    match (i, item_block) {
+                        (0, Block::Paragraph(_)) => {
+                            if let Some(first) = item_lines.first_mut() {
+                                *first = format!("{} {}", marker, first);
+                            }
+                        }
+                        (_, Block::List { .. }) => {}
+                        _ => lines.push("+".to_owned()),
+                    }
+                    lines.append(&mut item_lines);
+                }
+            }
+            lines
+        }
+        Block::Table { header, rows, .. } => {
+            let mut lines = vec!["[%header]".to_owned(), "|===".to_owned()];
+            for row in std::iter::once(header).chain(rows) {
+                let cells: Vec<String> = row.iter().map(|cell| inline(cell)).collect();
+                lines.push(format!("|{}", cells.join(" |")));
+            }
+            lines.push("|===".to_owned());
+            lines
+        }
+        Block::Html(html) => match comment(html) {
+            Some(text) => vec![format!("// {}", text)],
+            None => lines(html),
+        },
+        Block::Rule => vec!["'''".to_owned()],
+    }
+}
+
+/// Split `text` into lines
+fn lines(text: &str) -> Vec<String> {
+    text.lines().map(ToOwned::to_owned).collect()
+}
+
+/// Convert the inline markup of `text`: the bold and italic text, and the links
+///
+/// The code spans are written the same in both.
+fn inline(text: &[Inline]) -> String {
+    text.iter()
+        .map(|inline| match inline {
+            Inline::Text(text) | Inline::Html(text) => text.clone(),
+            Inline::Code(code) => format!("`{}`", code),
+            Inline::Emphasis(text) => format!("_{}_", self::inline(text)),
+            Inline::Strong(text) => format!("*{}*", self::inline(text)),
+            Inline::Strikethrough(text) => format!("[.line-through]#{}#", self::inline(text)),
+            Inline::Link { target, text, .. } => {
+                let label = match self::inline(text) {
+                    label if label == *target => String::new(),
+                    label => label,
+                };
+                match target.contains("://") {
+                    true if label.is_empty() => target.clone(),
+                    true => format!("{}[{}]", target, label),
+                    false => format!("link:{}[{}]", target, label),
+                }
+            }
+            Inline::Image { source, alt, .. } => format!("image:{}[{}]", source, alt),
+            Inline::SoftBreak => "\n".to_owned(),
+            Inline::HardBreak => " +\n".to_owned(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::markdown_to_asciidoc;
+
+    #[test]
+    fn convert_readme() {
+        let readme = "\
+[![Crates.io](https://img.shields.io/crates/v/a.svg)](https://crates.io/crates/a)
+[![Docs](https://docs.rs/a/badge.svg)](https://docs.rs/a)
+
+# my-crate
+
+Use `cargo add`, **now** and *here*, see [the guide][guide] and [the changelog](CHANGELOG.md).
+
+> [!WARNING]
+> Not stable.
+
+> Quoted.
+
+## Usage
+
+```rust,no_run
+# not a heading
+let a = 1;
+```
+
+- one
+  - nested
+1. first
+
+| Name | Value |
+| ---- | ----- |
+| `a`  | **1** |
+
+[guide]: https://example.com/guide";
+
+        assert_eq!(
+            markdown_to_asciidoc(readme),
+            "\
+image:https://img.shields.io/crates/v/a.svg[Crates.io,link=https://crates.io/crates/a] \
+image:https://docs.rs/a/badge.svg[Docs,link=https://docs.rs/a]
+
+= my-crate
+
+Use `cargo add`, *now* and _here_, see https://example.com/guide[the guide] and \
+link:CHANGELOG.md[the changelog].
+
+[WARNING]
+====
+Not stable.
+====
+
+[quote]
+____
+Quoted.
+____
+
+== Usage
+
+[source,rust]
+----
+# not a heading
+let a = 1;
+----
+
+* one
+** nested
+
+. first
+
+[%header]
+|===
+|Name |Value
+|`a` |*1*
+|==="
+        );
+    }
+
+    #[test]
+    fn code_block_with_dashes() {
+        assert_eq!(
+            markdown_to_asciidoc("```\n-----\n```"),
+            "[source]\n------\n-----\n------"
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! Build the readme of a crate from the library
+//!
+//! `ReadmeBuilder` gathers what `cargo readme` reads from its arguments: the project, the source of
+//! the docs, the template and the options. Each of them has a default, so only the project root is
+//! needed: the source is the entrypoint of the crate, like `src/lib.rs`, and the template is
+//! `README.tpl`, when the project has one.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use super::{generate_readme_with_options, Readme, ReadmeOptions};
+use crate::config::project;
+use crate::log;
+use crate::Error;
+
+/// Template used when none is set, if the project has one
+const DEFAULT_TEMPLATE: &str = "README.tpl";
+
+/// Where the source or the template is read from
+#[derive(Clone, Debug)]
+enum Input {
+    /// The default file, if any
+    Default,
+    /// A file, relative to the project root
+    Path(PathBuf),
+    /// Text in memory
+    Text(String),
+    /// Nothing, for the template only
+    None,
+}
+
+/// Builder of the readme of a crate
+///
+/// ```no_run
+/// use cargo_readme::ReadmeBuilder;
+///
+/// let readme = ReadmeBuilder::new(env!("CARGO_MANIFEST_DIR"))
+///     .no_template()
+///     .license(false)
+///     .generate()
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReadmeBuilder {
+    project_root: PathBuf,
+    source: Input,
+    template: Input,
+    options: ReadmeOptions,
+}
+
+impl ReadmeBuilder {
+    /// Create the builder of the readme of the crate in `project_root`, where `Cargo.toml` is
+    pub fn new<P: Into<PathBuf>>(project_root: P) -> Self {
+        ReadmeBuilder {
+            project_root: project_root.into(),
+            source: Input::Default,
+            template: Input::Default,
+            options: ReadmeOptions::default(),
+        }
+    }
+
+    /// Use the crate of the manifest at `path`, like `path/to/Cargo.toml`
+    pub fn manifest_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.project_root = path
+            .as_ref()
+            .parent()
+            .unwrap_or(Path::new("."))
+            .to_path_buf();
+        self
+    }
+
+    /// Read the docs from the source file at `path`, relative to the project root, instead of the
+    /// entrypoint of the crate
+    pub fn source_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.source = Input::Path(path.into());
+        self
+    }
+
+    /// Read the docs from the `source` code, instead of the entrypoint of the crate
+    ///
+    /// The files included with `include_str!` and the modules cannot be found from it.
+    pub fn source<S: Into<String>>(mut self, source: S) -> Self {
+        self.source = Input::Text(source.into());
+        self
+    }
+
+    /// Render the readme with the template file at `path`, relative to the project root, which
+    /// must exist
+    pub fn template_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.template = Input::Path(path.into());
+        self
+    }
+
+    /// Render the readme with the `template`
+    pub fn template<S: Into<String>>(mut self, template: S) -> Self {
+        self.template = Input::Text(template.into());
+        self
+    }
+
+    /// Render the readme without template, even when the project has a `README.tpl`
+    pub fn no_template(mut self) -> Self {
+        self.template = Input::None;
+        self
+    }
+
+    /// Use `options` for the generation, replacing the ones set before
+    pub fn options(mut self, options: ReadmeOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Change the options set before with `configure`, like
+    /// `.configure(|options| options.github_alerts = true)`
+    pub fn configure<F>(mut self, configure: F) -> Self
+    where
+        F: FnOnce(&mut ReadmeOptions),
+    {
+        configure(&mut self.options);
+        self
+    }
+
+    /// Prepend the crate name as title, when not using a template
+    pub fn title(mut self, add_title: bool) -> Self {
+        self.options.add_title = add_title;
+        self
+    }
+
+    /// Prepend the badges defined in `Cargo.toml`, when not using a template
+    pub fn badges(mut self, add_badges: bool) -> Self {
+        self.options.add_badges = add_badges;
+        self
+    }
+
+    /// Append the license defined in `Cargo.toml`, when not using a template
+    pub fn license(mut self, add_license: bool) -> Self {
+        self.options.add_license = add_license;
+        self
+    }
+
+    /// Shift the headings of the docs by `offset` levels
+    pub fn heading_offset(mut self, offset: isize) -> Self {
+        self.options.heading_offset = offset;
+        self
+    }
+
+    /// Run `hook` on each line of the extracted docs, before they are processed
+    pub fn line_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.options.hooks.line(hook);
+        self
+    }
+
+    /// Run `hook` on the processed docs, before they are rendered with the template
+    pub fn before_template<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(String) -> String + Send + Sync + 'static,
+    {
+        self.options.hooks.before_template(hook);
+        self
+    }
+
+    /// Run `hook` on the readme rendered with the template, before it is converted to the output
+    /// format
+    pub fn after_template<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(String) -> String + Send + Sync + 'static,
+    {
+        self.options.hooks.after_template(hook);
+        self
+    }
+
+    /// Generate the readme
+    pub fn generate(&self) -> Result<Readme, Error> {
+        let mut options = self.options.clone();
+
+        let mut source: Box<dyn Read + '_> = match self.source {
+            Input::Text(ref source) => Box::new(source.as_bytes()),
+            ref input => {
+                let path = match input {
+                    Input::Path(path) => self.project_root.join(path),
+                    _ => project::source_path(&self.project_root, None, None, None)?,
+                };
+                // the included files and the modules are found from the source file
+                if options.source_dir.is_none() {
+                    options.source_dir = path.parent().map(Path::to_path_buf);
+                }
+                if options.modules_dir.is_none() {
+                    options.modules_dir = Some(project::modules_dir(&path));
+                }
+                Box::new(open(&path)?)
+            }
+        };
+
+        let mut template: Option<Box<dyn Read + '_>> = match self.template {
+            Input::Text(ref template) => Some(Box::new(template.as_bytes())),
+            Input::Path(ref path) => Some(Box::new(open(&self.project_root.join(path))?)),
+            Input::Default => {
+                let path = self.project_root.join(DEFAULT_TEMPLATE);
+                match path.is_file() {
+                    true => {
+                        log::verbose(format_args!(
+                            "Using the template '{}'",
+                            path.to_string_lossy()
+                        ));
+                        Some(Box::new(open(&path)?))
+                    }
+                    false => None,
+                }
+            }
+            Input::None => None,
+        };
+
+        generate_readme_with_options(&self.project_root, &mut source, template.as_mut(), &options)
+    }
+}
+
+/// Open the file at `path`
+fn open(path: &Path) -> Result<File, Error> {
+    log::read(path);
+    File::open(path).map_err(|e| Error::io(path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReadmeBuilder;
+    use crate::Error;
+
+    #[test]
+    fn generate_from_project() {
+        let readme = ReadmeBuilder::new("tests/check").generate().unwrap().text;
+
+        assert_eq!(
+            readme,
+            "# check\n\nCheck the README.\n\nThe docs changed.\n\nLicense: MIT"
+        );
+    }
+
+    #[test]
+    fn generate_with_source_and_template() {
+        let readme = ReadmeBuilder::new("tests/check")
+            .manifest_path("tests/check/Cargo.toml")
+            .source("//! Some docs.\n//!\n//! # Usage\n")
+            .template("{{crate}} {{version}}\n\n{{readme}}")
+            .generate()
+            .unwrap()
+            .text;
+
+        assert_eq!(readme, "check 0.1.0\n\nSome docs.\n\n## Usage");
+    }
+
+    #[test]
+    fn generate_with_hooks() {
+        let readme = ReadmeBuilder::new("tests/check")
+            .source("//! See go/docs.\n//!\n//! # Usage\n")
+            .template("{{crate}}\n\n{{readme}}")
+            .line_hook(|line| line.replace("go/docs", "<https://example.com/docs>"))
+            .before_template(|readme| format!("{}\n\nMore docs.", readme))
+            .after_template(|readme| readme.replace("check", "checked"))
+            .generate()
+            .unwrap()
+            .text;
+
+        assert_eq!(
+            readme,
+            "checked\n\nSee <https://example.com/docs>.\n\n## Usage\n\nMore docs."
+        );
+    }
+
+    #[test]
+    fn generate_with_configured_options() {
+        let readme = ReadmeBuilder::new("tests/check")
+            .source("//! Some docs.\n//!\n//! # Usage\n")
+            .no_template()
+            .configure(|options| {
+                options.add_title = false;
+                options.heading_offset = 0;
+            })
+            .license(false)
+            .generate()
+            .unwrap()
+            .text;
+
+        assert_eq!(readme, "Some docs.\n\n# Usage");
+    }
+
+    #[test]
+    fn missing_template() {
+        let result = ReadmeBuilder::new("tests/check")
+            .template_path("MISSING.tpl")
+            .generate();
+
+        match result {
+            Err(Error::Io {
+                path: Some(path), ..
+            }) => assert!(path.ends_with("MISSING.tpl")),
+            _ => panic!("expected an I/O error"),
+        }
+    }
+}
@@ -0,0 +1,238 @@
+//! Convert the processed readme from markdown to Confluence wiki markup
+//!
+//! The headings become `h1.` to `h6.`, the code blocks `code` macros, or `noformat` macros without
+//! language, the GitHub alerts, like `> [!NOTE]`, the `info`, `tip`, `note` and `warning` macros,
+//! and the other quotes `quote` macros. The links are written `[text|target]`, the images
+//! `!source!`, the lists with `*` or `#` after the markers of their parent lists, like `#*`, and
+//! the tables with `||` around the header cells. The emphasis and code spans get their wiki
+//! markup, and the braces of the prose are escaped, as they would start a macro. The HTML
+//! comments are removed, as the wiki markup has no comments.
+
+use super::markdown::{comment, join_blocks, parse, Block, Inline};
+
+/// Convert the markdown `readme` to Confluence wiki markup
+pub fn markdown_to_confluence(readme: &str) -> String {
+    blocks(&parse(readme)).join("\n").trim().to_owned()
+}
+
+/// Convert the markdown `blocks` to the lines of wiki markup, with a blank line between them
+fn blocks(blocks: &[Block]) -> Vec<String> {
+    join_blocks(blocks.iter().map(block), false)
+}
+
+/// Convert a markdown block to the lines of wiki markup
+fn block(block: &Block) -> Vec<String> {
+    match block {
+        Block::Heading { level, text } => {
+            vec![format!("h{}. {}", (*level).clamp(1, 6), inline(text))]
+        }
+        Block::Paragraph(text) => lines(&inline(text)),
+        Block::Code { language, code } => {
+            let (open, close) = match language {
+                Some(language) => (format!("{{code:language={}}}", language), "{code}"),
+                None => ("{noformat}".to_owned(), "{noformat}"),
+            };
+            let mut lines = vec![open];
+            lines.extend(self::lines(code));
+            lines.push(close.to_owned());
+            lines
+        }
+        Block::Quote { alert, blocks } => {
+            let kind = match alert.map(|alert| alert.name()) {
+                None => "quote",
+                Some("NOTE") => "info",
+                Some("TIP") => "tip",
+                Some("IMPORTANT") => "note",
+                Some(_) => "warning",
+            };
+            let mut lines = vec![format!("{{{}}}", kind)];
+            lines.extend(self::blocks(blocks));
+            lines.push(format!("{{{}}}", kind));
+            lines
+        }
+        Block::List { start, items, .. } => list(start.is_some(), items, ""),
+        Block::Table { header, rows, .. } => {
+            let cells = |cells: &[Vec<Inline>]| -> Vec<String> {
+                cells.iter().map(|cell| inline(cell)).collect()
+            };
+            let mut lines = vec![format!("|| {} ||", cells(header).join(" || "))];
+            lines.extend(
+                rows.iter()
+                    .map(|row| format!("| {} |", cells(row).join(" | "))),
+            );
+            lines
+        }
+        // the wiki markup has no comments
+        Block::Html(html) => match comment(html) {
+            Some(_) => Vec::new(),
+            None => lines(&escape(html)),
+        },
+        Block::Rule => vec!["----".to_owned()],
+    }
+}
+
+/// Convert the `items` of a list, which are marked with `#` when `ordered`, and else with `*`,
+/// after the `prefix` of the markers of the lists it is nested in, like `#*` for a bullet list
+/// in a numbered one
+fn list(ordered: bool, items: &[Vec<Block>], prefix: &str) -> Vec<String> {
+    let marker = match ordered {
+        true => format!("{}#", prefix),
+        false => format!("{}*", prefix),
+    };
+    let mut lines = Vec::new();
+    for item in items {
+        // the text of the item is its first paragraph, on the line of its marker
+        let rest = match item.split_first() {
+            Some((Block::Paragraph(text), rest)) => {
+                lines.push(format!("{} {}", marker, inline(text).replace('\n', " ")));
+                rest
+            }
+            _ => {
+                lines.push(marker.clone());
+                item.as_slice()
+            }
+        };
+        for block in rest {
+            match block {
+                Block::List { start, items, .. } => {
+                    lines.extend(list(start.is_some(), items, &marker))
+                }
+                block => lines.extend(self::block(block)),
+            }
+        }
+    }
+    lines
+}
+
+/// Split `text` into lines
+fn lines(text: &str) -> Vec<String> {
+    text.lines().map(ToOwned::to_owned).collect()
+}
+
+/// Convert the inline markup of `text`: the code spans, the emphasis and the links, escaping the
+/// braces of the prose
+fn inline(text: &[Inline]) -> String {
+    text.iter()
+        .map(|inline| match inline {
+            Inline::Text(text) | Inline::Html(text) => escape(text),
+            Inline::Code(code) => format!("{{{{{}}}}}", escape(code)),
+            Inline::Emphasis(text) => format!("_{}_", self::inline(text)),
+            Inline::Strong(text) => format!("*{}*", self::inline(text)),
+            Inline::Strikethrough(text) => format!("-{}-", self::inline(text)),
+            Inline::Link { target, text, .. } => match text.as_slice() {
+                // a linked image is a link whose text is the image
+                [Inline::Image { source, .. }] => format!("[!{}!|{}]", source, target),
+                [] => format!("[{}]", target),
+                text => format!("[{}|{}]", self::inline(text), target),
+            },
+            Inline::Image { source, .. } => format!("!{}!", source),
+            Inline::SoftBreak | Inline::HardBreak => "\n".to_owned(),
+        })
+        .collect()
+}
+
+/// Escape the braces of `text`, which would start a macro
+fn escape(text: &str) -> String {
+    text.replace('{', "\\{").replace('}', "\\}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::markdown_to_confluence;
+
+    #[test]
+    fn convert_readme() {
+        let readme = "\
+<!-- cargo-readme start -->
+[![Crates.io](https://img.shields.io/crates/v/a.svg)](https://crates.io/crates/a)
+
+# my-crate
+
+Use `Vec<{T}>`, **now** and *here*, see [the guide][guide] and {this}.
+
+> [!NOTE]
+> Not stable.
+
+> Quoted.
+
+## Usage
+
+```rust,no_run
+let a = vec![1];
+```
+
+```
+plain
+```
+
+- one
+  - nested
+1. first
+
+| a | b |
+| - | - |
+| 1 | 2 |
+
+---
+
+[guide]: https://example.com/guide";
+
+        assert_eq!(
+            markdown_to_confluence(readme),
+            "\
+[!https://img.shields.io/crates/v/a.svg!|https://crates.io/crates/a]
+
+h1. my-crate
+
+Use {{Vec<\\{T\\}>}}, *now* and _here_, see [the guide|https://example.com/guide] and \\{this\\}.
+
+{info}
+Not stable.
+{info}
+
+{quote}
+Quoted.
+{quote}
+
+h2. Usage
+
+{code:language=rust}
+let a = vec![1];
+{code}
+
+{noformat}
+plain
+{noformat}
+
+* one
+** nested
+
+# first
+
+|| a || b ||
+| 1 | 2 |
+
+----"
+        );
+    }
+
+    #[test]
+    fn nested_list_markers() {
+        let readme = "\
+1. first
+   - nested
+     1. deep
+2. second
+   * other";
+
+        assert_eq!(
+            markdown_to_confluence(readme),
+            "\
+# first
+#* nested
+#*# deep
+# second
+#* other"
+        );
+    }
+}
@@ -0,0 +1,328 @@
+//! Rewrite the docs for the renderer of crates.io
+//!
+//! crates.io renders the README with a stricter sanitizer and highlighter than GitHub. The
+//! elements it removes, like `<script>` or `<svg>`, are removed with their content, and the tags it
+//! removes, like `<picture>` or `<source>`, are removed keeping their content, so the `<img>` of a
+//! `<picture>` is shown. The info strings of the code blocks are reduced to their language, and
+//! dropped when crates.io cannot highlight it. Relative links are resolved by crates.io from the
+//! `repository` set in `Cargo.toml`, so without one they are removed, keeping their text.
+
+use std::collections::HashSet;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::fences::{fenced, CodeBlocks, LineKind};
+use super::links::{
+    code_span_len, definition, inline_target, map_links, normalize_label, reference_label,
+};
+use super::relative::is_relative;
+
+/// Elements removed by crates.io along with their content
+const DROPPED_ELEMENTS: &[&str] = &[
+    "audio", "canvas", "embed", "iframe", "noscript", "object", "script", "style", "svg", "video",
+];
+
+/// Elements whose tags are removed by crates.io, keeping their content
+const UNWRAPPED_ELEMENTS: &[&str] = &[
+    "button", "center", "font", "form", "input", "label", "option", "picture", "select", "source",
+    "textarea",
+];
+
+/// Languages of the code blocks highlighted by crates.io, with their usual aliases
+const LANGUAGES: &[&str] = &[
+    "bash",
+    "c",
+    "console",
+    "cpp",
+    "css",
+    "diff",
+    "dockerfile",
+    "go",
+    "html",
+    "ini",
+    "java",
+    "js",
+    "javascript",
+    "json",
+    "kotlin",
+    "lua",
+    "makefile",
+    "markdown",
+    "md",
+    "plaintext",
+    "py",
+    "python",
+    "ruby",
+    "rust",
+    "sh",
+    "shell",
+    "sql",
+    "swift",
+    "text",
+    "toml",
+    "ts",
+    "typescript",
+    "txt",
+    "xml",
+    "yaml",
+    "yml",
+];
+
+lazy_static! {
+    // an HTML tag, like `<picture>`, `</svg>` or `<source srcset="dark.png" />`
+    static ref RE_TAG: Regex =
+        Regex::new(r"<(?P<close>/?)(?P<name>[A-Za-z][A-Za-z0-9-]*)(?:\s[^<>]*)?>").unwrap();
+}
+
+/// Rewrite the doc lines so crates.io renders them like GitHub
+///
+/// The relative links are removed when the crate has no `repository` to resolve them from.
+pub fn crates_io_safe(lines: Vec<String>, has_repository: bool) -> Vec<String> {
+    let broken = match has_repository {
+        true => HashSet::new(),
+        false => relative_definitions(&lines),
+    };
+
+    let mut code = CodeBlocks::default();
+    let mut dropped: Option<String> = None;
+    let mut result: Vec<String> = Vec::new();
+    let mut removed = false;
+    for line in lines {
+        // the fences in the content of a removed element are removed with it
+        let kind = match dropped {
+            Some(_) => LineKind::Text,
+            None => code.kind(&line),
+        };
+        match (kind, code.fence()) {
+            (LineKind::Open, Some(fence)) => {
+                result.push(fence_language(&line, fence));
+                continue;
+            }
+            (LineKind::Text, _) => {}
+            _ => {
+                result.push(line);
+                continue;
+            }
+        }
+
+        if definition(&line).is_some_and(|d| broken.contains(&normalize_label(d.label))) {
+            removed = true;
+            continue;
+        }
+
+        // do not leave two blank lines where a line is removed
+        let blank_after_removed =
+            removed && line.trim().is_empty() && result.last().is_none_or(|l| l.trim().is_empty());
+        removed = false;
+        if blank_after_removed {
+            continue;
+        }
+
+        let rewritten = rewrite_line(&line, &mut dropped, has_repository, &broken);
+        match rewritten.trim().is_empty() && !line.trim().is_empty() {
+            true => removed = true,
+            false => result.push(rewritten),
+        }
+    }
+
+    // nor a blank line at the end, where the last lines are removed
+    if removed {
+        while result.last().is_some_and(|l| l.trim().is_empty()) {
+            result.pop();
+        }
+    }
+
+    result
+}
+
+/// Collect the normalized labels of the reference definitions with a relative target
+fn relative_definitions(lines: &[String]) -> HashSet<String> {
+    let mut labels = HashSet::new();
+    for (_, line) in fenced(lines).filter(|(kind, _)| !kind.is_code()) {
+        if let Some(definition) = definition(line) {
+            if is_relative(unbracket(definition.target)) {
+                labels.insert(normalize_label(definition.label));
+            }
+        }
+    }
+    labels
+}
+
+/// Reduce the info string of the opening `fence` of a code block to a language crates.io knows
+fn fence_language(line: &str, fence: &str) -> String {
+    let indent = &line[..line.len() - line.trim_start().len()];
+    let info = line.trim_start()[fence.len()..].trim();
+    let language = info
+        .split([',', ' ', '{'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    match LANGUAGES.contains(&language.as_str()) {
+        true => format!("{}{}{}", indent, fence, language),
+        false => format!("{}{}", indent, fence),
+    }
+}
+
+/// Rewrite the HTML and the links of a line outside code blocks, skipping its code spans
+///
+/// `dropped` is the element whose content is being removed, which may span several lines.
+fn rewrite_line(
+    line: &str,
+    dropped: &mut Option<String>,
+    has_repository: bool,
+    broken: &HashSet<String>,
+) -> String {
+    let mut result = String::new();
+    let mut rest = line;
+    while !rest.is_empty() {
+        let (text, code) = match rest.find('`') {
+            Some(tick) => {
+                let len = code_span_len(&rest[tick..]);
+                (&rest[..tick], &rest[tick..tick + len])
+            }
+            None => (rest, ""),
+        };
+
+        let stripped = strip_html(text, dropped);
+        result.push_str(&match has_repository {
+            true => stripped,
+            false => unlink_relative(&stripped, broken),
+        });
+        if dropped.is_none() {
+            result.push_str(code);
+        }
+        rest = &rest[text.len() + code.len()..];
+    }
+
+    result
+}
+
+/// Remove the elements and tags crates.io removes from `text`, which has no code spans
+fn strip_html(text: &str, dropped: &mut Option<String>) -> String {
+    let mut result = String::new();
+    let mut pos = 0;
+    for cap in RE_TAG.captures_iter(text) {
+        let tag = cap.get(0).map_or("", |m| m.as_str());
+        let start = cap.get(0).map_or(0, |m| m.start());
+        let name = cap["name"].to_lowercase();
+        let closing = !cap["close"].is_empty();
+        if dropped.is_none() {
+            result.push_str(&text[pos..start]);
+        }
+        pos = start + tag.len();
+
+        match dropped {
+            Some(element) => {
+                if closing && name == *element {
+                    *dropped = None;
+                }
+            }
+            None if DROPPED_ELEMENTS.contains(&name.as_str()) => {
+                if !closing && !tag.ends_with("/>") {
+                    *dropped = Some(name);
+                }
+            }
+            None if UNWRAPPED_ELEMENTS.contains(&name.as_str()) => {}
+            None => result.push_str(tag),
+        }
+    }
+    if dropped.is_none() {
+        result.push_str(&text[pos..]);
+    }
+
+    result
+}
+
+/// Replace the links and images to relative targets by their text, in `text` without code spans
+///
+/// The reference links are replaced when their label is one of `broken`.
+fn unlink_relative(text: &str, broken: &HashSet<String>) -> String {
+    map_links(text, &mut |link_text, after, _| {
+        let relative = match inline_target(after) {
+            Some((target, _, rest)) => Some(rest).filter(|_| is_relative(unbracket(target))),
+            // a reference definition, not a reference link
+            None if after.starts_with(':') => None,
+            None => {
+                let (label, rest) = reference_label(link_text, after);
+                Some(rest).filter(|_| broken.contains(&normalize_label(label)))
+            }
+        };
+        relative.map(|rest| (unlink_relative(link_text, broken), rest))
+    })
+}
+
+/// Remove the angle brackets around a link target, like `<path with spaces.md>`
+fn unbracket(target: &str) -> &str {
+    target
+        .strip_prefix('<')
+        .and_then(|t| t.strip_suffix('>'))
+        .unwrap_or(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::crates_io_safe;
+
+    fn safe(lines: &[&str], has_repository: bool) -> Vec<String> {
+        crates_io_safe(
+            lines.iter().map(|l| l.to_string()).collect(),
+            has_repository,
+        )
+    }
+
+    const INPUT: &[&str] = &[
+        "<picture>",
+        "  <source srcset=\"logo-dark.png\" media=\"(prefers-color-scheme: dark)\">",
+        "  <img src=\"https://example.com/logo.png\" alt=\"logo\">",
+        "</picture>",
+        "",
+        "<svg width=\"10\">",
+        "  <circle r=\"5\" />",
+        "</svg>",
+        "",
+        "See [the guide](docs/GUIDE.md), [the site](https://example.com) and [the FAQ][faq].",
+        "Not `<script>` nor `[a](b.md)`.",
+        "",
+        "```rust,no_run",
+        "<script></script>",
+        "```",
+        "",
+        "```mermaid",
+        "graph TD;",
+        "```",
+        "",
+        "[faq]: FAQ.md",
+    ];
+
+    #[test]
+    fn crates_io_without_repository() {
+        assert_eq!(
+            safe(INPUT, false),
+            [
+                "  <img src=\"https://example.com/logo.png\" alt=\"logo\">",
+                "",
+                "See the guide, [the site](https://example.com) and the FAQ.",
+                "Not `<script>` nor `[a](b.md)`.",
+                "",
+                "```rust",
+                "<script></script>",
+                "```",
+                "",
+                "```",
+                "graph TD;",
+                "```",
+            ]
+        );
+    }
+
+    #[test]
+    fn crates_io_with_repository() {
+        let safe = safe(INPUT, true);
+        assert_eq!(
+            safe[2],
+            "See [the guide](docs/GUIDE.md), [the site](https://example.com) and [the FAQ][faq]."
+        );
+        assert_eq!(safe.last().unwrap(), "[faq]: FAQ.md");
+    }
+}
@@ -0,0 +1,251 @@
+//! Handle the mermaid diagrams and the display math of the docs
+//!
+//! The diagrams are written in ```` ```mermaid ```` code blocks, and the formulas in ```` ```math ````
+//! code blocks or between `$$` lines. They are passed through as they are by default, as GitHub
+//! renders both. For GitHub, the `$$` blocks can be turned into ```` ```math ```` code blocks, whose
+//! content is not read as markdown. For the renderers without diagrams nor math, like crates.io,
+//! they can be replaced by images rendered by [mermaid.ink](https://mermaid.ink) and
+//! [CodeCogs](https://latex.codecogs.com), linking to the diagram in the mermaid live editor or to
+//! the image of the formula.
+
+use percent_encoding as pe;
+use serde::Deserialize;
+use serde_json::json;
+
+use super::fences::{fence_language, CodeBlocks, LineKind};
+
+/// What to do with the mermaid diagrams and the display math
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DiagramBlocks {
+    /// Keep the blocks as they are
+    #[default]
+    Passthrough,
+    /// Write the blocks the way GitHub renders them, the math in ```` ```math ```` code blocks
+    Github,
+    /// Replace the blocks by linked images
+    Image,
+}
+
+/// Kind of a block handled here
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Mermaid,
+    Math,
+}
+
+/// Rewrite the mermaid and math blocks of the doc lines as given by `mode`
+pub fn diagram_blocks(lines: Vec<String>, mode: DiagramBlocks) -> Vec<String> {
+    if mode == DiagramBlocks::Passthrough {
+        return lines;
+    }
+
+    let mut result: Vec<String> = Vec::new();
+    // the opening line of the diagram or math block being read, its kind and its content
+    let mut block: Option<(String, Kind, Vec<String>)> = None;
+    let mut code_blocks = CodeBlocks::default();
+    for line in lines {
+        // the `$$` lines are not code fences for the other passes, only for this one, and a fence
+        // inside a `$$` block is part of the formula
+        if let Some((open, kind, mut code)) = block.take_if(|(open, _, _)| open.trim() == "$$") {
+            match line.trim() == "$$" {
+                true => result.extend(close(&open, kind, code, mode)),
+                false => {
+                    code.push(line);
+                    block = Some((open, kind, code));
+                }
+            }
+            continue;
+        }
+
+        match code_blocks.kind(&line) {
+            LineKind::Text if line.trim() == "$$" => block = Some((line, Kind::Math, Vec::new())),
+            LineKind::Open => {
+                let kind = match fence_language(&line) {
+                    Some("mermaid") => Some(Kind::Mermaid),
+                    Some("math") => Some(Kind::Math),
+                    _ => None,
+                };
+                match kind {
+                    Some(kind) => block = Some((line, kind, Vec::new())),
+                    None => result.push(line),
+                }
+            }
+            LineKind::Code => match block {
+                Some((_, _, ref mut code)) => code.push(line),
+                None => result.push(line),
+            },
+            LineKind::Close => match block.take() {
+                Some((open, kind, code)) => result.extend(close(&open, kind, code, mode)),
+                None => result.push(line),
+            },
+            LineKind::Indented => result.push(line),
+            LineKind::Text => {
+                // a formula on a single line, like `$$ x^2 $$`
+                let indent = &line[..line.len() - line.trim_start().len()];
+                match line
+                    .trim()
+                    .strip_prefix("$$")
+                    .and_then(|t| t.strip_suffix("$$"))
+                {
+                    Some(formula) if !formula.trim().is_empty() => {
+                        let code = [formula.trim().to_owned()];
+                        result.extend(render(Kind::Math, indent, &code, mode));
+                    }
+                    _ => result.push(line),
+                }
+            }
+        }
+    }
+
+    // a block that is not closed is left as is
+    if let Some((open, _, code)) = block {
+        result.push(open);
+        result.extend(code);
+    }
+
+    result
+}
+
+/// Render the block opened by the line `open`, of `kind`, once it is closed
+fn close(open: &str, kind: Kind, code: Vec<String>, mode: DiagramBlocks) -> Vec<String> {
+    let indent = &open[..open.len() - open.trim_start().len()];
+    let code: Vec<String> = code
+        .into_iter()
+        .map(|l| l.strip_prefix(indent).map(str::to_owned).unwrap_or(l))
+        .collect();
+    render(kind, indent, &code, mode)
+}
+
+/// Render the `code` of a block of `kind`, indented by `indent`, as given by `mode`
+fn render(kind: Kind, indent: &str, code: &[String], mode: DiagramBlocks) -> Vec<String> {
+    let fence = match code.iter().any(|l| l.trim_start().starts_with("```")) {
+        true => "~~~",
+        false => "```",
+    };
+    match (kind, mode) {
+        (Kind::Mermaid, DiagramBlocks::Image) => {
+            let source = code.join("\n");
+            let state = json!({ "code": source, "mermaid": { "theme": "default" } });
+            vec![format!(
+                "{}[![diagram](https://mermaid.ink/svg/{})](https://mermaid.live/view#base64:{})",
+                indent,
+                base64(source.as_bytes()),
+                base64(state.to_string().as_bytes())
+            )]
+        }
+        (Kind::Math, DiagramBlocks::Image) => {
+            let formula: Vec<&str> = code.iter().map(|l| l.trim()).collect();
+            let url = format!(
+                "https://latex.codecogs.com/svg.image?{}",
+                pe::utf8_percent_encode(&formula.join(" "), pe::NON_ALPHANUMERIC)
+            );
+            vec![format!("{}[![formula]({})]({})", indent, url, url)]
+        }
+        (kind, _) => {
+            let lang = match kind {
+                Kind::Mermaid => "mermaid",
+                Kind::Math => "math",
+            };
+            let mut block = vec![format!("{}{}{}", indent, fence, lang)];
+            block.extend(code.iter().map(|l| match l.is_empty() {
+                true => String::new(),
+                false => format!("{}{}", indent, l),
+            }));
+            block.push(format!("{}{}", indent, fence));
+            block
+        }
+    }
+}
+
+/// Encode `bytes` in base64, with the URL safe alphabet
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char),
+                false => encoded.push('='),
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{base64, diagram_blocks, DiagramBlocks};
+
+    fn rewrite(lines: &[&str], mode: DiagramBlocks) -> Vec<String> {
+        diagram_blocks(lines.iter().map(|l| l.to_string()).collect(), mode)
+    }
+
+    const INPUT: &[&str] = &[
+        "```mermaid",
+        "graph LR;",
+        "```",
+        "",
+        "$$",
+        "e^{i\\pi} + 1 = 0",
+        "$$",
+        "",
+        "- $$ x_1 $$",
+    ];
+
+    #[test]
+    fn fence_inside_math_block() {
+        let input = ["$$", "```", "$$", "text"];
+        assert_eq!(
+            rewrite(&input, DiagramBlocks::Github),
+            ["~~~math", "```", "~~~", "text"]
+        );
+    }
+
+    #[test]
+    fn passthrough_blocks() {
+        assert_eq!(rewrite(INPUT, DiagramBlocks::Passthrough), INPUT);
+    }
+
+    #[test]
+    fn github_blocks() {
+        assert_eq!(
+            rewrite(INPUT, DiagramBlocks::Github),
+            [
+                "```mermaid",
+                "graph LR;",
+                "```",
+                "",
+                "```math",
+                "e^{i\\pi} + 1 = 0",
+                "```",
+                "",
+                "- $$ x_1 $$",
+            ]
+        );
+    }
+
+    #[test]
+    fn image_blocks() {
+        let images = rewrite(INPUT, DiagramBlocks::Image);
+        assert!(images[0].starts_with(
+            "[![diagram](https://mermaid.ink/svg/Z3JhcGggTFI7)](https://mermaid.live/"
+        ));
+        assert_eq!(
+            images[2],
+            "[![formula](https://latex.codecogs.com/svg.image?e%5E%7Bi%5Cpi%7D%20%2B%201%20%3D%200)]\
+             (https://latex.codecogs.com/svg.image?e%5E%7Bi%5Cpi%7D%20%2B%201%20%3D%200)"
+        );
+    }
+
+    #[test]
+    fn encode_base64() {
+        assert_eq!(base64(b"graph"), "Z3JhcGg=");
+        assert_eq!(base64(b"ab?>"), "YWI_Pg==");
+    }
+}
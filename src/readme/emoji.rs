@@ -0,0 +1,309 @@
+//! Convert the emoji shortcodes of the prose, like `:crab:`, to Unicode emoji, or back
+//!
+//! GitHub expands the shortcodes, but crates.io, rustdoc and most other renderers show them as
+//! they are written. Writing the emoji themselves makes the README look the same everywhere, and
+//! writing the shortcodes keeps the source in ASCII. Only the usual shortcodes of GitHub are known,
+//! the others are left as they are, as are the code blocks, code spans, link targets and HTML tags.
+
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+use super::fences::fenced;
+use super::links::code_span_len;
+
+/// What to do with the emoji of the prose
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Emoji {
+    /// Keep the shortcodes and the emoji as they are
+    #[default]
+    Keep,
+    /// Convert the shortcodes, like `:crab:`, to Unicode emoji
+    Unicode,
+    /// Convert the Unicode emoji to shortcodes
+    Shortcodes,
+}
+
+/// Shortcodes of GitHub and their emoji
+const EMOJI: &[(&str, &str)] = &[
+    ("+1", "👍"),
+    ("-1", "👎"),
+    ("100", "💯"),
+    ("alarm_clock", "⏰"),
+    ("apple", "🍎"),
+    ("arrow_down", "⬇️"),
+    ("arrow_left", "⬅️"),
+    ("arrow_right", "➡️"),
+    ("arrow_up", "⬆️"),
+    ("art", "🎨"),
+    ("balloon", "🎈"),
+    ("bangbang", "‼️"),
+    ("beer", "🍺"),
+    ("bell", "🔔"),
+    ("bike", "🚲"),
+    ("bomb", "💣"),
+    ("book", "📖"),
+    ("books", "📚"),
+    ("boom", "💥"),
+    ("bookmark", "🔖"),
+    ("bow", "🙇"),
+    ("brain", "🧠"),
+    ("broken_heart", "💔"),
+    ("bug", "🐛"),
+    ("bulb", "💡"),
+    ("calendar", "📆"),
+    ("camera", "📷"),
+    ("cake", "🍰"),
+    ("cat", "🐱"),
+    ("chart_with_upwards_trend", "📈"),
+    ("checkered_flag", "🏁"),
+    ("clap", "👏"),
+    ("clipboard", "📋"),
+    ("cloud", "☁️"),
+    ("coffee", "☕"),
+    ("computer", "💻"),
+    ("confetti_ball", "🎊"),
+    ("construction", "🚧"),
+    ("cookie", "🍪"),
+    ("crab", "🦀"),
+    ("cry", "😢"),
+    ("dart", "🎯"),
+    ("dash", "💨"),
+    ("dog", "🐶"),
+    ("dragon", "🐉"),
+    ("earth_africa", "🌍"),
+    ("earth_americas", "🌎"),
+    ("earth_asia", "🌏"),
+    ("email", "📧"),
+    ("eyes", "👀"),
+    ("file_folder", "📁"),
+    ("fire", "🔥"),
+    ("gear", "⚙️"),
+    ("gem", "💎"),
+    ("ghost", "👻"),
+    ("gift", "🎁"),
+    ("globe_with_meridians", "🌐"),
+    ("green_heart", "💚"),
+    ("grinning", "😀"),
+    ("hammer", "🔨"),
+    ("hammer_and_wrench", "🛠️"),
+    ("heart", "❤️"),
+    ("heavy_check_mark", "✔️"),
+    ("heavy_minus_sign", "➖"),
+    ("heavy_plus_sign", "➕"),
+    ("hourglass", "⌛"),
+    ("house", "🏠"),
+    ("information_source", "ℹ️"),
+    ("joy", "😂"),
+    ("key", "🔑"),
+    ("label", "🏷️"),
+    ("laughing", "😆"),
+    ("link", "🔗"),
+    ("lipstick", "💄"),
+    ("lock", "🔒"),
+    ("loudspeaker", "📢"),
+    ("mag", "🔍"),
+    ("mailbox", "📫"),
+    ("memo", "📝"),
+    ("microscope", "🔬"),
+    ("moneybag", "💰"),
+    ("muscle", "💪"),
+    ("new", "🆕"),
+    ("no_entry", "⛔"),
+    ("no_entry_sign", "🚫"),
+    ("ok", "🆗"),
+    ("ok_hand", "👌"),
+    ("package", "📦"),
+    ("page_facing_up", "📄"),
+    ("paperclip", "📎"),
+    ("partying_face", "🥳"),
+    ("pencil2", "✏️"),
+    ("point_right", "👉"),
+    ("pray", "🙏"),
+    ("pushpin", "📌"),
+    ("question", "❓"),
+    ("rainbow", "🌈"),
+    ("recycle", "♻️"),
+    ("robot", "🤖"),
+    ("rocket", "🚀"),
+    ("rotating_light", "🚨"),
+    ("scroll", "📜"),
+    ("see_no_evil", "🙈"),
+    ("seedling", "🌱"),
+    ("shield", "🛡️"),
+    ("shrug", "🤷"),
+    ("smile", "😄"),
+    ("smiley", "😃"),
+    ("snail", "🐌"),
+    ("snake", "🐍"),
+    ("sparkles", "✨"),
+    ("speech_balloon", "💬"),
+    ("star", "⭐"),
+    ("star2", "🌟"),
+    ("stop_sign", "🛑"),
+    ("sunglasses", "😎"),
+    ("sunny", "☀️"),
+    ("tada", "🎉"),
+    ("test_tube", "🧪"),
+    ("thinking", "🤔"),
+    ("thumbsdown", "👎"),
+    ("thumbsup", "👍"),
+    ("trophy", "🏆"),
+    ("truck", "🚚"),
+    ("unicorn", "🦄"),
+    ("unlock", "🔓"),
+    ("warning", "⚠️"),
+    ("wave", "👋"),
+    ("white_check_mark", "✅"),
+    ("wink", "😉"),
+    ("wrench", "🔧"),
+    ("x", "❌"),
+    ("zap", "⚡"),
+];
+
+lazy_static! {
+    // an emoji shortcode, like `:crab:`
+    static ref RE_SHORTCODE: Regex = Regex::new(r":(?P<name>[a-z0-9_+-]+):").unwrap();
+    // the parts of a line that are not prose: link targets, autolinks and HTML tags
+    static ref RE_PROTECTED: Regex =
+        Regex::new(r"\]\([^)]*\)|<[A-Za-z/!][^<>]*>|[a-z]+://[^\s)>]+").unwrap();
+    // the emoji and their shortcode, the longest emoji first, so `❤️` is not read as `❤`
+    static ref SHORTCODES: Vec<(&'static str, &'static str)> = {
+        let mut shortcodes: Vec<(&str, &str)> = Vec::new();
+        // the first shortcode of an emoji is the one it is written with
+        for (name, emoji) in EMOJI {
+            if !shortcodes.iter().any(|(e, _)| e == emoji) {
+                shortcodes.push((emoji, name));
+            }
+        }
+        shortcodes.sort_by_key(|(emoji, _)| std::cmp::Reverse(emoji.len()));
+        shortcodes
+    };
+}
+
+/// Convert the emoji of the prose of the doc lines as given by `mode`
+pub fn convert_emoji(lines: Vec<String>, mode: Emoji) -> Vec<String> {
+    if mode == Emoji::Keep {
+        return lines;
+    }
+
+    fenced(lines)
+        .map(|(kind, line)| match kind.is_code() {
+            true => line,
+            false => convert_line(&line, mode),
+        })
+        .collect()
+}
+
+/// Convert the emoji of a line outside code blocks, skipping its code spans
+fn convert_line(line: &str, mode: Emoji) -> String {
+    let mut converted = String::new();
+    let mut rest = line;
+
+    while let Some(pos) = rest.find('`') {
+        converted.push_str(&prose(&rest[..pos], mode));
+        let len = code_span_len(&rest[pos..]);
+        converted.push_str(&rest[pos..pos + len]);
+        rest = &rest[pos + len..];
+    }
+    converted.push_str(&prose(rest, mode));
+
+    converted
+}
+
+/// Convert the emoji of `text`, which has no code spans, skipping its link targets and tags
+fn prose(text: &str, mode: Emoji) -> String {
+    let mut converted = String::new();
+    let mut pos = 0;
+    for m in RE_PROTECTED.find_iter(text) {
+        converted.push_str(&convert(&text[pos..m.start()], mode));
+        converted.push_str(m.as_str());
+        pos = m.end();
+    }
+    converted.push_str(&convert(&text[pos..], mode));
+
+    converted
+}
+
+/// Convert the shortcodes of `text` to emoji, or its emoji to shortcodes
+fn convert(text: &str, mode: Emoji) -> String {
+    match mode {
+        Emoji::Keep => text.to_owned(),
+        Emoji::Unicode => RE_SHORTCODE
+            .replace_all(text, |cap: &Captures| {
+                match EMOJI.iter().find(|(name, _)| *name == &cap["name"]) {
+                    Some((_, emoji)) => (*emoji).to_owned(),
+                    None => cap[0].to_owned(),
+                }
+            })
+            .into_owned(),
+        Emoji::Shortcodes => {
+            let mut converted = String::new();
+            let mut rest = text;
+            'chars: while let Some(c) = rest.chars().next() {
+                for (emoji, name) in SHORTCODES.iter() {
+                    if let Some(after) = rest.strip_prefix(emoji) {
+                        converted.push_str(&format!(":{}:", name));
+                        rest = after;
+                        continue 'chars;
+                    }
+                }
+                converted.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+            converted
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{convert_emoji, Emoji};
+
+    fn convert(lines: &[&str], mode: Emoji) -> Vec<String> {
+        convert_emoji(lines.iter().map(|l| l.to_string()).collect(), mode)
+    }
+
+    #[test]
+    fn shortcodes_to_unicode() {
+        assert_eq!(
+            convert(
+                &[
+                    ":crab: Fast :rocket:, :unknown: and `:crab:` at 10:30:00.",
+                    "[:tada:](https://a.com/:x:)",
+                    "```",
+                    ":crab:",
+                    "```",
+                ],
+                Emoji::Unicode
+            ),
+            [
+                "🦀 Fast 🚀, :unknown: and `:crab:` at 10:30:00.",
+                "[🎉](https://a.com/:x:)",
+                "```",
+                ":crab:",
+                "```",
+            ]
+        );
+    }
+
+    #[test]
+    fn unicode_to_shortcodes() {
+        assert_eq!(
+            convert(&["🦀 Loved ❤️, not `🦀`. 👍"], Emoji::Shortcodes),
+            [":crab: Loved :heart:, not `🦀`. :+1:"]
+        );
+    }
+
+    #[test]
+    fn indented_code_is_left_alone() {
+        let lines = &[
+            "Some code:",
+            "",
+            r#"    let s = ":crab:"; // [`Foo`] <b>x</b> [a](https://a.io) -- "q""#,
+            "",
+            "Done.",
+        ];
+
+        assert_eq!(convert(lines, Emoji::Unicode), lines);
+    }
+}
@@ -2,77 +2,511 @@
 
 use std::io::{self, BufRead, BufReader, Read};
 
+/// Style of the doc comments found in the source
+#[derive(PartialEq)]
+enum Style {
+    /// `//!` comments
+    SingleLine,
+    /// `/*! */` comments
+    MultiLine,
+}
+
+/// Include function used when the path of the source file is unknown, which always fails
+pub fn unresolved_include(path: &str) -> io::Result<Vec<String>> {
+    Err(io::Error::other(format!(
+        "Cannot resolve `include_str!(\"{}\")` without the path of the source file",
+        path
+    )))
+}
+
 /// Read the given `Read`er and return a `Vec` of the rustdoc lines found
-pub fn extract_docs<R: Read>(reader: R) -> io::Result<Vec<String>> {
-    let mut reader = BufReader::new(reader);
-    let mut line = String::new();
+///
+/// Doc comments can be written as `//!` lines or `/*! */` blocks (but the styles cannot be mixed),
+/// and as `#![doc = "..."]` attributes.
+///
+/// The docs of `#![cfg_attr(..., doc = "...")]` attributes are read when their predicate holds
+/// for the active `cfgs` (`doc` is always active). The docs of `#![doc = include_str!("...")]`
+/// attributes are read by the `include` function, which is given the path of the included file.
+pub fn extract_docs_with<R: Read, F>(
+    reader: R,
+    cfgs: &[String],
+    mut include: F,
+) -> io::Result<Vec<String>>
+where
+    F: FnMut(&str) -> io::Result<Vec<String>>,
+{
+    let mut lines = lossy_lines(reader);
+    let mut result = Vec::new();
+    let mut style = None;
+    let mut started = false;
 
-    while reader.read_line(&mut line)? > 0 {
-        if line.starts_with("//!") {
-            return extract_docs_singleline_style(line, reader);
-        }
+    while let Some(line) = lines.next() {
+        let line = line?;
+        // doc comments may be indented, like inside a `cfg` block
+        let trimmed = line.trim_start();
 
-        if line.starts_with("/*!") {
-            return extract_docs_multiline_style(line, reader);
+        if trimmed.starts_with("//!") {
+            if style == Some(Style::MultiLine) {
+                break;
+            }
+            style = Some(Style::SingleLine);
+            result.push(normalize_line(trimmed.to_owned()));
+        } else if trimmed.starts_with("/*!") {
+            if style == Some(Style::SingleLine) {
+                break;
+            }
+            style = Some(Style::MultiLine);
+            result.extend(extract_docs_multiline_style(
+                trimmed.to_owned(),
+                &mut lines,
+            )?);
+        } else if trimmed.starts_with("#![") {
+            let attribute = read_attribute(trimmed.to_owned(), &mut lines)?;
+            match parse_doc_attribute(&attribute, cfgs) {
+                Some(docs) => {
+                    for doc in docs {
+                        match doc {
+                            DocAttribute::Text(doc) => result.extend(doc),
+                            DocAttribute::Include(path) => result.extend(include(&path)?),
+                        }
+                    }
+                }
+                // other crate attributes do not end the docs
+                None => continue,
+            }
+        } else if started && !line.trim().is_empty() {
+            // doc ends, code starts
+            break;
+        } else {
+            continue;
         }
 
-        line.clear();
+        started = true;
     }
 
-    Ok(Vec::new())
+    Ok(result)
 }
 
-fn extract_docs_singleline_style<R: Read>(
+fn extract_docs_multiline_style<I: Iterator<Item = io::Result<String>>>(
     first_line: String,
-    reader: BufReader<R>,
+    lines: &mut I,
 ) -> io::Result<Vec<String>> {
-    let mut result = vec![normalize_line(first_line)];
-
-    for line in reader.lines() {
-        let line = line?;
-
-        if line.starts_with("//!") {
-            result.push(normalize_line(line));
-        } else if line.trim().len() > 0 {
-            // doc ends, code starts
+    // comment body, without the opening "/*!"
+    let mut body = first_line.trim_end().split_at(3).1.to_owned();
+    let mut nesting: usize = 1;
+    let mut scanned = 0;
+
+    loop {
+        if let Some(end) = find_comment_end(&body[scanned..], &mut nesting) {
+            body.truncate(scanned + end);
             break;
         }
+        scanned = body.len();
+
+        match lines.next() {
+            Some(line) => {
+                body.push('\n');
+                body.push_str(line?.trim_end());
+            }
+            None => break,
+        }
+    }
+
+    let mut result: Vec<String> = body.lines().map(|l| l.trim_end().to_owned()).collect();
+
+    // text on the same line as "/*!" is kept as is, an empty first line is dropped
+    let inline_first_line = result.first().is_some_and(|l| !l.trim().is_empty());
+    if inline_first_line {
+        result[0] = result[0].trim_start().to_owned();
+    } else if !result.is_empty() {
+        result.remove(0);
+    }
+
+    while result.last().is_some_and(|l| l.trim().is_empty()) {
+        result.pop();
     }
 
+    let skip = inline_first_line as usize;
+    strip_leading_asterisks(&mut result[skip..]);
+    unindent(&mut result[skip..]);
+
     Ok(result)
 }
 
-fn extract_docs_multiline_style<R: Read>(
+/// Find where the block comment ends in `text`, updating the `nesting` level
+///
+/// Returns the position of the closing "*/" that brings the nesting level to zero.
+fn find_comment_end(text: &str, nesting: &mut usize) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+
+    while i + 1 < bytes.len() {
+        match (bytes[i], bytes[i + 1]) {
+            (b'/', b'*') => {
+                *nesting += 1;
+                i += 2;
+            }
+            (b'*', b'/') => {
+                *nesting -= 1;
+                if *nesting == 0 {
+                    return Some(i);
+                }
+                i += 2;
+            }
+            _ => i += 1,
+        }
+    }
+
+    None
+}
+
+/// Remove the " * " decoration when every line of the block comment starts with it
+fn strip_leading_asterisks(lines: &mut [String]) {
+    let decorated = lines.iter().all(|l| {
+        let l = l.trim_start();
+        l.is_empty() || (l.starts_with('*') && !l.starts_with("*/"))
+    });
+
+    if lines.is_empty() || !decorated {
+        return;
+    }
+
+    for line in lines.iter_mut() {
+        let trimmed = line.trim_start();
+        let stripped = trimmed.strip_prefix('*').unwrap_or(trimmed);
+        *line = stripped.strip_prefix(' ').unwrap_or(stripped).to_owned();
+    }
+}
+
+/// Remove the indentation shared by all the non empty lines
+fn unindent(lines: &mut [String]) {
+    let indent = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.chars().take_while(|c| c.is_whitespace()).count())
+        .min()
+        .unwrap_or(0);
+
+    for line in lines.iter_mut() {
+        *line = line.chars().skip(indent).collect();
+    }
+}
+
+/// Read a whole inner attribute, which may span several lines
+fn read_attribute<I: Iterator<Item = io::Result<String>>>(
     first_line: String,
-    reader: BufReader<R>,
-) -> io::Result<Vec<String>> {
-    let mut result = Vec::new();
-    if first_line.starts_with("/*!") && first_line.trim().len() > "/*!".len() {
-        result.push(normalize_line(first_line));
+    lines: &mut I,
+) -> io::Result<String> {
+    let mut attribute = first_line;
+
+    loop {
+        if let Some(end) = find_attribute_end(&attribute) {
+            attribute.truncate(end + 1);
+            break;
+        }
+
+        match lines.next() {
+            Some(line) => {
+                attribute.push('\n');
+                attribute.push_str(&line?);
+            }
+            None => break,
+        }
     }
 
-    let mut nesting: isize = 0;
+    Ok(attribute)
+}
 
-    for line in reader.lines() {
-        let line = line?;
-        nesting += line.matches("/*").count() as isize;
-
-        if let Some(pos) = line.rfind("*/") {
-            nesting -= line.matches("*/").count() as isize;
-            if nesting < 0 {
-                let mut line = line;
-                let _ = line.split_off(pos);
-                if !line.trim().is_empty() {
-                    result.push(line);
+/// Find the position of the `]` closing the attribute, skipping string literals
+fn find_attribute_end(attribute: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = 0;
+
+    while i < attribute.len() {
+        let rest = &attribute[i..];
+        if let Some(len) = string_literal_len(rest) {
+            i += len?;
+            continue;
+        }
+
+        match rest.as_bytes()[0] {
+            b'[' => depth += 1,
+            b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
                 }
-                break;
             }
+            _ => {}
         }
+        i += rest.chars().next().map_or(1, char::len_utf8);
+    }
 
-        result.push(line.trim_end().to_owned());
+    None
+}
+
+/// Get the length of the string literal at the start of `text`
+///
+/// Returns `None` if `text` does not start with a string literal, and `Some(None)` if the literal
+/// is not terminated.
+fn string_literal_len(text: &str) -> Option<Option<usize>> {
+    if let Some(raw) = text.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        if !raw[hashes..].starts_with('"') {
+            return None;
+        }
+        let terminator = format!("\"{}", "#".repeat(hashes));
+        let content_start = 1 + hashes + 1;
+        return Some(
+            text[content_start..]
+                .find(&terminator)
+                .map(|end| content_start + end + terminator.len()),
+        );
     }
 
-    Ok(result)
+    if !text.starts_with('"') {
+        return None;
+    }
+
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some(Some(i + 1)),
+            _ => {}
+        }
+    }
+
+    Some(None)
+}
+
+/// Value of a `#![doc = ...]` attribute
+#[derive(Debug, PartialEq)]
+enum DocAttribute {
+    /// Doc lines given by a string literal
+    Text(Vec<String>),
+    /// Path of a file given by `include_str!("...")`
+    Include(String),
+}
+
+/// Get the values of a `#![doc = "..."]` or `#![doc = include_str!("...")]` attribute
+///
+/// `#![cfg_attr(predicate, doc = "...")]` attributes give their doc values when the predicate
+/// holds for the active `cfgs`. Returns `None` if the attribute gives no doc value.
+fn parse_doc_attribute(attribute: &str, cfgs: &[String]) -> Option<Vec<DocAttribute>> {
+    let inner = attribute.strip_prefix("#![")?.strip_suffix(']')?.trim();
+    parse_doc_meta(inner, cfgs)
+}
+
+/// Get the doc values of an attribute, without the surrounding `#![` and `]`
+fn parse_doc_meta(meta: &str, cfgs: &[String]) -> Option<Vec<DocAttribute>> {
+    if let Some(args) = strip_call(meta, "cfg_attr") {
+        let mut args = split_top_level(args).into_iter();
+        if !cfg_is_active(args.next()?, cfgs) {
+            return None;
+        }
+        let docs: Vec<DocAttribute> = args
+            .filter_map(|arg| parse_doc_meta(arg, cfgs))
+            .flatten()
+            .collect();
+        return if docs.is_empty() { None } else { Some(docs) };
+    }
+
+    let value = meta
+        .strip_prefix("doc")?
+        .trim_start()
+        .strip_prefix('=')?
+        .trim();
+    if let Some(path) = parse_include_str(value) {
+        return Some(vec![DocAttribute::Include(path)]);
+    }
+    let text = parse_string_literal(value)?;
+
+    Some(vec![DocAttribute::Text(doc_fragment_lines(&text))])
+}
+
+/// Check if a `cfg` predicate holds for the active `cfgs`
+///
+/// Predicates are a cfg option (`docsrs`, `feature = "serde"`), or a combination of predicates with
+/// `all(...)`, `any(...)` and `not(...)`. The `doc` option is always active.
+fn cfg_is_active(predicate: &str, cfgs: &[String]) -> bool {
+    let predicate = predicate.trim();
+    if let Some(args) = strip_call(predicate, "all") {
+        return split_top_level(args)
+            .into_iter()
+            .all(|p| cfg_is_active(p, cfgs));
+    }
+    if let Some(args) = strip_call(predicate, "any") {
+        return split_top_level(args)
+            .into_iter()
+            .any(|p| cfg_is_active(p, cfgs));
+    }
+    if let Some(arg) = strip_call(predicate, "not") {
+        return !cfg_is_active(arg, cfgs);
+    }
+
+    let option = parse_cfg_option(predicate);
+    option == ("doc".to_owned(), None) || cfgs.iter().any(|cfg| parse_cfg_option(cfg) == option)
+}
+
+/// Split a cfg option into its name and optional value, like `feature = "serde"`
+fn parse_cfg_option(option: &str) -> (String, Option<String>) {
+    match option.split_once('=') {
+        Some((name, value)) => {
+            let value = value.trim();
+            let value = parse_string_literal(value).unwrap_or_else(|| value.to_owned());
+            (name.trim().to_owned(), Some(value))
+        }
+        None => (option.trim().to_owned(), None),
+    }
+}
+
+/// Get the arguments of a call like `name(...)`
+fn strip_call<'a>(text: &'a str, name: &str) -> Option<&'a str> {
+    text.strip_prefix(name)?
+        .trim_start()
+        .strip_prefix('(')?
+        .strip_suffix(')')
+}
+
+/// Split a comma separated list, ignoring the commas in nested parentheses and string literals
+///
+/// Empty items, like the one after a trailing comma, are removed.
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut i = 0;
+    while i < text.len() {
+        let rest = &text[i..];
+        if let Some(len) = string_literal_len(rest) {
+            i += len.unwrap_or(rest.len());
+            continue;
+        }
+        match rest.as_bytes()[0] {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth -= 1,
+            b',' if depth == 0 => {
+                items.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+        i += rest.chars().next().map_or(1, char::len_utf8);
+    }
+    items.push(text[start..].trim());
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+/// Parse an `include_str!("...")` macro call, returning the path of the included file
+fn parse_include_str(value: &str) -> Option<String> {
+    let args = value
+        .strip_prefix("include_str")?
+        .trim_start()
+        .strip_prefix('!')?
+        .trim_start();
+    let path = args
+        .strip_prefix('(')
+        .and_then(|a| a.strip_suffix(')'))
+        .or_else(|| args.strip_prefix('[').and_then(|a| a.strip_suffix(']')))
+        .or_else(|| args.strip_prefix('{').and_then(|a| a.strip_suffix('}')))?
+        .trim();
+    // a trailing comma is allowed in macro calls
+    let path = path.strip_suffix(',').unwrap_or(path).trim_end();
+    parse_string_literal(path)
+}
+
+/// Parse a (raw) string literal, returning its unescaped value
+fn parse_string_literal(literal: &str) -> Option<String> {
+    if string_literal_len(literal)?? != literal.len() {
+        return None;
+    }
+
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        return Some(raw[hashes + 1..raw.len() - hashes - 1].to_owned());
+    }
+
+    let content = &literal[1..literal.len() - 1];
+    let mut result = String::new();
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next()? {
+            'n' => result.push('\n'),
+            'r' => result.push('\r'),
+            't' => result.push('\t'),
+            '0' => result.push('\0'),
+            '\\' => result.push('\\'),
+            '"' => result.push('"'),
+            '\'' => result.push('\''),
+            'x' => {
+                let hex: String = chars.by_ref().take(2).collect();
+                result.push(u8::from_str_radix(&hex, 16).ok()? as char);
+            }
+            'u' => {
+                let hex: String = chars
+                    .by_ref()
+                    .skip_while(|c| *c == '{')
+                    .take_while(|c| *c != '}')
+                    .collect();
+                result.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+            }
+            // line continuation: skip the newline and the leading whitespace of the next line
+            '\n' => {
+                while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                    chars.next();
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    Some(result)
+}
+
+/// Read the lines of `reader`, replacing the invalid UTF-8 sequences with `U+FFFD`
+///
+/// A stray invalid byte, even in code, would otherwise fail the whole extraction.
+fn lossy_lines<R: Read>(reader: R) -> impl Iterator<Item = io::Result<String>> {
+    BufReader::new(reader).split(b'\n').map(|line| {
+        line.map(|bytes| {
+            let line = String::from_utf8_lossy(&bytes);
+            line.strip_suffix('\r').unwrap_or(&line).to_owned()
+        })
+    })
+}
+
+/// Split the text of a doc attribute into lines
+///
+/// Multi-line texts have their surrounding empty lines and common indentation removed, single
+/// line texts lose a leading space, like `//!` comments.
+fn doc_fragment_lines(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = text.split('\n').map(|l| l.trim_end().to_owned()).collect();
+
+    if lines.len() <= 1 {
+        return lines
+            .into_iter()
+            .map(|l| l.strip_prefix(' ').map(str::to_owned).unwrap_or(l))
+            .collect();
+    }
+
+    while lines.first().is_some_and(|l| l.trim().is_empty()) {
+        lines.remove(0);
+    }
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+
+    unindent(&mut lines);
+    lines
 }
 
 /// Strip the "//!" or "/*!" from a line and a single whitespace
@@ -92,6 +526,10 @@ mod tests {
     use super::*;
     use std::io::Cursor;
 
+    fn extract_docs<R: Read>(reader: R) -> io::Result<Vec<String>> {
+        extract_docs_with(reader, &[], unresolved_include)
+    }
+
     const EXPECTED: &[&str] = &[
         "first line",
         "",
@@ -217,4 +655,169 @@ mod tests {
         let result = extract_docs(input).unwrap();
         assert_eq!(result, EXPECTED_MULTILINE_NESTED_2);
     }
+
+    const INPUT_MULTILINE_SINGLE_LINE: &str = "/*! single line doc */\nfn main() {}";
+
+    #[test]
+    fn extract_docs_multiline_style_single_line() {
+        let input = Cursor::new(INPUT_MULTILINE_SINGLE_LINE.as_bytes());
+        let result = extract_docs(input).unwrap();
+        assert_eq!(result, &["single line doc"]);
+    }
+
+    const INPUT_MULTILINE_INDENTED: &str = "/*!\n    first line\n\n    ```\n    let indented = true;\n        let nested = true;\n    ```\n*/\nfn main() {}";
+
+    const EXPECTED_MULTILINE_INDENTED: &[&str] = &[
+        "first line",
+        "",
+        "```",
+        "let indented = true;",
+        "    let nested = true;",
+        "```",
+    ];
+
+    #[test]
+    fn extract_docs_multiline_style_unindent() {
+        let input = Cursor::new(INPUT_MULTILINE_INDENTED.as_bytes());
+        let result = extract_docs(input).unwrap();
+        assert_eq!(result, EXPECTED_MULTILINE_INDENTED);
+    }
+
+    const INPUT_MULTILINE_ASTERISKS: &str =
+        "/*!\n * first line\n *\n * second line\n */\nfn main() {}";
+
+    #[test]
+    fn extract_docs_multiline_style_asterisks() {
+        let input = Cursor::new(INPUT_MULTILINE_ASTERISKS.as_bytes());
+        let result = extract_docs(input).unwrap();
+        assert_eq!(result, &["first line", "", "second line"]);
+    }
+
+    const INPUT_DOC_ATTRIBUTE: &str = "\
+                                       #![doc = \"first line\"]\n\
+                                       #![doc = \"\"]\n\
+                                       #![doc = \"escaped \\\"quotes\\\" [brackets]\"]\n\
+                                       #![deny(missing_docs)]\n\
+                                       //! comment line\n\
+                                       use std::any::Any;";
+
+    const EXPECTED_DOC_ATTRIBUTE: &[&str] = &[
+        "first line",
+        "",
+        "escaped \"quotes\" [brackets]",
+        "comment line",
+    ];
+
+    #[test]
+    fn extract_docs_attribute() {
+        let input = Cursor::new(INPUT_DOC_ATTRIBUTE.as_bytes());
+        let result = extract_docs(input).unwrap();
+        assert_eq!(result, EXPECTED_DOC_ATTRIBUTE);
+    }
+
+    const INPUT_DOC_ATTRIBUTE_RAW: &str =
+        "#![doc = r#\"\n    # Title\n\n    Some \"quoted\" text\n\"#]\nfn main() {}";
+
+    #[test]
+    fn extract_docs_attribute_raw_string() {
+        let input = Cursor::new(INPUT_DOC_ATTRIBUTE_RAW.as_bytes());
+        let result = extract_docs(input).unwrap();
+        assert_eq!(result, &["# Title", "", "Some \"quoted\" text"]);
+    }
+
+    const INPUT_DOC_INCLUDE: &str = "\
+                                     #![doc = include_str!(\"../docs/intro.md\")]\n\
+                                     #![doc = \"\"]\n\
+                                     #![doc = include_str! { \"../docs/usage.md\", }]\n\
+                                     fn main() {}";
+
+    #[test]
+    fn extract_docs_attribute_include_str() {
+        let input = Cursor::new(INPUT_DOC_INCLUDE.as_bytes());
+        let result = extract_docs_with(input, &[], |path| Ok(vec![format!("<{}>", path)]));
+        assert_eq!(
+            result.unwrap(),
+            &["<../docs/intro.md>", "", "<../docs/usage.md>"]
+        );
+    }
+
+    #[test]
+    fn extract_docs_attribute_include_str_without_source_path() {
+        let input = Cursor::new(INPUT_DOC_INCLUDE.as_bytes());
+        assert!(extract_docs(input).is_err());
+    }
+
+    const INPUT_CFG_ATTR: &str = "\
+                                  #![cfg_attr(doc, doc = \"always\")]\n\
+                                  #![cfg_attr(docsrs, doc = \"docs.rs\", doc = \"only\")]\n\
+                                  #![cfg_attr(feature = \"serde\", doc = \"serde, enabled\")]\n\
+                                  #![cfg_attr(not(docsrs), doc = \"not docs.rs\")]\n\
+                                  #![cfg_attr(all(docsrs, any(unix, windows)), doc = \"nested\")]\n\
+                                  #![cfg_attr(docsrs, feature(doc_cfg))]\n\
+                                  fn main() {}";
+
+    #[test]
+    fn extract_docs_cfg_attr_default() {
+        let input = Cursor::new(INPUT_CFG_ATTR.as_bytes());
+        let result = extract_docs(input).unwrap();
+        assert_eq!(result, &["always", "not docs.rs"]);
+    }
+
+    #[test]
+    fn extract_docs_cfg_attr_active_cfgs() {
+        let input = Cursor::new(INPUT_CFG_ATTR.as_bytes());
+        let cfgs = [
+            "docsrs".to_owned(),
+            "feature=\"serde\"".to_owned(),
+            "unix".to_owned(),
+        ];
+        let result = extract_docs_with(input, &cfgs, |_| Ok(Vec::new())).unwrap();
+        assert_eq!(
+            result,
+            &["always", "docs.rs", "only", "serde, enabled", "nested"]
+        );
+    }
+
+    const INPUT_INDENTED: &str = "    //! first line\n\
+                                  \t//!\n\
+                                  \x20 //!   indented code\n\
+                                  fn main() {}";
+
+    #[test]
+    fn extract_docs_leading_whitespace() {
+        let input = Cursor::new(INPUT_INDENTED.as_bytes());
+        let result = extract_docs(input).unwrap();
+        assert_eq!(result, &["first line", "", "  indented code"]);
+    }
+
+    const INPUT_CRLF: &str =
+        "//! first line\r\n//!\r\n//! ```\r\n//! let crlf = true;\r\n//! ```\r\nfn main() {}\r\n";
+
+    #[test]
+    fn extract_docs_crlf() {
+        let input = Cursor::new(INPUT_CRLF.as_bytes());
+        let result = extract_docs(input).unwrap();
+        assert_eq!(
+            result,
+            &["first line", "", "```", "let crlf = true;", "```"]
+        );
+    }
+
+    const INPUT_MULTILINE_CRLF: &str =
+        "/*!\r\nfirst line\r\n\r\nsecond line\r\n*/\r\nfn main() {}\r\n";
+
+    #[test]
+    fn extract_docs_multiline_style_crlf() {
+        let input = Cursor::new(INPUT_MULTILINE_CRLF.as_bytes());
+        let result = extract_docs(input).unwrap();
+        assert_eq!(result, &["first line", "", "second line"]);
+    }
+
+    #[test]
+    fn extract_docs_invalid_utf8() {
+        let input =
+            Cursor::new(b"//! caf\xe9 docs\nfn main() { let s = b\"\xff\xfe\"; }\n".to_vec());
+        let result = extract_docs(input).unwrap();
+        assert_eq!(result, &["caf\u{FFFD} docs"]);
+    }
 }
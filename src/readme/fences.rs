@@ -0,0 +1,263 @@
+//! Tell the lines of the fenced code blocks from the prose
+//!
+//! The passes over the docs read them line by line and leave the code alone, so they all need
+//! to know whether a line opens, closes or is inside a code block. A block is closed by a line
+//! with only a fence of the same character, at least as long as the opening one, so a
+//! ```` ``` ```` line inside a `~~~` block, or inside a ```` ```` ```` block, is code.
+//!
+//! The indented code blocks are told apart too, as in CommonMark: the lines indented by 4 spaces
+//! or more, which cannot continue a paragraph, so they follow a blank line, a heading or another
+//! code block, and are indented further than the content of the list item they are in, if any.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // a list item, like `- item` or `1. item`, whose content starts after the marker
+    static ref RE_LIST_ITEM: Regex =
+        Regex::new(r"^(?P<marker>\s*(?:[-*+]|\d{1,9}[.)])(?:\s+|$))").unwrap();
+    // an ATX heading, like `## Usage`
+    static ref RE_HEADING: Regex = Regex::new(r"^\s{0,3}#{1,6}(?:\s|$)").unwrap();
+}
+
+/// Where a line is, with regard to the fenced code blocks
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineKind {
+    /// A line outside of the code blocks
+    Text,
+    /// The fence opening a code block, maybe with an info string, like ```` ```rust ````
+    Open,
+    /// A line inside a code block
+    Code,
+    /// The fence closing a code block
+    Close,
+    /// A line of an indented code block, which has no fences
+    Indented,
+}
+
+impl LineKind {
+    /// Tell whether the line is a fence or is inside a code block
+    pub fn is_code(self) -> bool {
+        self != LineKind::Text
+    }
+}
+
+/// Tracker of the code blocks of markdown read line by line
+#[derive(Clone, Debug, Default)]
+pub struct CodeBlocks {
+    /// Fence of the code block being read, if any
+    fence: Option<String>,
+    /// Whether the last line is in an indented code block
+    indented: bool,
+    /// Whether the last line is prose that an indented line would continue
+    paragraph: bool,
+    /// Indentation of the content of the last list item, while in a list
+    list_indent: Option<usize>,
+}
+
+impl CodeBlocks {
+    /// Get where `line`, the next line of the markdown, is
+    pub fn kind(&mut self, line: &str) -> LineKind {
+        if self.fence.is_none() {
+            if let Some(kind) = self.indented_kind(line) {
+                return kind;
+            }
+        }
+
+        match (code_fence(line), self.fence.as_deref()) {
+            (Some(fence), None) => {
+                self.fence = Some(fence);
+                LineKind::Open
+            }
+            // a closing fence has no info string
+            (Some(fence), Some(open))
+                if fence.starts_with(open) && line.trim() == fence.as_str() =>
+            {
+                self.fence = None;
+                LineKind::Close
+            }
+            (_, Some(_)) => LineKind::Code,
+            (None, None) => LineKind::Text,
+        }
+    }
+
+    /// Get the fence of the code block being read, if any, like ```` ``` ````
+    pub fn fence(&self) -> Option<&str> {
+        self.fence.as_deref()
+    }
+
+    /// Get where `line` is when it is blank or in an indented code block, outside the fenced
+    /// blocks, or keep track of the paragraphs and lists it is in otherwise
+    fn indented_kind(&mut self, line: &str) -> Option<LineKind> {
+        if line.trim().is_empty() {
+            // the blank lines of an indented code block are left as they are
+            self.paragraph = false;
+            return Some(LineKind::Text);
+        }
+
+        let indent = line.len() - line.trim_start().len();
+        let content_indent = self.list_indent.filter(|_| indent > 0).unwrap_or(0);
+        if indent >= content_indent + 4 && (self.indented || !self.paragraph) {
+            self.indented = true;
+            return Some(LineKind::Indented);
+        }
+
+        self.indented = false;
+        if let Some(cap) = RE_LIST_ITEM.captures(line) {
+            self.list_indent = Some(cap["marker"].len());
+        } else if indent == 0 {
+            self.list_indent = None;
+        }
+        // a heading is a block of its own, and the fence closing a block is told by the caller
+        self.paragraph = !RE_HEADING.is_match(line) && code_fence(line).is_none();
+        None
+    }
+}
+
+/// Iterator over lines of markdown along with where they are, made by [`fenced`]
+pub struct Fenced<I> {
+    lines: I,
+    blocks: CodeBlocks,
+}
+
+impl<I> Iterator for Fenced<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = (LineKind, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.lines.next()?;
+        Some((self.blocks.kind(line.as_ref()), line))
+    }
+}
+
+/// Iterate over `lines` of markdown, telling for each one where it is
+pub fn fenced<I>(lines: I) -> Fenced<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    Fenced {
+        lines: lines.into_iter(),
+        blocks: CodeBlocks::default(),
+    }
+}
+
+/// Get the fence of a line that opens or closes a code block, without the info string
+pub fn code_fence(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    let fence_char = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let fence: String = trimmed.chars().take_while(|c| *c == fence_char).collect();
+    if fence.len() >= 3 {
+        Some(fence)
+    } else {
+        None
+    }
+}
+
+/// Get the info string of the fence `line`, like `rust,no_run` for ```` ```rust,no_run ````
+pub fn info_string(line: &str) -> &str {
+    let trimmed = line.trim_start();
+    let fence_len = code_fence(trimmed).map_or(0, |fence| fence.len());
+    trimmed[fence_len..].trim()
+}
+
+/// Get the attributes of the info string of the fence `line`, separated by commas or spaces
+pub fn fence_attributes(line: &str) -> impl Iterator<Item = &str> {
+    info_string(line)
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+}
+
+/// Get the language of the code block opened by the fence `line`, the first attribute of its info
+/// string, like `rust` for ```` ```rust,no_run ````
+pub fn fence_language(line: &str) -> Option<&str> {
+    fence_attributes(line).next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fence_language, fenced, LineKind};
+
+    #[test]
+    fn closing_fence_matches_opening_one() {
+        let lines = ["~~~text", "```", "[in code](x.md)", "~~~", "[prose](y.md)"];
+        let kinds: Vec<LineKind> = fenced(lines).map(|(kind, _)| kind).collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                LineKind::Open,
+                LineKind::Code,
+                LineKind::Code,
+                LineKind::Close,
+                LineKind::Text
+            ]
+        );
+    }
+
+    #[test]
+    fn longer_fence_closes_block() {
+        let lines = ["````md", "```", "```", "`````", "text"];
+        let kinds: Vec<LineKind> = fenced(lines).map(|(kind, _)| kind).collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                LineKind::Open,
+                LineKind::Code,
+                LineKind::Code,
+                LineKind::Close,
+                LineKind::Text
+            ]
+        );
+    }
+
+    #[test]
+    fn language_of_fence() {
+        assert_eq!(fence_language("```rust,no_run"), Some("rust"));
+        assert_eq!(fence_language("  ~~~ toml"), Some("toml"));
+        assert_eq!(fence_language("```"), None);
+    }
+
+    #[test]
+    fn indented_code_blocks() {
+        let lines = [
+            "Text",
+            "    continued",
+            "",
+            "    code",
+            "",
+            "    more code",
+            "- item",
+            "",
+            "    item paragraph",
+            "",
+            "        item code",
+            "# Heading",
+            "    code after heading",
+        ];
+        let kinds: Vec<LineKind> = fenced(lines).map(|(kind, _)| kind).collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                LineKind::Text,
+                LineKind::Text,
+                LineKind::Text,
+                LineKind::Indented,
+                LineKind::Text,
+                LineKind::Indented,
+                LineKind::Text,
+                LineKind::Text,
+                LineKind::Text,
+                LineKind::Text,
+                LineKind::Indented,
+                LineKind::Text,
+                LineKind::Indented,
+            ]
+        );
+    }
+}
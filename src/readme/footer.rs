@@ -0,0 +1,70 @@
+//! Footer comment telling which version of cargo-readme generated the readme, and from which source
+//!
+//! The hash of the source is a 64 bit FNV-1a hash, which is simple and stable across platforms
+//! and Rust versions, so the footer only changes when the source does.
+
+/// Start of the footer comment
+const FOOTER_PREFIX: &str = "<!-- cargo-readme ";
+
+/// Hash of the sources the docs are read from
+pub struct SourceHash(u64);
+
+impl Default for SourceHash {
+    fn default() -> Self {
+        SourceHash(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl SourceHash {
+    /// Add `bytes` to the hash
+    pub fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    /// Get the footer of a readme generated from the sources hashed
+    pub fn footer(&self) -> String {
+        format!(
+            "{}{} source-hash fnv1a-64:{:016x} -->",
+            FOOTER_PREFIX,
+            env!("CARGO_PKG_VERSION"),
+            self.0
+        )
+    }
+}
+
+/// Append the `footer` to `readme`, after a blank line
+pub fn append_footer(readme: String, footer: &str) -> String {
+    format!("{}\n\n{}", readme.trim_end(), footer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SourceHash;
+
+    fn hash(bytes: &[u8]) -> String {
+        let mut hash = SourceHash::default();
+        hash.update(bytes);
+        format!("{:016x}", hash.0)
+    }
+
+    #[test]
+    fn fnv1a_hash() {
+        assert_eq!(hash(b""), "cbf29ce484222325");
+        assert_eq!(hash(b"a"), "af63dc4c8601ec8c");
+        assert_eq!(hash(b"foobar"), "85944171f73967e8");
+    }
+
+    #[test]
+    fn footer_with_version() {
+        assert_eq!(
+            SourceHash::default().footer(),
+            format!(
+                "<!-- cargo-readme {} source-hash fnv1a-64:cbf29ce484222325 -->",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+    }
+}
@@ -0,0 +1,222 @@
+//! Handle the footnotes of the docs, like `text[^1]` with the definition `[^1]: Note.`
+//!
+//! A footnote definition spans its first line and the following lines, up to a blank line. Lines
+//! inside code blocks are never taken for footnotes.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::fences::CodeBlocks;
+
+lazy_static! {
+    // Is this the start of a footnote definition?
+    static ref RE_DEFINITION: Regex = Regex::new(r"^ {0,3}\[\^(?P<label>[^\]\s]+)\]:[ \t]*(?P<text>.*)$").unwrap();
+    // Footnote reference, like `[^1]`
+    static ref RE_REFERENCE: Regex = Regex::new(r"\[\^(?P<label>[^\]\s]+)\]").unwrap();
+}
+
+/// A footnote definition, with all its lines
+struct Definition {
+    label: String,
+    lines: Vec<String>,
+}
+
+/// Append the footnote definitions of `removed`, the lines removed from the docs, that are still
+/// referenced by `lines`
+///
+/// This keeps the footnotes working when the section holding their definitions is removed.
+pub fn restore_definitions(mut lines: Vec<String>, removed: &[String]) -> Vec<String> {
+    let references = references(&lines);
+    let definitions: Vec<Definition> = definitions(removed)
+        .into_iter()
+        .filter(|d| references.contains(&d.label))
+        .collect();
+
+    for definition in definitions {
+        if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+            lines.push(String::new());
+        }
+        lines.extend(definition.lines);
+    }
+
+    lines
+}
+
+/// Turn the footnotes into inline text in parentheses, for renderers without footnotes
+///
+/// `text[^1]` with the definition `[^1]: Note.` becomes `text (Note.)`, and the definition is
+/// removed. References without a definition are left as is.
+pub fn inline_footnotes(lines: Vec<String>) -> Vec<String> {
+    let definitions = definitions(&lines);
+    if definitions.is_empty() {
+        return lines;
+    }
+
+    let mut code = CodeBlocks::default();
+    let mut in_definition = false;
+    let mut result = Vec::new();
+
+    for line in lines {
+        if code.kind(&line).is_code() {
+            result.push(line);
+            continue;
+        }
+        if RE_DEFINITION.is_match(&line) {
+            in_definition = true;
+        } else if line.trim().is_empty() {
+            // skip the blank line after a removed definition
+            if in_definition {
+                in_definition = false;
+                continue;
+            }
+        }
+        if in_definition {
+            continue;
+        }
+
+        let inlined = RE_REFERENCE.replace_all(&line, |cap: &regex::Captures| {
+            match definitions.iter().find(|d| d.label == cap["label"]) {
+                Some(definition) => {
+                    // do not double the space before the reference, like in `text [^1]`
+                    let before = &line[..cap.get(0).map_or(0, |m| m.start())];
+                    let space = match before.chars().last() {
+                        Some(c) if !c.is_whitespace() => " ",
+                        _ => "",
+                    };
+                    format!("{}({})", space, definition_text(definition))
+                }
+                None => cap[0].to_owned(),
+            }
+        });
+        result.push(inlined.into_owned());
+    }
+
+    // remove the blank lines left before the definitions at the end
+    while result.last().is_some_and(|l| l.trim().is_empty()) {
+        result.pop();
+    }
+
+    result
+}
+
+/// Get the text of a footnote definition, on a single line
+fn definition_text(definition: &Definition) -> String {
+    let text: Vec<&str> = definition
+        .lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| match i {
+            0 => RE_DEFINITION
+                .captures(line)
+                .and_then(|cap| cap.name("text"))
+                .map_or("", |t| t.as_str()),
+            _ => line.trim(),
+        })
+        .filter(|l| !l.is_empty())
+        .collect();
+
+    text.join(" ")
+}
+
+/// Find the footnote definitions of `lines`
+fn definitions(lines: &[String]) -> Vec<Definition> {
+    let mut code = CodeBlocks::default();
+    let mut definitions: Vec<Definition> = Vec::new();
+    let mut in_definition = false;
+
+    for line in lines {
+        if code.kind(line).is_code() {
+            in_definition = false;
+            continue;
+        }
+        if let Some(cap) = RE_DEFINITION.captures(line) {
+            definitions.push(Definition {
+                label: cap["label"].to_owned(),
+                lines: vec![line.clone()],
+            });
+            in_definition = true;
+        } else if line.trim().is_empty() {
+            in_definition = false;
+        } else if in_definition {
+            if let Some(definition) = definitions.last_mut() {
+                definition.lines.push(line.clone());
+            }
+        }
+    }
+
+    definitions
+}
+
+/// Get the labels of the footnotes referenced in `lines`, outside of their definitions
+fn references(lines: &[String]) -> Vec<String> {
+    let mut code = CodeBlocks::default();
+
+    lines
+        .iter()
+        .filter(|line| !code.kind(line).is_code())
+        .flat_map(|line| {
+            // the label of a definition is not a reference
+            let line = match RE_DEFINITION.captures(line) {
+                Some(cap) => cap.name("text").map_or("", |t| t.as_str()),
+                None => line.as_str(),
+            };
+            RE_REFERENCE
+                .captures_iter(line)
+                .map(|cap| cap["label"].to_owned())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{inline_footnotes, restore_definitions};
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(ToOwned::to_owned).collect()
+    }
+
+    #[test]
+    fn restore_referenced_definitions() {
+        let kept = lines("Text with a note[^1] and another[^long].");
+        let removed = lines(
+            "# Notes\n\n[^1]: First note.\n\n[^unused]: Unused note.\n\n[^long]: Long note\n  on two lines.",
+        );
+
+        assert_eq!(
+            restore_definitions(kept, &removed),
+            lines(
+                "Text with a note[^1] and another[^long].\n\n[^1]: First note.\n\n[^long]: Long note\n  on two lines."
+            )
+        );
+    }
+
+    #[test]
+    fn restore_nothing_without_references() {
+        let kept = lines("Text without notes.");
+        let removed = lines("[^1]: First note.");
+
+        assert_eq!(restore_definitions(kept.clone(), &removed), kept);
+    }
+
+    #[test]
+    fn inline_footnote() {
+        let input = lines(
+            "Text with a note[^1] and another [^2].\n\n```\nlet a = b[^1];\n```\n\n[^1]: First note.\n\n[^2]: Second note\n  on two lines.",
+        );
+
+        assert_eq!(
+            inline_footnotes(input),
+            lines(
+                "Text with a note (First note.) and another (Second note on two lines.).\n\n```\nlet a = b[^1];\n```"
+            )
+        );
+    }
+
+    #[test]
+    fn inline_keeps_undefined_references() {
+        let input = lines("Text[^missing].");
+
+        assert_eq!(inline_footnotes(input.clone()), input);
+    }
+}
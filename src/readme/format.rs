@@ -0,0 +1,232 @@
+//! Render the readme in the output format
+//!
+//! The docs are processed as markdown, whatever the output format. The processed readme is then
+//! turned into the output by the renderer of the format, so a new format only needs a variant of
+//! `OutputFormat` and its renderer. The renderers converting from the markdown all walk the blocks
+//! and inlines parsed once by the `markdown` module, rather than matching the syntax themselves.
+//!
+//! The reStructuredText and AsciiDoc outputs are converted from the markdown, for Sphinx and
+//! Antora or Asciidoctor based sites. The HTML output is a standalone page, with the Rust code
+//! highlighted, for a simple landing page. The man page output is a roff page of section 1, for
+//! binary crates. The plain text output strips the markdown syntax, for the long descriptions
+//! of `--help` or the `README.txt` of release archives. The mdBook output is markdown, as a
+//! chapter of a book. The Org output is converted from the markdown, for the docs kept with Emacs.
+//! The Confluence output is wiki markup, to push the docs into a Confluence space.
+//!
+//! The JSON output describes the readme for other tools, like static site generators: the crate
+//! information, the values of the template variables, the sections with their heading level, and
+//! the code blocks with their language and attributes, along with the markdown of the readme. The
+//! code blocks are the ones of the docs, as written, since the processing drops their attributes.
+
+use serde_json::json;
+
+use super::asciidoc::markdown_to_asciidoc;
+use super::confluence::markdown_to_confluence;
+use super::fences::{fence_attributes, fenced, LineKind};
+use super::html_page::render_html;
+use super::man::render_man;
+use super::mdbook::mdbook_chapter;
+use super::org::markdown_to_org;
+use super::process::is_rustdoc_attribute;
+use super::rst::markdown_to_rst;
+use super::sections::Headings;
+use super::template;
+use super::text::markdown_to_text;
+use crate::config::Manifest;
+
+/// Format of the output
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum OutputFormat {
+    /// Markdown, as processed
+    #[default]
+    Markdown,
+    /// A JSON document describing the crate and the readme
+    Json,
+    /// reStructuredText, converted from the markdown
+    Rst,
+    /// AsciiDoc, converted from the markdown
+    Asciidoc,
+    /// A standalone HTML page
+    Html,
+    /// A roff man page
+    Man,
+    /// Plain text, without the markdown syntax
+    Text,
+    /// Markdown, as a chapter of an mdBook
+    Mdbook,
+    /// Org, converted from the markdown
+    Org,
+    /// Confluence wiki markup, converted from the markdown
+    Confluence,
+}
+
+/// Render the processed `readme` of the crate of `cargo` in `format`
+///
+/// `docs` are the doc lines of the crate, as extracted, before they were processed, and `date` the
+/// date of the man page.
+pub fn render_output(
+    format: OutputFormat,
+    readme: String,
+    cargo: &Manifest,
+    docs: &[String],
+    date: Option<&str>,
+) -> String {
+    match format {
+        OutputFormat::Markdown => readme,
+        OutputFormat::Json => render_json(&readme, cargo, docs),
+        OutputFormat::Rst => markdown_to_rst(&readme),
+        OutputFormat::Asciidoc => markdown_to_asciidoc(&readme),
+        OutputFormat::Html => render_html(&readme, cargo),
+        OutputFormat::Man => render_man(&readme, cargo, date),
+        OutputFormat::Text => markdown_to_text(&readme),
+        OutputFormat::Mdbook => mdbook_chapter(&readme),
+        OutputFormat::Org => markdown_to_org(&readme),
+        OutputFormat::Confluence => markdown_to_confluence(&readme),
+    }
+}
+
+/// Render the JSON document of the `readme` of the crate of `cargo`, with the code blocks of `docs`
+///
+/// The `generator` field tells the document was generated by cargo-readme, like the comment at the
+/// top of the markdown files.
+fn render_json(readme: &str, cargo: &Manifest, docs: &[String]) -> String {
+    let variables: serde_json::Map<String, serde_json::Value> = template::variables(cargo)
+        .into_iter()
+        .map(|(name, value)| (name.to_owned(), json!(value)))
+        .collect();
+
+    let document = json!({
+        "generator": format!("cargo-readme {}", env!("CARGO_PKG_VERSION")),
+        "crate": {
+            "name": cargo.name,
+            "version": cargo.version,
+            "description": cargo.description,
+            "license": cargo.license,
+            "license_file": cargo.license_file,
+            "repository": cargo.repository,
+            "rust_version": cargo.rust_version,
+            "keywords": cargo.keywords,
+            "categories": cargo.categories,
+            "badges": cargo.badges,
+        },
+        "variables": variables,
+        "sections": sections(readme),
+        "code_blocks": code_blocks(docs),
+        "readme": readme,
+    });
+
+    serde_json::to_string_pretty(&document).unwrap_or_default()
+}
+
+/// Get the sections of `readme`, each with its heading, level and content
+///
+/// The content before the first heading is a section of level 0, without heading.
+fn sections(readme: &str) -> Vec<serde_json::Value> {
+    let mut headings = Headings::default();
+    let mut sections = Vec::new();
+    let mut current: (usize, Option<String>, Vec<&str>) = (0, None, Vec::new());
+
+    for line in readme.lines() {
+        match headings.heading(line) {
+            Some((level, text)) => {
+                let (level, heading, content) =
+                    std::mem::replace(&mut current, (level, Some(text), Vec::new()));
+                push_section(&mut sections, level, heading, &content);
+            }
+            None => current.2.push(line),
+        }
+    }
+    let (level, heading, content) = current;
+    push_section(&mut sections, level, heading, &content);
+
+    sections
+}
+
+/// Add a section to `sections`, unless it is an empty section without heading
+fn push_section(
+    sections: &mut Vec<serde_json::Value>,
+    level: usize,
+    heading: Option<String>,
+    content: &[&str],
+) {
+    let content = content.join("\n").trim().to_owned();
+    if heading.is_some() || !content.is_empty() {
+        sections.push(json!({
+            "level": level,
+            "heading": heading,
+            "content": content,
+        }));
+    }
+}
+
+/// Get the code blocks of the `docs`, each with its language, attributes and code
+///
+/// The info string of the code block, like `rust,no_run`, starts with the language, followed by
+/// the attributes. As in rustdoc, the code blocks without language, or with only doc test
+/// attributes, like `no_run`, are Rust.
+fn code_blocks(docs: &[String]) -> Vec<serde_json::Value> {
+    let mut blocks = Vec::new();
+    // the opening fence of the code block being read, and its code
+    let mut block: Option<(&str, Vec<&str>)> = None;
+
+    for (kind, line) in fenced(docs) {
+        match kind {
+            LineKind::Open => block = Some((line, Vec::new())),
+            LineKind::Code => block.iter_mut().for_each(|(_, code)| code.push(line)),
+            LineKind::Close => {
+                if let Some((open, code)) = block.take() {
+                    let attributes: Vec<&str> = fence_attributes(open).collect();
+                    let (language, attributes) = match attributes.split_first() {
+                        Some((language, rest)) if !is_rustdoc_attribute(language) => {
+                            (*language, rest)
+                        }
+                        _ => ("rust", attributes.as_slice()),
+                    };
+                    blocks.push(json!({
+                        "language": language,
+                        "attributes": attributes,
+                        "code": code.join("\n"),
+                    }));
+                }
+            }
+            LineKind::Text | LineKind::Indented => {}
+        }
+    }
+
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{code_blocks, sections};
+    use serde_json::json;
+
+    #[test]
+    fn readme_sections() {
+        let readme = "# crate\n\nIntro.\n\n## Usage\n\n```\n# not a heading\n```\n";
+
+        assert_eq!(
+            sections(readme),
+            [
+                json!({"level": 1, "heading": "crate", "content": "Intro."}),
+                json!({"level": 2, "heading": "Usage", "content": "```\n# not a heading\n```"}),
+            ]
+        );
+    }
+
+    #[test]
+    fn docs_code_blocks() {
+        let docs = "```rust,no_run\nlet a = 1;\n```\n\n~~~\n```\n~~~\n\n```ignore\n```\n\n```text\nout\n```";
+        let docs: Vec<String> = docs.lines().map(ToOwned::to_owned).collect();
+
+        assert_eq!(
+            code_blocks(&docs),
+            [
+                json!({"language": "rust", "attributes": ["no_run"], "code": "let a = 1;"}),
+                json!({"language": "rust", "attributes": [], "code": "```"}),
+                json!({"language": "rust", "attributes": ["ignore"], "code": ""}),
+                json!({"language": "text", "attributes": [], "code": "out"}),
+            ]
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Front matter block for static site generators, like Hugo, Jekyll or Zola
+//!
+//! The front matter is put at the top of the readme, so the generated file can also be used as a
+//! page. It has the title, the description, the date and the tags, from the keywords, of the
+//! crate. The date is the one given, like the date of the existing readme so it does not change
+//! every day, and is left out when there is none.
+
+use crate::config::Manifest;
+
+/// Format of the front matter
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FrontMatter {
+    /// YAML between `---` lines, for Jekyll and Hugo
+    Yaml,
+    /// TOML between `+++` lines, for Zola and Hugo
+    Toml,
+}
+
+/// Prepend the front matter in `format` to the `readme` of the crate of `cargo`, dated `date`
+pub fn prepend_front_matter(
+    readme: String,
+    format: FrontMatter,
+    cargo: &Manifest,
+    date: Option<&str>,
+) -> String {
+    format!(
+        "{}\n\n{}",
+        front_matter(format, cargo, date),
+        readme.trim_start()
+    )
+}
+
+/// Get the front matter in `format` of the crate of `cargo`, dated `date` when it is given
+///
+/// The `generator` field tells the page was generated by cargo-readme.
+fn front_matter(format: FrontMatter, cargo: &Manifest, date: Option<&str>) -> String {
+    let (delimiter, separator) = match format {
+        FrontMatter::Yaml => ("---", ": "),
+        FrontMatter::Toml => ("+++", " = "),
+    };
+    let tags: Vec<String> = cargo.keywords.iter().map(|k| quote(k)).collect();
+
+    let mut fields = vec![("title", quote(&cargo.name))];
+    if let Some(ref description) = cargo.description {
+        fields.push(("description", quote(description.trim())));
+    }
+    if let Some(date) = date {
+        fields.push(("date", date.to_owned()));
+    }
+    fields.push(("tags", format!("[{}]", tags.join(", "))));
+    fields.push(("generator", quote("cargo-readme")));
+
+    let fields: Vec<String> = fields
+        .into_iter()
+        .map(|(name, value)| format!("{}{}{}", name, separator, value))
+        .collect();
+
+    format!("{0}\n{1}\n{0}", delimiter, fields.join("\n"))
+}
+
+/// Quote `text` as a double quoted string, which is the same in YAML and TOML
+fn quote(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{front_matter, FrontMatter};
+    use crate::config::Manifest;
+
+    fn manifest() -> Manifest {
+        let mut cargo = Manifest::with_name("my-crate");
+        cargo.description = Some("A \"quoted\" crate".to_owned());
+        cargo.keywords = vec!["readme".to_owned(), "docs".to_owned()];
+        cargo
+    }
+
+    #[test]
+    fn yaml_front_matter() {
+        assert_eq!(
+            front_matter(FrontMatter::Yaml, &manifest(), Some("2024-01-31")),
+            "\
+---
+title: \"my-crate\"
+description: \"A \\\"quoted\\\" crate\"
+date: 2024-01-31
+tags: [\"readme\", \"docs\"]
+generator: \"cargo-readme\"
+---"
+        );
+    }
+
+    #[test]
+    fn toml_front_matter() {
+        assert_eq!(
+            front_matter(
+                FrontMatter::Toml,
+                &Manifest::with_name("my-crate"),
+                Some("2024-01-31")
+            ),
+            "\
++++
+title = \"my-crate\"
+date = 2024-01-31
+tags = []
+generator = \"cargo-readme\"
++++"
+        );
+    }
+
+    #[test]
+    fn front_matter_without_date() {
+        assert_eq!(
+            front_matter(FrontMatter::Yaml, &Manifest::with_name("my-crate"), None),
+            "---\ntitle: \"my-crate\"\ntags: []\ngenerator: \"cargo-readme\"\n---"
+        );
+    }
+}
@@ -0,0 +1,228 @@
+//! Highlight the Rust code blocks of the HTML output
+//!
+//! The code is split into tokens: comments, strings, characters, lifetimes, numbers, attributes,
+//! keywords, types and macros, which are wrapped in `<span>` elements with a class, like
+//! `<span class="kw">fn</span>`, styled by the stylesheet of the page. This is a lexer, not a
+//! parser, so a few tokens, like the keywords used as field names, may be highlighted wrongly.
+
+/// Keywords of Rust, including the reserved ones and the primitive values
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "union",
+    "unsafe", "use", "where", "while", "yield",
+];
+
+/// Primitive types, highlighted as types
+const PRIMITIVES: &[&str] = &[
+    "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64",
+    "i128", "isize", "f32", "f64",
+];
+
+/// Escape the HTML special characters of `text`
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Highlight the Rust `code`, escaping its HTML special characters
+pub fn highlight_rust(code: &str) -> String {
+    let chars: Vec<char> = code.chars().collect();
+    let mut html = String::with_capacity(code.len() * 2);
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let (class, len) = if c == '/' && chars.get(i + 1) == Some(&'/') {
+            ("com", until(&chars, i, |c| c == '\n'))
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            ("com", block_comment_len(&chars, i))
+        } else if c == '"' || raw_string_hashes(&chars, i).is_some() || byte_string(&chars, i) {
+            ("str", string_len(&chars, i))
+        } else if c == '\'' {
+            quote_len(&chars, i)
+        } else if c == '#' && matches!(chars.get(i + 1), Some('[') | Some('!')) {
+            ("attr", attribute_len(&chars, i))
+        } else if c.is_ascii_digit() {
+            (
+                "num",
+                until(&chars, i, |c| {
+                    !(c.is_alphanumeric() || c == '_' || c == '.')
+                }),
+            )
+        } else if c.is_alphabetic() || c == '_' {
+            let len = until(&chars, i, |c| !(c.is_alphanumeric() || c == '_'));
+            let word: String = chars[i..i + len].iter().collect();
+            if chars.get(i + len) == Some(&'!') && chars.get(i + len + 1) != Some(&'=') {
+                ("mac", len + 1)
+            } else if KEYWORDS.contains(&word.as_str()) {
+                ("kw", len)
+            } else if PRIMITIVES.contains(&word.as_str()) || c.is_uppercase() {
+                ("ty", len)
+            } else {
+                ("", len)
+            }
+        } else {
+            ("", 1)
+        };
+
+        let token: String = chars[i..i + len].iter().collect();
+        match class {
+            "" => html.push_str(&escape(&token)),
+            class => html.push_str(&format!(
+                "<span class=\"{}\">{}</span>",
+                class,
+                escape(&token)
+            )),
+        }
+        i += len;
+    }
+
+    html
+}
+
+/// Get the length of the token at `start`, up to the first character matching `end`, excluded
+fn until(chars: &[char], start: usize, end: impl Fn(char) -> bool) -> usize {
+    chars[start + 1..]
+        .iter()
+        .position(|c| end(*c))
+        .map_or(chars.len() - start, |p| p + 1)
+}
+
+/// Get the length of the block comment at `start`, which may be nested
+fn block_comment_len(chars: &[char], start: usize) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+    while i + 1 < chars.len() {
+        match (chars[i], chars[i + 1]) {
+            ('/', '*') => {
+                depth += 1;
+                i += 2;
+            }
+            ('*', '/') => {
+                depth -= 1;
+                i += 2;
+                if depth == 0 {
+                    return i - start;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    chars.len() - start
+}
+
+/// Get the number of `#` of the raw string, like `r#"text"#`, starting at `start`
+fn raw_string_hashes(chars: &[char], start: usize) -> Option<usize> {
+    let start = match (chars.get(start), chars.get(start + 1)) {
+        (Some('b'), Some('r')) => start + 2,
+        (Some('r'), _) => start + 1,
+        _ => return None,
+    };
+    let hashes = chars[start..].iter().take_while(|c| **c == '#').count();
+    match chars.get(start + hashes) {
+        Some('"') => Some(hashes),
+        _ => None,
+    }
+}
+
+/// Tell if a byte string, like `b"text"`, starts at `start`
+fn byte_string(chars: &[char], start: usize) -> bool {
+    chars.get(start) == Some(&'b') && chars.get(start + 1) == Some(&'"')
+}
+
+/// Get the length of the string, byte string or raw string at `start`
+fn string_len(chars: &[char], start: usize) -> usize {
+    if let Some(hashes) = raw_string_hashes(chars, start) {
+        let open = chars[start..].iter().position(|c| *c == '"').unwrap_or(0) + 1;
+        let mut i = start + open;
+        while i < chars.len() {
+            if chars[i] == '"' && chars[i + 1..].iter().take_while(|c| **c == '#').count() >= hashes
+            {
+                return i + 1 + hashes - start;
+            }
+            i += 1;
+        }
+        return chars.len() - start;
+    }
+
+    let mut i = start + if chars[start] == 'b' { 2 } else { 1 };
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            '"' => return i + 1 - start,
+            _ => i += 1,
+        }
+    }
+    chars.len() - start
+}
+
+/// Get the class and length of the character or lifetime at `start`
+fn quote_len(chars: &[char], start: usize) -> (&'static str, usize) {
+    match (chars.get(start + 1), chars.get(start + 2)) {
+        (Some('\\'), _) => ("str", until(chars, start + 2, |c| c == '\'') + 3),
+        (Some(_), Some('\'')) => ("str", 3),
+        (Some(c), _) if c.is_alphabetic() || *c == '_' => (
+            "life",
+            until(chars, start, |c| !(c.is_alphanumeric() || c == '_')),
+        ),
+        _ => ("", 1),
+    }
+}
+
+/// Get the length of the attribute at `start`, like `#[derive(Debug)]`, up to its closing bracket
+fn attribute_len(chars: &[char], start: usize) -> usize {
+    let mut depth = 0;
+    for (i, c) in chars.iter().enumerate().skip(start) {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1 - start;
+                }
+            }
+            '\n' if depth == 0 => return i - start,
+            _ => {}
+        }
+    }
+    chars.len() - start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::highlight_rust;
+
+    #[test]
+    fn highlight_tokens() {
+        assert_eq!(
+            highlight_rust(
+                "#[test]\nfn a<'b>(x: &'b str) -> u8 { println!(\"<{}>\", 'c'); 1 } // end"
+            ),
+            "<span class=\"attr\">#[test]</span>\n\
+             <span class=\"kw\">fn</span> a&lt;<span class=\"life\">'b</span>&gt;(x: \
+             &amp;<span class=\"life\">'b</span> <span class=\"ty\">str</span>) -&gt; \
+             <span class=\"ty\">u8</span> { <span class=\"mac\">println!</span>(\
+             <span class=\"str\">&quot;&lt;{}&gt;&quot;</span>, <span class=\"str\">'c'</span>); \
+             <span class=\"num\">1</span> } <span class=\"com\">// end</span>"
+        );
+    }
+
+    #[test]
+    fn highlight_strings_and_comments() {
+        assert_eq!(
+            highlight_rust("let s = r#\"a \"b\"\"#; /* a /* b */ c */ for"),
+            "<span class=\"kw\">let</span> s = <span class=\"str\">r#&quot;a &quot;b&quot;&quot;#</span>; \
+             <span class=\"com\">/* a /* b */ c */</span> <span class=\"kw\">for</span>"
+        );
+    }
+}
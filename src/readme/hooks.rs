@@ -0,0 +1,123 @@
+//! Closures run by the library on the docs while the readme is generated
+//!
+//! The line hooks run on each line of the extracted docs, before they are processed, so their
+//! output is processed like the docs themselves. The document hooks run on the whole readme, once
+//! the docs are processed and before the template is rendered, or once the template is rendered,
+//! before the readme is converted to its output format. The hooks run in the order they are added.
+
+use std::fmt;
+use std::sync::Arc;
+
+/// Hook run on each line of the docs
+type LineHook = Arc<dyn Fn(&str) -> String + Send + Sync>;
+/// Hook run on the whole readme
+type DocumentHook = Arc<dyn Fn(String) -> String + Send + Sync>;
+
+/// Closures rewriting the docs, like link shorteners or word filters
+///
+/// ```
+/// use cargo_readme::Hooks;
+///
+/// let mut hooks = Hooks::default();
+/// hooks
+///     .line(|line| line.replace("go/docs", "https://example.com/docs"))
+///     .after_template(|readme| readme.replace("TODO", ""));
+/// ```
+#[derive(Clone, Default)]
+pub struct Hooks {
+    lines: Vec<LineHook>,
+    before_template: Vec<DocumentHook>,
+    after_template: Vec<DocumentHook>,
+}
+
+impl Hooks {
+    /// Run `hook` on each line of the extracted docs, replacing it with the returned line
+    pub fn line<F>(&mut self, hook: F) -> &mut Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.lines.push(Arc::new(hook));
+        self
+    }
+
+    /// Run `hook` on the processed docs, before they are rendered with the template
+    pub fn before_template<F>(&mut self, hook: F) -> &mut Self
+    where
+        F: Fn(String) -> String + Send + Sync + 'static,
+    {
+        self.before_template.push(Arc::new(hook));
+        self
+    }
+
+    /// Run `hook` on the readme rendered with the template, before it is converted to the output
+    /// format
+    pub fn after_template<F>(&mut self, hook: F) -> &mut Self
+    where
+        F: Fn(String) -> String + Send + Sync + 'static,
+    {
+        self.after_template.push(Arc::new(hook));
+        self
+    }
+
+    /// Run the line hooks on `lines`
+    pub(crate) fn run_lines(&self, lines: Vec<String>) -> Vec<String> {
+        if self.lines.is_empty() {
+            return lines;
+        }
+
+        lines
+            .into_iter()
+            .map(|line| self.lines.iter().fold(line, |line, hook| hook(&line)))
+            .collect()
+    }
+
+    /// Run the hooks of before the template on `readme`
+    pub(crate) fn run_before_template(&self, readme: String) -> String {
+        self.before_template
+            .iter()
+            .fold(readme, |readme, hook| hook(readme))
+    }
+
+    /// Run the hooks of after the template on `readme`
+    pub(crate) fn run_after_template(&self, readme: String) -> String {
+        self.after_template
+            .iter()
+            .fold(readme, |readme, hook| hook(readme))
+    }
+}
+
+impl fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Hooks")
+            .field("lines", &self.lines.len())
+            .field("before_template", &self.before_template.len())
+            .field("after_template", &self.after_template.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Hooks;
+
+    #[test]
+    fn run_hooks_in_order() {
+        let mut hooks = Hooks::default();
+        hooks
+            .line(|line| line.replace("a", "b"))
+            .line(|line| line.replace("b", "c"))
+            .before_template(|readme| format!("{}!", readme))
+            .after_template(|readme| readme.to_uppercase());
+
+        assert_eq!(
+            hooks.run_lines(vec!["a".to_owned(), "x".to_owned()]),
+            vec!["c", "x"]
+        );
+        assert_eq!(hooks.run_before_template("docs".to_owned()), "docs!");
+        assert_eq!(hooks.run_after_template("docs".to_owned()), "DOCS");
+        assert_eq!(
+            format!("{:?}", hooks),
+            "Hooks { lines: 2, before_template: 1, after_template: 1 }"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Sanitize the raw HTML of the docs
+//!
+//! Rustdoc renders raw HTML, like `<div class="warning">`, which crates.io and GitHub may render
+//! differently or not at all. The HTML tags and comments can be stripped, or escaped to be shown as
+//! text. Code blocks and code spans are left untouched.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::fences::fenced;
+use super::links::code_span_len;
+
+lazy_static! {
+    // HTML comment or tag, like `<div class="warning">` or `</div>`, but not autolinks like
+    // `<https://example.com>`
+    static ref RE_HTML: Regex = Regex::new(r"<!--.*?-->|</?[A-Za-z][A-Za-z0-9-]*(?:\s[^<>]*)?/?>").unwrap();
+}
+
+/// What to do with the raw HTML of the docs
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SanitizeHtml {
+    /// Keep the HTML as it is
+    #[default]
+    Passthrough,
+    /// Remove the HTML tags and comments, keeping the text between tags
+    Strip,
+    /// Escape the HTML tags and comments, so they are shown as text
+    Escape,
+}
+
+/// Sanitize the HTML of the doc lines as given by `mode`
+///
+/// With `SanitizeHtml::Strip`, the lines with only HTML are removed.
+pub fn sanitize_html(lines: Vec<String>, mode: SanitizeHtml) -> Vec<String> {
+    if mode == SanitizeHtml::Passthrough {
+        return lines;
+    }
+
+    let mut result: Vec<String> = Vec::new();
+    let mut removed = false;
+    for (kind, line) in fenced(lines) {
+        if !kind.is_code() {
+            // do not leave two blank lines where a line is removed
+            let blank_after_removed = removed
+                && line.trim().is_empty()
+                && result.last().is_none_or(|l| l.trim().is_empty());
+            removed = false;
+            if blank_after_removed {
+                continue;
+            }
+            match sanitize_line(line, mode) {
+                Some(line) => result.push(line),
+                None => removed = true,
+            }
+            continue;
+        }
+        result.push(line);
+    }
+
+    // nor a blank line at the end, where the last lines are removed
+    if removed {
+        while result.last().is_some_and(|l| l.trim().is_empty()) {
+            result.pop();
+        }
+    }
+
+    result
+}
+
+/// Sanitize the HTML of a line outside code blocks, skipping its code spans
+fn sanitize_line(line: String, mode: SanitizeHtml) -> Option<String> {
+    if !RE_HTML.is_match(&line) {
+        return Some(line);
+    }
+
+    let mut result = String::new();
+    let mut rest = line.as_str();
+    while !rest.is_empty() {
+        let (text, code) = match rest.find('`') {
+            Some(tick) => {
+                let len = code_span_len(&rest[tick..]);
+                (&rest[..tick], &rest[tick..tick + len])
+            }
+            None => (rest, ""),
+        };
+
+        result.push_str(
+            &RE_HTML.replace_all(text, |cap: &regex::Captures| match mode {
+                SanitizeHtml::Escape => cap[0].replace('<', "&lt;").replace('>', "&gt;"),
+                _ => String::new(),
+            }),
+        );
+        result.push_str(code);
+        rest = &rest[text.len() + code.len()..];
+    }
+
+    if mode == SanitizeHtml::Strip && result.trim().is_empty() {
+        return None;
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sanitize_html, SanitizeHtml};
+
+    fn sanitize(lines: &[&str], mode: SanitizeHtml) -> Vec<String> {
+        sanitize_html(lines.iter().map(|l| l.to_string()).collect(), mode)
+    }
+
+    const INPUT: &[&str] = &[
+        "<div class=\"warning\">",
+        "",
+        "Be <b>careful</b> with `<T>`.<!-- comment -->",
+        "",
+        "</div>",
+        "",
+        "See <https://example.com>.",
+        "",
+        "```",
+        "<div></div>",
+        "```",
+    ];
+
+    #[test]
+    fn passthrough_html() {
+        assert_eq!(sanitize(INPUT, SanitizeHtml::Passthrough), INPUT);
+    }
+
+    #[test]
+    fn strip_html() {
+        assert_eq!(
+            sanitize(INPUT, SanitizeHtml::Strip),
+            [
+                "Be careful with `<T>`.",
+                "",
+                "See <https://example.com>.",
+                "",
+                "```",
+                "<div></div>",
+                "```",
+            ]
+        );
+    }
+
+    #[test]
+    fn escape_html() {
+        assert_eq!(
+            sanitize(INPUT, SanitizeHtml::Escape),
+            [
+                "&lt;div class=\"warning\"&gt;",
+                "",
+                "Be &lt;b&gt;careful&lt;/b&gt; with `<T>`.&lt;!-- comment --&gt;",
+                "",
+                "&lt;/div&gt;",
+                "",
+                "See <https://example.com>.",
+                "",
+                "```",
+                "<div></div>",
+                "```",
+            ]
+        );
+    }
+
+    #[test]
+    fn indented_code_is_left_alone() {
+        let lines: &[&str] = &[
+            "Some code:",
+            "",
+            r#"    let s = ":crab:"; // [`Foo`] <b>x</b> [a](https://a.io) -- "q""#,
+            "",
+            "Done.",
+        ];
+        let owned = lines.iter().map(|l| l.to_string()).collect();
+
+        assert_eq!(sanitize_html(owned, SanitizeHtml::Escape), lines);
+    }
+}
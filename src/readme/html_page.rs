@@ -0,0 +1,368 @@
+//! Render the processed readme as a standalone HTML page
+//!
+//! The page is self-contained, with its stylesheet inline, so it can be published as it is, like
+//! the `index.html` of a landing page. The markdown is converted covering what READMEs use:
+//! headings, with the same anchors as GitHub, paragraphs, lists, block quotes and GitHub alerts,
+//! tables, code blocks, links, images and emphasis. The raw HTML is kept as it is. The Rust code
+//! blocks are highlighted.
+
+use super::highlight::{escape, highlight_rust};
+use super::markdown::{parse, plain_text, Alignment, Block, Inline};
+use super::toc::Slugs;
+use crate::config::Manifest;
+
+/// Stylesheet of the page, with the classes of the highlighted code
+const STYLE: &str = "\
+body { margin: 0; font: 16px/1.6 -apple-system, BlinkMacSystemFont, \"Segoe UI\", Helvetica, Arial, sans-serif; color: #1f2328; background: #fff; }
+main { max-width: 880px; margin: 0 auto; padding: 32px 24px; }
+h1, h2 { padding-bottom: .3em; border-bottom: 1px solid #d1d9e0; }
+a { color: #0969da; }
+img { max-width: 100%; }
+code { font: 85% ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; background: #eff1f3; padding: .2em .4em; border-radius: 6px; }
+pre { background: #f6f8fa; padding: 16px; overflow: auto; border-radius: 6px; }
+pre code { background: none; padding: 0; }
+blockquote { margin: 0; padding: 0 1em; color: #59636e; border-left: .25em solid #d1d9e0; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #d1d9e0; padding: 6px 13px; }
+.alert { padding: .5em 1em; margin-bottom: 16px; border-left: .25em solid #0969da; }
+.alert-title { font-weight: 600; }
+.alert-tip { border-color: #1a7f37; }
+.alert-important { border-color: #8250df; }
+.alert-warning { border-color: #9a6700; }
+.alert-caution { border-color: #d1242f; }
+.kw { color: #cf222e; }
+.ty { color: #953800; }
+.str { color: #0a3069; }
+.num, .life { color: #0550ae; }
+.mac { color: #8250df; }
+.attr { color: #116329; }
+.com { color: #6e7781; font-style: italic; }";
+
+/// Render the `readme` of the crate of `cargo` as a standalone HTML page
+///
+/// The `generator` meta tag tells the page was generated by cargo-readme, like the comment at the
+/// top of the markdown files.
+pub fn render_html(readme: &str, cargo: &Manifest) -> String {
+    let body = Renderer::default().blocks(&parse(readme));
+
+    let description = match cargo.description {
+        Some(ref description) => format!(
+            "<meta name=\"description\" content=\"{}\">\n",
+            escape(description.trim())
+        ),
+        None => String::new(),
+    };
+
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">
+<meta name=\"generator\" content=\"cargo-readme {version}\">
+{description}<title>{title}</title>
+<style>
+{style}
+</style>
+</head>
+<body>
+<main>
+{body}
+</main>
+</body>
+</html>",
+        version = env!("CARGO_PKG_VERSION"),
+        description = description,
+        title = escape(&cargo.name),
+        style = STYLE,
+        body = body,
+    )
+}
+
+/// Convert the markdown blocks to HTML
+#[derive(Default)]
+struct Renderer {
+    slugs: Slugs,
+}
+
+impl Renderer {
+    /// Convert the markdown `blocks` to HTML
+    fn blocks(&mut self, blocks: &[Block]) -> String {
+        let mut html = Vec::new();
+        for block in blocks {
+            html.push(self.block(block, false));
+        }
+        html.join("\n")
+    }
+
+    /// Convert a markdown block to HTML, without `<p>` around the paragraphs of the items of a
+    /// `tight` list
+    fn block(&mut self, block: &Block, tight: bool) -> String {
+        match block {
+            Block::Heading { level, text } => format!(
+                "<h{0} id=\"{1}\">{2}</h{0}>",
+                level,
+                escape(&self.slugs.anchor(&plain_text(text))),
+                inline(text)
+            ),
+            Block::Paragraph(text) if tight => inline(text),
+            Block::Paragraph(text) => format!("<p>{}</p>", inline(text)),
+            // a bare fence is plain text, as in the other formats
+            Block::Code { language, code } => match language.as_deref() {
+                Some("rust") => format!(
+                    "<pre><code class=\"language-rust\">{}</code></pre>",
+                    highlight_rust(code)
+                ),
+                Some(language) => format!(
+                    "<pre><code class=\"language-{}\">{}</code></pre>",
+                    escape(language),
+                    escape(code)
+                ),
+                None => format!("<pre><code>{}</code></pre>", escape(code)),
+            },
+            Block::Quote {
+                alert: Some(alert),
+                blocks,
+            } => format!(
+                "<div class=\"alert alert-{}\">\n<p class=\"alert-title\">{}</p>\n{}\n</div>",
+                alert.name().to_lowercase(),
+                alert.title(),
+                self.blocks(blocks)
+            ),
+            Block::Quote {
+                alert: None,
+                blocks,
+            } => {
+                format!("<blockquote>\n{}\n</blockquote>", self.blocks(blocks))
+            }
+            Block::List {
+                start,
+                tight,
+                items,
+            } => {
+                let (open, tag) = match start {
+                    None => ("<ul>".to_owned(), "ul"),
+                    Some(1) => ("<ol>".to_owned(), "ol"),
+                    Some(start) => (format!("<ol start=\"{}\">", start), "ol"),
+                };
+                let mut html = vec![open];
+                for item in items {
+                    let mut content = Vec::new();
+                    for block in item {
+                        content.push(self.block(block, *tight));
+                    }
+                    html.push(format!("<li>{}\n</li>", content.join("\n")));
+                }
+                html.push(format!("</{}>", tag));
+                html.join("\n")
+            }
+            Block::Table {
+                alignments,
+                header,
+                rows,
+            } => table(alignments, header, rows),
+            Block::Html(html) => html.clone(),
+            Block::Rule => "<hr>".to_owned(),
+        }
+    }
+}
+
+/// Render the table of `rows` with its `header`, whose columns are aligned by `alignments`
+fn table(alignments: &[Alignment], header: &[Vec<Inline>], rows: &[Vec<Vec<Inline>>]) -> String {
+    let alignments: Vec<&str> = alignments
+        .iter()
+        .map(|alignment| match alignment {
+            Alignment::Center => " style=\"text-align: center\"",
+            Alignment::Right => " style=\"text-align: right\"",
+            Alignment::Left => " style=\"text-align: left\"",
+            Alignment::None => "",
+        })
+        .collect();
+    let row = |cells: &[Vec<Inline>], tag: &str| {
+        let cells: Vec<String> = cells
+            .iter()
+            .enumerate()
+            .map(|(n, cell)| {
+                let align = alignments.get(n).copied().unwrap_or("");
+                format!("<{0}{1}>{2}</{0}>", tag, align, inline(cell))
+            })
+            .collect();
+        format!("<tr>{}</tr>", cells.join(""))
+    };
+
+    let mut html = vec![
+        "<table>".to_owned(),
+        "<thead>".to_owned(),
+        row(header, "th"),
+    ];
+    html.push("</thead>".to_owned());
+    if !rows.is_empty() {
+        html.push("<tbody>".to_owned());
+        html.extend(rows.iter().map(|cells| row(cells, "td")));
+        html.push("</tbody>".to_owned());
+    }
+    html.push("</table>".to_owned());
+
+    html.join("\n")
+}
+
+/// Convert the inline markup of `text`: the code spans, links, images and emphasis
+///
+/// The text is escaped, and the HTML tags are kept as they are.
+fn inline(text: &[Inline]) -> String {
+    text.iter()
+        .map(|inline| match inline {
+            Inline::Text(text) => escape(text),
+            Inline::Code(code) => format!("<code>{}</code>", escape(code)),
+            Inline::Emphasis(text) => format!("<em>{}</em>", self::inline(text)),
+            Inline::Strong(text) => format!("<strong>{}</strong>", self::inline(text)),
+            Inline::Strikethrough(text) => format!("<del>{}</del>", self::inline(text)),
+            Inline::Link {
+                target,
+                title,
+                text,
+            } => format!(
+                "<a href=\"{}\"{}>{}</a>",
+                escape(target),
+                self::title(title),
+                self::inline(text)
+            ),
+            Inline::Image { source, title, alt } => format!(
+                "<img src=\"{}\" alt=\"{}\"{}>",
+                escape(source),
+                escape(alt),
+                self::title(title)
+            ),
+            Inline::Html(html) => html.clone(),
+            Inline::SoftBreak => "\n".to_owned(),
+            Inline::HardBreak => "<br>\n".to_owned(),
+        })
+        .collect()
+}
+
+/// Get the `title` attribute of a link or image, if it has one
+fn title(title: &str) -> String {
+    match title {
+        "" => String::new(),
+        title => format!(" title=\"{}\"", escape(title)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_html, Renderer};
+    use crate::config::Manifest;
+    use crate::readme::markdown::{parse, Block};
+
+    fn blocks(markdown: &str) -> String {
+        Renderer::default().blocks(&parse(markdown))
+    }
+
+    fn inline(markdown: &str) -> String {
+        match &parse(markdown)[..] {
+            [Block::Paragraph(text)] => super::inline(text),
+            blocks => panic!("not a paragraph: {:?}", blocks),
+        }
+    }
+
+    #[test]
+    fn inline_markup() {
+        assert_eq!(
+            inline("See [`Foo<T>`](https://docs.rs/a \"docs\"), **bold**, *it* and <br> & `**`."),
+            "See <a href=\"https://docs.rs/a\" title=\"docs\"><code>Foo&lt;T&gt;</code></a>, \
+             <strong>bold</strong>, <em>it</em> and <br> &amp; <code>**</code>."
+        );
+        assert_eq!(
+            inline("[![CI](https://ci/badge.svg)](https://ci) <https://example.com>"),
+            "<a href=\"https://ci\"><img src=\"https://ci/badge.svg\" alt=\"CI\"></a> \
+             <a href=\"https://example.com\">https://example.com</a>"
+        );
+    }
+
+    #[test]
+    fn block_markup() {
+        let markdown = "\
+# my-crate
+
+Some *text*
+on two lines.
+
+## Usage
+
+- one
+- two
+  - nested
+3. three
+
+> [!NOTE]
+> Be careful.
+
+| Name | Value |
+| ---- | ----: |
+| a    | 1     |
+
+```rust
+let a = \"<b>\";
+```
+
+```toml
+a = 1
+```";
+
+        assert_eq!(
+            blocks(markdown),
+            "\
+<h1 id=\"my-crate\">my-crate</h1>
+<p>Some <em>text</em>
+on two lines.</p>
+<h2 id=\"usage\">Usage</h2>
+<ul>
+<li>one
+</li>
+<li>two
+<ul>
+<li>nested
+</li>
+</ul>
+</li>
+</ul>
+<ol start=\"3\">
+<li>three
+</li>
+</ol>
+<div class=\"alert alert-note\">
+<p class=\"alert-title\">Note</p>
+<p>Be careful.</p>
+</div>
+<table>
+<thead>
+<tr><th>Name</th><th style=\"text-align: right\">Value</th></tr>
+</thead>
+<tbody>
+<tr><td>a</td><td style=\"text-align: right\">1</td></tr>
+</tbody>
+</table>
+<pre><code class=\"language-rust\"><span class=\"kw\">let</span> a = <span class=\"str\">&quot;&lt;b&gt;&quot;</span>;</code></pre>
+<pre><code class=\"language-toml\">a = 1</code></pre>"
+        );
+    }
+
+    #[test]
+    fn bare_fence_is_plain_text() {
+        assert_eq!(
+            blocks("```\nlet a = 1;\n```"),
+            "<pre><code>let a = 1;</code></pre>"
+        );
+    }
+
+    #[test]
+    fn reference_links() {
+        let readme = "See [the docs][docs] and [`Parser`].\n\n[docs]: https://docs.rs/a \"Docs\"";
+        let page = render_html(readme, &Manifest::with_name("a"));
+
+        assert!(page.contains(
+            "<p>See <a href=\"https://docs.rs/a\" title=\"Docs\">the docs</a> and \
+             [<code>Parser</code>].</p>\n</main>"
+        ));
+    }
+}
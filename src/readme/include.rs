@@ -0,0 +1,99 @@
+//! Read files included with `#![doc = include_str!("...")]`
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::relative;
+use crate::log;
+
+/// Read the docs of an included file
+///
+/// Relative links in the file are rewritten so they still work from `project_root`, where the
+/// readme is written.
+pub fn read_included_docs(path: &Path, project_root: &Path) -> io::Result<Vec<String>> {
+    log::verbose(format_args!(
+        "Reading the included file '{}'",
+        path.to_string_lossy()
+    ));
+    log::read(path);
+    let content = fs::read(path)?;
+    let content = String::from_utf8_lossy(&content);
+
+    let file_dir = path.parent().unwrap_or(Path::new("."));
+    let base = relative::relative_path(
+        &fs::canonicalize(project_root)?,
+        &fs::canonicalize(file_dir)?,
+    );
+
+    let mut lines: Vec<String> = content.lines().map(|l| l.trim_end().to_owned()).collect();
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+
+    Ok(rebase_links(lines, &base))
+}
+
+/// Prefix the relative link targets found in `lines` with `base`
+fn rebase_links(lines: Vec<String>, base: &Path) -> Vec<String> {
+    if base.as_os_str().is_empty() {
+        return lines;
+    }
+
+    relative::rewrite_relative_links(lines, |target| {
+        relative::normalize(&base.join(target))
+            .to_string_lossy()
+            .replace(std::path::MAIN_SEPARATOR, "/")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rebase(line: &str) -> String {
+        rebase_links(vec![line.to_owned()], Path::new("docs")).remove(0)
+    }
+
+    #[test]
+    fn rebase_inline_link() {
+        assert_eq!(
+            rebase("See [the guide](guide.md) and ![logo](../assets/logo.png)."),
+            "See [the guide](docs/guide.md) and ![logo](assets/logo.png)."
+        );
+    }
+
+    #[test]
+    fn rebase_inline_link_with_title() {
+        assert_eq!(
+            rebase("[guide](./guide.md \"The guide\")"),
+            "[guide](docs/guide.md \"The guide\")"
+        );
+    }
+
+    #[test]
+    fn rebase_reference_definition() {
+        assert_eq!(rebase("[guide]: guide.md"), "[guide]: docs/guide.md");
+        assert_eq!(
+            rebase("  [guide]: <guide.md>"),
+            "  [guide]: <docs/guide.md>"
+        );
+    }
+
+    #[test]
+    fn rebase_keeps_absolute_targets() {
+        let line = "[a](https://example.com) [b](#anchor) [c](mailto:me@example.com) [d](/root)";
+        assert_eq!(rebase(line), line);
+    }
+
+    #[test]
+    fn rebase_keeps_code() {
+        assert_eq!(
+            rebase("`[a](b.md)` [c](d.md)"),
+            "`[a](b.md)` [c](docs/d.md)"
+        );
+
+        let lines = vec!["```".to_owned(), "[a](b.md)".to_owned(), "```".to_owned()];
+        assert_eq!(rebase_links(lines.clone(), Path::new("docs")), lines);
+    }
+}
@@ -0,0 +1,61 @@
+//! Normalize the line endings of the texts read, and convert those of the output
+//!
+//! Files written on Windows may use `\r\n` line endings. They are read as `\n`, and the output
+//! uses `\n` unless other line endings are asked for.
+
+/// Line endings of the output
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum LineEndings {
+    /// Unix line endings, `\n`
+    #[default]
+    Lf,
+    /// Windows line endings, `\r\n`
+    Crlf,
+    /// Line endings of the platform, `\r\n` on Windows and `\n` elsewhere
+    Native,
+}
+
+/// Replace the `\r\n` line endings of `text` with `\n`
+pub fn normalize_line_endings(text: &str) -> String {
+    text.replace("\r\n", "\n")
+}
+
+/// Convert the line endings of `text` to `line_endings`, whatever line endings it uses
+pub fn convert_line_endings(text: &str, line_endings: LineEndings) -> String {
+    let text = normalize_line_endings(text);
+
+    match line_endings {
+        LineEndings::Crlf => text.replace('\n', "\r\n"),
+        LineEndings::Native if cfg!(windows) => text.replace('\n', "\r\n"),
+        _ => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{convert_line_endings, normalize_line_endings, LineEndings};
+
+    #[test]
+    fn normalize_crlf() {
+        assert_eq!(normalize_line_endings("a\r\nb\nc\r\n"), "a\nb\nc\n");
+    }
+
+    #[test]
+    fn convert_to_lf() {
+        assert_eq!(convert_line_endings("a\r\nb\n", LineEndings::Lf), "a\nb\n");
+    }
+
+    #[test]
+    fn convert_to_crlf() {
+        assert_eq!(
+            convert_line_endings("a\r\nb\n", LineEndings::Crlf),
+            "a\r\nb\r\n"
+        );
+    }
+
+    #[test]
+    fn convert_to_native() {
+        let expected = if cfg!(windows) { "a\r\nb" } else { "a\nb" };
+        assert_eq!(convert_line_endings("a\nb", LineEndings::Native), expected);
+    }
+}
@@ -0,0 +1,612 @@
+//! Find the links of the docs, and rewrite rustdoc intra-doc links, which do not work outside of
+//! rustdoc
+//!
+//! Intra-doc links point to rust items instead of URLs, like ``[`Manifest`]`` or
+//! `[the manifest](crate::config::Manifest)`. They can be turned into links to the documentation
+//! on docs.rs, or stripped to keep only their text.
+//!
+//! The other passes find the links and the reference definitions with the parsers of this module.
+//! As in CommonMark, the target of an inline link may have balanced parentheses, like
+//! `[text](https://example.com/a_(b))`, and the text of a link may have nested brackets.
+
+use lazy_static::lazy_static;
+use percent_encoding as pe;
+use regex::Regex;
+use std::collections::HashMap;
+
+use super::fences::fenced;
+
+lazy_static! {
+    // Is this a path to a rust item, optionally with a disambiguator?
+    static ref RE_ITEM_PATH: Regex = Regex::new(r"^(?:[a-z]+@)?(?P<path>(?:::)?[A-Za-z_][A-Za-z0-9_]*(?:::[A-Za-z_][A-Za-z0-9_]*)*)(?P<suffix>\(\)|!|!\(\)|!\[\]|!\{\})?$").unwrap();
+    // Is this a reference definition, like `[label]: target "title"`? Footnotes, like `[^1]: Note.`,
+    // are not
+    static ref RE_DEFINITION: Regex = Regex::new(r#"^ {0,3}\[(?P<label>[^\]^][^\]]*)\]:\s*(?P<target><[^>]*>|\S+)(?:\s+(?P<title>"[^"]*"|'[^']*'|\([^)]*\)))?\s*$"#).unwrap();
+}
+
+/// Crates documented at doc.rust-lang.org instead of docs.rs
+const STD_CRATES: &[&str] = &["std", "core", "alloc", "proc_macro", "test"];
+
+/// Primitive types, documented in the standard library
+const PRIMITIVES: &[&str] = &[
+    "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64",
+    "i128", "isize", "f32", "f64",
+];
+
+/// What to do with the intra-doc links
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum IntraDocLinks {
+    /// Leave the links as they are
+    #[default]
+    Keep,
+    /// Point the links to the documentation of the crate on docs.rs
+    DocsRs,
+    /// Remove the links, keeping their text
+    Strip,
+}
+
+/// Location of the documentation of a crate on docs.rs
+pub struct DocsRs {
+    pub crate_name: String,
+    pub version: String,
+    /// Name of the library target, which is part of the documentation path
+    pub lib_name: String,
+}
+
+impl DocsRs {
+    /// Get the URL of the documentation of the item at `path`
+    ///
+    /// The kind of the item is not known, so the URL searches the item in the documentation.
+    fn url(&self, path: &str) -> String {
+        let mut segments: Vec<&str> = path.trim_start_matches("::").split("::").collect();
+        while segments.len() > 1 && matches!(segments[0], "crate" | "self" | "super") {
+            segments.remove(0);
+        }
+
+        if segments.len() == 1 && PRIMITIVES.contains(&segments[0]) {
+            return format!(
+                "https://doc.rust-lang.org/std/primitive.{}.html",
+                segments[0]
+            );
+        }
+        if segments.len() > 1 && STD_CRATES.contains(&segments[0]) {
+            return format!(
+                "https://doc.rust-lang.org/{}/?search={}",
+                segments[0],
+                search_query(&segments[1..])
+            );
+        }
+
+        format!(
+            "https://docs.rs/{}/{}/{}/?search={}",
+            self.crate_name,
+            self.version,
+            self.lib_name,
+            search_query(&segments)
+        )
+    }
+}
+
+fn search_query(segments: &[&str]) -> String {
+    pe::utf8_percent_encode(&segments.join("::"), pe::NON_ALPHANUMERIC).to_string()
+}
+
+/// Rewrite the intra-doc links found in `lines`, outside of code blocks
+///
+/// `docs` is `None` when the crate has no documentation on docs.rs, like unpublished crates. With
+/// `strip_unresolved`, the links that are not rewritten into URLs are removed, keeping their text,
+/// as well as the shortcut reference links with code and no definition, like ``[`Vec<T>`]``.
+pub fn rewrite_intra_doc_links(
+    lines: Vec<String>,
+    mode: IntraDocLinks,
+    strip_unresolved: bool,
+    docs: Option<&DocsRs>,
+) -> Vec<String> {
+    if mode == IntraDocLinks::Keep && !strip_unresolved {
+        return lines;
+    }
+
+    let rewriter = Rewriter {
+        docs: docs.filter(|_| mode == IntraDocLinks::DocsRs),
+        strip: mode == IntraDocLinks::Strip || strip_unresolved,
+        definitions: reference_definitions(&lines)
+            .into_iter()
+            .map(|(label, definition)| (label, definition.target.to_owned()))
+            .collect(),
+    };
+
+    fenced(lines)
+        .filter_map(|(kind, line)| match kind.is_code() {
+            true => Some(line),
+            false => rewriter.rewrite_line(line),
+        })
+        .collect()
+}
+
+/// A reference definition, like `[label]: target "title"`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Definition<'a> {
+    pub label: &'a str,
+    /// Target, as written, like `<path with spaces.md>`
+    pub target: &'a str,
+    /// Title, as written with its quotes, if any
+    pub title: Option<&'a str>,
+}
+
+/// Get the reference definition of `line`, if it is one
+pub fn definition(line: &str) -> Option<Definition<'_>> {
+    let cap = RE_DEFINITION.captures(line)?;
+    Some(Definition {
+        label: cap.name("label")?.as_str(),
+        target: cap.name("target")?.as_str(),
+        title: cap.name("title").map(|m| m.as_str()),
+    })
+}
+
+/// Collect the reference definitions outside code blocks, by their normalized label
+pub fn reference_definitions<S: AsRef<str>>(lines: &[S]) -> HashMap<String, Definition<'_>> {
+    let mut definitions = HashMap::new();
+    for (_, line) in fenced(lines).filter(|(kind, _)| !kind.is_code()) {
+        if let Some(definition) = definition(line.as_ref()) {
+            // as in CommonMark, the first definition of a label wins
+            definitions
+                .entry(normalize_label(definition.label))
+                .or_insert(definition);
+        }
+    }
+    definitions
+}
+
+/// Normalize a reference label, which is matched case insensitively with its whitespace collapsed
+pub fn normalize_label(label: &str) -> String {
+    label
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Get the path of the rust item a link points to, if the link is an intra-doc link
+///
+/// The disambiguators, like `struct@` or `()`, are removed from the path. Link texts without
+/// backticks, like `[Foo]`, are only considered when `shortcut` is `false` or when they are
+/// clearly paths, like `[foo::Bar]` or `[bar()]`.
+fn item_path(target: &str, shortcut: bool) -> Option<String> {
+    let (target, code) = match target.strip_prefix('`').and_then(|t| t.strip_suffix('`')) {
+        Some(inner) => (inner.trim(), true),
+        None => (target.trim(), false),
+    };
+    let cap = RE_ITEM_PATH.captures(target)?;
+    let path = &cap["path"];
+
+    let explicit =
+        code || path.contains("::") || target.contains('@') || cap.name("suffix").is_some();
+    if shortcut && !explicit {
+        return None;
+    }
+    Some(path.to_owned())
+}
+
+struct Rewriter<'a> {
+    /// Documentation the links are resolved to, if any
+    docs: Option<&'a DocsRs>,
+    /// Remove the links that are not resolved
+    strip: bool,
+    /// Targets of the reference definitions, by their normalized label
+    definitions: HashMap<String, String>,
+}
+
+impl<'a> Rewriter<'a> {
+    /// Rewrite the links of a line, returning `None` if the line must be removed
+    fn rewrite_line(&self, line: String) -> Option<String> {
+        if let Some(definition) = definition(&line) {
+            let path = match item_path(definition.target, false) {
+                Some(path) => path,
+                None => return Some(line),
+            };
+            return match self.docs {
+                Some(docs) => {
+                    let colon = line.find("]:").unwrap_or_default();
+                    let target = line[colon..].replacen(definition.target, &docs.url(&path), 1);
+                    Some(format!("{}{}", &line[..colon], target))
+                }
+                None if self.strip => None,
+                None => Some(line),
+            };
+        }
+
+        // images are left as they are
+        Some(map_links(
+            &line,
+            &mut |text, after, is_image| match is_image {
+                true => None,
+                false => Some(self.rewrite_link(text, after)),
+            },
+        ))
+    }
+
+    /// Rewrite the link with the given `text`, returning the new link and the rest of the line
+    fn rewrite_link<'b>(&self, text: &'b str, after: &'b str) -> (String, &'b str) {
+        // inline link, like `[text](target)`
+        if let Some((target, _, rest)) = inline_target(after) {
+            let original = format!("[{}]{}", text, &after[..after.len() - rest.len()]);
+            return match item_path(target, false) {
+                Some(path) => (self.link(text, &path, original), rest),
+                None => (original, rest),
+            };
+        }
+
+        // full, collapsed or shortcut reference link, like `[text][label]`, `[text][]` or `[label]`
+        let (label, rest) = reference_label(text, after);
+        let original = format!("[{}]{}", text, &after[..after.len() - rest.len()]);
+        (
+            self.rewrite_reference(text, label, label == text && rest == after, original),
+            rest,
+        )
+    }
+
+    /// Rewrite a reference link, which is an intra-doc link if its label has no definition
+    fn rewrite_reference(
+        &self,
+        text: &str,
+        label: &str,
+        shortcut: bool,
+        original: String,
+    ) -> String {
+        match self.definitions.get(&normalize_label(label)) {
+            // the definition line is rewritten, or removed when stripping
+            Some(target) => match item_path(target, false) {
+                Some(_) if self.docs.is_none() && self.strip => text.to_owned(),
+                _ => original,
+            },
+            None => match item_path(label, shortcut) {
+                Some(path) => self.link(text, &path, original),
+                // code without definition, like [`Vec<T>`], is not a working link
+                None if shortcut && self.strip && is_code_span(text) => text.to_owned(),
+                None => original,
+            },
+        }
+    }
+
+    /// Render an intra-doc link to the item at `path`, or its `original` markdown if not resolved
+    fn link(&self, text: &str, path: &str, original: String) -> String {
+        match self.docs {
+            Some(docs) => format!("[{}]({})", text, docs.url(path)),
+            None if self.strip => text.to_owned(),
+            None => original,
+        }
+    }
+}
+
+/// Check if `text` is a single code span, like `` `Vec<T>` ``
+fn is_code_span(text: &str) -> bool {
+    text.starts_with('`') && code_span_len(text) == text.len()
+}
+
+/// Get the length of the code span at the start of `text`, or of the backticks if it is not closed
+pub fn code_span_len(text: &str) -> usize {
+    let ticks = text.len() - text.trim_start_matches('`').len();
+    let delimiter = &text[..ticks];
+    match text[ticks..].find(delimiter) {
+        Some(end) => ticks + end + ticks,
+        None => ticks,
+    }
+}
+
+/// Get the length of the link text at the start of `text`, including the brackets
+///
+/// Nested brackets and code spans are part of the link text.
+pub fn link_text_len(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = 0;
+    while i < text.len() {
+        match text.as_bytes()[i] {
+            b'`' => {
+                i += code_span_len(&text[i..]);
+                continue;
+            }
+            b'\\' => i += 1,
+            b'[' => depth += 1,
+            b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Get the target and the title, as written, of an inline link from what follows its text, like
+/// `(target "title")`, and the rest of the line
+///
+/// The target may have balanced parentheses, like `(https://example.com/a_(b))`.
+pub fn inline_target(after: &str) -> Option<(&str, Option<&str>, &str)> {
+    let inner = after.strip_prefix('(')?;
+    let start = inner.len() - inner.trim_start().len();
+
+    let mut end = start;
+    if inner[start..].starts_with('<') {
+        end += inner[start..].find('>')? + 1;
+    } else {
+        let mut depth = 0;
+        let bytes = inner.as_bytes();
+        while end < inner.len() {
+            match bytes[end] {
+                b'\\' => end += 1,
+                b'(' => depth += 1,
+                b')' if depth == 0 => break,
+                b')' => depth -= 1,
+                b if b.is_ascii_whitespace() => break,
+                _ => {}
+            }
+            end += 1;
+        }
+        end = end.min(inner.len());
+    }
+    let target = &inner[start..end];
+    if target.is_empty() {
+        return None;
+    }
+
+    let rest = inner[end..].trim_start();
+    let (title, rest) = match rest.chars().next()? {
+        ')' => (None, rest),
+        open @ ('"' | '\'' | '(') => {
+            let close = match open {
+                '(' => ')',
+                quote => quote,
+            };
+            let len = rest[1..].find(close)? + 2;
+            (Some(&rest[..len]), rest[len..].trim_start())
+        }
+        _ => return None,
+    };
+    let rest = rest.strip_prefix(')')?;
+
+    Some((target, title, rest))
+}
+
+/// Get the label of a reference link with the given `text`, and the rest of the line, from what
+/// follows its text, like `[label]` for a full reference link or nothing for a shortcut one
+pub fn reference_label<'a>(text: &'a str, after: &'a str) -> (&'a str, &'a str) {
+    match after
+        .strip_prefix('[')
+        .and_then(|inner| Some((inner, inner.find(']')?)))
+    {
+        // a collapsed reference link, like `[text][]`, uses its text as label
+        Some((inner, 0)) => (text, &inner[1..]),
+        Some((inner, end)) => (&inner[..end], &inner[end + 1..]),
+        None => (text, after),
+    }
+}
+
+/// Rewrite of a link, given its text, what follows it and whether it is an image
+pub type RewriteLink<'a, 'b> = dyn FnMut(&'a str, &'a str, bool) -> Option<(String, &'a str)> + 'b;
+
+/// Rewrite the links of a line, outside code spans, with `rewrite`
+///
+/// `rewrite` is given the text of a link, what follows it and whether it is an image, and returns
+/// the new link and the rest of the line, or `None` to leave the link as it is, with the links of
+/// its text, like the image of a badge, rewritten.
+pub fn map_links<'a>(line: &'a str, rewrite: &mut RewriteLink<'a, '_>) -> String {
+    let mut result = String::new();
+    let mut rest = line;
+    while let Some(pos) = rest.find(['`', '[']) {
+        result.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+
+        if rest.starts_with('`') {
+            let len = code_span_len(rest);
+            result.push_str(&rest[..len]);
+            rest = &rest[len..];
+            continue;
+        }
+
+        if let Some(len) = link_text_len(rest) {
+            let is_image = result.ends_with('!');
+            if let Some((link, after)) = rewrite(&rest[1..len - 1], &rest[len..], is_image) {
+                // the `!` of an image is part of the link
+                if is_image {
+                    result.pop();
+                }
+                result.push_str(&link);
+                rest = after;
+                continue;
+            }
+        }
+        result.push('[');
+        rest = &rest[1..];
+    }
+    result.push_str(rest);
+
+    result
+}
+
+/// An inline link or image, like `[text](target "title")` or `![alt](src)`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Link<'a> {
+    pub image: bool,
+    /// Text of the link, or alternative text of the image, as written
+    pub text: &'a str,
+    /// Target, as written, like `<path with spaces.md>`
+    pub target: &'a str,
+    /// Title, as written with its quotes, if any
+    pub title: Option<&'a str>,
+}
+
+/// Replace the inline links and images of `text`, outside code spans, by what `replace` returns
+/// for them, or leave them as they are when it returns `None`
+///
+/// The links are replaced with their text as written, so `replace` converts the links of the text,
+/// like the image of a badge, if needed.
+pub fn replace_links<F>(text: &str, mut replace: F) -> String
+where
+    F: FnMut(&Link) -> Option<String>,
+{
+    map_links(text, &mut |link_text, after, image| {
+        let (target, title, rest) = inline_target(after)?;
+        let link = Link {
+            image,
+            text: link_text,
+            target,
+            title,
+        };
+        Some((replace(&link)?, rest))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn docs() -> DocsRs {
+        DocsRs {
+            crate_name: "my-crate".to_owned(),
+            version: "1.2.3".to_owned(),
+            lib_name: "my_crate".to_owned(),
+        }
+    }
+
+    fn rewrite(lines: &[&str], mode: IntraDocLinks) -> Vec<String> {
+        let lines = lines.iter().map(|l| l.to_string()).collect();
+        rewrite_intra_doc_links(lines, mode, false, Some(&docs()))
+    }
+
+    const INPUT: &[&str] = &[
+        "Use [`Parser`] and [the builder](crate::builder::Builder), or [`parse()`].",
+        "See [`std::fs::File`], [`u32`] and [docs][struct@Config].",
+        "Keep [a link](https://example.com), [a file](docs/guide.md) and [MIT].",
+        "Also [`Item`][item] and `[not a link]`.",
+        "",
+        "```",
+        "let v = [`Parser`];",
+        "```",
+        "",
+        "[item]: crate::Item",
+    ];
+
+    #[test]
+    fn rewrite_to_docs_rs() {
+        let result = rewrite(INPUT, IntraDocLinks::DocsRs);
+        let expected: &[&str] = &[
+            "Use [`Parser`](https://docs.rs/my-crate/1.2.3/my_crate/?search=Parser) and \
+             [the builder](https://docs.rs/my-crate/1.2.3/my_crate/?search=builder%3A%3ABuilder), \
+             or [`parse()`](https://docs.rs/my-crate/1.2.3/my_crate/?search=parse).",
+            "See [`std::fs::File`](https://doc.rust-lang.org/std/?search=fs%3A%3AFile), \
+             [`u32`](https://doc.rust-lang.org/std/primitive.u32.html) and \
+             [docs](https://docs.rs/my-crate/1.2.3/my_crate/?search=Config).",
+            "Keep [a link](https://example.com), [a file](docs/guide.md) and [MIT].",
+            "Also [`Item`][item] and `[not a link]`.",
+            "",
+            "```",
+            "let v = [`Parser`];",
+            "```",
+            "",
+            "[item]: https://docs.rs/my-crate/1.2.3/my_crate/?search=Item",
+        ];
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn strip_links() {
+        let result = rewrite(INPUT, IntraDocLinks::Strip);
+        let expected: &[&str] = &[
+            "Use `Parser` and the builder, or `parse()`.",
+            "See `std::fs::File`, `u32` and docs.",
+            "Keep [a link](https://example.com), [a file](docs/guide.md) and [MIT].",
+            "Also `Item` and `[not a link]`.",
+            "",
+            "```",
+            "let v = [`Parser`];",
+            "```",
+            "",
+        ];
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn keep_links() {
+        assert_eq!(rewrite(INPUT, IntraDocLinks::Keep), INPUT);
+    }
+
+    const INPUT_UNRESOLVED: &[&str] = &[
+        "Use [`Parser`], [`Vec<T>`] or [the builder](crate::Builder).",
+        "Keep [a link](https://example.com) and [MIT].",
+    ];
+
+    #[test]
+    fn strip_unresolved_links() {
+        let lines = INPUT_UNRESOLVED.iter().map(|l| l.to_string()).collect();
+        let result = rewrite_intra_doc_links(lines, IntraDocLinks::Keep, true, Some(&docs()));
+        let expected: &[&str] = &[
+            "Use `Parser`, `Vec<T>` or the builder.",
+            "Keep [a link](https://example.com) and [MIT].",
+        ];
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn strip_unresolved_links_without_docs() {
+        let lines = INPUT_UNRESOLVED.iter().map(|l| l.to_string()).collect();
+        let result = rewrite_intra_doc_links(lines, IntraDocLinks::DocsRs, true, None);
+        assert_eq!(result[0], "Use `Parser`, `Vec<T>` or the builder.");
+    }
+
+    #[test]
+    fn resolve_links_before_stripping_unresolved() {
+        let lines = INPUT_UNRESOLVED.iter().map(|l| l.to_string()).collect();
+        let result = rewrite_intra_doc_links(lines, IntraDocLinks::DocsRs, true, Some(&docs()));
+        assert_eq!(
+            result[0],
+            "Use [`Parser`](https://docs.rs/my-crate/1.2.3/my_crate/?search=Parser), `Vec<T>` or \
+             [the builder](https://docs.rs/my-crate/1.2.3/my_crate/?search=Builder)."
+        );
+    }
+
+    #[test]
+    fn inline_target_with_parentheses() {
+        assert_eq!(
+            inline_target("(https://x.io/a_(b)) and more"),
+            Some(("https://x.io/a_(b)", None, " and more"))
+        );
+        assert_eq!(
+            inline_target("(<a b.md> \"Title\"))"),
+            Some(("<a b.md>", Some("\"Title\""), ")"))
+        );
+        assert_eq!(inline_target("(https://x.io/a_(b)"), None);
+    }
+
+    #[test]
+    fn replace_nested_links() {
+        let line = "[![CI](ci.svg)](https://ci) and [`a]`](https://x.io/a_(b)), not `[c](d)`";
+        let replaced = replace_links(line, |link| {
+            Some(format!("<{}|{}>", link.text, link.target))
+        });
+        assert_eq!(
+            replaced,
+            "<![CI](ci.svg)|https://ci> and <`a]`|https://x.io/a_(b)>, not `[c](d)`"
+        );
+    }
+
+    #[test]
+    fn indented_code_is_left_alone() {
+        let lines = &[
+            "Some code:",
+            "",
+            r#"    let s = ":crab:"; // [`Foo`] <b>x</b> [a](https://a.io) -- "q""#,
+            "",
+            "Done.",
+        ];
+
+        assert_eq!(rewrite(lines, IntraDocLinks::DocsRs), lines);
+        let owned = lines.iter().map(|l| l.to_string()).collect();
+        assert_eq!(
+            rewrite_intra_doc_links(owned, IntraDocLinks::Keep, true, None),
+            lines
+        );
+    }
+}
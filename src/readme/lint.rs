@@ -0,0 +1,195 @@
+//! Check the generated readme for problems
+//!
+//! The readme is checked for an empty output, missing required sections, relative links to files
+//! that do not exist, headings found several times and code blocks without language. Each problem
+//! is reported with the severity set in `LintConfig`.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::fences::{fenced, info_string, LineKind};
+use super::relative::rewrite_relative_links;
+use super::sections::{matches_heading, Headings};
+use crate::config::{LintConfig, Severity};
+
+/// Problem found in the readme
+#[derive(Clone, Debug, PartialEq)]
+pub struct LintProblem {
+    pub severity: Severity,
+    /// Line of the readme the problem is at, starting at 1
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// Check the `readme` generated in `project_root` for problems
+///
+/// The relative links are resolved from `project_root`, where the readme is written. The problems
+/// with the `Allow` severity are left out.
+pub fn lint_readme(project_root: &Path, readme: &str, config: &LintConfig) -> Vec<LintProblem> {
+    let mut problems = Vec::new();
+    let mut report = |severity: Severity, line: Option<usize>, message: String| {
+        if severity != Severity::Allow {
+            problems.push(LintProblem {
+                severity,
+                line,
+                message,
+            });
+        }
+    };
+
+    if readme.trim().is_empty() {
+        report(config.empty, None, "the README is empty".to_owned());
+        return problems;
+    }
+
+    let mut headings = Headings::default();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut found_sections = Vec::new();
+
+    for (index, (kind, line)) in fenced(readme.lines()).enumerate() {
+        let line_number = index + 1;
+        let heading = headings.heading(line);
+
+        if kind == LineKind::Open && info_string(line).is_empty() {
+            report(
+                config.fence_without_language,
+                Some(line_number),
+                format!("code block without language at line {}", line_number),
+            );
+        }
+        if kind.is_code() {
+            continue;
+        }
+
+        if let Some((_, text)) = heading {
+            match seen.get(&text.to_lowercase()) {
+                Some(first) => report(
+                    config.duplicate_heading,
+                    Some(line_number),
+                    format!(
+                        "duplicate heading `{}` at line {}, first at line {}",
+                        text, line_number, first
+                    ),
+                ),
+                None => {
+                    seen.insert(text.to_lowercase(), line_number);
+                }
+            }
+            found_sections.push(text);
+            continue;
+        }
+
+        for target in relative_targets(line) {
+            // the anchor and the query are not part of the path
+            let path = target.split(['#', '?']).next().unwrap_or_default();
+            if !path.is_empty() && !project_root.join(path).exists() {
+                report(
+                    config.broken_link,
+                    Some(line_number),
+                    format!("broken link to `{}` at line {}", target, line_number),
+                );
+            }
+        }
+    }
+
+    for section in &config.required_sections {
+        if !found_sections
+            .iter()
+            .any(|text| matches_heading(section, text))
+        {
+            report(
+                config.missing_section,
+                None,
+                format!("missing section `{}`", section),
+            );
+        }
+    }
+
+    problems
+}
+
+/// Get the relative link targets of a line outside code blocks
+fn relative_targets(line: &str) -> Vec<String> {
+    let targets = RefCell::new(Vec::new());
+    rewrite_relative_links(vec![line.to_owned()], |target| {
+        targets.borrow_mut().push(target.to_owned());
+        target.to_owned()
+    });
+    targets.into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lint_readme, LintProblem};
+    use crate::config::{LintConfig, Severity};
+    use std::path::Path;
+
+    fn lint(readme: &str, config: &LintConfig) -> Vec<LintProblem> {
+        lint_readme(Path::new("tests/check"), readme, config)
+    }
+
+    fn messages(problems: Vec<LintProblem>) -> Vec<String> {
+        problems.into_iter().map(|p| p.message).collect()
+    }
+
+    #[test]
+    fn clean_readme() {
+        let readme = "# crate\n\n## Usage\n\nSee [the manifest](Cargo.toml#L1).\n\n```rust\nlet a = 1;\n```\n";
+        assert!(lint(readme, &LintConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn empty_readme() {
+        assert_eq!(
+            lint("\n\n", &LintConfig::default()),
+            [LintProblem {
+                severity: Severity::Error,
+                line: None,
+                message: "the README is empty".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn problems_in_order() {
+        let readme = "\
+# crate
+
+## Usage
+
+See [the guide](docs/guide.md) and [the site](https://example.com).
+
+```
+# not a heading
+```
+
+## Usage
+";
+        let config = LintConfig {
+            required_sections: vec!["Usage".to_owned(), "License".to_owned()],
+            ..LintConfig::default()
+        };
+
+        assert_eq!(
+            messages(lint(readme, &config)),
+            [
+                "broken link to `docs/guide.md` at line 5",
+                "code block without language at line 7",
+                "duplicate heading `Usage` at line 11, first at line 3",
+                "missing section `License`",
+            ]
+        );
+    }
+
+    #[test]
+    fn allowed_problems_are_left_out() {
+        let readme = "# crate\n\n```\ncode\n```\n";
+        let config = LintConfig {
+            fence_without_language: Severity::Allow,
+            ..LintConfig::default()
+        };
+
+        assert!(lint(readme, &config).is_empty());
+    }
+}
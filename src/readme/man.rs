@@ -0,0 +1,279 @@
+//! Render the processed readme as a roff man page
+//!
+//! The docs of a binary crate are usually written with sections like `NAME`, `SYNOPSIS` and
+//! `OPTIONS`. The title of the readme is taken for the `.TH` header, the headings of level 2 become
+//! sections, and the deeper ones subsections. When the docs have no `NAME` section, one is made
+//! from the name and the description of the crate. The list items starting with a code span, like
+//! ``- `--verbose`: print more``, become tagged paragraphs, the usual layout of the options. The
+//! images, like badges, are left out. The page is dated with the date given, or else with the
+//! version of the crate, so it only changes with the docs.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::markdown::{comment, image_paragraph, parse, plain_text, Block, Inline};
+use crate::config::Manifest;
+
+lazy_static! {
+    // the separator between the tag and the text of a tagged item, like `: ` or ` - `
+    static ref RE_TAG_SEPARATOR: Regex = Regex::new(r"^(?::|\s+[—–-])\s+").unwrap();
+}
+
+/// Render the `readme` of the crate of `cargo` as a man page of section 1, dated `date`
+pub fn render_man(readme: &str, cargo: &Manifest, date: Option<&str>) -> String {
+    // the page is named after the binary, when the crate has a single one
+    let name = match cargo.bin.as_slice() {
+        [bin] => bin.name.clone().unwrap_or_else(|| cargo.name.clone()),
+        _ => cargo.name.clone(),
+    };
+
+    let mut title = None;
+    let man = blocks(&parse(readme), &mut title);
+
+    let mut page = vec![format!(
+        ".TH {} 1 {} {} {}",
+        quote(&name.to_uppercase()),
+        quote(date.unwrap_or(&cargo.version)),
+        quote(&format!("{} {}", cargo.name, cargo.version)),
+        quote("User Commands")
+    )];
+    if !man.iter().any(|line| line == ".SH \"NAME\"") {
+        let description = cargo
+            .description
+            .as_deref()
+            .or(title.as_deref().filter(|title| *title != name));
+        page.push(".SH \"NAME\"".to_owned());
+        page.push(match description {
+            Some(description) => format!("{} \\- {}", escape(&name), escape(description.trim())),
+            None => escape(&name),
+        });
+    }
+    page.extend(man);
+
+    page.join("\n")
+}
+
+/// Quote an argument of a request, like the title of a section
+fn quote(text: &str) -> String {
+    format!("\"{}\"", escape(text).replace('"', "\\(dq"))
+}
+
+/// Escape the backslashes and the dashes of `text`, so they are shown as they are
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\e").replace('-', "\\-")
+}
+
+/// Escape a line starting with a dot or an apostrophe, which would be read as a request
+fn escape_line(line: &str) -> String {
+    match line.starts_with(['.', '\'']) {
+        true => format!("\\&{}", line),
+        false => line.to_owned(),
+    }
+}
+
+/// Convert the markdown `blocks` to the lines of the page, taking the first heading of level 1 as
+/// its `title`
+fn blocks(blocks: &[Block], title: &mut Option<String>) -> Vec<String> {
+    let mut man = Vec::new();
+    for block in blocks {
+        match block {
+            Block::Heading { level, text } => {
+                let text = plain_text(text);
+                match level {
+                    1 if title.is_none() => *title = Some(text),
+                    1 | 2 => man.push(format!(".SH {}", quote(&text.to_uppercase()))),
+                    _ => man.push(format!(".SS {}", quote(&text))),
+                }
+            }
+            // the images, and the links made of an image, like badges, are left out
+            Block::Paragraph(text) => {
+                if image_paragraph(text).is_none() {
+                    let lines = lines(text);
+                    if !lines.is_empty() {
+                        man.push(".PP".to_owned());
+                        man.extend(lines);
+                    }
+                }
+            }
+            Block::Code { code, .. } => {
+                man.extend([".PP", ".RS 4", ".nf"].map(ToOwned::to_owned));
+                man.extend(code.lines().map(|line| escape_line(&escape(line))));
+                man.extend([".fi", ".RE"].map(ToOwned::to_owned));
+            }
+            Block::Quote { alert, blocks } => {
+                if let Some(alert) = alert {
+                    man.push(".PP".to_owned());
+                    man.push(format!("\\fB{}\\fR", alert.title()));
+                }
+                man.extend(self::blocks(blocks, title));
+            }
+            Block::List { start, items, .. } => {
+                for (i, item) in items.iter().enumerate() {
+                    // the text of the item is its first paragraph, and its other blocks are
+                    // indented under it
+                    let (text, rest) = match item.split_first() {
+                        Some((Block::Paragraph(text), rest)) => (text.as_slice(), rest),
+                        _ => (&[][..], item.as_slice()),
+                    };
+                    match (start, tagged(text)) {
+                        (None, Some((tag, text))) => {
+                            man.push(".TP".to_owned());
+                            man.extend(lines(&tag));
+                            man.extend(lines(&text));
+                        }
+                        (start, _) => {
+                            match start {
+                                Some(start) => man.push(format!(".IP {}. 4", start + i as u64)),
+                                None => man.push(".IP \\(bu 2".to_owned()),
+                            }
+                            man.extend(lines(text));
+                        }
+                    }
+                    if !rest.is_empty() {
+                        man.push(".RS 4".to_owned());
+                        man.extend(self::blocks(rest, title));
+                        man.push(".RE".to_owned());
+                    }
+                }
+            }
+            // the cells of the rows are separated by tabs
+            Block::Table { header, rows, .. } => {
+                man.extend([".PP", ".nf"].map(ToOwned::to_owned));
+                for row in std::iter::once(header).chain(rows) {
+                    let cells: Vec<String> = row.iter().map(|cell| inline(cell)).collect();
+                    man.push(escape_line(&cells.join("\t")));
+                }
+                man.push(".fi".to_owned());
+            }
+            Block::Html(html) => {
+                if let Some(text) = comment(html) {
+                    man.push(format!(".\\\" {}", text));
+                }
+            }
+            Block::Rule => {}
+        }
+    }
+    man
+}
+
+/// Split the text of a list item starting with code spans, like ``- `--verbose`: print more``,
+/// into its tag and its text
+fn tagged(text: &[Inline]) -> Option<(Vec<Inline>, Vec<Inline>)> {
+    let mut tag = Vec::new();
+    let mut inlines = text.iter();
+    while let Some(Inline::Code(code)) = inlines.next() {
+        tag.push(Inline::Code(code.clone()));
+        match inlines.next() {
+            Some(Inline::Text(separator)) if separator.trim() == "," => {
+                tag.push(Inline::Text(separator.clone()))
+            }
+            Some(Inline::Text(rest)) => {
+                let separator = RE_TAG_SEPARATOR.find(rest)?;
+                let mut text = vec![Inline::Text(rest[separator.end()..].to_owned())];
+                text.extend(inlines.cloned());
+                return Some((tag, text));
+            }
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// Convert the inline markup of `text` to the escaped lines of a paragraph
+fn lines(text: &[Inline]) -> Vec<String> {
+    inline(text)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(escape_line)
+        .collect()
+}
+
+/// Convert the inline markup of `text`, escaping it: the code spans and the bold text are shown
+/// in bold, the italic text in italic and the links with their target
+fn inline(text: &[Inline]) -> String {
+    text.iter()
+        .map(|inline| match inline {
+            Inline::Text(text) => escape(text),
+            Inline::Code(code) => format!("\\fB{}\\fR", escape(code)),
+            Inline::Strong(text) => format!("\\fB{}\\fR", self::inline(text)),
+            Inline::Emphasis(text) => format!("\\fI{}\\fR", self::inline(text)),
+            Inline::Strikethrough(text) => self::inline(text),
+            // the images, and the links made of images, are left out
+            Inline::Link { text, .. }
+                if text
+                    .iter()
+                    .all(|inline| matches!(inline, Inline::Image { .. })) =>
+            {
+                String::new()
+            }
+            Inline::Link { target, text, .. } => match plain_text(text) {
+                label if label == *target || label.is_empty() => escape(target),
+                _ => format!("{} <{}>", self::inline(text), escape(target)),
+            },
+            Inline::Image { .. } | Inline::Html(_) => String::new(),
+            Inline::SoftBreak | Inline::HardBreak => "\n".to_owned(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_man;
+    use crate::config::Manifest;
+
+    #[test]
+    fn man_page() {
+        let mut cargo = Manifest::with_name("my-tool");
+        cargo.version = "1.2.0".to_owned();
+        cargo.description = Some("Do things".to_owned());
+        let readme = "\
+[![CI](https://ci/badge.svg)](https://ci)
+
+# my-tool
+
+Run `my-tool` on **files**, see [the guide](https://example.com).
+
+## Synopsis
+
+```sh
+my-tool [OPTIONS] <FILE>
+```
+
+## Options
+
+- `-v`, `--verbose`: print more
+- `.hidden` files are skipped
+1. first";
+
+        let page = render_man(readme, &cargo, Some("2024-01-31"));
+        let mut lines = page.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            ".TH \"MY\\-TOOL\" 1 \"2024\\-01\\-31\" \"my\\-tool 1.2.0\" \"User Commands\""
+        );
+        assert_eq!(
+            lines.collect::<Vec<_>>().join("\n"),
+            "\
+.SH \"NAME\"
+my\\-tool \\- Do things
+.PP
+Run \\fBmy\\-tool\\fR on \\fBfiles\\fR, see the guide <https://example.com>.
+.SH \"SYNOPSIS\"
+.PP
+.RS 4
+.nf
+my\\-tool [OPTIONS] <FILE>
+.fi
+.RE
+.SH \"OPTIONS\"
+.TP
+\\fB\\-v\\fR, \\fB\\-\\-verbose\\fR
+print more
+.IP \\(bu 2
+\\fB.hidden\\fR files are skipped
+.IP 1. 4
+first"
+        );
+    }
+}
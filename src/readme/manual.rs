@@ -0,0 +1,271 @@
+//! Preserve the manually maintained sections of an existing readme
+//!
+//! A section is manual when its heading is tagged with `<!-- cargo-readme manual -->`, like
+//! `## Contributing <!-- cargo-readme manual -->`. When the readme is generated again, manual
+//! sections are carried over verbatim: they replace the generated section with the same heading,
+//! or are inserted after the section they followed in the existing readme.
+
+use super::sections::{matches_heading, Headings};
+
+/// Comment tagging the heading of a manual section
+pub const MANUAL_MARKER: &str = "<!-- cargo-readme manual -->";
+
+/// Carry the manual sections of `existing`, the existing readme, over to `readme`, the generated
+/// docs
+pub fn preserve_manual_sections(existing: &str, readme: &str) -> String {
+    let existing: Vec<String> = existing.lines().map(ToOwned::to_owned).collect();
+    let mut lines: Vec<String> = readme.lines().map(ToOwned::to_owned).collect();
+
+    for section in manual_sections(&existing) {
+        let headings = find_headings(&lines);
+
+        if let Some(position) = headings
+            .iter()
+            .position(|h| matches_heading(&section.heading, &h.text))
+        {
+            // replace the generated section with the same heading
+            let start = headings[position].line;
+            let end = section_end(&headings, position, lines.len());
+            let mut replacement = section.lines;
+            if end < lines.len() {
+                replacement.push(String::new());
+            }
+            lines.splice(start..end, replacement);
+            continue;
+        }
+
+        let anchor = section.previous_heading.as_ref().and_then(|previous| {
+            headings
+                .iter()
+                .position(|h| matches_heading(previous, &h.text))
+        });
+        let insert_at = match anchor {
+            // before the heading following the section the manual section followed
+            Some(anchor) => headings.get(anchor + 1).map_or(lines.len(), |h| h.line),
+            None if section.previous_heading.is_none() => 0,
+            None => lines.len(),
+        };
+        insert_section(&mut lines, insert_at, section.lines);
+    }
+
+    lines.join("\n")
+}
+
+/// A manual section, with the heading that preceded it in the existing readme
+struct ManualSection {
+    heading: String,
+    previous_heading: Option<String>,
+    lines: Vec<String>,
+}
+
+/// A heading of a readme, with the line it is found at
+struct Heading {
+    line: usize,
+    level: usize,
+    text: String,
+}
+
+/// Find the manual sections of `lines`
+fn manual_sections(lines: &[String]) -> Vec<ManualSection> {
+    let headings = find_headings(lines);
+
+    headings
+        .iter()
+        .enumerate()
+        .filter(|(_, h)| h.text.contains(MANUAL_MARKER))
+        .map(|(i, h)| {
+            let end = section_end(&headings, i, lines.len());
+            let mut section_lines = lines[h.line..end].to_vec();
+            while section_lines.last().is_some_and(|l| l.trim().is_empty()) {
+                section_lines.pop();
+            }
+
+            ManualSection {
+                heading: h.text.replace(MANUAL_MARKER, "").trim().to_owned(),
+                previous_heading: i.checked_sub(1).map(|p| {
+                    headings[p]
+                        .text
+                        .replace(MANUAL_MARKER, "")
+                        .trim()
+                        .to_owned()
+                }),
+                lines: section_lines,
+            }
+        })
+        .collect()
+}
+
+/// Find the headings of `lines`
+fn find_headings(lines: &[String]) -> Vec<Heading> {
+    let mut finder = Headings::default();
+
+    lines
+        .iter()
+        .enumerate()
+        .filter_map(|(line, l)| {
+            finder
+                .heading(l)
+                .map(|(level, text)| Heading { line, level, text })
+        })
+        .collect()
+}
+
+/// Get the line where the section of heading `index` ends, at the next heading of the same or a
+/// higher level
+fn section_end(headings: &[Heading], index: usize, len: usize) -> usize {
+    let level = headings[index].level;
+
+    headings[index + 1..]
+        .iter()
+        .find(|h| h.level <= level)
+        .map_or(len, |h| h.line)
+}
+
+/// Insert `section` at line `position` of `lines`, separated by blank lines
+fn insert_section(lines: &mut Vec<String>, position: usize, mut section: Vec<String>) {
+    if position > 0 && !lines[position - 1].trim().is_empty() {
+        section.insert(0, String::new());
+    }
+    if position < lines.len() {
+        section.push(String::new());
+    }
+    lines.splice(position..position, section);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::preserve_manual_sections;
+
+    const GENERATED: &str = "\
+# my-crate
+
+Generated intro.
+
+## Usage
+
+Generated usage.
+
+## Contributing
+
+Generated contributing.";
+
+    #[test]
+    fn replace_generated_section() {
+        let existing = "\
+# my-crate
+
+Old intro.
+
+## Contributing <!-- cargo-readme manual -->
+
+Hand-written contributing.
+
+## Usage
+
+Old usage.";
+
+        let expected = "\
+# my-crate
+
+Generated intro.
+
+## Usage
+
+Generated usage.
+
+## Contributing <!-- cargo-readme manual -->
+
+Hand-written contributing.";
+
+        assert_eq!(preserve_manual_sections(existing, GENERATED), expected);
+    }
+
+    #[test]
+    fn insert_after_previous_section() {
+        let existing = "\
+# my-crate
+
+## Usage
+
+Old usage.
+
+## Support <!-- cargo-readme manual -->
+
+Hand-written support.
+
+### Contact
+
+Hand-written contact.
+
+## Contributing
+
+Old contributing.";
+
+        let expected = "\
+# my-crate
+
+Generated intro.
+
+## Usage
+
+Generated usage.
+
+## Support <!-- cargo-readme manual -->
+
+Hand-written support.
+
+### Contact
+
+Hand-written contact.
+
+## Contributing
+
+Generated contributing.";
+
+        assert_eq!(preserve_manual_sections(existing, GENERATED), expected);
+    }
+
+    #[test]
+    fn insert_first_section() {
+        let existing = "\
+# Notice <!-- cargo-readme manual -->
+
+Hand-written notice.
+
+# my-crate";
+
+        assert_eq!(
+            preserve_manual_sections(existing, GENERATED),
+            format!(
+                "# Notice <!-- cargo-readme manual -->\n\nHand-written notice.\n\n{}",
+                GENERATED
+            )
+        );
+    }
+
+    #[test]
+    fn append_when_previous_section_is_gone() {
+        let existing = "\
+## Removed
+
+## Extra <!-- cargo-readme manual -->
+
+Hand-written extra.";
+
+        assert_eq!(
+            preserve_manual_sections(existing, GENERATED),
+            format!(
+                "{}\n\n## Extra <!-- cargo-readme manual -->\n\nHand-written extra.",
+                GENERATED
+            )
+        );
+    }
+
+    #[test]
+    fn keep_readme_without_manual_sections() {
+        assert_eq!(
+            preserve_manual_sections("# Old\n\nOld", GENERATED),
+            GENERATED
+        );
+    }
+}
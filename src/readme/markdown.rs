@@ -0,0 +1,498 @@
+//! Parse the processed readme into blocks and inlines, for the renderers of the output formats
+//!
+//! The renderers of the formats converted from the markdown all need its structure: the headings,
+//! paragraphs, lists, quotes, tables and code blocks, and the code spans, emphasis and links of
+//! their text. The readme is parsed once, as CommonMark with the tables, the strikethrough and the
+//! GitHub alerts, like `> [!NOTE]`, of GitHub Flavored Markdown, and the events of the parser are
+//! gathered into a tree of blocks, which the renderers walk. The reference links are resolved by
+//! the parser, and their definitions left out.
+
+use pulldown_cmark::{BlockQuoteKind, CodeBlockKind, Event, LinkType, Options, Parser, Tag};
+
+pub use pulldown_cmark::Alignment;
+
+/// A block of markdown
+#[derive(Clone, Debug, PartialEq)]
+pub enum Block {
+    /// A heading, from level 1 to 6
+    Heading {
+        level: usize,
+        text: Vec<Inline>,
+    },
+    Paragraph(Vec<Inline>),
+    /// A fenced or indented code block, with the language of its info string, if any
+    Code {
+        language: Option<String>,
+        code: String,
+    },
+    /// A block quote, or a GitHub alert
+    Quote {
+        alert: Option<Alert>,
+        blocks: Vec<Block>,
+    },
+    /// A list, numbered from `start` when it is ordered. The items of a tight list, and their
+    /// blocks, are not separated by blank lines
+    List {
+        start: Option<u64>,
+        tight: bool,
+        items: Vec<Vec<Block>>,
+    },
+    /// A table, with the alignment of its columns, and the cells of its header and of its rows
+    Table {
+        alignments: Vec<Alignment>,
+        header: Vec<Vec<Inline>>,
+        rows: Vec<Vec<Vec<Inline>>>,
+    },
+    /// Raw HTML, like `<div class="warning">` or `<!-- cargo-readme manual -->`
+    Html(String),
+    /// A thematic break, like `---`
+    Rule,
+}
+
+/// Inline content of a block
+#[derive(Clone, Debug, PartialEq)]
+pub enum Inline {
+    Text(String),
+    Code(String),
+    Emphasis(Vec<Inline>),
+    Strong(Vec<Inline>),
+    Strikethrough(Vec<Inline>),
+    /// A link, whose text is its target for an autolink, like `<https://example.com>`
+    Link {
+        target: String,
+        title: String,
+        text: Vec<Inline>,
+    },
+    Image {
+        source: String,
+        title: String,
+        alt: String,
+    },
+    /// An HTML tag or comment, like `<br>`
+    Html(String),
+    SoftBreak,
+    HardBreak,
+}
+
+/// Kind of a GitHub alert, like `> [!NOTE]`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Alert {
+    Note,
+    Tip,
+    Important,
+    Warning,
+    Caution,
+}
+
+impl Alert {
+    /// Get the name of the alert, as written in the markdown, like `NOTE`
+    pub fn name(self) -> &'static str {
+        match self {
+            Alert::Note => "NOTE",
+            Alert::Tip => "TIP",
+            Alert::Important => "IMPORTANT",
+            Alert::Warning => "WARNING",
+            Alert::Caution => "CAUTION",
+        }
+    }
+
+    /// Get the title of the alert, as shown by GitHub, like `Note`
+    pub fn title(self) -> &'static str {
+        match self {
+            Alert::Note => "Note",
+            Alert::Tip => "Tip",
+            Alert::Important => "Important",
+            Alert::Warning => "Warning",
+            Alert::Caution => "Caution",
+        }
+    }
+}
+
+impl From<BlockQuoteKind> for Alert {
+    fn from(kind: BlockQuoteKind) -> Self {
+        match kind {
+            BlockQuoteKind::Note => Alert::Note,
+            BlockQuoteKind::Tip => Alert::Tip,
+            BlockQuoteKind::Important => Alert::Important,
+            BlockQuoteKind::Warning => Alert::Warning,
+            BlockQuoteKind::Caution => Alert::Caution,
+        }
+    }
+}
+
+/// Image of a paragraph made only of images, like the badges, with the target of the link it is
+/// in, if any
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlockImage<'a> {
+    pub source: &'a str,
+    pub alt: &'a str,
+    pub link: Option<&'a str>,
+}
+
+/// Parse the markdown `readme` into its blocks
+pub fn parse(readme: &str) -> Vec<Block> {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_GFM;
+    blocks(&mut Parser::new_ext(readme, options))
+}
+
+/// Gather the blocks of `events`, until the end of the block they are in
+fn blocks(events: &mut Parser) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    while let Some(event) = events.next() {
+        match event {
+            Event::Start(tag) => blocks.extend(block(tag, events)),
+            Event::Rule => blocks.push(Block::Rule),
+            Event::End(_) => break,
+            _ => {}
+        }
+    }
+    blocks
+}
+
+/// Gather the block opened by `tag` from `events`
+fn block(tag: Tag, events: &mut Parser) -> Option<Block> {
+    Some(match tag {
+        Tag::Heading { level, .. } => Block::Heading {
+            level: level as usize,
+            text: inlines(events),
+        },
+        Tag::Paragraph => Block::Paragraph(inlines(events)),
+        Tag::CodeBlock(kind) => {
+            // the language is the first attribute of the info string, like `rust` in `rust,no_run`
+            let language = match kind {
+                CodeBlockKind::Fenced(info) => info
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .find(|part| !part.is_empty())
+                    .map(ToOwned::to_owned),
+                CodeBlockKind::Indented => None,
+            };
+            let code = text(events);
+            Block::Code {
+                language,
+                code: code.strip_suffix('\n').unwrap_or(&code).to_owned(),
+            }
+        }
+        Tag::BlockQuote(kind) => Block::Quote {
+            alert: kind.map(Alert::from),
+            blocks: blocks(events),
+        },
+        Tag::List(start) => {
+            let mut items = Vec::new();
+            let mut tight = true;
+            while let Some(Event::Start(Tag::Item)) = events.next() {
+                let (item, loose) = item(events);
+                items.push(item);
+                tight &= !loose;
+            }
+            Block::List {
+                start,
+                tight,
+                items,
+            }
+        }
+        Tag::Table(alignments) => {
+            let mut header = Vec::new();
+            let mut rows = Vec::new();
+            while let Some(Event::Start(row)) = events.next() {
+                let mut cells = Vec::new();
+                while let Some(Event::Start(Tag::TableCell)) = events.next() {
+                    cells.push(inlines(events));
+                }
+                match row {
+                    Tag::TableHead => header = cells,
+                    _ => rows.push(cells),
+                }
+            }
+            Block::Table {
+                alignments,
+                header,
+                rows,
+            }
+        }
+        Tag::HtmlBlock => Block::Html(text(events).trim_end().to_owned()),
+        _ => {
+            // the blocks that are not enabled, like the footnote definitions, are skipped
+            blocks(events);
+            return None;
+        }
+    })
+}
+
+/// Gather the blocks of a list item from `events`, and tell whether its text is in paragraphs,
+/// as in a loose list
+///
+/// The text of the items of a tight list is not in a paragraph, it is made one.
+fn item(events: &mut Parser) -> (Vec<Block>, bool) {
+    let mut blocks = Vec::new();
+    let mut text = Vec::new();
+    let mut loose = false;
+    while let Some(event) = events.next() {
+        let tag = match event {
+            Event::Start(tag) if is_block(&tag) => tag,
+            Event::Rule => {
+                blocks.push(Block::Rule);
+                continue;
+            }
+            Event::End(_) => break,
+            event => {
+                push_inline(&mut text, event, events);
+                continue;
+            }
+        };
+        if !text.is_empty() {
+            blocks.push(Block::Paragraph(std::mem::take(&mut text)));
+        }
+        loose |= matches!(tag, Tag::Paragraph);
+        blocks.extend(block(tag, events));
+    }
+    if !text.is_empty() {
+        blocks.push(Block::Paragraph(text));
+    }
+    (blocks, loose)
+}
+
+/// Tell whether `tag` opens a block, instead of inline content
+fn is_block(tag: &Tag) -> bool {
+    !matches!(
+        tag,
+        Tag::Emphasis | Tag::Strong | Tag::Strikethrough | Tag::Link { .. } | Tag::Image { .. }
+    )
+}
+
+/// Gather the inline content of `events`, until the end of the element it is in
+fn inlines(events: &mut Parser) -> Vec<Inline> {
+    let mut inlines = Vec::new();
+    while let Some(event) = events.next() {
+        match event {
+            Event::End(_) => break,
+            event => push_inline(&mut inlines, event, events),
+        }
+    }
+    inlines
+}
+
+/// Push the inline content of `event` to `inlines`, gathering its content from `events`
+///
+/// The text split by the parser, like around an escaped character, is joined back.
+fn push_inline(inlines: &mut Vec<Inline>, event: Event, events: &mut Parser) {
+    let inline = match event {
+        Event::Text(text) => {
+            if let Some(Inline::Text(last)) = inlines.last_mut() {
+                last.push_str(&text);
+                return;
+            }
+            Inline::Text(text.into_string())
+        }
+        Event::Code(code) => Inline::Code(code.into_string()),
+        Event::Html(html) | Event::InlineHtml(html) => Inline::Html(html.into_string()),
+        Event::SoftBreak => Inline::SoftBreak,
+        Event::HardBreak => Inline::HardBreak,
+        Event::Start(Tag::Emphasis) => Inline::Emphasis(self::inlines(events)),
+        Event::Start(Tag::Strong) => Inline::Strong(self::inlines(events)),
+        Event::Start(Tag::Strikethrough) => Inline::Strikethrough(self::inlines(events)),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            ..
+        }) => Inline::Link {
+            target: match link_type {
+                LinkType::Email => format!("mailto:{}", dest_url),
+                _ => dest_url.into_string(),
+            },
+            title: title.into_string(),
+            text: self::inlines(events),
+        },
+        Event::Start(Tag::Image {
+            dest_url, title, ..
+        }) => Inline::Image {
+            source: dest_url.into_string(),
+            title: title.into_string(),
+            alt: plain_text(&self::inlines(events)),
+        },
+        Event::Start(_) => {
+            self::inlines(events);
+            return;
+        }
+        _ => return,
+    };
+    inlines.push(inline);
+}
+
+/// Gather the text of a code block or an HTML block from `events`
+fn text(events: &mut Parser) -> String {
+    let mut text = String::new();
+    for event in events.by_ref() {
+        match event {
+            Event::Text(part) | Event::Html(part) => text.push_str(&part),
+            Event::End(_) => break,
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Get the text of `inlines` without their markup, the images being replaced by their
+/// alternative text and the line breaks by spaces
+pub fn plain_text(inlines: &[Inline]) -> String {
+    inlines
+        .iter()
+        .map(|inline| match inline {
+            Inline::Text(text) | Inline::Code(text) => text.clone(),
+            Inline::Emphasis(inlines)
+            | Inline::Strong(inlines)
+            | Inline::Strikethrough(inlines)
+            | Inline::Link { text: inlines, .. } => plain_text(inlines),
+            Inline::Image { alt, .. } => alt.clone(),
+            Inline::Html(_) => String::new(),
+            Inline::SoftBreak | Inline::HardBreak => " ".to_owned(),
+        })
+        .collect()
+}
+
+/// Get the images of a paragraph made only of images, or of links whose text is an image, like
+/// the badges
+pub fn image_paragraph(text: &[Inline]) -> Option<Vec<BlockImage<'_>>> {
+    let mut images = Vec::new();
+    for inline in text {
+        match inline {
+            Inline::Image { source, alt, .. } => images.push(BlockImage {
+                source,
+                alt,
+                link: None,
+            }),
+            Inline::Link { target, text, .. } => match text.as_slice() {
+                [Inline::Image { source, alt, .. }] => images.push(BlockImage {
+                    source,
+                    alt,
+                    link: Some(target),
+                }),
+                _ => return None,
+            },
+            Inline::SoftBreak | Inline::HardBreak => {}
+            Inline::Text(text) if text.trim().is_empty() => {}
+            _ => return None,
+        }
+    }
+    Some(images).filter(|images| !images.is_empty())
+}
+
+/// Get the text of an HTML block made of a single comment, like `<!-- cargo-readme manual -->`
+pub fn comment(html: &str) -> Option<&str> {
+    let text = html.trim().strip_prefix("<!--")?.strip_suffix("-->")?;
+    match text.contains("-->") || text.contains('\n') {
+        true => None,
+        false => Some(text.trim()),
+    }
+}
+
+/// Join the lines of the rendered `blocks`, with a blank line between them unless `tight`
+pub fn join_blocks<I>(blocks: I, tight: bool) -> Vec<String>
+where
+    I: IntoIterator<Item = Vec<String>>,
+{
+    let mut lines = Vec::new();
+    for block in blocks.into_iter().filter(|block| !block.is_empty()) {
+        if !tight && !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.extend(block);
+    }
+    lines
+}
+
+/// Indent `lines` by `width` spaces, except the blank ones, and prefix the first one with `marker`
+/// instead, like the lines of a list item
+pub fn indent(lines: Vec<String>, width: usize, marker: Option<&str>) -> Vec<String> {
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| match (i, marker) {
+            (0, Some(marker)) => format!("{:width$}{}", marker, line, width = width),
+            _ if line.is_empty() => line,
+            _ => format!("{}{}", " ".repeat(width), line),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, Alert, Block, Inline};
+
+    fn text(text: &str) -> Inline {
+        Inline::Text(text.to_owned())
+    }
+
+    #[test]
+    fn parse_blocks() {
+        let readme = "\
+# Title
+
+Some *text*, `code` and [a link][link].
+
+- one
+- two
+  - nested
+
+> [!NOTE]
+> Be careful.
+
+    indented
+
+[link]: https://example.com";
+
+        assert_eq!(
+            parse(readme),
+            [
+                Block::Heading {
+                    level: 1,
+                    text: vec![text("Title")]
+                },
+                Block::Paragraph(vec![
+                    text("Some "),
+                    Inline::Emphasis(vec![text("text")]),
+                    text(", "),
+                    Inline::Code("code".to_owned()),
+                    text(" and "),
+                    Inline::Link {
+                        target: "https://example.com".to_owned(),
+                        title: String::new(),
+                        text: vec![text("a link")]
+                    },
+                    text("."),
+                ]),
+                Block::List {
+                    start: None,
+                    tight: true,
+                    items: vec![
+                        vec![Block::Paragraph(vec![text("one")])],
+                        vec![
+                            Block::Paragraph(vec![text("two")]),
+                            Block::List {
+                                start: None,
+                                tight: true,
+                                items: vec![vec![Block::Paragraph(vec![text("nested")])]]
+                            }
+                        ],
+                    ]
+                },
+                Block::Quote {
+                    alert: Some(Alert::Note),
+                    blocks: vec![Block::Paragraph(vec![text("Be careful.")])]
+                },
+                Block::Code {
+                    language: None,
+                    code: "indented".to_owned()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn escaped_text_is_joined() {
+        assert_eq!(
+            parse("Not \\*emphasis\\*."),
+            [Block::Paragraph(vec![text("Not *emphasis*.")])]
+        );
+    }
+}
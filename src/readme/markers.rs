@@ -0,0 +1,124 @@
+//! Update an existing readme between marker comments
+//!
+//! Only the content between `<!-- cargo-readme start -->` and `<!-- cargo-readme end -->` is
+//! replaced, so the hand-written content above and below the markers is left untouched.
+
+use crate::Error;
+
+/// Comment marking the start of the generated content
+pub const START_MARKER: &str = "<!-- cargo-readme start -->";
+/// Comment marking the end of the generated content
+pub const END_MARKER: &str = "<!-- cargo-readme end -->";
+
+/// Get the content between the markers of `existing`
+///
+/// An error is returned if the markers are missing or out of order.
+pub fn marked_content(existing: &str) -> Result<&str, Error> {
+    marked_range(existing, START_MARKER, END_MARKER).map(|(start, end)| &existing[start..end])
+}
+
+/// Replace the content between the markers of `existing` with `readme`
+///
+/// An error is returned if the markers are missing or out of order.
+pub fn update_between_markers(existing: &str, readme: &str) -> Result<String, Error> {
+    replace_between(existing, START_MARKER, END_MARKER, readme)
+}
+
+/// Replace the content between `start_marker` and `end_marker` in `existing` with `content`
+pub(crate) fn replace_between(
+    existing: &str,
+    start_marker: &str,
+    end_marker: &str,
+    content: &str,
+) -> Result<String, Error> {
+    let (start, end) = marked_range(existing, start_marker, end_marker)?;
+
+    Ok(format!(
+        "{}\n\n{}\n\n{}",
+        &existing[..start],
+        content.trim(),
+        &existing[end..]
+    ))
+}
+
+/// Get the range of the content between `start_marker` and `end_marker` in `existing`
+fn marked_range(
+    existing: &str,
+    start_marker: &str,
+    end_marker: &str,
+) -> Result<(usize, usize), Error> {
+    let start = existing
+        .find(start_marker)
+        .map(|start| start + start_marker.len())
+        .ok_or_else(|| Error::marker(format!("Missing `{}` marker", start_marker)))?;
+    let end = existing[start..]
+        .find(end_marker)
+        .map(|end| start + end)
+        .ok_or_else(|| {
+            Error::marker(format!(
+                "Missing `{}` marker after `{}`",
+                end_marker, start_marker
+            ))
+        })?;
+
+    Ok((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::update_between_markers;
+
+    #[test]
+    fn update_content_between_markers() {
+        let existing = "\
+# Title
+
+Hand-written intro.
+
+<!-- cargo-readme start -->
+Old content
+<!-- cargo-readme end -->
+
+Hand-written footer.
+";
+        let expected = "\
+# Title
+
+Hand-written intro.
+
+<!-- cargo-readme start -->
+
+New content
+
+<!-- cargo-readme end -->
+
+Hand-written footer.
+";
+
+        assert_eq!(
+            update_between_markers(existing, "New content\n").unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn update_empty_markers() {
+        let existing = "<!-- cargo-readme start --><!-- cargo-readme end -->";
+
+        assert_eq!(
+            update_between_markers(existing, "New").unwrap(),
+            "<!-- cargo-readme start -->\n\nNew\n\n<!-- cargo-readme end -->"
+        );
+    }
+
+    #[test]
+    fn missing_markers() {
+        assert!(update_between_markers("No markers", "New").is_err());
+        assert!(update_between_markers("<!-- cargo-readme start -->", "New").is_err());
+        assert!(update_between_markers(
+            "<!-- cargo-readme end --> <!-- cargo-readme start -->",
+            "New"
+        )
+        .is_err());
+    }
+}
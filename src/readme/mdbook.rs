@@ -0,0 +1,49 @@
+//! Render the processed readme as a chapter of an mdBook
+//!
+//! The chapter is markdown, like the readme, but mdBook takes the first heading as the title of
+//! the chapter, so the headings are shifted for the chapter to start at level 1. The hidden lines
+//! of the Rust code blocks and their doc test attributes are kept, as `mdbook test` runs them and
+//! mdBook hides the lines itself.
+
+use super::sections::Headings;
+
+/// Shift the headings of `readme` so the highest one is at level 1
+pub fn mdbook_chapter(readme: &str) -> String {
+    let mut headings = Headings::default();
+    let top = readme
+        .lines()
+        .filter_map(|line| headings.heading(line))
+        .map(|(level, _)| level)
+        .min()
+        .unwrap_or(1);
+
+    if top == 1 {
+        return readme.to_owned();
+    }
+
+    let mut headings = Headings::default();
+    readme
+        .lines()
+        .map(|line| match headings.heading(line) {
+            Some((level, text)) => format!("{} {}", "#".repeat(level + 1 - top), text),
+            None => line.to_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mdbook_chapter;
+
+    #[test]
+    fn headings_start_at_level_1() {
+        let readme = "## Usage\n\n```rust\n# use a::b;\n```\n\n### Options ###\n";
+
+        assert_eq!(
+            mdbook_chapter(readme),
+            "# Usage\n\n```rust\n# use a::b;\n```\n\n## Options"
+        );
+        assert_eq!(mdbook_chapter("# crate\n\n## Usage"), "# crate\n\n## Usage");
+    }
+}
@@ -1,15 +1,274 @@
-use std::io::Read;
-use std::path::Path;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
 
+mod alerts;
+mod asciidoc;
+mod builder;
+mod confluence;
+mod crates_io;
+mod diagrams;
+mod emoji;
 mod extract;
+mod fences;
+mod footer;
+mod footnotes;
+mod format;
+mod front_matter;
+mod highlight;
+mod hooks;
+mod html;
+mod html_page;
+mod include;
+mod line_endings;
+mod links;
+mod lint;
+mod man;
+mod manual;
+mod markdown;
+mod markers;
+mod mdbook;
+mod modules;
+mod normalize;
+mod org;
 mod process;
+mod punctuation;
+mod references;
+mod regions;
+mod relative;
+mod rst;
+mod rustdoc;
+mod sections;
+mod stats;
+mod tabs;
 mod template;
+mod text;
+mod toc;
+mod whitespace;
+mod wrap;
 
+pub use self::builder::ReadmeBuilder;
+pub use self::diagrams::DiagramBlocks;
+pub use self::emoji::Emoji;
+pub use self::format::OutputFormat;
+pub use self::front_matter::FrontMatter;
+pub use self::hooks::Hooks;
+pub use self::html::SanitizeHtml;
+pub use self::line_endings::{convert_line_endings, LineEndings};
+pub use self::links::IntraDocLinks;
+pub use self::lint::{lint_readme, LintProblem};
+pub use self::markers::{marked_content, update_between_markers};
+pub use self::process::{DoctestAttributes, HiddenLines};
+pub use self::references::LinkStyle;
+pub use self::relative::AssetCopy;
+pub use self::stats::DocStats;
+pub use self::toc::{table_of_contents, update_table_of_contents, AnchorStyle};
+
+use self::footer::{append_footer, SourceHash};
+use self::format::render_output;
+use self::front_matter::prepend_front_matter;
 use crate::config;
+use crate::log;
+use crate::Error;
+
+/// Where the doc comments are read from
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Backend {
+    /// Parse the source file
+    #[default]
+    Source,
+    /// Run `cargo +nightly rustdoc` with JSON output on the library target, or on the binary
+    /// target `bin`, and read the crate docs as rustdoc sees them
+    RustdocJson { bin: Option<String> },
+}
+
+/// Options for the generation of the readme
+///
+/// New options may be added, so the options are made from `ReadmeOptions::default()`, or set with
+/// `ReadmeBuilder::configure`.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct ReadmeOptions {
+    /// Where the doc comments are read from
+    pub backend: Backend,
+    /// Prepend the crate name as title, when not using a template
+    pub add_title: bool,
+    /// Prepend the title even when the docs already start with a heading with the crate name,
+    /// which is otherwise replaced by the title
+    pub always_add_title: bool,
+    /// Use the first line of the docs as title, instead of the crate name, when it is a top level
+    /// heading, like `# Title`
+    pub title_from_docs: bool,
+    /// Prepend the badges defined in `Cargo.toml`, when not using a template
+    pub add_badges: bool,
+    /// Append the license defined in `Cargo.toml`, when not using a template
+    pub add_license: bool,
+    /// Number of levels the headings are shifted by, `1` leaves the top level heading to the crate
+    /// name, negative values promote the headings
+    pub heading_offset: isize,
+    /// What to do with the hidden lines of doc tests
+    pub hidden_lines: HiddenLines,
+    /// Info string of the rust code blocks, like `rust` or `rust,ignore`, empty for a bare fence
+    pub fence_lang: String,
+    /// What to do with the attributes of doc tests telling how they are run, like `no_run`
+    pub doctest_attributes: DoctestAttributes,
+    /// Expand the tabs of the code blocks to tab stops every given number of columns
+    pub expand_tabs: Option<usize>,
+    /// Convert the warning blocks, like `<div class="warning">`, and the blockquotes starting with
+    /// `**Note:**` or `**Warning:**` into GitHub alerts
+    pub github_alerts: bool,
+    /// What to do with the raw HTML, like `<div class="warning">`
+    pub sanitize_html: SanitizeHtml,
+    /// What to do with the mermaid diagrams and the display math, instead of the `diagrams` set in
+    /// `[package.metadata.readme]`
+    pub diagrams: Option<DiagramBlocks>,
+    /// What to do with the intra-doc links, like ``[`Item`]``
+    pub intra_doc_links: IntraDocLinks,
+    /// Remove the intra-doc links that cannot be resolved, keeping their text
+    pub strip_unresolved_links: bool,
+    /// Rewrite the relative links into absolute URLs to the `repository` set in `Cargo.toml`
+    pub absolute_links: bool,
+    /// Rewrite the constructs crates.io renders poorly: the HTML it removes, the code blocks it
+    /// cannot highlight and the relative links it cannot resolve without a `repository`
+    pub crates_io: bool,
+    /// Copy the images found relative to the source file next to the readme, instead of
+    /// rewriting their links
+    pub copy_assets: bool,
+    /// Append the docs of the public modules, declared with `pub mod name;`, under a heading with
+    /// the module path. Requires `source_dir`
+    pub include_modules: bool,
+    /// Headings of the sections to remove from the docs, along with their content, in addition to
+    /// the `exclude-sections` set in `[package.metadata.readme]`
+    pub exclude_sections: Vec<String>,
+    /// Heading of the only section to keep, without the heading itself
+    pub only_section: Option<String>,
+    /// Turn the footnotes into inline text in parentheses, for renderers without footnotes
+    pub inline_footnotes: bool,
+    /// Convert the straight quotes, `--`, `---` and `...` of the prose into their typographic
+    /// form, like the smart punctuation of rustdoc
+    pub smart_punctuation: bool,
+    /// What to do with the emoji of the prose: keep them, or convert the shortcodes, like
+    /// `:crab:`, to Unicode emoji, or back
+    pub emoji: Emoji,
+    /// Name of the crate, instead of the `name` set in `Cargo.toml`
+    pub crate_name: Option<String>,
+    /// License of the crate, instead of the `license` set in `Cargo.toml`
+    pub license: Option<String>,
+    /// Do not read `Cargo.toml`, like for a source read from stdin. The crate is only known by
+    /// `crate_name`, so there are no badges, license or version
+    pub no_cargo_toml: bool,
+    /// Style of the links of the output, inline or reference links collected at the end
+    pub link_style: LinkStyle,
+    /// Normalize the markdown syntax of the output: ATX headings, `-` bullets, backtick fences and
+    /// emphasis with `*`, whatever the style of the doc comments
+    pub normalize_markdown: bool,
+    /// Wrap the prose paragraphs of the output at the given column, leaving the code blocks,
+    /// tables and headings as they are
+    pub wrap: Option<usize>,
+    /// Remove the trailing whitespace of every line of the output, keeping the hard line breaks as
+    /// backslashes
+    pub strip_trailing_whitespace: bool,
+    /// Collapse the runs of blank lines of the output, outside code blocks, into a single one
+    pub collapse_blank_lines: bool,
+    /// Append a `<!-- cargo-readme VERSION source-hash ... -->` comment, with the version of
+    /// cargo-readme and the hash of the source files, to tell the readme was generated from them
+    pub footer: bool,
+    /// Format of the output, the docs being processed as markdown whatever the format
+    pub output_format: OutputFormat,
+    /// Prepend a front matter block in this format, with the title, description, date and tags of
+    /// the crate, for static site generators
+    pub front_matter: Option<FrontMatter>,
+    /// Date of the front matter and of the man page, like `2024-01-31`. Without it, the front
+    /// matter has no date and the man page is dated with the version of the crate
+    pub date: Option<String>,
+    /// Content of the existing readme, whose manual sections, tagged with
+    /// `<!-- cargo-readme manual -->`, are carried over to the generated docs
+    pub existing_readme: Option<String>,
+    /// Other source files, whose docs are appended in order to the docs of the source file
+    pub extra_sources: Vec<PathBuf>,
+    /// Directory of the source file, used to resolve `#![doc = include_str!("...")]`
+    pub source_dir: Option<PathBuf>,
+    /// Directory the readme is written to, which the links to the images found relative to the
+    /// source file are rebased on, and where they are copied to. Defaults to the project root
+    pub output_dir: Option<PathBuf>,
+    /// Directory of the modules declared in the source file, when it is not `source_dir`, like
+    /// `src/foo/` for the module `src/foo.rs`
+    pub modules_dir: Option<PathBuf>,
+    /// Active cfg options, like `docsrs` or `feature="serde"`, used to read the docs of
+    /// `#![cfg_attr(..., doc = "...")]` attributes
+    pub cfgs: Vec<String>,
+    /// Closures rewriting the extracted lines, and the readme before and after the template
+    pub hooks: Hooks,
+}
+
+impl Default for ReadmeOptions {
+    fn default() -> Self {
+        ReadmeOptions {
+            backend: Backend::Source,
+            add_title: true,
+            always_add_title: false,
+            title_from_docs: false,
+            add_badges: true,
+            add_license: true,
+            heading_offset: 1,
+            hidden_lines: HiddenLines::Hide,
+            fence_lang: "rust".to_owned(),
+            doctest_attributes: DoctestAttributes::Drop,
+            expand_tabs: None,
+            github_alerts: false,
+            sanitize_html: SanitizeHtml::Passthrough,
+            diagrams: None,
+            intra_doc_links: IntraDocLinks::Keep,
+            strip_unresolved_links: false,
+            absolute_links: false,
+            crates_io: false,
+            copy_assets: false,
+            include_modules: false,
+            exclude_sections: Vec::new(),
+            only_section: None,
+            inline_footnotes: false,
+            smart_punctuation: false,
+            emoji: Emoji::Keep,
+            link_style: LinkStyle::Keep,
+            normalize_markdown: false,
+            wrap: None,
+            strip_trailing_whitespace: false,
+            collapse_blank_lines: false,
+            footer: false,
+            output_format: OutputFormat::Markdown,
+            front_matter: None,
+            date: None,
+            crate_name: None,
+            license: None,
+            no_cargo_toml: false,
+            existing_readme: None,
+            extra_sources: Vec::new(),
+            source_dir: None,
+            output_dir: None,
+            modules_dir: None,
+            cfgs: Vec::new(),
+            hooks: Hooks::default(),
+        }
+    }
+}
+
+/// Readme generated from the docs, along with the files to copy next to it
+#[derive(Clone, Debug, PartialEq)]
+pub struct Readme {
+    /// Text of the readme
+    pub text: String,
+    /// Copies of the images found relative to the source file, with `copy_assets`, to make when
+    /// the readme is written
+    pub asset_copies: Vec<AssetCopy>,
+    /// Warnings about the docs, like when there are none and the description is used instead
+    pub warnings: Vec<String>,
+}
 
 /// Generates readme data from `source` file
 ///
-/// Optionally, a template can be used to render the output
+/// Optionally, a template can be used to render the output. If `source` has no doc comments, the
+/// `description` from `Cargo.toml` is used instead.
+#[deprecated(note = "use `ReadmeBuilder` instead")]
 pub fn generate_readme<T: Read>(
     project_root: &Path,
     source: &mut T,
@@ -18,10 +277,665 @@ pub fn generate_readme<T: Read>(
     add_badges: bool,
     add_license: bool,
     indent_headings: bool,
-) -> Result<String, String> {
-    let lines = extract::extract_docs(source).map_err(|e| format!("{}", e))?;
+) -> Result<String, Error> {
+    let options = ReadmeOptions {
+        add_title,
+        add_badges,
+        add_license,
+        heading_offset: indent_headings as isize,
+        ..Default::default()
+    };
+
+    generate_readme_with_options(project_root, source, template, &options).map(|r| r.text)
+}
+
+/// Generates readme data from `source` file, using the given options
+///
+/// Optionally, a template can be used to render the output. If `source` has no doc comments, the
+/// `description` from `Cargo.toml` is used instead.
+pub fn generate_readme_with_options<T: Read>(
+    project_root: &Path,
+    source: &mut T,
+    template: Option<&mut T>,
+    options: &ReadmeOptions,
+) -> Result<Readme, Error> {
+    // get manifest from Cargo.toml, or from the options
+    let cargo = get_manifest(project_root, options)?;
+
+    let source_string = read_source(source)?;
+    let lines = extract_crate_docs(project_root, &source_string, &cargo, options)?;
 
-    let readme = process::process_docs(lines, indent_headings).join("\n");
+    readme_from_docs(
+        Some(project_root),
+        &source_string,
+        lines,
+        &cargo,
+        template,
+        options,
+    )
+}
+
+/// Generates readme data from the text of the source file and of `Cargo.toml`, in memory
+///
+/// Nothing is read from the filesystem, nor from the environment: the files included with
+/// `include_str!` and the modules are not read, the links to images are left as they are, and the
+/// absolute links are made from the root of the repository. Optionally, a template can be used to
+/// render the output.
+pub fn generate_readme_from_str(
+    source: &str,
+    cargo_toml: &str,
+    template: Option<&str>,
+    options: &ReadmeOptions,
+) -> Result<Readme, Error> {
+    if options.backend != Backend::Source
+        || options.source_dir.is_some()
+        || !options.extra_sources.is_empty()
+    {
+        return Err(Error::Usage(
+            "The docs are only read from the given source in memory, without other backend, \
+             source directory or extra sources"
+                .to_owned(),
+        ));
+    }
+
+    let cargo = override_manifest(config::parse_manifest(cargo_toml)?, options);
+    let lines = extract_docs(None, source.as_bytes(), None, &options.cfgs)?;
+    let mut template = template.map(str::as_bytes);
+
+    readme_from_docs(None, source, lines, &cargo, template.as_mut(), options)
+}
+
+/// Generates readme data from the doc `lines` extracted from `source_string`
+///
+/// The files of the crate, like the included modules and images, are looked for from
+/// `project_root`, when it is given.
+fn readme_from_docs<T: Read>(
+    project_root: Option<&Path>,
+    source_string: &str,
+    lines: Vec<String>,
+    cargo: &config::Manifest,
+    template: Option<&mut T>,
+    options: &ReadmeOptions,
+) -> Result<Readme, Error> {
+    log::trace(format_args!("Found {} lines of docs", lines.len()));
+    let mut lines = options.hooks.run_lines(lines);
+
+    // fall back to the package description when there are no doc comments
+    let mut warnings = Vec::new();
+    if lines.is_empty() {
+        if let Some(ref description) = cargo.description {
+            warnings.push("no doc comments found, using `package.description` instead".to_owned());
+            lines = description
+                .lines()
+                .map(|l| l.trim_end().to_owned())
+                .collect();
+        }
+    }
+
+    // the docs as written, for the outputs describing them
+    let docs = lines.clone();
+
+    // the title is left out of the processing, so it is not shifted nor taken for a section
+    let title = match options.title_from_docs {
+        true => sections::take_title(&mut lines),
+        false => None,
+    };
+    if let Some(ref title) = title {
+        log::trace(format_args!("Using the first heading `{}` as title", title));
+    }
+
+    let mut asset_copies = Vec::new();
+    let mut readme = process_docs(
+        project_root,
+        options.source_dir.as_deref(),
+        lines,
+        cargo,
+        options,
+        options.heading_offset,
+        &mut asset_copies,
+    )?
+    .join("\n");
+
+    if let (true, Some(project_root), Some(source_dir)) = (
+        options.include_modules,
+        project_root,
+        options.source_dir.as_deref(),
+    ) {
+        let mut sections = vec![readme];
+        sections.extend(module_sections(
+            project_root,
+            source_string,
+            source_dir,
+            cargo,
+            options,
+            &mut asset_copies,
+        )?);
+        sections.retain(|s| !s.trim().is_empty());
+        readme = sections.join("\n\n");
+    }
+
+    let readme = match title {
+        Some(title) => {
+            let readme = format!("# {}\n\n{}", title, readme).trim_end().to_owned();
+            // the title of the docs replaces the crate name
+            let options = ReadmeOptions {
+                add_title: false,
+                ..options.clone()
+            };
+            render(template, readme, cargo, &options)?
+        }
+        None => render(template, readme, cargo, options)?,
+    };
+
+    let mut hash = SourceHash::default();
+    hash.update(source_string.as_bytes());
+    Ok(Readme {
+        text: finish(readme, cargo, &hash, &docs, options),
+        asset_copies,
+        warnings,
+    })
+}
+
+/// Gets statistics about the doc comments of the crate read from `source`
+///
+/// The doc comments are counted as written, before they are processed for the readme.
+pub fn docs_stats<T: Read>(
+    project_root: &Path,
+    source: &mut T,
+    options: &ReadmeOptions,
+) -> Result<DocStats, Error> {
+    let cargo = get_manifest(project_root, options)?;
+    let source_string = read_source(source)?;
+    let lines = extract_crate_docs(project_root, &source_string, &cargo, options)?;
+
+    Ok(stats::doc_stats(&lines))
+}
+
+/// Read the source file of the crate
+fn read_source<T: Read>(source: &mut T) -> Result<String, Error> {
+    // invalid UTF-8 sequences, like in string literals, must not fail the whole extraction
+    let mut source_bytes = Vec::new();
+    source
+        .read_to_end(&mut source_bytes)
+        .map_err(|source| Error::Io { path: None, source })?;
+
+    Ok(String::from_utf8_lossy(&source_bytes).into_owned())
+}
+
+/// Extract the doc comments of the crate, from its source or with the backend of `options`
+fn extract_crate_docs(
+    project_root: &Path,
+    source_string: &str,
+    cargo: &config::Manifest,
+    options: &ReadmeOptions,
+) -> Result<Vec<String>, Error> {
+    let lines = match options.backend {
+        Backend::Source => {
+            log::trace(format_args!("Extracting the doc comments of the source"));
+            let mut lines = extract_docs(
+                Some(project_root),
+                source_string.as_bytes(),
+                options.source_dir.as_deref(),
+                &options.cfgs,
+            )?;
+            for path in &options.extra_sources {
+                log::verbose(format_args!(
+                    "Reading the docs from '{}'",
+                    path.to_string_lossy()
+                ));
+                log::read(path);
+                let source = File::open(path).map_err(|e| Error::io(path, e))?;
+                let extra = extract_docs(Some(project_root), source, path.parent(), &options.cfgs)?;
+                if !lines.is_empty() && !extra.is_empty() {
+                    lines.push(String::new());
+                }
+                lines.extend(extra);
+            }
+            lines
+        }
+        Backend::RustdocJson { ref bin } => {
+            log::trace(format_args!(
+                "Reading the docs from the rustdoc JSON output"
+            ));
+            rustdoc::extract_docs(
+                project_root,
+                &lib_name(cargo),
+                bin.as_deref(),
+                &options.cfgs,
+            )?
+        }
+    };
+
+    Ok(lines)
+}
+
+/// Generates readme data from the source files of several binary targets
+///
+/// The docs of each binary are placed under a heading with the binary name, one level below the
+/// crate name. Optionally, a template can be used to render the output.
+pub fn generate_readme_bins<T: Read>(
+    project_root: &Path,
+    sources: Vec<(String, PathBuf)>,
+    template: Option<&mut T>,
+    options: &ReadmeOptions,
+) -> Result<Readme, Error> {
+    // get manifest from Cargo.toml, or from the options
+    let cargo = get_manifest(project_root, options)?;
+
+    // binaries are one level below the crate name, their docs one level below the binary name
+    let heading_level = options.heading_offset.max(0) as usize + 1;
+    let docs_offset = options.heading_offset + 1;
+
+    let mut sections = Vec::new();
+    let mut docs = Vec::new();
+    let mut asset_copies = Vec::new();
+    let mut hash = SourceHash::default();
+    for (name, path) in sources {
+        log::verbose(format_args!(
+            "Reading the source of `{}` from '{}'",
+            name,
+            path.to_string_lossy()
+        ));
+        log::read(&path);
+        let mut source = File::open(&path).map_err(|e| Error::io(&path, e))?;
+        let source = read_source(&mut source)?;
+        hash.update(source.as_bytes());
+        let lines = extract_docs(
+            Some(project_root),
+            source.as_bytes(),
+            path.parent(),
+            &options.cfgs,
+        )?;
+        let lines = options.hooks.run_lines(lines);
+        docs.extend(lines.iter().cloned());
+        let processed = process_docs(
+            Some(project_root),
+            path.parent(),
+            lines,
+            &cargo,
+            options,
+            docs_offset,
+            &mut asset_copies,
+        )?
+        .join("\n");
+        let heading = format!("{} {}", "#".repeat(heading_level), name);
+
+        if processed.trim().is_empty() {
+            sections.push(heading);
+        } else {
+            sections.push(format!("{}\n\n{}", heading, processed));
+        }
+    }
+
+    let readme = sections.join("\n\n");
+    let readme = render(template, readme, &cargo, options)?;
+
+    Ok(Readme {
+        text: finish(readme, &cargo, &hash, &docs, options),
+        asset_copies,
+        warnings: Vec::new(),
+    })
+}
+
+/// Render the `readme` made of the `docs` in the output format of `options`, with the front matter
+/// and the footer when they are asked for
+fn finish(
+    readme: String,
+    cargo: &config::Manifest,
+    hash: &SourceHash,
+    docs: &[String],
+    options: &ReadmeOptions,
+) -> String {
+    let mut readme = render_output(
+        options.output_format,
+        readme,
+        cargo,
+        docs,
+        options.date.as_deref(),
+    );
+    if let Some(format) = options.front_matter {
+        readme = prepend_front_matter(readme, format, cargo, options.date.as_deref());
+    }
+
+    match options.footer {
+        true => append_footer(readme, &hash.footer()),
+        false => readme,
+    }
+}
+
+/// Get the docs of the public modules declared in `source`, each under a heading with the module
+/// path, one level below the crate name
+fn module_sections(
+    project_root: &Path,
+    source: &str,
+    source_dir: &Path,
+    cargo: &config::Manifest,
+    options: &ReadmeOptions,
+    asset_copies: &mut Vec<AssetCopy>,
+) -> Result<Vec<String>, Error> {
+    let heading_level = options.heading_offset.max(0) as usize + 1;
+
+    let mut sections = Vec::new();
+    let modules_dir = options.modules_dir.as_deref().unwrap_or(source_dir);
+    for module in modules::find_modules(source, source_dir, modules_dir)? {
+        log::verbose(format_args!(
+            "Reading the docs of the module `{}` from '{}'",
+            module.path,
+            module.file.to_string_lossy()
+        ));
+        let mut source = File::open(&module.file).map_err(|e| Error::io(&module.file, e))?;
+        let lines = extract_docs(
+            Some(project_root),
+            &mut source,
+            module.file.parent(),
+            &options.cfgs,
+        )?;
+        let lines = options.hooks.run_lines(lines);
+        // modules without docs are left out
+        if lines.is_empty() {
+            continue;
+        }
+        let docs = process_docs(
+            Some(project_root),
+            module.file.parent(),
+            lines,
+            cargo,
+            options,
+            options.heading_offset + 1,
+            asset_copies,
+        )?
+        .join("\n");
+
+        sections.push(format!(
+            "{} {}\n\n{}",
+            "#".repeat(heading_level),
+            module.path,
+            docs
+        ));
+    }
+
+    Ok(sections)
+}
+
+/// Extract the doc lines from `source`, resolving `include_str!` relative to `source_dir`
+///
+/// The included files cannot be read without `project_root` and `source_dir`.
+fn extract_docs<R: Read>(
+    project_root: Option<&Path>,
+    source: R,
+    source_dir: Option<&Path>,
+    cfgs: &[String],
+) -> Result<Vec<String>, Error> {
+    // the error of the included file, kept apart as the extraction only returns an `io::Error`
+    let mut include_error = None;
+    let lines = extract::extract_docs_with(source, cfgs, |path| {
+        let result = match (project_root, source_dir) {
+            (Some(project_root), Some(source_dir)) => {
+                include::read_included_docs(&source_dir.join(path), project_root)
+            }
+            _ => extract::unresolved_include(path),
+        };
+        result.map_err(|source| {
+            let error = io::Error::new(source.kind(), source.to_string());
+            let path = source_dir.map_or_else(|| PathBuf::from(path), |dir| dir.join(path));
+            include_error = Some(Error::Include { path, source });
+            error
+        })
+    });
+
+    lines.map_err(|source| include_error.unwrap_or(Error::Io { path: None, source }))
+}
+
+/// Get the manifest of the crate, from `Cargo.toml` unless `options.no_cargo_toml` is set
+fn get_manifest(project_root: &Path, options: &ReadmeOptions) -> Result<config::Manifest, Error> {
+    let cargo = match (options.no_cargo_toml, options.crate_name.as_deref()) {
+        (true, Some(name)) => {
+            log::verbose(format_args!(
+                "Not reading `Cargo.toml`, the crate is `{}`",
+                name
+            ));
+            let mut cargo = config::Manifest::with_name(name);
+            cargo.license = options.license.clone();
+            return Ok(cargo);
+        }
+        (true, None) => {
+            return Err(Error::Usage(
+                "The crate name is required without `Cargo.toml`".to_owned(),
+            ))
+        }
+        (false, _) => {
+            log::verbose(format_args!(
+                "Reading '{}'",
+                project_root.join("Cargo.toml").to_string_lossy()
+            ));
+            config::get_manifest(project_root)?
+        }
+    };
+
+    Ok(override_manifest(cargo, options))
+}
+
+/// Override the crate name and license of the manifest `cargo` with the ones of `options`
+fn override_manifest(mut cargo: config::Manifest, options: &ReadmeOptions) -> config::Manifest {
+    if let Some(ref name) = options.crate_name {
+        cargo.name = name.clone();
+    }
+    if let Some(ref license) = options.license {
+        cargo.license = Some(license.clone());
+        cargo.license_file = None;
+    }
+
+    cargo
+}
+
+/// Transform the doc lines into markdown, shifting the headings by `heading_offset` levels
+///
+/// The links to images are rebased from `source_dir`, the directory of the source file, to the
+/// project root, when both are known, or the copies of the images are added to `asset_copies`.
+fn process_docs(
+    project_root: Option<&Path>,
+    source_dir: Option<&Path>,
+    lines: Vec<String>,
+    cargo: &config::Manifest,
+    options: &ReadmeOptions,
+    heading_offset: isize,
+    asset_copies: &mut Vec<AssetCopy>,
+) -> Result<Vec<String>, Error> {
+    let excluded: Vec<String> = options
+        .exclude_sections
+        .iter()
+        .chain(&cargo.exclude_sections)
+        .cloned()
+        .collect();
+    let lines = regions::readme_regions(lines)?;
+    if !excluded.is_empty() {
+        log::trace(format_args!("Removing the sections {:?}", excluded));
+    }
+    let mut lines = sections::exclude_sections(lines, &excluded);
+    if let Some(ref heading) = options.only_section {
+        log::trace(format_args!("Keeping only the section `{}`", heading));
+        lines = sections::only_section(lines, heading)?;
+    }
+    if options.inline_footnotes {
+        log::trace(format_args!("Inlining the footnotes"));
+        lines = footnotes::inline_footnotes(lines);
+    }
+
+    log::trace(format_args!("Shifting the headings by {}", heading_offset));
+    log::trace(format_args!(
+        "Processing the code blocks, hidden lines: {:?}, doc test attributes: {:?}",
+        options.hidden_lines, options.doctest_attributes
+    ));
+
+    let lines = process::process_docs_with(
+        lines,
+        heading_offset,
+        options.hidden_lines,
+        &options.fence_lang,
+        options.doctest_attributes,
+    );
+    let lines = match options.expand_tabs {
+        Some(width) => {
+            log::trace(format_args!(
+                "Expanding the tabs of the code blocks to {}",
+                width
+            ));
+            tabs::expand_tabs(lines, width)
+        }
+        None => lines,
+    };
+    let diagrams = options.diagrams.or(cargo.diagrams).unwrap_or_default();
+    if diagrams != DiagramBlocks::Passthrough {
+        log::trace(format_args!(
+            "Rewriting the diagrams and the math: {:?}",
+            diagrams
+        ));
+    }
+    let lines = diagrams::diagram_blocks(lines, diagrams);
+    // the warning blocks are converted before their HTML is sanitized
+    let lines = if options.github_alerts {
+        log::trace(format_args!(
+            "Converting the warning blocks into GitHub alerts"
+        ));
+        alerts::github_alerts(lines)
+    } else {
+        lines
+    };
+    if options.sanitize_html != SanitizeHtml::Passthrough {
+        log::trace(format_args!(
+            "Sanitizing the HTML: {:?}",
+            options.sanitize_html
+        ));
+    }
+    let lines = html::sanitize_html(lines, options.sanitize_html);
+
+    let docs = links::DocsRs {
+        crate_name: cargo.name.clone(),
+        version: cargo.version.clone(),
+        lib_name: lib_name(cargo),
+    };
+    // unpublished crates have no documentation on docs.rs
+    let docs = Some(docs).filter(|_| cargo.publish);
+    if options.intra_doc_links != IntraDocLinks::Keep {
+        log::trace(format_args!(
+            "Rewriting the intra-doc links: {:?}",
+            options.intra_doc_links
+        ));
+    }
+    let lines = links::rewrite_intra_doc_links(
+        lines,
+        options.intra_doc_links,
+        options.strip_unresolved_links,
+        docs.as_ref(),
+    );
+
+    let lines = match (source_dir, project_root) {
+        (Some(source_dir), Some(project_root)) => {
+            let output_dir = options.output_dir.as_deref().unwrap_or(project_root);
+            let (lines, copies) =
+                relative::rebase_assets(lines, source_dir, output_dir, options.copy_assets);
+            asset_copies.extend(copies);
+            lines
+        }
+        _ => lines,
+    };
+
+    // the relative links of an mdBook chapter would point into the book, not the repository
+    let absolute = options.absolute_links
+        || (options.output_format == OutputFormat::Mdbook && cargo.repository.is_some());
+    let lines = match absolute {
+        true => {
+            log::trace(format_args!(
+                "Rewriting the relative links into absolute URLs"
+            ));
+            absolute_links(project_root, lines, cargo)?
+        }
+        false => lines,
+    };
+
+    let lines = match options.smart_punctuation {
+        true => {
+            log::trace(format_args!("Converting the punctuation"));
+            punctuation::smart_punctuation(lines)
+        }
+        false => lines,
+    };
+
+    if options.emoji != Emoji::Keep {
+        log::trace(format_args!("Converting the emoji: {:?}", options.emoji));
+    }
+    let lines = emoji::convert_emoji(lines, options.emoji);
+
+    // the relative links are resolved by crates.io from the repository, when there is one
+    let lines = match options.crates_io {
+        true => {
+            log::trace(format_args!("Rewriting the docs for crates.io"));
+            crates_io::crates_io_safe(lines, cargo.repository.is_some())
+        }
+        false => lines,
+    };
+
+    // the reference links are resolved with the definitions of these docs only, so they do not
+    // collide with the labels of other docs, like the docs of modules
+    Ok(match options.link_style {
+        LinkStyle::Keep => lines,
+        LinkStyle::Inline | LinkStyle::Reference => {
+            log::trace(format_args!("Inlining the reference links"));
+            references::inline_references(lines)
+        }
+    })
+}
+
+/// Rewrite the relative links into absolute URLs to the `repository` set in `Cargo.toml`
+fn absolute_links(
+    project_root: Option<&Path>,
+    lines: Vec<String>,
+    cargo: &config::Manifest,
+) -> Result<Vec<String>, Error> {
+    let repository = cargo.repository.as_ref().ok_or(Error::MissingRepository)?;
+    let branch = cargo.repository_branch.as_deref().unwrap_or("HEAD");
+    // the links are relative to the project root, where the readme is, taken for the root of the
+    // repository when it is not known
+    let repository_root = project_root.and_then(|project_root| {
+        config::project::find_repository_root(project_root).map(|root| (project_root, root))
+    });
+    let path_in_repository = match repository_root {
+        Some((project_root, repository_root)) => {
+            let project_root = project_root
+                .canonicalize()
+                .map_err(|e| Error::io(project_root, e))?;
+            relative::relative_path(&repository_root, &project_root)
+        }
+        None => PathBuf::new(),
+    };
+
+    Ok(relative::absolute_links(
+        lines,
+        repository,
+        branch,
+        &path_in_repository,
+    ))
+}
+
+/// Get the name of the library target, as used in paths
+fn lib_name(cargo: &config::Manifest) -> String {
+    cargo
+        .lib
+        .as_ref()
+        .and_then(|lib| lib.name.clone())
+        .unwrap_or_else(|| cargo.name.replace('-', "_"))
+}
+
+/// Render the readme, optionally using a template
+///
+/// The manual sections of the existing readme, if any, are carried over to the docs first.
+fn render<T: Read>(
+    template: Option<&mut T>,
+    readme: String,
+    cargo: &config::Manifest,
+    options: &ReadmeOptions,
+) -> Result<String, Error> {
+    let readme = options.hooks.run_before_template(readme);
 
     // get template from file
     let template = if let Some(template) = template {
@@ -30,19 +944,173 @@ pub fn generate_readme<T: Read>(
         None
     };
 
-    // get manifest from Cargo.toml
-    let cargo = config::get_manifest(project_root)?;
+    let render = |template, readme| {
+        template::render(
+            template,
+            readme,
+            cargo,
+            options.add_title,
+            options.always_add_title,
+            options.add_badges,
+            options.add_license,
+        )
+    };
+
+    let readme = match options.existing_readme {
+        Some(ref existing) => {
+            // leave out what the template adds around the docs, like the title or the license
+            let placeholder = "\u{0}";
+            let rendered = render(template.clone(), placeholder.to_owned())?;
+            let (prefix, suffix) = rendered.split_once(placeholder).unwrap_or_default();
+            let existing = existing.trim();
+            let existing = existing.strip_prefix(prefix.trim()).unwrap_or(existing);
+            let existing = existing.strip_suffix(suffix.trim()).unwrap_or(existing);
+
+            manual::preserve_manual_sections(existing, &readme)
+        }
+        None => readme,
+    };
 
-    template::render(template, readme, &cargo, add_title, add_badges, add_license)
+    match template {
+        Some(_) => log::trace(format_args!("Rendering the template")),
+        None => log::trace(format_args!(
+            "Rendering without template, with title: {}, badges: {}, license: {}",
+            options.add_title, options.add_badges, options.add_license
+        )),
+    }
+    let mut readme = render(template, readme)?;
+    if options.link_style == LinkStyle::Reference {
+        log::trace(format_args!("Collecting the links as reference links"));
+        readme = references::reference_links(&readme);
+    }
+    if options.normalize_markdown {
+        log::trace(format_args!("Normalizing the markdown syntax"));
+        readme = normalize::normalize_markdown(&readme);
+    }
+    if let Some(width) = options.wrap {
+        log::trace(format_args!("Wrapping the paragraphs at {} columns", width));
+        readme = wrap::wrap_paragraphs(&readme, width);
+    }
+    if options.strip_trailing_whitespace {
+        log::trace(format_args!("Stripping the trailing whitespace"));
+        readme = whitespace::strip_trailing_whitespace(&readme);
+    }
+    if options.collapse_blank_lines {
+        log::trace(format_args!("Collapsing the blank lines"));
+        readme = whitespace::collapse_blank_lines(&readme);
+    }
+
+    Ok(options.hooks.run_after_template(readme))
 }
 
 /// Load a template String from a file
-fn get_template_string<T: Read>(template: &mut T) -> Result<String, String> {
+fn get_template_string<T: Read>(template: &mut T) -> Result<String, Error> {
     let mut template_string = String::new();
-    match template.read_to_string(&mut template_string) {
-        Err(e) => return Err(format!("Error: {}", e)),
-        _ => {}
+    template
+        .read_to_string(&mut template_string)
+        .map_err(|source| Error::Io { path: None, source })?;
+
+    Ok(line_endings::normalize_line_endings(&template_string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate_readme_from_str, FrontMatter, ReadmeOptions};
+    use crate::Error;
+
+    const CARGO_TOML: &str = r#"
+[package]
+name = "memory"
+version = "1.2.3"
+license = "MIT"
+"#;
+
+    #[test]
+    fn generate_in_memory() {
+        let source =
+            "//! Docs in memory.\n//!\n//! # Usage\n//!\n//! ```\n//! let a = 1;\n//! ```\n";
+
+        assert_eq!(
+            generate_readme_from_str(source, CARGO_TOML, None, &ReadmeOptions::default())
+                .unwrap()
+                .text,
+            "# memory\n\nDocs in memory.\n\n## Usage\n\n```rust\nlet a = 1;\n```\n\nLicense: MIT"
+        );
     }
 
-    Ok(template_string)
+    #[test]
+    fn generate_in_memory_with_template() {
+        let template = "{{crate}} {{version}}\n\n{{readme}}\n\n{{installation}}";
+
+        assert_eq!(
+            generate_readme_from_str(
+                "//! Docs.",
+                CARGO_TOML,
+                Some(template),
+                &ReadmeOptions::default()
+            )
+            .unwrap()
+            .text,
+            "memory 1.2.3\n\nDocs.\n\n```sh\ncargo add memory@1.2.3\n```"
+        );
+    }
+
+    #[test]
+    fn generate_in_memory_warnings_and_date() {
+        let cargo_toml =
+            "[package]\nname = \"memory\"\nversion = \"1.2.3\"\ndescription = \"Kept.\"";
+        let options = ReadmeOptions {
+            front_matter: Some(FrontMatter::Toml),
+            date: Some("2024-01-31".to_owned()),
+            add_license: false,
+            ..Default::default()
+        };
+        let readme = generate_readme_from_str("fn main() {}", cargo_toml, None, &options).unwrap();
+
+        assert_eq!(
+            readme.text,
+            "+++\ntitle = \"memory\"\ndescription = \"Kept.\"\ndate = 2024-01-31\ntags = []\n\
+             generator = \"cargo-readme\"\n+++\n\n# memory\n\nKept."
+        );
+        assert_eq!(
+            readme.warnings,
+            vec!["no doc comments found, using `package.description` instead"]
+        );
+    }
+
+    #[test]
+    fn generate_in_memory_without_files() {
+        let options = ReadmeOptions {
+            source_dir: Some("src".into()),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            generate_readme_from_str("//! Docs.", CARGO_TOML, None, &options),
+            Err(Error::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn generate_in_memory_errors() {
+        let options = ReadmeOptions::default();
+
+        assert!(matches!(
+            generate_readme_from_str("//! Docs.", "[package", None, &options),
+            Err(Error::ManifestParse(_))
+        ));
+        assert!(matches!(
+            generate_readme_from_str("//! Docs.", CARGO_TOML, Some("# {{crate}}"), &options),
+            Err(Error::TemplateMissingReadmeTag)
+        ));
+        assert!(matches!(
+            generate_readme_from_str(
+                "//! Docs.",
+                "[package]\nname = \"memory\"\nversion = \"1.2.3\"",
+                Some("{{readme}}\n\n{{license}}"),
+                &options
+            ),
+            Err(Error::MissingLicense)
+        ));
+    }
 }
@@ -0,0 +1,165 @@
+//! Find the public modules declared with `pub mod name;`
+//!
+//! The modules are followed recursively from the crate root, so their docs can be added to the
+//! readme. As in rustc, the file of module `name` declared in `dir` is `dir/name.rs` or
+//! `dir/name/mod.rs`, and its own submodules are in `dir/name/`. The file of a module with a
+//! `#[path = "..."]` attribute is relative to the directory of the file declaring it, and its own
+//! submodules are next to it.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::log;
+use crate::Error;
+
+lazy_static! {
+    // Is this the declaration of a public module in another file?
+    static ref RE_PUB_MOD: Regex = Regex::new(r"^pub\s+mod\s+(?:r#)?(?P<name>\w+)\s*;").unwrap();
+    // Is this a `#[path = "..."]` attribute, giving the file of the next module?
+    static ref RE_PATH_ATTR: Regex = Regex::new(r#"^#\[\s*path\s*=\s*"(?P<path>[^"]*)"\s*\]"#).unwrap();
+}
+
+/// A public module and the file it is defined in
+#[derive(Debug, PartialEq)]
+pub struct Module {
+    /// Path of the module from the source file, like `foo::bar`
+    pub path: String,
+    /// Source file of the module
+    pub file: PathBuf,
+}
+
+/// Find the public modules declared in `source` and in their own files
+///
+/// The `#[path]` attributes of `source` are relative to `source_dir`, the directory of the source
+/// file, and its other modules are looked for in `module_dir`. The submodules of a module follow
+/// the module itself.
+pub fn find_modules(
+    source: &str,
+    source_dir: &Path,
+    module_dir: &Path,
+) -> Result<Vec<Module>, Error> {
+    let mut modules = Vec::new();
+    collect_modules(source, source_dir, module_dir, "", &mut modules)?;
+
+    Ok(modules)
+}
+
+/// Collect the modules declared in `source`, as in `find_modules`
+fn collect_modules(
+    source: &str,
+    file_dir: &Path,
+    module_dir: &Path,
+    parent: &str,
+    modules: &mut Vec<Module>,
+) -> Result<(), Error> {
+    for (name, path_attr) in declared_modules(source) {
+        let (file, submodule_dir) = match path_attr {
+            // as in rustc, the submodules of a module loaded with `#[path]` are next to its file
+            Some(path) => {
+                let file = file_dir.join(path);
+                let dir = file.parent().unwrap_or(file_dir).to_path_buf();
+                (file, dir)
+            }
+            None => (module_file(module_dir, &name)?, module_dir.join(&name)),
+        };
+        log::read(&file);
+        let content = fs::read(&file)
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            .map_err(|e| Error::io(&file, e))?;
+        let path = if parent.is_empty() {
+            name.clone()
+        } else {
+            format!("{}::{}", parent, name)
+        };
+
+        let file_dir = file.parent().unwrap_or(file_dir).to_path_buf();
+        modules.push(Module {
+            path: path.clone(),
+            file,
+        });
+        collect_modules(&content, &file_dir, &submodule_dir, &path, modules)?;
+    }
+
+    Ok(())
+}
+
+/// Get the names of the public modules declared with `pub mod name;` in `source`, along with
+/// their `#[path = "..."]` attribute
+fn declared_modules(source: &str) -> Vec<(String, Option<String>)> {
+    let mut modules = Vec::new();
+    let mut path_attr = None;
+
+    for line in source.lines().map(str::trim) {
+        if let Some(cap) = RE_PATH_ATTR.captures(line) {
+            path_attr = Some(cap["path"].to_owned());
+        } else if let Some(cap) = RE_PUB_MOD.captures(line) {
+            modules.push((cap["name"].to_owned(), path_attr.take()));
+        } else if !(line.is_empty() || line.starts_with("#[") || line.starts_with("//")) {
+            // the attribute belongs to another item
+            path_attr = None;
+        }
+    }
+
+    modules
+}
+
+/// Find the file of module `name` declared in `dir`
+fn module_file(dir: &Path, name: &str) -> Result<PathBuf, Error> {
+    let file = dir.join(format!("{}.rs", name));
+    if file.is_file() {
+        return Ok(file);
+    }
+    let file = dir.join(name).join("mod.rs");
+    if file.is_file() {
+        return Ok(file);
+    }
+
+    Err(Error::Module {
+        name: name.to_owned(),
+        dir: dir.to_path_buf(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::declared_modules;
+
+    #[test]
+    fn find_public_modules() {
+        let source = "
+            //! Crate docs
+            pub mod alpha;
+            mod private;
+            pub mod r#type;
+            pub(crate) mod internal;
+            pub mod inline {}
+            // pub mod commented;
+        ";
+
+        let names: Vec<String> = declared_modules(source).into_iter().map(|m| m.0).collect();
+        assert_eq!(names, ["alpha", "type"]);
+    }
+
+    #[test]
+    fn find_path_attribute() {
+        let source = r#"
+            #[path = "other/file.rs"]
+            /// Doc comment
+            #[cfg(feature = "foo")]
+            pub mod foo;
+            #[path = "private.rs"]
+            mod private;
+            pub mod bar;
+        "#;
+
+        assert_eq!(
+            declared_modules(source),
+            [
+                ("foo".to_owned(), Some("other/file.rs".to_owned())),
+                ("bar".to_owned(), None),
+            ]
+        );
+    }
+}
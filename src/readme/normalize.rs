@@ -0,0 +1,393 @@
+//! Normalize the markdown syntax of the output, whatever the style of the doc comments
+//!
+//! The same markdown can be written in several ways, which linters like markdownlint ask to be
+//! consistent. The headings are written with `#`, without closing sequence, the setext headings
+//! underlined with `===` or `---` included. The thematic breaks are written `---`, the bullet list
+//! markers `-`, the ordered list markers `1.`, the code fences with backticks, and the emphasis and
+//! strong emphasis with `*` and `**`. The code blocks, HTML blocks, code spans, link targets and
+//! HTML tags are left untouched.
+
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+use super::fences::{code_fence, CodeBlocks, LineKind};
+use super::links::code_span_len;
+
+lazy_static! {
+    // an ATX heading, like `##  Usage ##`
+    static ref RE_ATX: Regex =
+        Regex::new(r"^ {0,3}(?P<level>#{1,6})(?:[ \t]+(?P<text>.*?))?(?:[ \t]+#+)?[ \t]*$")
+            .unwrap();
+    // the underline of a setext heading, like `=====` or `-----`
+    static ref RE_SETEXT: Regex = Regex::new(r"^ {0,3}(?P<underline>=+|-+)[ \t]*$").unwrap();
+    // a bullet list item marked with `*` or `+`
+    static ref RE_BULLET: Regex = Regex::new(r"^(?P<indent>\s*)[*+](?P<space>[ \t]+|$)").unwrap();
+    // an ordered list item marked with `)`, like `1)`
+    static ref RE_ORDERED: Regex =
+        Regex::new(r"^(?P<indent>\s*)(?P<number>\d{1,9})\)(?P<space>[ \t]+|$)").unwrap();
+    // the parts of a line that are not prose: link targets, autolinks and HTML tags
+    static ref RE_PROTECTED: Regex =
+        Regex::new(r"\]\([^)]*\)|<[A-Za-z/!][^<>]*>|[a-z]+://[^\s)>]+").unwrap();
+    // the start of an HTML block ended by a blank line, like `<div class="warning">`
+    static ref RE_HTML_BLOCK: Regex = Regex::new(
+        r"(?i)^ {0,3}</?(?:address|article|aside|base|basefont|blockquote|body|caption|center|col|colgroup|dd|details|dialog|dir|div|dl|dt|fieldset|figcaption|figure|footer|form|frame|frameset|h[1-6]|head|header|hr|html|iframe|legend|li|link|main|menu|menuitem|nav|noframes|ol|optgroup|option|p|param|search|section|summary|table|tbody|td|tfoot|th|thead|title|tr|track|ul)(?:[ \t]|/?>|$)"
+    )
+    .unwrap();
+    // a line of a single tag, starting an HTML block unless in a paragraph, like `<img src="a.png">`
+    static ref RE_HTML_TAG_LINE: Regex = Regex::new(
+        r"^ {0,3}(?:<[A-Za-z][A-Za-z0-9-]*(?:\s+[^<>]*)?/?>|</[A-Za-z][A-Za-z0-9-]*\s*>)\s*$"
+    )
+    .unwrap();
+    // strong emphasis with underscores, like `__text__`
+    static ref RE_STRONG: Regex =
+        Regex::new(r"(?P<pre>^|[^\w\\_])__(?P<text>[^_\s](?:[^_]*[^_\s])?)__(?P<post>$|[^\w_])")
+            .unwrap();
+    // emphasis with underscores, like `_text_`
+    static ref RE_EMPHASIS: Regex =
+        Regex::new(r"(?P<pre>^|[^\w\\_])_(?P<text>[^_\s](?:[^_]*[^_\s])?)_(?P<post>$|[^\w_])")
+            .unwrap();
+}
+
+/// Normalize the markdown syntax of `text`
+pub fn normalize_markdown(text: &str) -> String {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let mut code = CodeBlocks::default();
+    // the fence replacing the one of the code block being read
+    let mut replacement = String::new();
+    // how the HTML block being read ends
+    let mut html: Option<HtmlEnd> = None;
+    let mut result: Vec<String> = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        match html {
+            // the blank line ending the block is not part of it
+            Some(HtmlEnd::Blank) if line.trim().is_empty() => html = None,
+            Some(end) => {
+                if end_of_html(line, end) {
+                    html = None;
+                }
+                result.push(line.to_string());
+                continue;
+            }
+            None => {}
+        }
+
+        match code.kind(line) {
+            LineKind::Open => {
+                let fence = code.fence().unwrap_or_default();
+                replacement = fence_replacement(fence, &lines[i + 1..]);
+                result.push(line.replacen(fence, &replacement, 1));
+                continue;
+            }
+            LineKind::Close => {
+                let fence = code_fence(line).unwrap_or_default();
+                result.push(line.replacen(&fence, &replacement, 1));
+                continue;
+            }
+            LineKind::Code | LineKind::Indented => {
+                result.push(line.to_string());
+                continue;
+            }
+            LineKind::Text => {}
+        }
+
+        let paragraph = result.last().is_some_and(|l| is_paragraph(l));
+        if let Some(end) = html_block(line, paragraph) {
+            html = Some(end).filter(|&end| !end_of_html(line, end));
+            result.push(line.to_string());
+            continue;
+        }
+
+        // a setext heading underlines a paragraph, which may span several lines
+        if let Some(cap) = RE_SETEXT.captures(line) {
+            let start = result
+                .iter()
+                .rposition(|l| l.trim().is_empty())
+                .map_or(0, |blank| blank + 1);
+            if start < result.len() && result[start..].iter().all(|l| is_paragraph(l)) {
+                let level = match cap["underline"].starts_with('=') {
+                    true => "#",
+                    false => "##",
+                };
+                let text: Vec<&str> = result[start..].iter().map(|l| l.trim()).collect();
+                let heading = format!("{} {}", level, text.join(" "));
+                result.truncate(start);
+                result.push(heading);
+                continue;
+            }
+        }
+
+        let previous = result.last().map(String::as_str);
+
+        if is_thematic_break(line) {
+            // `---` under a paragraph would make it a heading
+            if previous.is_some_and(|l| !l.trim().is_empty()) {
+                result.push(String::new());
+            }
+            result.push("---".to_owned());
+        } else if let Some(cap) = RE_ATX.captures(line) {
+            let text = cap.name("text").map_or("", |t| t.as_str());
+            result.push(
+                format!("{} {}", &cap["level"], inline(text))
+                    .trim_end()
+                    .to_owned(),
+            );
+        } else {
+            let line = RE_BULLET.replace(line, "$indent-$space");
+            let line = RE_ORDERED.replace(&line, "$indent$number.$space");
+            result.push(inline(&line));
+        }
+    }
+
+    result.join("\n")
+}
+
+/// How an HTML block ends
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum HtmlEnd {
+    /// Before a blank line
+    Blank,
+    /// At the line containing the text, like `-->`
+    Line(&'static str),
+}
+
+/// Get how the HTML block started by `line` ends, if it starts one, as in CommonMark
+///
+/// A line of a single tag, like `<img src="a.png">`, does not start a block after a `paragraph`
+/// line.
+fn html_block(line: &str, paragraph: bool) -> Option<HtmlEnd> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let start = line[indent..].to_lowercase();
+
+    for (tag, end) in [
+        ("<script", "</script>"),
+        ("<pre", "</pre>"),
+        ("<style", "</style>"),
+        ("<textarea", "</textarea>"),
+    ] {
+        let rest = start.strip_prefix(tag);
+        if rest.is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t', '>'])) {
+            return Some(HtmlEnd::Line(end));
+        }
+    }
+    if start.starts_with("<!--") {
+        return Some(HtmlEnd::Line("-->"));
+    }
+    if start.starts_with("<?") {
+        return Some(HtmlEnd::Line("?>"));
+    }
+    if start.starts_with("<![cdata[") {
+        return Some(HtmlEnd::Line("]]>"));
+    }
+    if start.starts_with("<!") && start[2..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return Some(HtmlEnd::Line(">"));
+    }
+
+    match RE_HTML_BLOCK.is_match(line) || (!paragraph && RE_HTML_TAG_LINE.is_match(line)) {
+        true => Some(HtmlEnd::Blank),
+        false => None,
+    }
+}
+
+/// Tell if `line` is the last one of an HTML block ending with `end`
+fn end_of_html(line: &str, end: HtmlEnd) -> bool {
+    match end {
+        HtmlEnd::Blank => false,
+        HtmlEnd::Line(end) => line.to_lowercase().contains(end),
+    }
+}
+
+/// Get the backtick fence replacing the opening `fence` of a code block, when it uses tildes
+///
+/// The fence is kept when the code has lines starting with backticks, which would close it.
+fn fence_replacement(fence: &str, rest: &[&str]) -> String {
+    if fence.starts_with('`') {
+        return fence.to_owned();
+    }
+    let code = rest.iter().take_while(|line| {
+        !code_fence(line).is_some_and(|f| f.starts_with(fence) && line.trim() == f)
+    });
+    match code
+        .into_iter()
+        .any(|line| line.trim_start().starts_with("```"))
+    {
+        true => fence.to_owned(),
+        false => "`".repeat(fence.len()),
+    }
+}
+
+/// Tell if `line` is a paragraph line, which could be the text of a setext heading
+fn is_paragraph(line: &str) -> bool {
+    let trimmed = line.trim();
+    !trimmed.is_empty()
+        && !line.starts_with("    ")
+        && !trimmed.starts_with(['#', '>', '<', '|'])
+        && !RE_BULLET.is_match(line)
+        && !RE_ORDERED.is_match(line)
+        && !line.trim_start().starts_with("- ")
+        && !is_thematic_break(line)
+}
+
+/// Tell if `line` is a thematic break, like `***`, `- - -` or `___`
+fn is_thematic_break(line: &str) -> bool {
+    if line.starts_with("    ") {
+        return false;
+    }
+    let marks: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    marks.len() >= 3
+        && ['*', '-', '_']
+            .iter()
+            .any(|mark| marks.chars().all(|c| c == *mark))
+}
+
+/// Normalize the emphasis of `line`, skipping its code spans
+fn inline(line: &str) -> String {
+    let mut converted = String::new();
+    let mut rest = line;
+
+    while let Some(pos) = rest.find('`') {
+        converted.push_str(&prose(&rest[..pos]));
+        let len = code_span_len(&rest[pos..]);
+        converted.push_str(&rest[pos..pos + len]);
+        rest = &rest[pos + len..];
+    }
+    converted.push_str(&prose(rest));
+
+    converted
+}
+
+/// Normalize the emphasis of `text`, which has no code spans, skipping its link targets and tags
+fn prose(text: &str) -> String {
+    let mut converted = String::new();
+    let mut pos = 0;
+    for m in RE_PROTECTED.find_iter(text) {
+        converted.push_str(&emphasis(&text[pos..m.start()]));
+        converted.push_str(m.as_str());
+        pos = m.end();
+    }
+    converted.push_str(&emphasis(&text[pos..]));
+
+    converted
+}
+
+/// Write the emphasis and strong emphasis of `text` with `*` instead of `_`
+fn emphasis(text: &str) -> String {
+    let text = RE_STRONG.replace_all(text, |cap: &Captures| {
+        format!("{}**{}**{}", &cap["pre"], &cap["text"], &cap["post"])
+    });
+    RE_EMPHASIS
+        .replace_all(&text, |cap: &Captures| {
+            format!("{}*{}*{}", &cap["pre"], &cap["text"], &cap["post"])
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_markdown;
+
+    #[test]
+    fn normalize_blocks() {
+        let markdown = "\
+Title
+=====
+
+Usage
+-----
+
+Some text,
+on two lines
+---
+
+##   Options ##
+
+* one
+  + two
+1) first
+
+***
+
+~~~rust
+let a = 1;
+~~~
+";
+
+        assert_eq!(
+            normalize_markdown(markdown),
+            "\
+# Title
+
+## Usage
+
+## Some text, on two lines
+
+## Options
+
+- one
+  - two
+1. first
+
+---
+
+```rust
+let a = 1;
+```
+"
+        );
+    }
+
+    #[test]
+    fn normalize_emphasis() {
+        assert_eq!(
+            normalize_markdown(
+                "Use __bold__, _italic_ and *kept*, not snake_case_name, `_code_` or \
+                 [a](https://a.com/_b_) <img src=\"_c_.png\">."
+            ),
+            "Use **bold**, *italic* and *kept*, not snake_case_name, `_code_` or \
+             [a](https://a.com/_b_) <img src=\"_c_.png\">."
+        );
+    }
+
+    #[test]
+    fn indented_code_is_left_alone() {
+        let text = "Some code:\n\n    * not a list\n    __not bold__\n\nDone.";
+
+        assert_eq!(normalize_markdown(text), text);
+    }
+
+    #[test]
+    fn html_blocks_are_left_alone() {
+        let text = "\
+<div class=\"warning\">
+* inside html
+__not bold__
+</div>
+
+<!-- a comment
++ on lines
+-->
+<pre>
+* kept
+
+1) kept
+</pre>
+
+* outside";
+
+        assert_eq!(
+            normalize_markdown(text),
+            text.replace("* outside", "- outside")
+        );
+    }
+
+    #[test]
+    fn inline_tag_in_paragraph() {
+        assert_eq!(
+            normalize_markdown("Some text\n<span>\n* item"),
+            "Some text\n<span>\n- item"
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Convert the processed readme from markdown to Org
+//!
+//! The headings start with `*` repeated by level, the code blocks become `SRC` blocks, or `EXAMPLE`
+//! blocks without language, the GitHub alerts, like `> [!NOTE]`, become special blocks and the other
+//! quotes become `QUOTE` blocks. The links and images become Org links, the images, like badges,
+//! being written as links to the image, which Org shows inline. The bullets are written `-`, the
+//! tables get an Org delimiter row, and the emphasis and code spans their Org markup. The raw HTML
+//! is left as it is.
+
+use super::markdown::{comment, indent, join_blocks, parse, Block, Inline};
+
+/// Convert the markdown `readme` to Org
+pub fn markdown_to_org(readme: &str) -> String {
+    blocks(&parse(readme), false).join("\n").trim().to_owned()
+}
+
+/// Convert the markdown `blocks` to the lines of Org, with a blank line between them unless
+/// `tight`
+fn blocks(blocks: &[Block], tight: bool) -> Vec<String> {
+    join_blocks(blocks.iter().map(block), tight)
+}
+
+/// Convert a markdown block to the lines of Org
+fn block(block: &Block) -> Vec<String> {
+    match block {
+        Block::Heading { level, text } => vec![format!("{} {}", "*".repeat(*level), inline(text))],
+        Block::Paragraph(text) => lines(&inline(text)),
+        Block::Code { language, code } => {
+            let (begin, end) = match language {
+                Some(language) => (format!("#+BEGIN_SRC {}", language), "#+END_SRC"),
+                None => ("#+BEGIN_EXAMPLE".to_owned(), "#+END_EXAMPLE"),
+            };
+            let mut lines = vec![begin];
+            // the lines that Org would read as headings or keywords are escaped with a comma
+            lines.extend(code.lines().map(|line| {
+                match line.starts_with('*') || line.trim_start().starts_with("#+") {
+                    true => format!(",{}", line),
+                    false => line.to_owned(),
+                }
+            }));
+            lines.push(end.to_owned());
+            lines
+        }
+        Block::Quote { alert, blocks } => {
+            let kind = alert.map_or("QUOTE", |alert| alert.name());
+            let mut lines = vec![format!("#+BEGIN_{}", kind)];
+            lines.extend(self::blocks(blocks, false));
+            lines.push(format!("#+END_{}", kind));
+            lines
+        }
+        Block::List {
+            start,
+            tight,
+            items,
+        } => join_blocks(
+            items.iter().enumerate().map(|(i, item)| {
+                let marker = match start {
+                    Some(start) => format!("{}. ", start + i as u64),
+                    None => "- ".to_owned(),
+                };
+                indent(self::blocks(item, *tight), marker.len(), Some(&marker))
+            }),
+            *tight,
+        ),
+        Block::Table { header, rows, .. } => {
+            let rows: Vec<Vec<String>> = std::iter::once(header)
+                .chain(rows)
+                .map(|cells| cells.iter().map(|cell| inline(cell)).collect())
+                .collect();
+            let row = |cells: &[String]| format!("| {} |", cells.join(" | "));
+            // the delimiter row spans the widest cell of each column
+            let columns: Vec<String> = (0..header.len())
+                .map(|j| {
+                    let width = rows
+                        .iter()
+                        .filter_map(|cells| cells.get(j))
+                        .map(|cell| cell.chars().count())
+                        .max()
+                        .unwrap_or(0);
+                    "-".repeat(width + 2)
+                })
+                .collect();
+            let mut lines = vec![row(&rows[0]), format!("|{}|", columns.join("+"))];
+            lines.extend(rows[1..].iter().map(|cells| row(cells)));
+            lines
+        }
+        Block::Html(html) => match comment(html) {
+            Some(text) => vec![format!("# {}", text)],
+            None => lines(html),
+        },
+        Block::Rule => vec!["-----".to_owned()],
+    }
+}
+
+/// Split `text` into lines
+fn lines(text: &str) -> Vec<String> {
+    text.lines().map(ToOwned::to_owned).collect()
+}
+
+/// Convert the inline markup of `text`: the code spans, the emphasis and the links
+fn inline(text: &[Inline]) -> String {
+    text.iter()
+        .map(|inline| match inline {
+            Inline::Text(text) | Inline::Html(text) => text.clone(),
+            // a code span with a `~` is written verbatim instead
+            Inline::Code(code) => match code.contains('~') {
+                true => format!("={}=", code),
+                false => format!("~{}~", code),
+            },
+            Inline::Emphasis(text) => format!("/{}/", self::inline(text)),
+            Inline::Strong(text) => format!("*{}*", self::inline(text)),
+            Inline::Strikethrough(text) => format!("+{}+", self::inline(text)),
+            Inline::Link { target, text, .. } => match text.as_slice() {
+                // a linked image is a link whose description is the image
+                [Inline::Image { source, .. }] => format!("[[{}][{}]]", target, source),
+                [] => format!("[[{}]]", target),
+                text => format!("[[{}][{}]]", target, self::inline(text)),
+            },
+            Inline::Image { source, .. } => format!("[[{}]]", source),
+            Inline::SoftBreak | Inline::HardBreak => "\n".to_owned(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::markdown_to_org;
+
+    #[test]
+    fn convert_readme() {
+        let readme = "\
+[![Crates.io](https://img.shields.io/crates/v/a.svg)](https://crates.io/crates/a)
+
+# my-crate
+
+Use `cargo add`, **now** and *here*, see [the guide][guide] and [the changelog](CHANGELOG.md).
+
+> [!WARNING]
+> Not stable.
+
+> Quoted.
+
+## Usage
+
+```rust,no_run
+# not a heading
+*ptr = 1;
+```
+
+* one
+  + nested
+1. first
+
+| a | b |
+| - | - |
+| 1 | 2 |
+
+---
+
+[guide]: https://example.com/guide";
+
+        assert_eq!(
+            markdown_to_org(readme),
+            "\
+[[https://crates.io/crates/a][https://img.shields.io/crates/v/a.svg]]
+
+* my-crate
+
+Use ~cargo add~, *now* and /here/, see [[https://example.com/guide][the guide]] and \
+[[CHANGELOG.md][the changelog]].
+
+#+BEGIN_WARNING
+Not stable.
+#+END_WARNING
+
+#+BEGIN_QUOTE
+Quoted.
+#+END_QUOTE
+
+** Usage
+
+#+BEGIN_SRC rust
+# not a heading
+,*ptr = 1;
+#+END_SRC
+
+- one
+  - nested
+
+1. first
+
+| a | b |
+|---+---|
+| 1 | 2 |
+
+-----"
+        );
+    }
+
+    #[test]
+    fn code_block_without_language() {
+        assert_eq!(
+            markdown_to_org("```\n#+TITLE: code\n```"),
+            "#+BEGIN_EXAMPLE\n,#+TITLE: code\n#+END_EXAMPLE"
+        );
+    }
+}
@@ -1,80 +1,280 @@
 //! Transform code blocks from rustdoc into markdown
 //!
 //! Rewrite code block start tags, changing rustdoc into equivalent in markdown:
-//! - "```", "```no_run", "```ignore" and "```should_panic" are converted to "```rust"
+//! - "```", "```no_run", "```ignore", "```rust,should_panic" and any other code block with only
+//!   rustdoc attributes are converted to "```rust", or to the configured fence language, which
+//!   may keep attributes like `no_run` as "```rust,no_run" or as an HTML comment
+//! - "```text" is converted to "```"
 //! - markdown heading are indentend to be one level lower, so the crate name is at the top level
+//!
+//! Code blocks may be indented, like inside list items, and keep their indentation. As in
+//! CommonMark, a code block is closed by a fence of the same character (backtick or tilde) that is
+//! at least as long as the opening one. The display math blocks, between `$$` lines, are left as
+//! they are.
 
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::iter::{IntoIterator, Iterator};
 
 lazy_static! {
-    // Is this code block rust?
-    static ref RE_CODE_RUST: Regex = Regex::new(r"^(?P<delimiter>`{3,4}|~{3,4})(?:rust|(?:(?:rust,)?(?:no_run|ignore|should_panic)))?$").unwrap();
-    // Is this code block just text?
-    static ref RE_CODE_TEXT: Regex = Regex::new(r"^(?P<delimiter>`{3,4}|~{3,4})text$").unwrap();
-    // Is this code block a language other than rust?
-    static ref RE_CODE_OTHER: Regex = Regex::new(r"^(?P<delimiter>`{3,4}|~{3,4})\w[\w,\+]*$").unwrap();
+    // Is this the start of a code block?
+    static ref RE_CODE_START: Regex = Regex::new(r"^(?P<indent>\s*)(?:(?P<backticks>`{3,})(?P<info>[^`]*)|(?P<tildes>~{3,})(?P<tilde_info>.*)|(?P<dollars>\$\$)\s*)$").unwrap();
+    // Is this a markdown heading? Lines like `#include <stdio.h>` are not headings
+    static ref RE_HEADING: Regex = Regex::new(r"^ {0,3}#{1,6}(?:[ \t]|$)").unwrap();
+    // Is this a rustdoc error code, like `E0123`?
+    static ref RE_ERROR_CODE: Regex = Regex::new(r"^E\d{4}$").unwrap();
 }
 
-/// Process and concatenate the doc lines into a single String
+/// Process the doc lines
 ///
-/// The processing transforms doc tests into regular rust code blocks and optionally indent the
-/// markdown headings in order to leave the top heading to the crate name
-pub fn process_docs<S: Into<String>, L: Into<Vec<S>>>(
+/// The processing transforms doc tests into regular rust code blocks, handling their hidden lines as
+/// given by `hidden_lines`, and shifts the markdown headings by `heading_offset` levels, usually in
+/// order to leave the top heading to the crate name. The rust code blocks are opened with the
+/// `fence_lang` info string, like `rust`, or with a bare fence when it is empty, and their
+/// attributes, like `no_run`, are handled as given by `doctest_attributes`
+pub fn process_docs_with<S: Into<String>, L: Into<Vec<S>>>(
     lines: L,
-    indent_headings: bool,
+    heading_offset: isize,
+    hidden_lines: HiddenLines,
+    fence_lang: &str,
+    doctest_attributes: DoctestAttributes,
 ) -> Vec<String> {
-    lines.into().into_iter().process_docs(indent_headings)
+    let mut p = Processor::new(heading_offset, hidden_lines, fence_lang, doctest_attributes);
+    lines
+        .into()
+        .into_iter()
+        .flat_map(|line| p.process_line(line.into()))
+        .collect()
+}
+
+/// What to do with the hidden lines of doc tests, like `# use std::fmt;`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum HiddenLines {
+    /// Remove the hidden lines, as rustdoc does
+    #[default]
+    Hide,
+    /// Keep the hidden lines as they are, with their `#` mark
+    Show,
+    /// Keep the hidden lines without their `#` mark
+    Strip,
+}
+
+/// What to do with the attributes of doc tests telling how they are run, like `no_run`, `ignore`
+/// or `should_panic`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum DoctestAttributes {
+    /// Remove the attributes, the code block is only marked as rust
+    #[default]
+    Drop,
+    /// Keep the attributes in the info string, like `rust,no_run`
+    Keep,
+    /// Keep the attributes in an HTML comment before the code block, like `<!-- no_run -->`
+    Comment,
 }
 
 pub struct Processor {
     section: Section,
-    indent_headings: bool,
+    heading_offset: isize,
+    hidden_lines: HiddenLines,
+    fence_lang: String,
+    doctest_attributes: DoctestAttributes,
     delimiter: Option<String>,
+    indent: String,
 }
 
 impl Processor {
-    pub fn new(indent_headings: bool) -> Self {
+    pub fn new(
+        heading_offset: isize,
+        hidden_lines: HiddenLines,
+        fence_lang: &str,
+        doctest_attributes: DoctestAttributes,
+    ) -> Self {
         Processor {
             section: Section::None,
-            indent_headings: indent_headings,
+            heading_offset,
+            hidden_lines,
+            fence_lang: fence_lang.to_owned(),
+            doctest_attributes,
             delimiter: None,
+            indent: String::new(),
         }
     }
 
-    pub fn process_line(&mut self, mut line: String) -> Option<String> {
-        // Skip lines that should be hidden in docs
-        if self.section == Section::CodeRust && line.starts_with("# ") {
-            return None;
+    /// Process a doc line into the lines of the output, which may be none or more than one
+    pub fn process_line(&mut self, mut line: String) -> Vec<String> {
+        // Skip lines that should be hidden in docs, and unescape "##" lines
+        if self.section == Section::CodeRust && !self.is_closing_fence(&line) {
+            let trimmed = line.trim();
+            if trimmed.starts_with("##") {
+                return vec![line.replacen("##", "#", 1)];
+            }
+            if trimmed == "#" || trimmed.starts_with("# ") || trimmed.starts_with("#\t") {
+                return match self.hidden_lines {
+                    HiddenLines::Hide => vec![],
+                    HiddenLines::Show => vec![line],
+                    HiddenLines::Strip => vec![strip_hidden_mark(&line)],
+                };
+            }
         }
 
-        // indent heading when outside code
-        if self.indent_headings && self.section == Section::None && line.starts_with("#") {
-            line.insert(0, '#');
+        // shift heading when outside code
+        if self.heading_offset != 0 && self.section == Section::None && RE_HEADING.is_match(&line) {
+            line = shift_heading(&line, self.heading_offset);
         } else if self.section == Section::None {
             let l = line.clone();
-            if let Some(cap) = RE_CODE_RUST.captures(&l) {
-                self.section = Section::CodeRust;
-                self.delimiter = cap.name("delimiter").map(|x| x.as_str().to_owned());
-                line = format!("{}rust", self.delimiter.as_ref().unwrap());
-            } else if let Some(cap) = RE_CODE_TEXT.captures(&l) {
-                self.section = Section::CodeOther;
-                self.delimiter = cap.name("delimiter").map(|x| x.as_str().to_owned());
-                line = self.delimiter.clone().unwrap();
-            } else if let Some(cap) = RE_CODE_OTHER.captures(&l) {
-                self.section = Section::CodeOther;
-                self.delimiter = cap.name("delimiter").map(|x| x.as_str().to_owned());
+            if let Some(cap) = RE_CODE_START.captures(&l) {
+                self.start_code_block(&cap);
+                let delimiter = self.delimiter.as_ref().unwrap();
+                let info = cap
+                    .name("info")
+                    .or_else(|| cap.name("tilde_info"))
+                    .map_or("", |x| x.as_str());
+
+                let lang = match cap.name("dollars") {
+                    Some(_) => Lang::Other,
+                    None => code_block_lang(info),
+                };
+                match lang {
+                    Lang::Rust => {
+                        self.section = Section::CodeRust;
+                        let attributes = run_attributes(info);
+                        match self.doctest_attributes {
+                            DoctestAttributes::Keep if !attributes.is_empty() => {
+                                let info: Vec<&str> = Some(self.fence_lang.as_str())
+                                    .filter(|lang| !lang.is_empty())
+                                    .into_iter()
+                                    .chain(attributes)
+                                    .collect();
+                                line = format!("{}{}{}", self.indent, delimiter, info.join(","));
+                            }
+                            DoctestAttributes::Comment if !attributes.is_empty() => {
+                                return vec![
+                                    format!("{}<!-- {} -->", self.indent, attributes.join(", ")),
+                                    format!("{}{}{}", self.indent, delimiter, self.fence_lang),
+                                ];
+                            }
+                            _ => line = format!("{}{}{}", self.indent, delimiter, self.fence_lang),
+                        }
+                    }
+                    Lang::Text => {
+                        self.section = Section::CodeOther;
+                        line = format!("{}{}", self.indent, delimiter);
+                    }
+                    Lang::Other => self.section = Section::CodeOther,
+                }
             }
-        } else if self.section != Section::None && Some(&line) == self.delimiter.as_ref() {
+        } else if self.section != Section::None && self.is_closing_fence(&line) {
             self.section = Section::None;
-            line = self.delimiter.take().unwrap_or("```".to_owned());
+            self.delimiter = None;
+            line = format!("{}{}", self.indent, line.trim());
         }
 
-        Some(line)
+        vec![line]
+    }
+
+    /// Check if `line` closes the current code block
+    fn is_closing_fence(&self, line: &str) -> bool {
+        let delimiter = match self.delimiter {
+            Some(ref delimiter) => delimiter,
+            None => return false,
+        };
+        let fence = line.trim();
+        let fence_char = delimiter.chars().next();
+
+        fence.len() >= delimiter.len() && fence.chars().all(|c| Some(c) == fence_char)
+    }
+
+    fn start_code_block(&mut self, cap: &regex::Captures) {
+        self.delimiter = cap
+            .name("backticks")
+            .or_else(|| cap.name("tildes"))
+            .or_else(|| cap.name("dollars"))
+            .map(|x| x.as_str().to_owned());
+        self.indent = cap
+            .name("indent")
+            .map(|x| x.as_str().to_owned())
+            .unwrap_or_default();
     }
 }
 
+/// Shift the level of a markdown heading by `offset`, keeping it between level 1 and 6
+fn shift_heading(line: &str, offset: isize) -> String {
+    let position = line.find('#').unwrap_or(0);
+    let (indent, heading) = line.split_at(position);
+    let level = heading.len() - heading.trim_start_matches('#').len();
+    let new_level = (level as isize + offset).clamp(1, 6) as usize;
+
+    format!("{}{}{}", indent, "#".repeat(new_level), &heading[level..])
+}
+
+/// Remove the `#` mark of a hidden line and the whitespace after it, keeping the indentation
+fn strip_hidden_mark(line: &str) -> String {
+    let (indent, rest) = line.split_at(line.find('#').unwrap_or(0));
+    let rest = rest.strip_prefix('#').unwrap_or(rest);
+    let rest = rest.strip_prefix(|c| c == ' ' || c == '\t').unwrap_or(rest);
+
+    if rest.is_empty() {
+        String::new()
+    } else {
+        format!("{}{}", indent, rest)
+    }
+}
+
+/// Language of a code block, as given by its info string
+#[derive(Debug, PartialEq)]
+enum Lang {
+    Rust,
+    Text,
+    Other,
+}
+
+/// Get the language of a code block from its info string, like `rust,no_run` or `python`
+///
+/// As in rustdoc, the attributes are separated by commas or whitespace, and a code block is rust
+/// when it has the `rust` attribute or when all its attributes are rustdoc attributes.
+fn code_block_lang(info: &str) -> Lang {
+    let attributes: Vec<&str> = info
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|a| !a.is_empty())
+        .collect();
+
+    if attributes == ["text"] {
+        return Lang::Text;
+    }
+    if attributes.contains(&"rust") || attributes.iter().all(|a| is_rustdoc_attribute(a)) {
+        return Lang::Rust;
+    }
+    Lang::Other
+}
+
+/// Get the attributes of a rust code block telling how its doc test is run, like `no_run`
+fn run_attributes(info: &str) -> Vec<&str> {
+    info.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|a| {
+            matches!(
+                *a,
+                "ignore" | "should_panic" | "no_run" | "compile_fail" | "allow_fail"
+            ) || a.starts_with("ignore-")
+        })
+        .collect()
+}
+
+/// Check if the code block attribute is one of the attributes rustdoc uses for doc tests
+pub fn is_rustdoc_attribute(attribute: &str) -> bool {
+    matches!(
+        attribute,
+        "ignore"
+            | "should_panic"
+            | "no_run"
+            | "compile_fail"
+            | "test_harness"
+            | "standalone_crate"
+            | "allow_fail"
+    ) || attribute.starts_with("ignore-")
+        || attribute.starts_with("edition")
+        || RE_ERROR_CODE.is_match(attribute)
+}
+
 #[derive(PartialEq)]
 enum Section {
     CodeRust,
@@ -82,23 +282,19 @@ enum Section {
     None,
 }
 
-pub trait DocProcess<S: Into<String>> {
-    fn process_docs(self, indent_headings: bool) -> Vec<String>
-    where
-        Self: Sized + Iterator<Item = S>,
-    {
-        let mut p = Processor::new(indent_headings);
-        self.into_iter()
-            .filter_map(|line| p.process_line(line.into()))
-            .collect()
-    }
-}
-
-impl<S: Into<String>, I: Iterator<Item = S>> DocProcess<S> for I {}
-
 #[cfg(test)]
 mod tests {
-    use super::process_docs;
+    use super::{process_docs_with, DoctestAttributes, HiddenLines};
+
+    fn process_docs(lines: &[&str], indent_headings: bool) -> Vec<String> {
+        process_docs_with(
+            lines,
+            indent_headings as isize,
+            HiddenLines::Hide,
+            "rust",
+            DoctestAttributes::Drop,
+        )
+    }
 
     const INPUT_HIDDEN_LINE: &[&str] = &[
         "```",
@@ -250,6 +446,14 @@ mod tests {
         assert_eq!(result, INPUT_OTHER_CODE_BLOCK_WITH_SYMBOLS);
     }
 
+    const INPUT_MATH_BLOCK: &[&str] = &["$$", "# x = y", "$$", "# heading"];
+
+    #[test]
+    fn keep_math_block() {
+        let result = process_docs(INPUT_MATH_BLOCK, true);
+        assert_eq!(result, ["$$", "# x = y", "$$", "## heading"]);
+    }
+
     const INPUT_INDENT_HEADINGS: &[&str] = &[
         "# heading 1",
         "some text",
@@ -276,6 +480,25 @@ mod tests {
         assert_eq!(result, INPUT_INDENT_HEADINGS);
     }
 
+    const EXPECTED_INDENT_HEADINGS_TWICE: &[&str] = &[
+        "### heading 1",
+        "some text",
+        "#### heading 2",
+        "some other text",
+    ];
+
+    #[test]
+    fn indent_markdown_headings_with_offset() {
+        let result = process_docs_with(
+            INPUT_INDENT_HEADINGS,
+            2,
+            HiddenLines::Hide,
+            "rust",
+            DoctestAttributes::Drop,
+        );
+        assert_eq!(result, EXPECTED_INDENT_HEADINGS_TWICE);
+    }
+
     const INPUT_ALTERNATE_DELIMITER_4_BACKTICKS: &[&str] = &["````", "let i = 1;", "````"];
 
     const EXPECTED_ALTERNATE_DELIMITER_4_BACKTICKS: &[&str] = &["````rust", "let i = 1;", "````"];
@@ -377,4 +600,357 @@ mod tests {
         let result = process_docs(INPUT_ALTERNATE_DELIMITER_MIXED, false);
         assert_eq!(result, EXPECTED_ALTERNATE_DELIMITER_MIXED);
     }
+
+    const INPUT_INDENTED_CODE_BLOCK: &[&str] = &[
+        "1. First step:",
+        "",
+        "   ```",
+        "   # fn main() {",
+        "   let step = 1;",
+        "   # }",
+        "   ```",
+        "2. Second step:",
+        "",
+        "   ```text",
+        "   # not hidden",
+        "   ```",
+    ];
+
+    const EXPECTED_INDENTED_CODE_BLOCK: &[&str] = &[
+        "1. First step:",
+        "",
+        "   ```rust",
+        "   let step = 1;",
+        "   ```",
+        "2. Second step:",
+        "",
+        "   ```",
+        "   # not hidden",
+        "   ```",
+    ];
+
+    #[test]
+    fn transform_indented_code_block() {
+        let result = process_docs(INPUT_INDENTED_CODE_BLOCK, true);
+        assert_eq!(result, EXPECTED_INDENTED_CODE_BLOCK);
+    }
+
+    const INPUT_LONG_FENCES: &[&str] = &[
+        "`````",
+        "````text",
+        "let i = 1;",
+        "````",
+        "``````",
+        "",
+        "~~~",
+        "```",
+        "# hidden",
+        "~~~~",
+    ];
+
+    const EXPECTED_LONG_FENCES: &[&str] = &[
+        "`````rust",
+        "````text",
+        "let i = 1;",
+        "````",
+        "``````",
+        "",
+        "~~~rust",
+        "```",
+        "~~~~",
+    ];
+
+    #[test]
+    fn close_code_block_with_longer_fence() {
+        let result = process_docs(INPUT_LONG_FENCES, true);
+        assert_eq!(result, EXPECTED_LONG_FENCES);
+    }
+
+    const INPUT_DOCTEST_ATTRIBUTES: &[&str] = &[
+        "```rust,no_run",
+        "```",
+        "```ignore,should_panic",
+        "```",
+        "```edition2021",
+        "```",
+        "```compile_fail,E0308",
+        "```",
+        "```ignore-windows no_run",
+        "```",
+        "```rust,html",
+        "```",
+        "```sh,no_run",
+        "```",
+    ];
+
+    const EXPECTED_DOCTEST_ATTRIBUTES: &[&str] = &[
+        "```rust",
+        "```",
+        "```rust",
+        "```",
+        "```rust",
+        "```",
+        "```rust",
+        "```",
+        "```rust",
+        "```",
+        "```rust",
+        "```",
+        "```sh,no_run",
+        "```",
+    ];
+
+    #[test]
+    fn transform_doctest_attributes() {
+        let result = process_docs(INPUT_DOCTEST_ATTRIBUTES, true);
+        assert_eq!(result, EXPECTED_DOCTEST_ATTRIBUTES);
+    }
+
+    const INPUT_NOT_HEADINGS: &[&str] = &[
+        "# heading",
+        "   ## indented heading",
+        "#",
+        "#hashtag",
+        "    # indented code",
+        "```C",
+        "#include <stdio.h>",
+        "# define ANSWER 42",
+        "```",
+    ];
+
+    const EXPECTED_NOT_HEADINGS: &[&str] = &[
+        "## heading",
+        "   ### indented heading",
+        "##",
+        "#hashtag",
+        "    # indented code",
+        "```C",
+        "#include <stdio.h>",
+        "# define ANSWER 42",
+        "```",
+    ];
+
+    #[test]
+    fn indent_only_markdown_headings() {
+        let result = process_docs(INPUT_NOT_HEADINGS, true);
+        assert_eq!(result, EXPECTED_NOT_HEADINGS);
+    }
+
+    const INPUT_HIDDEN_LINE_RULES: &[&str] = &[
+        "```",
+        "#![allow(unused)]",
+        "#[derive(Debug)]",
+        "#",
+        "#\tuse std::fmt;",
+        "    # let hidden = true;",
+        "struct Unit;",
+        "let s = \"",
+        "## not a heading",
+        "\";",
+        "```",
+    ];
+
+    const EXPECTED_HIDDEN_LINE_RULES: &[&str] = &[
+        "```rust",
+        "#![allow(unused)]",
+        "#[derive(Debug)]",
+        "struct Unit;",
+        "let s = \"",
+        "# not a heading",
+        "\";",
+        "```",
+    ];
+
+    #[test]
+    fn hidden_line_rules() {
+        let result = process_docs(INPUT_HIDDEN_LINE_RULES, true);
+        assert_eq!(result, EXPECTED_HIDDEN_LINE_RULES);
+    }
+
+    const INPUT_SHOW_HIDDEN_LINES: &[&str] = &[
+        "```",
+        "# fn main() {",
+        "    let answer = 42;",
+        "    #",
+        "    # assert_eq!(answer, 42);",
+        "# }",
+        "```",
+    ];
+
+    #[test]
+    fn show_hidden_lines() {
+        let result = process_docs_with(
+            INPUT_SHOW_HIDDEN_LINES,
+            1,
+            HiddenLines::Show,
+            "rust",
+            DoctestAttributes::Drop,
+        );
+        assert_eq!(result[0], "```rust");
+        assert_eq!(result[1..], INPUT_SHOW_HIDDEN_LINES[1..]);
+    }
+
+    const EXPECTED_STRIP_HIDDEN_LINES: &[&str] = &[
+        "```rust",
+        "fn main() {",
+        "    let answer = 42;",
+        "",
+        "    assert_eq!(answer, 42);",
+        "}",
+        "```",
+    ];
+
+    #[test]
+    fn strip_hidden_lines() {
+        let result = process_docs_with(
+            INPUT_SHOW_HIDDEN_LINES,
+            1,
+            HiddenLines::Strip,
+            "rust",
+            DoctestAttributes::Drop,
+        );
+        assert_eq!(result, EXPECTED_STRIP_HIDDEN_LINES);
+    }
+
+    const INPUT_SHIFT_HEADINGS: &[&str] = &["# heading 1", "### heading 3", "###### heading 6"];
+
+    #[test]
+    fn demote_markdown_headings() {
+        let result = process_docs_with(
+            INPUT_SHIFT_HEADINGS,
+            2,
+            HiddenLines::Hide,
+            "rust",
+            DoctestAttributes::Drop,
+        );
+        assert_eq!(
+            result,
+            &["### heading 1", "##### heading 3", "###### heading 6"]
+        );
+    }
+
+    #[test]
+    fn promote_markdown_headings() {
+        let result = process_docs_with(
+            INPUT_SHIFT_HEADINGS,
+            -2,
+            HiddenLines::Hide,
+            "rust",
+            DoctestAttributes::Drop,
+        );
+        assert_eq!(result, &["# heading 1", "# heading 3", "#### heading 6"]);
+    }
+
+    const INPUT_FENCE_LANG: &[&str] = &[
+        "```no_run",
+        "let answer = 42;",
+        "```",
+        "",
+        "```text",
+        "plain text",
+        "```",
+    ];
+
+    #[test]
+    fn bare_fence_lang() {
+        let result = process_docs_with(
+            INPUT_FENCE_LANG,
+            1,
+            HiddenLines::Hide,
+            "",
+            DoctestAttributes::Drop,
+        );
+        assert_eq!(result[0], "```");
+        assert_eq!(result[4], "```");
+    }
+
+    #[test]
+    fn custom_fence_lang() {
+        let result = process_docs_with(
+            INPUT_FENCE_LANG,
+            1,
+            HiddenLines::Hide,
+            "rust,ignore",
+            DoctestAttributes::Drop,
+        );
+        assert_eq!(result[0], "```rust,ignore");
+        assert_eq!(result[4], "```");
+    }
+
+    const INPUT_RUN_ATTRIBUTES: &[&str] = &[
+        "```no_run",
+        "loop {}",
+        "```",
+        "  ```rust,should_panic,ignore",
+        "  panic!();",
+        "  ```",
+        "```edition2021",
+        "let a = 1;",
+        "```",
+    ];
+
+    #[test]
+    fn drop_doctest_attributes() {
+        let result = process_docs_with(
+            INPUT_RUN_ATTRIBUTES,
+            1,
+            HiddenLines::Hide,
+            "rust",
+            DoctestAttributes::Drop,
+        );
+        assert_eq!(result[0], "```rust");
+        assert_eq!(result[3], "  ```rust");
+        assert_eq!(result[6], "```rust");
+    }
+
+    #[test]
+    fn keep_doctest_attributes() {
+        let result = process_docs_with(
+            INPUT_RUN_ATTRIBUTES,
+            1,
+            HiddenLines::Hide,
+            "rust",
+            DoctestAttributes::Keep,
+        );
+        assert_eq!(result[0], "```rust,no_run");
+        assert_eq!(result[3], "  ```rust,should_panic,ignore");
+        assert_eq!(result[6], "```rust");
+
+        let result = process_docs_with(
+            INPUT_RUN_ATTRIBUTES,
+            1,
+            HiddenLines::Hide,
+            "",
+            DoctestAttributes::Keep,
+        );
+        assert_eq!(result[0], "```no_run");
+        assert_eq!(result[6], "```");
+    }
+
+    #[test]
+    fn comment_doctest_attributes() {
+        let result = process_docs_with(
+            INPUT_RUN_ATTRIBUTES,
+            1,
+            HiddenLines::Hide,
+            "rust",
+            DoctestAttributes::Comment,
+        );
+        assert_eq!(
+            result,
+            [
+                "<!-- no_run -->",
+                "```rust",
+                "loop {}",
+                "```",
+                "  <!-- should_panic, ignore -->",
+                "  ```rust",
+                "  panic!();",
+                "  ```",
+                "```rust",
+                "let a = 1;",
+                "```",
+            ]
+        );
+    }
 }
@@ -0,0 +1,165 @@
+//! Convert the punctuation of the prose into its typographic form, like rustdoc does
+//!
+//! rustdoc renders the docs with the smart punctuation of pulldown-cmark: the straight quotes
+//! become curly quotes, `--` an en dash, `---` an em dash and `...` an ellipsis. The same
+//! conversion gives the README the look of the docs on docs.rs. The code blocks, code spans, link
+//! targets, HTML tags and escaped characters, like `\"`, are left as they are.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::fences::fenced;
+use super::links::{code_span_len, definition, inline_target};
+
+lazy_static! {
+    // the parts of a line that are not prose, besides the link targets: autolinks and HTML tags
+    static ref RE_PROTECTED: Regex = Regex::new(r"<[A-Za-z/!][^<>]*>|[a-z]+://[^\s)>]+").unwrap();
+    // a line of markup only, like a thematic break, a setext underline or a table delimiter row
+    static ref RE_MARKUP: Regex = Regex::new(r"^[\s|:=*_-]+$").unwrap();
+}
+
+/// Convert the punctuation of the prose of the doc lines
+pub fn smart_punctuation(lines: Vec<String>) -> Vec<String> {
+    fenced(lines)
+        .map(|(kind, line)| {
+            if kind.is_code() || definition(&line).is_some() || RE_MARKUP.is_match(&line) {
+                return line;
+            }
+            convert_line(&line)
+        })
+        .collect()
+}
+
+/// Convert the punctuation of a line outside code blocks, skipping its code spans
+fn convert_line(line: &str) -> String {
+    let mut converted = String::new();
+    let mut rest = line;
+
+    while let Some(pos) = rest.find('`') {
+        prose(&rest[..pos], &mut converted);
+        let len = code_span_len(&rest[pos..]);
+        converted.push_str(&rest[pos..pos + len]);
+        rest = &rest[pos + len..];
+    }
+    prose(rest, &mut converted);
+
+    converted
+}
+
+/// Convert the punctuation of `text`, which has no code spans, skipping its link targets and tags
+fn prose(text: &str, converted: &mut String) {
+    let mut rest = text;
+    while let Some(pos) = rest.find("](") {
+        let after = &rest[pos + 1..];
+        let len = inline_target(after).map_or(1, |(_, _, tail)| after.len() - tail.len());
+        unprotected(&rest[..pos + 1], converted);
+        converted.push_str(&after[..len]);
+        rest = &after[len..];
+    }
+    unprotected(rest, converted);
+}
+
+/// Convert the punctuation of `text`, which has no code spans nor link targets, skipping its tags
+fn unprotected(text: &str, converted: &mut String) {
+    let mut pos = 0;
+    for m in RE_PROTECTED.find_iter(text) {
+        punctuation(&text[pos..m.start()], converted);
+        converted.push_str(m.as_str());
+        pos = m.end();
+    }
+    punctuation(&text[pos..], converted);
+}
+
+/// Append `text` to `converted`, with its punctuation converted
+///
+/// A quote is an opening quote at the start of a word, and a closing quote or an apostrophe
+/// elsewhere, as told by the character before it.
+fn punctuation(text: &str, converted: &mut String) {
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                converted.push(c);
+                if let Some(escaped) = chars.next() {
+                    converted.push(escaped);
+                }
+            }
+            '"' | '\'' => {
+                let opening = converted
+                    .chars()
+                    .last()
+                    .is_none_or(|before| before.is_whitespace() || "([{*_–—‘“".contains(before));
+                converted.push(match (c, opening) {
+                    ('"', true) => '“',
+                    ('"', false) => '”',
+                    (_, true) => '‘',
+                    (_, false) => '’',
+                });
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                chars.next();
+                match chars.peek() == Some(&'-') {
+                    true => {
+                        chars.next();
+                        converted.push('—');
+                    }
+                    false => converted.push('–'),
+                }
+            }
+            '.' if chars.clone().take(2).eq(['.', '.']) => {
+                chars.nth(1);
+                converted.push('…');
+            }
+            c => converted.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::smart_punctuation;
+
+    fn convert(lines: &[&str]) -> Vec<String> {
+        smart_punctuation(lines.iter().map(|l| l.to_string()).collect())
+    }
+
+    #[test]
+    fn smart_punctuation_in_prose() {
+        assert_eq!(
+            convert(&[
+                "\"Quoted\" -- isn't it 'nice'? Wait... yes --- it is.",
+                "Not `\"code\"`, [links](https://a.com/--x \"title\") nor \\\"escapes\\\".",
+                "<img alt=\"don't\">",
+                "```",
+                "let s = \"...\";",
+                "```",
+                "[label]: https://example.com \"Title\"",
+                "| --- | :-: |",
+            ]),
+            [
+                "“Quoted” – isn’t it ‘nice’? Wait… yes — it is.",
+                "Not `\"code\"`, [links](https://a.com/--x \"title\") nor \\\"escapes\\\".",
+                "<img alt=\"don't\">",
+                "```",
+                "let s = \"...\";",
+                "```",
+                "[label]: https://example.com \"Title\"",
+                "| --- | :-: |",
+            ]
+        );
+    }
+
+    #[test]
+    fn indented_code_is_left_alone() {
+        let lines: &[&str] = &[
+            "Some code:",
+            "",
+            r#"    let s = ":crab:"; // [`Foo`] <b>x</b> [a](https://a.io) -- "q""#,
+            "",
+            "Done.",
+        ];
+        let owned = lines.iter().map(|l| l.to_string()).collect();
+
+        assert_eq!(smart_punctuation(owned), lines);
+    }
+}
@@ -0,0 +1,240 @@
+//! Convert the links between the inline and the reference styles
+//!
+//! Inline links, like `[text](https://example.com)`, keep their target in the prose, while
+//! reference links, like `[text][1]`, point to a definition, like `[1]: https://example.com`.
+//! Reference links are first resolved with the definitions of their own docs, so the labels of the
+//! docs of different modules do not collide, and can then be collected at the end of the readme
+//! with numbered labels. Lines inside code blocks and code spans are left untouched.
+
+use std::collections::HashMap;
+
+use super::fences::fenced;
+use super::links::{
+    definition, inline_target, map_links, normalize_label, reference_definitions, reference_label,
+};
+
+/// Style of the links of the output
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum LinkStyle {
+    /// Leave the links as they are
+    #[default]
+    Keep,
+    /// Turn the reference links into inline links, like `[text](https://example.com)`
+    Inline,
+    /// Turn the links into reference links with numbered labels, like `[text][1]`, whose
+    /// definitions are collected at the end
+    Reference,
+}
+
+/// Turn the reference links of `lines` into inline links, removing the definitions they use
+///
+/// The references without a definition, like intra-doc links, are left as they are.
+pub fn inline_references(lines: Vec<String>) -> Vec<String> {
+    let definitions = definitions(&lines);
+    if definitions.is_empty() {
+        return lines;
+    }
+
+    let mut result: Vec<String> = Vec::new();
+    for (kind, line) in fenced(lines) {
+        if !kind.is_code() {
+            if definition(&line).is_some() {
+                // do not leave two blank lines where the definition was
+                if result.last().is_some_and(|l| l.trim().is_empty()) {
+                    result.pop();
+                }
+                continue;
+            }
+            result.push(inline_links(&line, &definitions));
+            continue;
+        }
+        result.push(line);
+    }
+
+    while result.last().is_some_and(|l| l.trim().is_empty()) {
+        result.pop();
+    }
+
+    result
+}
+
+/// Turn the inline links of `text` into reference links, whose definitions are appended at the end
+///
+/// Links to the same target share the same label. The labels are numbers, skipping the labels of
+/// the definitions already in `text`.
+pub fn reference_links(text: &str) -> String {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let existing = definitions(&lines);
+
+    let mut targets: Vec<String> = Vec::new();
+    let mut labels: HashMap<String, String> = HashMap::new();
+    let mut next_label = 1;
+
+    let mut result: Vec<String> = Vec::new();
+    for (kind, line) in fenced(lines) {
+        if !kind.is_code() && definition(line).is_none() {
+            result.push(map_links(line, &mut |link_text, after, is_image| {
+                // images are left inline
+                if is_image {
+                    return None;
+                }
+                let (target, rest) = match inline_target(after)? {
+                    (target, Some(title), rest) => (format!("{} {}", target, title), rest),
+                    (target, None, rest) => (target.to_owned(), rest),
+                };
+                let label = labels.entry(target.clone()).or_insert_with(|| {
+                    while existing.contains_key(&next_label.to_string()) {
+                        next_label += 1;
+                    }
+                    targets.push(target.clone());
+                    next_label += 1;
+                    (next_label - 1).to_string()
+                });
+                Some((format!("[{}][{}]", link_text, label), rest))
+            }));
+            continue;
+        }
+        result.push(line.to_owned());
+    }
+
+    if targets.is_empty() {
+        return text.to_owned();
+    }
+
+    let mut readme = result.join("\n").trim_end().to_owned();
+    readme.push('\n');
+    for target in targets {
+        readme.push_str(&format!("\n[{}]: {}", labels[&target], target));
+    }
+
+    readme
+}
+
+/// Turn the reference links of `line` into inline links, with the given `definitions`
+fn inline_links(line: &str, definitions: &HashMap<String, String>) -> String {
+    map_links(line, &mut |text, after, is_image| {
+        inline_reference(text, after, is_image, definitions)
+    })
+}
+
+/// Turn a reference link or image with the given `text` into an inline one, if it has a definition
+///
+/// The links of the text, like the image of a badge, are turned into inline links too.
+fn inline_reference<'a>(
+    text: &'a str,
+    after: &'a str,
+    is_image: bool,
+    definitions: &HashMap<String, String>,
+) -> Option<(String, &'a str)> {
+    // inline links are left as they are
+    if after.starts_with('(') {
+        return None;
+    }
+
+    let (label, rest) = reference_label(text, after);
+    let target = definitions.get(&normalize_label(label))?;
+    let image = match is_image {
+        true => "!",
+        false => "",
+    };
+    let text = inline_links(text, definitions);
+    Some((format!("{}[{}]({})", image, text, target), rest))
+}
+
+/// Collect the targets of the reference definitions outside code blocks, with their title, by
+/// their normalized label
+fn definitions<S: AsRef<str>>(lines: &[S]) -> HashMap<String, String> {
+    reference_definitions(lines)
+        .into_iter()
+        .map(|(label, definition)| {
+            let target = match definition.title {
+                Some(title) => format!("{} {}", definition.target, title),
+                None => definition.target.to_owned(),
+            };
+            (label, target)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{inline_references, reference_links};
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(ToOwned::to_owned).collect()
+    }
+
+    #[test]
+    fn inline_reference_links() {
+        let input = "\
+See [the guide][guide], [Docs][] and [docs], but not [`Parser`] or `[guide]`.
+![logo][logo] [![badge][logo]][guide]
+
+```
+[guide]
+```
+
+[guide]: https://example.com/guide \"Guide\"
+[docs]: https://docs.rs
+
+[logo]: logo.png";
+
+        let expected = "\
+See [the guide](https://example.com/guide \"Guide\"), [Docs](https://docs.rs) and \
+[docs](https://docs.rs), but not [`Parser`] or `[guide]`.
+![logo](logo.png) [![badge](logo.png)](https://example.com/guide \"Guide\")
+
+```
+[guide]
+```";
+
+        assert_eq!(inline_references(lines(input)), lines(expected));
+    }
+
+    #[test]
+    fn inline_keeps_docs_without_definitions() {
+        let input = lines("See [`Parser`] and [^1].\n\n[^1]: Note.");
+
+        assert_eq!(inline_references(input.clone()), input);
+    }
+
+    #[test]
+    fn collect_reference_links() {
+        let input = "\
+See [the guide](https://example.com/guide) and [the other guide](https://example.com/guide).
+Also [docs](https://docs.rs \"Docs\"), ![logo](logo.png) and `[code](https://example.com)`.
+
+```
+[code](https://example.com)
+```
+
+[1]: https://example.com/existing";
+
+        let expected = "\
+See [the guide][2] and [the other guide][2].
+Also [docs][3], ![logo](logo.png) and `[code](https://example.com)`.
+
+```
+[code](https://example.com)
+```
+
+[1]: https://example.com/existing
+
+[2]: https://example.com/guide
+[3]: https://docs.rs \"Docs\"";
+
+        assert_eq!(reference_links(input), expected);
+    }
+
+    #[test]
+    fn collect_nothing_without_links() {
+        assert_eq!(reference_links("No links."), "No links.");
+    }
+
+    #[test]
+    fn indented_code_is_left_alone() {
+        let text = "Some code:\n\n    let s = \":crab:\"; // [`Foo`] <b>x</b> [a](https://a.io) -- \"q\"\n\nDone.";
+
+        assert_eq!(reference_links(text), text);
+    }
+}
@@ -0,0 +1,191 @@
+//! Select the regions of the docs meant for the readme or for rustdoc only
+//!
+//! Content meant for rustdoc only, like navigation between the modules, is put between the
+//! `<!-- cargo-readme skip-start -->` and `<!-- cargo-readme skip-end -->` markers, or the
+//! `<!-- cargo-readme docs-only-start -->` and `<!-- cargo-readme docs-only-end -->` markers. Being
+//! HTML comments, the markers are not shown by rustdoc.
+//!
+//! Content meant for the readme only, like badges, is put inside an HTML comment, between the
+//! `<!-- cargo-readme readme-only-start` and `cargo-readme readme-only-end -->` lines, so rustdoc
+//! hides it while the readme shows it. Markers inside code blocks are left untouched.
+
+use super::fences::fenced;
+use crate::Error;
+
+/// Comment marking the start of a region removed from the readme
+pub const SKIP_START_MARKER: &str = "<!-- cargo-readme skip-start -->";
+/// Comment marking the end of a region removed from the readme
+pub const SKIP_END_MARKER: &str = "<!-- cargo-readme skip-end -->";
+/// Comment marking the start of a region shown by rustdoc only
+pub const DOCS_ONLY_START_MARKER: &str = "<!-- cargo-readme docs-only-start -->";
+/// Comment marking the end of a region shown by rustdoc only
+pub const DOCS_ONLY_END_MARKER: &str = "<!-- cargo-readme docs-only-end -->";
+/// Opening of the comment hiding a region shown by the readme only
+pub const README_ONLY_START_MARKER: &str = "<!-- cargo-readme readme-only-start";
+/// Closing of the comment hiding a region shown by the readme only
+pub const README_ONLY_END_MARKER: &str = "cargo-readme readme-only-end -->";
+
+/// The kinds of regions, with their start and end markers and whether the readme keeps them
+const REGIONS: [(&str, &str, bool); 3] = [
+    (SKIP_START_MARKER, SKIP_END_MARKER, false),
+    (DOCS_ONLY_START_MARKER, DOCS_ONLY_END_MARKER, false),
+    (README_ONLY_START_MARKER, README_ONLY_END_MARKER, true),
+];
+
+/// Remove the regions of `lines` meant for rustdoc only and keep the content of those meant for
+/// the readme only, removing the markers
+///
+/// An error is returned if the markers are not paired.
+pub fn readme_regions(lines: Vec<String>) -> Result<Vec<String>, Error> {
+    // the region the line is in, with its start and end markers and whether it is kept
+    let mut region: Option<(&str, &str, bool)> = None;
+    let mut changed = false;
+    let mut result: Vec<String> = Vec::new();
+
+    for (kind, line) in fenced(lines) {
+        if !kind.is_code() {
+            let marker = line.trim();
+            if let Some(&(start, end, keep)) = REGIONS.iter().find(|r| r.0 == marker) {
+                if let Some((open, _, _)) = region {
+                    return Err(Error::marker(format!(
+                        "Unexpected `{}` marker after `{}`",
+                        start, open
+                    )));
+                }
+                region = Some((start, end, keep));
+                changed = true;
+                continue;
+            }
+            if let Some(&(start, end, _)) = REGIONS.iter().find(|r| r.1 == marker) {
+                match region {
+                    Some((_, expected, _)) if expected == end => {}
+                    Some((open, expected, _)) => {
+                        return Err(Error::marker(format!(
+                            "Unexpected `{}` marker, `{}` must be closed by `{}`",
+                            end, open, expected
+                        )));
+                    }
+                    None => {
+                        return Err(Error::marker(format!(
+                            "Missing `{}` marker before `{}`",
+                            start, end
+                        )))
+                    }
+                }
+                // do not leave two blank lines where a removed region was
+                if region.is_some_and(|(_, _, keep)| !keep)
+                    && result.last().is_some_and(|l| l.trim().is_empty())
+                {
+                    result.pop();
+                }
+                region = None;
+                continue;
+            }
+        }
+        if region.is_none_or(|(_, _, keep)| keep) {
+            result.push(line);
+        }
+    }
+
+    if let Some((start, end, _)) = region {
+        return Err(Error::marker(format!(
+            "Missing `{}` marker after `{}`",
+            end, start
+        )));
+    }
+    if !changed {
+        return Ok(result);
+    }
+
+    while result.first().is_some_and(|l| l.trim().is_empty()) {
+        result.remove(0);
+    }
+    while result.last().is_some_and(|l| l.trim().is_empty()) {
+        result.pop();
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::readme_regions;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(ToOwned::to_owned).collect()
+    }
+
+    #[test]
+    fn skip_marked_regions() {
+        let input = "\
+<!-- cargo-readme skip-start -->
+See the [`parser`] module.
+<!-- cargo-readme skip-end -->
+
+Intro.
+
+<!-- cargo-readme skip-start -->
+Only in rustdoc.
+
+```
+<!-- cargo-readme skip-end -->
+```
+<!-- cargo-readme skip-end -->
+
+End.";
+
+        assert_eq!(
+            readme_regions(lines(input)).unwrap(),
+            lines("Intro.\n\nEnd.")
+        );
+    }
+
+    #[test]
+    fn keep_docs_without_markers() {
+        let input = lines("Intro.\n\n\n```\n<!-- cargo-readme skip-start -->\n```\n");
+
+        assert_eq!(readme_regions(input.clone()).unwrap(), input);
+    }
+
+    #[test]
+    fn unpaired_markers() {
+        let missing_end = lines("Intro.\n<!-- cargo-readme skip-start -->\nEnd.");
+        let missing_start = lines("Intro.\n<!-- cargo-readme skip-end -->\nEnd.");
+
+        assert!(readme_regions(missing_end).is_err());
+        assert!(readme_regions(missing_start).is_err());
+    }
+
+    #[test]
+    fn select_readme_and_docs_only_regions() {
+        let input = "\
+<!-- cargo-readme readme-only-start
+[![CI](https://example.com/ci.svg)](https://example.com/ci)
+cargo-readme readme-only-end -->
+
+Intro.
+
+<!-- cargo-readme docs-only-start -->
+See the [`parser`] module.
+<!-- cargo-readme docs-only-end -->
+
+End.";
+
+        let expected = "\
+[![CI](https://example.com/ci.svg)](https://example.com/ci)
+
+Intro.
+
+End.";
+
+        assert_eq!(readme_regions(lines(input)).unwrap(), lines(expected));
+    }
+
+    #[test]
+    fn mismatched_markers() {
+        let input =
+            lines("<!-- cargo-readme docs-only-start -->\nText\n<!-- cargo-readme skip-end -->");
+
+        assert!(readme_regions(input).is_err());
+    }
+}
@@ -0,0 +1,352 @@
+//! Rewrite the relative links of the docs
+
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use super::fences::fenced;
+
+/// Get the path of `to` relative to `from`, both being absolute directories
+pub fn relative_path(from: &Path, to: &Path) -> PathBuf {
+    let from: Vec<Component> = from.components().collect();
+    let to: Vec<Component> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut result = PathBuf::new();
+    for _ in common..from.len() {
+        result.push("..");
+    }
+    for component in &to[common..] {
+        result.push(component);
+    }
+    result
+}
+
+/// Rewrite the relative link targets found in `lines` with the `rewrite` function
+///
+/// Inline links, images and reference definitions are rewritten, code blocks are left untouched.
+/// URLs, absolute paths and anchors are not relative links.
+pub fn rewrite_relative_links<F: Fn(&str) -> String>(
+    lines: Vec<String>,
+    rewrite: F,
+) -> Vec<String> {
+    fenced(lines)
+        .map(|(kind, line)| match kind.is_code() {
+            true => line,
+            false => match rewrite_reference_definition(&line, &rewrite) {
+                Some(line) => line,
+                None => rewrite_inline_links(&line, &rewrite),
+            },
+        })
+        .collect()
+}
+
+/// Rewrite a reference definition, like `[label]: path/to/file.md`
+fn rewrite_reference_definition<F: Fn(&str) -> String>(line: &str, rewrite: &F) -> Option<String> {
+    let indent = line.len() - line.trim_start().len();
+    let rest = line[indent..].strip_prefix('[')?;
+    let label_end = rest.find(']')?;
+    if label_end == 0 || rest.starts_with('^') || !rest[label_end..].starts_with("]:") {
+        return None;
+    }
+    let after = &rest[label_end + 2..];
+    let target_start = after.len() - after.trim_start().len();
+    let target = after[target_start..].split_whitespace().next()?;
+
+    let prefix_len = indent + 1 + label_end + 2 + target_start;
+    Some(format!(
+        "{}{}{}",
+        &line[..prefix_len],
+        rewrite_target(target, rewrite),
+        &line[prefix_len + target.len()..]
+    ))
+}
+
+/// Rewrite the targets of the inline links and images of a line, like `[text](path/to/file.md)`
+fn rewrite_inline_links<F: Fn(&str) -> String>(line: &str, rewrite: &F) -> String {
+    let mut result = String::new();
+    let mut rest = line;
+    let mut in_code_span = false;
+
+    while let Some(pos) = rest.find(['`', ']']) {
+        let (before, after) = rest.split_at(pos);
+        result.push_str(before);
+        if let Some(after) = after.strip_prefix('`') {
+            in_code_span = !in_code_span;
+            result.push('`');
+            rest = after;
+            continue;
+        }
+
+        result.push(']');
+        rest = &after[1..];
+        if in_code_span {
+            continue;
+        }
+        match rest.strip_prefix('(') {
+            Some(target) => rest = target,
+            None => continue,
+        }
+
+        result.push('(');
+        let target_len = rest.find([')', ' ']).unwrap_or(rest.len());
+        result.push_str(&rewrite_target(&rest[..target_len], rewrite));
+        rest = &rest[target_len..];
+    }
+    result.push_str(rest);
+
+    result
+}
+
+/// Rewrite a link target, if the target is a relative path
+fn rewrite_target<F: Fn(&str) -> String>(target: &str, rewrite: &F) -> String {
+    let (open, inner, close) = match target.strip_prefix('<').and_then(|t| t.strip_suffix('>')) {
+        Some(inner) => ("<", inner, ">"),
+        None => ("", target, ""),
+    };
+
+    if !is_relative(inner) {
+        return target.to_owned();
+    }
+
+    format!("{}{}{}", open, rewrite(inner), close)
+}
+
+/// Check if the link target is a relative path: URLs, absolute paths and anchors are not
+pub fn is_relative(target: &str) -> bool {
+    !target.is_empty()
+        && !target.starts_with('#')
+        && !target.starts_with('/')
+        && !has_scheme(target)
+}
+
+/// Check if the link target starts with a URL scheme, like `https:` or `mailto:`
+fn has_scheme(target: &str) -> bool {
+    match target.find(':') {
+        Some(end) => {
+            let scheme = &target[..end];
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        }
+        None => false,
+    }
+}
+
+/// Rewrite the relative links into absolute URLs to the files in the repository
+///
+/// `path_in_repository` is the path of the directory the links are relative to, from the root of
+/// the repository. Images link to the raw files, other links to the file pages. Links to files
+/// outside of the repository are left as they are.
+pub fn absolute_links(
+    lines: Vec<String>,
+    repository: &str,
+    branch: &str,
+    path_in_repository: &Path,
+) -> Vec<String> {
+    let repository = repository.trim_end_matches('/');
+    let repository = repository.strip_suffix(".git").unwrap_or(repository);
+    // GitLab separates the repository path from the file pages with `/-/`
+    let separator = if repository.contains("gitlab") {
+        "/-"
+    } else {
+        ""
+    };
+
+    rewrite_relative_links(lines, |target| {
+        let path = normalize(&path_in_repository.join(target));
+        if path.starts_with("..") {
+            return target.to_owned();
+        }
+        let path = path
+            .to_string_lossy()
+            .replace(std::path::MAIN_SEPARATOR, "/");
+        let kind = if is_image(&path) { "raw" } else { "blob" };
+        format!("{}{}/{}/{}/{}", repository, separator, kind, branch, path)
+    })
+}
+
+/// Copy of an image found relative to the source file, to the same path next to the readme
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssetCopy {
+    /// Path of the image, next to the source file
+    pub from: PathBuf,
+    /// Path the image is copied to, in the directory of the readme
+    pub to: PathBuf,
+}
+
+impl AssetCopy {
+    /// Copy the image, creating the directories it is copied to
+    pub fn copy(&self) -> io::Result<()> {
+        if let Some(parent) = self.to.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(&self.from, &self.to).map(|_| ())
+    }
+}
+
+/// Rewrite the links to images relative to `source_dir` so they work from `output_dir`, the
+/// directory of the readme
+///
+/// Only the images found relative to the source file, and not relative to the readme, are
+/// rewritten. With `copy`, the links are left as they are and the copies of the images to the
+/// same path in `output_dir` are returned instead, to be made when the readme is written.
+/// Images outside of `source_dir` are never copied.
+pub fn rebase_assets(
+    lines: Vec<String>,
+    source_dir: &Path,
+    output_dir: &Path,
+    copy: bool,
+) -> (Vec<String>, Vec<AssetCopy>) {
+    let base = match (source_dir.canonicalize(), output_dir.canonicalize()) {
+        (Ok(source_dir), Ok(output_dir)) => relative_path(&output_dir, &source_dir),
+        _ => return (lines, Vec::new()),
+    };
+    let copies = RefCell::new(Vec::new());
+
+    let lines = rewrite_relative_links(lines, |target| {
+        let path = target.split(['#', '?']).next().unwrap_or(target);
+        if !is_image(path) || !source_dir.join(path).is_file() || output_dir.join(path).is_file() {
+            return target.to_owned();
+        }
+
+        let normalized = normalize(Path::new(path));
+        if copy && !normalized.starts_with("..") {
+            copies.borrow_mut().push(AssetCopy {
+                from: source_dir.join(path),
+                to: output_dir.join(&normalized),
+            });
+            return target.to_owned();
+        }
+
+        let rebased = normalize(&base.join(target));
+        rebased
+            .to_string_lossy()
+            .replace(std::path::MAIN_SEPARATOR, "/")
+    });
+
+    (lines, copies.into_inner())
+}
+
+/// Check if the link target is an image, from its extension
+fn is_image(path: &str) -> bool {
+    let path = path.split(['#', '?']).next().unwrap_or(path).to_lowercase();
+    [".png", ".jpg", ".jpeg", ".gif", ".svg", ".webp"]
+        .iter()
+        .any(|ext| path.ends_with(ext))
+}
+
+/// Remove the `.` components and resolve the `..` components that follow a normal component
+pub fn normalize(path: &Path) -> PathBuf {
+    let mut components: Vec<Component> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if matches!(components.last(), Some(Component::Normal(_))) => {
+                components.pop();
+            }
+            _ => components.push(component),
+        }
+    }
+    components.iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_path_to_subdirectory() {
+        let result = relative_path(Path::new("/project"), Path::new("/project/docs/guide"));
+        assert_eq!(result, PathBuf::from("docs/guide"));
+    }
+
+    #[test]
+    fn relative_path_to_sibling() {
+        let result = relative_path(Path::new("/project/crate"), Path::new("/project/docs"));
+        assert_eq!(result, PathBuf::from("../docs"));
+    }
+
+    fn absolute(line: &str, repository: &str, path_in_repository: &str) -> String {
+        let lines = vec![line.to_owned()];
+        absolute_links(lines, repository, "HEAD", Path::new(path_in_repository)).remove(0)
+    }
+
+    #[test]
+    fn absolute_links_github() {
+        assert_eq!(
+            absolute(
+                "See [contributing](CONTRIBUTING.md) and ![logo](./assets/logo.png).",
+                "https://github.com/owner/repo",
+                ""
+            ),
+            "See [contributing](https://github.com/owner/repo/blob/HEAD/CONTRIBUTING.md) and \
+             ![logo](https://github.com/owner/repo/raw/HEAD/assets/logo.png)."
+        );
+    }
+
+    #[test]
+    fn absolute_links_in_workspace_member() {
+        assert_eq!(
+            absolute(
+                "[design](../../docs/design.md#goals) [guide]: guide.md",
+                "https://gitlab.com/owner/repo.git",
+                "crates/member"
+            ),
+            "[design](https://gitlab.com/owner/repo/-/blob/HEAD/docs/design.md#goals) \
+             [guide]: guide.md"
+        );
+        assert_eq!(
+            absolute(
+                "[guide]: guide.md",
+                "https://github.com/owner/repo/",
+                "crates/member"
+            ),
+            "[guide]: https://github.com/owner/repo/blob/HEAD/crates/member/guide.md"
+        );
+    }
+
+    #[test]
+    fn absolute_links_keep_other_links() {
+        let line = "[a](https://example.com) [b](#anchor) [c](../outside.md) `[d](e.md)`";
+        assert_eq!(absolute(line, "https://github.com/owner/repo", ""), line);
+    }
+
+    #[test]
+    fn absolute_links_after_nested_fences() {
+        let lines = [
+            "~~~text",
+            "```",
+            "[a](a.md)",
+            "~~~",
+            "",
+            "See [b](b.md).",
+            "",
+            "    [c](c.md)",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+        let result = absolute_links(
+            lines,
+            "https://github.com/owner/repo",
+            "HEAD",
+            Path::new(""),
+        );
+        assert_eq!(
+            result,
+            [
+                "~~~text",
+                "```",
+                "[a](a.md)",
+                "~~~",
+                "",
+                "See [b](https://github.com/owner/repo/blob/HEAD/b.md).",
+                "",
+                "    [c](c.md)"
+            ]
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! Convert the processed readme from markdown to reStructuredText
+//!
+//! The headings are underlined, with `=`, `-`, `~`, `^`, `"` and `'` from level 1 to 6, the code
+//! blocks become `code-block` directives, the images alone in their paragraph, like badges, become
+//! `image` directives, the tables `list-table` directives, the GitHub alerts, like `> [!NOTE]`,
+//! admonitions, like `.. note::`, and the links become anonymous hyperlinks. Lists, bold and
+//! italic text are written the same in both.
+
+use super::markdown::{comment, image_paragraph, indent, join_blocks, parse, Block, Inline};
+
+/// Characters underlining the headings, by level
+const UNDERLINES: [char; 6] = ['=', '-', '~', '^', '"', '\''];
+
+/// Convert the markdown `readme` to reStructuredText
+pub fn markdown_to_rst(readme: &str) -> String {
+    blocks(&parse(readme), false).join("\n").trim().to_owned()
+}
+
+/// Convert the markdown `blocks` to the lines of reStructuredText, with a blank line between them
+/// unless `tight`
+fn blocks(blocks: &[Block], tight: bool) -> Vec<String> {
+    join_blocks(blocks.iter().map(block), tight)
+}
+
+/// Convert a markdown block to the lines of reStructuredText
+fn block(block: &Block) -> Vec<String> {
+    match block {
+        Block::Heading { level, text } => {
+            let text = inline(text);
+            let underline = UNDERLINES[(*level).clamp(1, 6) - 1]
+                .to_string()
+                .repeat(text.chars().count().max(1));
+            vec![text, underline]
+        }
+        Block::Paragraph(text) => match image_paragraph(text) {
+            Some(images) => join_blocks(
+                images.into_iter().map(|image| {
+                    let mut lines = vec![directive("image", Some(image.source))];
+                    if let Some(link) = image.link {
+                        lines.push(format!("   :target: {}", link));
+                    }
+                    if !image.alt.is_empty() {
+                        lines.push(format!("   :alt: {}", image.alt));
+                    }
+                    lines
+                }),
+                false,
+            ),
+            None => lines(&inline(text)),
+        },
+        Block::Code { language, code } => {
+            let mut lines = vec![directive("code-block", language.as_deref()), String::new()];
+            lines.extend(indent(self::lines(code), 3, None));
+            lines
+        }
+        // the content of an admonition and of a block quote is indented
+        Block::Quote { alert, blocks } => {
+            let content = indent(self::blocks(blocks, false), 3, None);
+            match alert {
+                Some(alert) => {
+                    let mut lines =
+                        vec![directive(&alert.name().to_lowercase(), None), String::new()];
+                    lines.extend(content);
+                    lines
+                }
+                None => content,
+            }
+        }
+        // the blocks of an item are separated by blank lines, as a nested list needs them
+        Block::List {
+            start,
+            tight,
+            items,
+        } => join_blocks(
+            items.iter().enumerate().map(|(i, item)| {
+                let marker = match start {
+                    Some(start) => format!("{}. ", start + i as u64),
+                    None => "- ".to_owned(),
+                };
+                indent(self::blocks(item, false), marker.len(), Some(&marker))
+            }),
+            *tight,
+        ),
+        Block::Table { header, rows, .. } => {
+            let mut lines = vec![
+                directive("list-table", None),
+                "   :header-rows: 1".to_owned(),
+                String::new(),
+            ];
+            for row in std::iter::once(header).chain(rows) {
+                for (j, cell) in row.iter().enumerate() {
+                    let bullet = match j {
+                        0 => "   * -",
+                        _ => "     -",
+                    };
+                    lines.push(format!("{} {}", bullet, inline(cell)).trim_end().to_owned());
+                }
+            }
+            lines
+        }
+        Block::Html(html) => match comment(html) {
+            Some(text) => vec![format!(".. {}", text)],
+            None => lines(html),
+        },
+        Block::Rule => vec!["----".to_owned()],
+    }
+}
+
+/// Get the first line of the directive `name`, with its `argument`
+fn directive(name: &str, argument: Option<&str>) -> String {
+    match argument {
+        Some(argument) => format!(".. {}:: {}", name, argument),
+        None => format!(".. {}::", name),
+    }
+}
+
+/// Split `text` into lines
+fn lines(text: &str) -> Vec<String> {
+    text.lines().map(ToOwned::to_owned).collect()
+}
+
+/// Convert the inline markup of `text`: the code spans and the links, the emphasis being written
+/// the same in both
+fn inline(text: &[Inline]) -> String {
+    text.iter()
+        .map(|inline| match inline {
+            Inline::Text(text) | Inline::Html(text) => text.clone(),
+            Inline::Code(code) => format!("``{}``", code),
+            Inline::Emphasis(text) => format!("*{}*", self::inline(text)),
+            Inline::Strong(text) => format!("**{}**", self::inline(text)),
+            Inline::Strikethrough(text) => self::inline(text),
+            // the links, and the images, become anonymous hyperlinks
+            Inline::Link { target, text, .. } => match self::inline(text) {
+                label if label.is_empty() || label == *target => format!("`<{}>`__", target),
+                label => format!("`{} <{}>`__", label, target),
+            },
+            Inline::Image { source, alt, .. } => match alt.as_str() {
+                "" => format!("`<{}>`__", source),
+                alt => format!("`{} <{}>`__", alt, source),
+            },
+            Inline::SoftBreak | Inline::HardBreak => "\n".to_owned(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::markdown_to_rst;
+
+    #[test]
+    fn convert_readme() {
+        let readme = "\
+[![Crates.io](https://img.shields.io/crates/v/a.svg)](https://crates.io/crates/a)
+
+# my-crate
+
+Use `cargo add` and see [the guide][guide] or [the wiki](https://example.com/wiki_(rust)).
+> Note.
+
+## Usage
+
+```rust,no_run
+# not a heading
+let a = 1;
+```
+
+- one
+- two
+
+| Name | Value |
+| ---- | ----- |
+| `a`  | 1     |
+
+> [!WARNING]
+> Not stable.
+
+[guide]: https://example.com/guide";
+
+        assert_eq!(
+            markdown_to_rst(readme),
+            "\
+.. image:: https://img.shields.io/crates/v/a.svg
+   :target: https://crates.io/crates/a
+   :alt: Crates.io
+
+my-crate
+========
+
+Use ``cargo add`` and see `the guide <https://example.com/guide>`__ or `the wiki <https://example.com/wiki_(rust)>`__.
+
+   Note.
+
+Usage
+-----
+
+.. code-block:: rust
+
+   # not a heading
+   let a = 1;
+
+- one
+- two
+
+.. list-table::
+   :header-rows: 1
+
+   * - Name
+     - Value
+   * - ``a``
+     - 1
+
+.. warning::
+
+   Not stable."
+        );
+    }
+}
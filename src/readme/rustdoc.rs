@@ -0,0 +1,159 @@
+//! Read the crate docs from the JSON output of rustdoc
+//!
+//! Running `cargo +nightly rustdoc -- -Z unstable-options --output-format json` gives the docs as
+//! rustdoc sees them, including the docs generated by macros, `include_str!` and `cfg_attr`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::Error;
+
+/// Run rustdoc on the library target, or on the binary target `bin`, and extract the crate docs
+///
+/// The `cfgs`, like `docsrs` or `feature="serde"`, are passed to rustdoc with `--cfg`.
+pub fn extract_docs(
+    project_root: &Path,
+    lib_name: &str,
+    bin: Option<&str>,
+    cfgs: &[String],
+) -> Result<Vec<String>, Error> {
+    let manifest_path = project_root.join("Cargo.toml");
+
+    let mut command = Command::new("cargo");
+    command
+        .arg("+nightly")
+        .arg("rustdoc")
+        .arg("--quiet")
+        .arg("--manifest-path")
+        .arg(&manifest_path);
+    match bin {
+        Some(bin) => command.args(["--bin", bin]),
+        None => command.arg("--lib"),
+    };
+    command.args(["--", "-Z", "unstable-options", "--output-format", "json"]);
+    for cfg in cfgs {
+        command.args(["--cfg", cfg]);
+    }
+
+    let output = command
+        .output()
+        .map_err(|e| Error::Rustdoc(format!("Could not run `cargo +nightly rustdoc`: {}", e)))?;
+    if !output.status.success() {
+        return Err(Error::Rustdoc(format!(
+            "`cargo +nightly rustdoc` failed:\n{}",
+            String::from_utf8_lossy(&output.stderr).trim_end()
+        )));
+    }
+
+    let crate_name = bin.unwrap_or(lib_name).replace('-', "_");
+    let json_path = target_dir(&manifest_path)?
+        .join("doc")
+        .join(format!("{}.json", crate_name));
+    let json = fs::read_to_string(&json_path).map_err(|e| Error::io(&json_path, e))?;
+
+    crate_docs(&json)
+}
+
+/// Get the target directory of the package, where rustdoc writes its output
+fn target_dir(manifest_path: &Path) -> Result<PathBuf, Error> {
+    let output = Command::new("cargo")
+        .args(["metadata", "--no-deps", "--format-version", "1"])
+        .arg("--manifest-path")
+        .arg(manifest_path)
+        .output()
+        .map_err(|e| Error::Rustdoc(format!("Could not run `cargo metadata`: {}", e)))?;
+    if !output.status.success() {
+        return Err(Error::Rustdoc(format!(
+            "`cargo metadata` failed:\n{}",
+            String::from_utf8_lossy(&output.stderr).trim_end()
+        )));
+    }
+
+    let metadata: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| Error::Rustdoc(format!("Invalid `cargo metadata` output: {}", e)))?;
+    metadata["target_directory"]
+        .as_str()
+        .map(PathBuf::from)
+        .ok_or_else(|| {
+            Error::Rustdoc("`cargo metadata` did not give the target directory".to_owned())
+        })
+}
+
+/// Get the docs of the root module from the rustdoc JSON output
+fn crate_docs(json: &str) -> Result<Vec<String>, Error> {
+    let krate: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| Error::Rustdoc(format!("Invalid rustdoc output: {}", e)))?;
+
+    // the item ids are strings in older format versions, and numbers in newer ones
+    let root = match &krate["root"] {
+        serde_json::Value::String(id) => id.clone(),
+        serde_json::Value::Number(id) => id.to_string(),
+        _ => {
+            return Err(Error::Rustdoc(
+                "Invalid rustdoc output: the root item is missing".to_owned(),
+            ))
+        }
+    };
+    let docs = krate["index"][&root]["docs"].as_str().unwrap_or_default();
+
+    // the trailing spaces are kept, as two of them make a hard line break
+    Ok(docs.lines().map(str::to_owned).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::crate_docs;
+
+    #[test]
+    fn read_crate_docs() {
+        let json = r#"{
+            "root": 0,
+            "index": {
+                "0": { "name": "my_crate", "docs": "My crate  \n\n# Examples" },
+                "1": { "name": "item", "docs": "Item docs" }
+            }
+        }"#;
+
+        assert_eq!(crate_docs(json).unwrap(), ["My crate  ", "", "# Examples"]);
+    }
+
+    #[test]
+    fn read_crate_docs_from_rustdoc_output() {
+        let json = include_str!("../../tests/rustdoc-json/rustdoc.json");
+
+        assert_eq!(
+            crate_docs(json).unwrap(),
+            [
+                "Docs generated by a macro.",
+                "",
+                "The line ends with a hard break  ",
+                "before this one.",
+                "",
+                "# Examples",
+                "",
+                "```",
+                "# let hidden = true;",
+                "let answer = 42;",
+                "```"
+            ]
+        );
+    }
+
+    #[test]
+    fn read_crate_docs_with_string_ids() {
+        let json = r#"{
+            "root": "0:0:1",
+            "index": { "0:0:1": { "name": "my_crate", "docs": "My crate" } }
+        }"#;
+
+        assert_eq!(crate_docs(json).unwrap(), ["My crate"]);
+    }
+
+    #[test]
+    fn read_crate_without_docs() {
+        let json = r#"{ "root": 0, "index": { "0": { "name": "my_crate", "docs": null } } }"#;
+
+        assert!(crate_docs(json).unwrap().is_empty());
+    }
+}
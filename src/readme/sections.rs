@@ -0,0 +1,257 @@
+//! Select the sections of the docs by their heading
+//!
+//! A section starts at a markdown heading and ends before the next heading of the same or a
+//! higher level. Headings are matched by their text, ignoring case, and lines inside code blocks
+//! are never taken for headings. The footnote definitions of the removed lines that are still
+//! referenced are kept.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::fences::CodeBlocks;
+use super::footnotes;
+use crate::Error;
+
+lazy_static! {
+    // Is this a markdown heading? Captures its level and text
+    static ref RE_HEADING: Regex = Regex::new(r"^ {0,3}(?P<level>#{1,6})(?:[ \t]+(?P<text>.*?))?[ \t]*$").unwrap();
+}
+
+/// Remove the sections whose heading is one of `headings`, along with their content
+pub fn exclude_sections(lines: Vec<String>, headings: &[String]) -> Vec<String> {
+    if headings.is_empty() {
+        return lines;
+    }
+
+    let mut excluded_level = None;
+    let mut finder = Headings::default();
+
+    let (mut lines, removed): (Vec<String>, Vec<String>) = lines.into_iter().partition(|line| {
+        if let Some((level, text)) = finder.heading(line) {
+            if excluded_level.is_some_and(|excluded| level <= excluded) {
+                excluded_level = None;
+            }
+            if excluded_level.is_none() && headings.iter().any(|h| matches_heading(h, &text)) {
+                excluded_level = Some(level);
+            }
+        }
+        excluded_level.is_none()
+    });
+
+    // the blank lines before a removed last section are left over
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+
+    footnotes::restore_definitions(lines, &removed)
+}
+
+/// Keep only the content of the section whose heading is `heading`, without the heading itself
+///
+/// An error is returned if there is no such section.
+pub fn only_section(lines: Vec<String>, heading: &str) -> Result<Vec<String>, Error> {
+    let mut section_level = None;
+    let mut found = false;
+    let mut finder = Headings::default();
+
+    let (mut lines, removed): (Vec<String>, Vec<String>) = lines.into_iter().partition(|line| {
+        if let Some((level, text)) = finder.heading(line) {
+            if section_level.is_some_and(|section| level <= section) {
+                section_level = None;
+            }
+            if !found && matches_heading(heading, &text) {
+                found = true;
+                section_level = Some(level);
+                return false;
+            }
+        }
+        section_level.is_some()
+    });
+
+    if !found {
+        return Err(Error::MissingSection(heading.to_owned()));
+    }
+
+    while lines.first().is_some_and(|l| l.trim().is_empty()) {
+        lines.remove(0);
+    }
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+
+    Ok(footnotes::restore_definitions(lines, &removed))
+}
+
+/// Take the title of the docs, their first line when it is a top level heading, like `# Title`
+///
+/// The heading and the blank lines after it are removed from `lines`.
+pub fn take_title(lines: &mut Vec<String>) -> Option<String> {
+    let first = lines.iter().position(|l| !l.trim().is_empty())?;
+    let title = match Headings::default().heading(&lines[first]) {
+        Some((1, text)) if !text.is_empty() => text,
+        _ => return None,
+    };
+
+    let end = lines[first + 1..]
+        .iter()
+        .position(|l| !l.trim().is_empty())
+        .map_or(lines.len(), |p| first + 1 + p);
+    lines.drain(..end);
+
+    Some(title)
+}
+
+/// Check if the heading `text` is the heading named `name`
+pub fn matches_heading(name: &str, text: &str) -> bool {
+    name.trim().eq_ignore_ascii_case(text)
+}
+
+/// Find the headings in a sequence of lines, skipping code blocks
+#[derive(Default)]
+pub struct Headings {
+    code: CodeBlocks,
+}
+
+impl Headings {
+    /// Get the level and text of `line` if it is a heading
+    pub fn heading(&mut self, line: &str) -> Option<(usize, String)> {
+        if self.code.kind(line).is_code() {
+            return None;
+        }
+
+        RE_HEADING.captures(line).map(|cap| {
+            let text = cap.name("text").map_or("", |t| t.as_str());
+            // remove the optional closing sequence, like in `## Usage ##`
+            let text = text.trim_end_matches('#').trim_end();
+            (cap["level"].len(), text.to_owned())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{exclude_sections, only_section, take_title};
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(ToOwned::to_owned).collect()
+    }
+
+    const INPUT: &str = "\
+Intro
+
+# Usage
+
+Use it.
+
+## Implementation notes
+
+Internal details.
+
+```
+# not a heading
+```
+
+### Details
+
+More details.
+
+## Examples
+
+Example.
+
+# License
+
+MIT";
+
+    #[test]
+    fn exclude_section() {
+        let result = exclude_sections(lines(INPUT), &["implementation notes".to_owned()]);
+
+        assert_eq!(
+            result,
+            lines("Intro\n\n# Usage\n\nUse it.\n\n## Examples\n\nExample.\n\n# License\n\nMIT")
+        );
+    }
+
+    #[test]
+    fn exclude_several_sections() {
+        let result = exclude_sections(lines(INPUT), &["Usage".to_owned(), "License".to_owned()]);
+
+        assert_eq!(result, lines("Intro\n"));
+    }
+
+    #[test]
+    fn exclude_section_with_closing_sequence() {
+        let result = exclude_sections(lines("# A #\n\na\n\n# B\n\nb"), &["a".to_owned()]);
+
+        assert_eq!(result, lines("# B\n\nb"));
+    }
+
+    #[test]
+    fn exclude_nothing() {
+        assert_eq!(exclude_sections(lines(INPUT), &[]), lines(INPUT));
+        assert_eq!(
+            exclude_sections(lines(INPUT), &["not a heading".to_owned()]),
+            lines(INPUT)
+        );
+    }
+
+    #[test]
+    fn only_one_section() {
+        let result = only_section(lines(INPUT), "implementation notes").unwrap();
+
+        assert_eq!(
+            result,
+            lines("Internal details.\n\n```\n# not a heading\n```\n\n### Details\n\nMore details.")
+        );
+    }
+
+    #[test]
+    fn only_last_section() {
+        assert_eq!(only_section(lines(INPUT), "License").unwrap(), ["MIT"]);
+    }
+
+    #[test]
+    fn only_missing_section() {
+        assert!(only_section(lines(INPUT), "not a heading").is_err());
+    }
+
+    #[test]
+    fn keep_referenced_footnotes() {
+        let input = lines("Intro[^1]\n\n# Notes\n\nNotes.\n\n[^1]: The note.");
+
+        assert_eq!(
+            exclude_sections(input.clone(), &["notes".to_owned()]),
+            lines("Intro[^1]\n\n[^1]: The note.")
+        );
+        assert_eq!(
+            only_section(
+                lines("# Usage\n\nUse it[^1].\n\n# Notes\n\n[^1]: The note."),
+                "usage"
+            )
+            .unwrap(),
+            lines("Use it[^1].\n\n[^1]: The note.")
+        );
+    }
+
+    #[test]
+    fn take_first_heading_as_title() {
+        let mut docs = lines("\n# My Crate #\n\nIntro\n\n# Usage");
+
+        assert_eq!(take_title(&mut docs), Some("My Crate".to_owned()));
+        assert_eq!(docs, lines("Intro\n\n# Usage"));
+    }
+
+    #[test]
+    fn take_no_title() {
+        let input = lines("Intro\n\n# Usage");
+        let mut docs = input.clone();
+        assert_eq!(take_title(&mut docs), None);
+        assert_eq!(docs, input);
+
+        let input = lines("## Not a title\n\nIntro");
+        let mut docs = input.clone();
+        assert_eq!(take_title(&mut docs), None);
+        assert_eq!(docs, input);
+    }
+}
@@ -0,0 +1,127 @@
+//! Count the contents of the doc comments
+//!
+//! The doc comments are counted as written, before they are processed for the readme, so the code
+//! blocks still have their doc test attributes, like `ignore`.
+
+use super::fences::{fence_attributes, fenced, LineKind};
+use super::links::{code_span_len, definition};
+use super::sections::Headings;
+
+/// Statistics about the doc comments of a crate
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DocStats {
+    /// Lines of doc comments, blank lines included
+    pub doc_lines: usize,
+    pub code_blocks: usize,
+    /// Code blocks marked `ignore`, which are not run as doc tests
+    pub ignored_code_blocks: usize,
+    pub headings: usize,
+    /// Inline links and images, reference links and definitions, and autolinks
+    pub links: usize,
+    /// Words outside code blocks
+    pub words: usize,
+}
+
+/// Count the contents of the doc comment `lines`
+pub fn doc_stats(lines: &[String]) -> DocStats {
+    let mut stats = DocStats {
+        doc_lines: lines.len(),
+        ..DocStats::default()
+    };
+    let mut headings = Headings::default();
+
+    for (kind, line) in fenced(lines) {
+        let heading = headings.heading(line);
+
+        match kind {
+            LineKind::Open => {
+                stats.code_blocks += 1;
+                if fence_attributes(line).any(|attribute| attribute == "ignore") {
+                    stats.ignored_code_blocks += 1;
+                }
+                continue;
+            }
+            LineKind::Code | LineKind::Close | LineKind::Indented => continue,
+            LineKind::Text => {}
+        }
+
+        if heading.is_some() {
+            stats.headings += 1;
+        }
+        stats.links += count_links(line);
+        stats.words += line
+            .split_whitespace()
+            .filter(|word| word.chars().any(char::is_alphanumeric))
+            .count();
+    }
+
+    stats
+}
+
+/// Count the links of a line, outside its code spans
+fn count_links(line: &str) -> usize {
+    if definition(line).is_some() {
+        return 1;
+    }
+
+    let mut count = 0;
+    let mut rest = line;
+    while let Some(pos) = rest.find(['`', ']', '<']) {
+        rest = &rest[pos..];
+        if rest.starts_with('`') {
+            rest = &rest[code_span_len(rest)..];
+            continue;
+        }
+        // the end of the link text, followed by the target or the label, or an autolink
+        if ["](", "][", "<http://", "<https://"]
+            .iter()
+            .any(|start| rest.starts_with(start))
+        {
+            count += 1;
+        }
+        rest = &rest[1..];
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{doc_stats, DocStats};
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(ToOwned::to_owned).collect()
+    }
+
+    #[test]
+    fn count_docs() {
+        let docs = "\
+My crate, see [the guide](docs/guide.md) and <https://example.com>.
+
+# Examples
+
+```
+let a = 1; // [not](a/link)
+```
+
+```rust,ignore
+# not a heading
+```
+
+Use `[not](a/link)` with [the reference][guide].
+
+[guide]: https://example.com/guide";
+
+        assert_eq!(
+            doc_stats(&lines(docs)),
+            DocStats {
+                doc_lines: 15,
+                code_blocks: 2,
+                ignored_code_blocks: 1,
+                headings: 1,
+                links: 4,
+                words: 15,
+            }
+        );
+    }
+}
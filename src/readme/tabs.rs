@@ -0,0 +1,62 @@
+//! Expand the tabs of the code blocks into spaces
+//!
+//! Tabs are rendered with different widths, so the code blocks using them for alignment may not
+//! look as in the source. Each tab is replaced by the spaces up to the next tab stop, which keeps
+//! the alignment. The tabs outside code blocks are left untouched.
+
+use super::fences::{fenced, LineKind};
+
+/// Expand the tabs of the lines inside code blocks to tab stops every `width` columns
+pub fn expand_tabs(lines: Vec<String>, width: usize) -> Vec<String> {
+    fenced(lines)
+        .map(
+            |(kind, line)| match kind == LineKind::Code && line.contains('\t') {
+                true => expand_line(&line, width),
+                false => line,
+            },
+        )
+        .collect()
+}
+
+/// Replace each tab of `line` with the spaces up to the next multiple of `width` columns
+fn expand_line(line: &str, width: usize) -> String {
+    let mut result = String::new();
+    let mut column = 0;
+    for c in line.chars() {
+        if c == '\t' {
+            let spaces = width - column % width;
+            result.push_str(&" ".repeat(spaces));
+            column += spaces;
+        } else {
+            result.push(c);
+            column += 1;
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expand_tabs;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(ToOwned::to_owned).collect()
+    }
+
+    #[test]
+    fn expand_tabs_in_code_blocks() {
+        let input = "Some\ttext\n\n```rust\nfn main() {\n\tlet a\t= 1;\n\tlet abcd\t= 2;\n}\n```";
+        let expected =
+            "Some\ttext\n\n```rust\nfn main() {\n    let a   = 1;\n    let abcd    = 2;\n}\n```";
+
+        assert_eq!(expand_tabs(lines(input), 4), lines(expected));
+    }
+
+    #[test]
+    fn expand_tabs_with_width() {
+        let input = "~~~\na\tb\n\tc\n~~~";
+
+        assert_eq!(expand_tabs(lines(input), 2), lines("~~~\na b\n  c\n~~~"));
+    }
+}
@@ -1,4 +1,6 @@
-use crate::config::Manifest;
+use super::sections::{matches_heading, Headings};
+use crate::config::{badges, Manifest};
+use crate::Error;
 
 /// Renders the template
 ///
@@ -8,21 +10,44 @@ pub fn render(
     readme: String,
     cargo: &Manifest,
     add_title: bool,
+    always_add_title: bool,
     add_badges: bool,
     add_license: bool,
-) -> Result<String, String> {
+) -> Result<String, Error> {
     let title: &str = &cargo.name;
 
     let badges: Vec<&str> = cargo.badges.iter().map(AsRef::as_ref).collect();
     let badges: &[&str] = badges.as_ref();
 
-    let license: Option<&str> = cargo.license.as_ref().map(AsRef::as_ref);
+    let license = cargo.license_link();
+    let license: Option<&str> = license.as_deref();
 
     let version: &str = cargo.version.as_ref();
 
+    let installation = installation(cargo);
+
+    let keywords = keywords(cargo);
+
+    let categories = categories(cargo);
+
+    let substitutions = Substitutions {
+        title,
+        badges,
+        license,
+        version,
+        installation: &installation,
+        keywords: &keywords,
+        categories: &categories,
+    };
+
     if let Some(template) = template {
-        process_template(template, readme, title, badges, license, version)
+        process_template(template, readme, &substitutions)
     } else {
+        // a heading of the docs with the crate name is replaced by the title, not duplicated
+        let readme = match add_title && !always_add_title {
+            true => remove_title_heading(readme, title),
+            false => readme,
+        };
         process_string(
             readme,
             title,
@@ -35,6 +60,18 @@ pub fn render(
     }
 }
 
+/// Values substituted in the template
+#[derive(Clone, Copy, Default)]
+struct Substitutions<'a> {
+    title: &'a str,
+    badges: &'a [&'a str],
+    license: Option<&'a str>,
+    version: &'a str,
+    installation: &'a str,
+    keywords: &'a str,
+    categories: &'a str,
+}
+
 /// Process the substitutions of the template
 ///
 /// Available variable:
@@ -43,29 +80,37 @@ pub fn render(
 /// - `{{badges}}` badges defined in `Cargo.toml`
 /// - `{{license}}` license defined in `Cargo.toml`
 /// - `{{version}}` version defined in `Cargo.toml`
+/// - `{{installation}}` installation instructions, depending on the crate being a library or binary
+/// - `{{keywords}}` keywords defined in `Cargo.toml`, as links to crates.io
+/// - `{{categories}}` categories defined in `Cargo.toml`, as links to crates.io
 fn process_template(
     mut template: String,
     readme: String,
-    title: &str,
-    badges: &[&str],
-    license: Option<&str>,
-    version: &str,
-) -> Result<String, String> {
+    substitutions: &Substitutions,
+) -> Result<String, Error> {
+    let Substitutions {
+        title,
+        badges,
+        license,
+        version,
+        installation,
+        keywords,
+        categories,
+    } = *substitutions;
+
     template = template.trim_end_matches("\n").to_owned();
 
     if !template.contains("{{readme}}") {
-        return Err("Missing `{{readme}}` in template".to_owned());
+        return Err(Error::TemplateMissingReadmeTag);
     }
 
     if template.contains("{{crate}}") {
-        template = template.replace("{{crate}}", &title);
+        template = template.replace("{{crate}}", title);
     }
 
     if template.contains("{{badges}}") {
         if badges.is_empty() {
-            return Err(
-                "`{{badges}}` was found in template but no badges were provided".to_owned(),
-            );
+            return Err(Error::MissingBadges);
         }
         let badges = badges.join("\n");
         template = template.replace("{{badges}}", &badges);
@@ -73,20 +118,74 @@ fn process_template(
 
     if template.contains("{{license}}") {
         if let Some(license) = license {
-            template = template.replace("{{license}}", &license);
+            template = template.replace("{{license}}", license);
         } else {
-            return Err(
-                "`{{license}}` was found in template but no license was provided".to_owned(),
-            );
+            return Err(Error::MissingLicense);
         }
     }
 
     template = template.replace("{{version}}", version);
 
+    template = template.replace("{{installation}}", installation);
+
+    template = template.replace("{{keywords}}", keywords);
+
+    template = template.replace("{{categories}}", categories);
+
     let result = template.replace("{{readme}}", &readme);
     Ok(result)
 }
 
+/// Get the values of the template variables, except `{{readme}}`, by name
+///
+/// The variables without value, like `{{license}}` when the crate has no license, are `None`.
+pub fn variables(cargo: &Manifest) -> Vec<(&'static str, Option<String>)> {
+    vec![
+        ("crate", Some(cargo.name.clone())),
+        (
+            "badges",
+            Some(cargo.badges.join("\n")).filter(|b| !b.is_empty()),
+        ),
+        ("license", cargo.license_link()),
+        ("version", Some(cargo.version.clone())),
+        ("installation", Some(installation(cargo))),
+        ("keywords", Some(keywords(cargo))),
+        ("categories", Some(categories(cargo))),
+    ]
+}
+
+/// Build the keywords of the crate, as links to crates.io, one per line
+fn keywords(cargo: &Manifest) -> String {
+    cargo
+        .keywords
+        .iter()
+        .map(|k| badges::keyword(k))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Build the categories of the crate, as links to crates.io, one per line
+fn categories(cargo: &Manifest) -> String {
+    cargo
+        .categories
+        .iter()
+        .map(|c| badges::category(c))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Build the installation instructions for the crate
+///
+/// Libraries get a `cargo add` snippet pinned to the current version, binaries get a
+/// `cargo install` snippet.
+fn installation(cargo: &Manifest) -> String {
+    if cargo.has_lib {
+        format!("```sh\ncargo add {}@{}\n```", cargo.name, cargo.version)
+    } else {
+        format!("```sh\ncargo install {}\n```", cargo.name)
+    }
+}
+
 /// Process output without template
 fn process_string(
     mut readme: String,
@@ -96,7 +195,7 @@ fn process_string(
     add_title: bool,
     add_badges: bool,
     add_license: bool,
-) -> Result<String, String> {
+) -> Result<String, Error> {
     if add_title {
         readme = prepend_title(readme, title);
     }
@@ -116,7 +215,7 @@ fn process_string(
 
 /// Prepend badges to output string
 fn prepend_badges(readme: String, badges: &[&str]) -> String {
-    if badges.len() > 0 {
+    if !badges.is_empty() {
         let badges = badges.join("\n");
         if !readme.is_empty() {
             format!("{}\n\n{}", badges, readme)
@@ -138,6 +237,18 @@ fn prepend_title(readme: String, crate_name: &str) -> String {
     }
 }
 
+/// Remove the first line of `readme` if it is a heading with the crate name, ignoring case, along
+/// with the blank lines after it
+fn remove_title_heading(readme: String, crate_name: &str) -> String {
+    let trimmed = readme.trim_start();
+    let (first, rest) = trimmed.split_once('\n').unwrap_or((trimmed, ""));
+
+    match Headings::default().heading(first) {
+        Some((_, text)) if matches_heading(crate_name, &text) => rest.trim_start().to_owned(),
+        _ => readme,
+    }
+}
+
 /// Append license to output string
 fn append_license(readme: String, license: &str) -> String {
     let license = format!("License: {}", license);
@@ -150,20 +261,29 @@ fn append_license(readme: String, license: &str) -> String {
 
 #[cfg(test)]
 mod tests {
+    use super::Substitutions;
+    use crate::Error;
+
     const TEMPLATE_MINIMAL: &str = "{{readme}}";
     const TEMPLATE_WITH_TITLE: &str = "# {{crate}}\n\n{{readme}}";
     const TEMPLATE_WITH_BADGES: &str = "{{badges}}\n\n{{readme}}";
     const TEMPLATE_WITH_LICENSE: &str = "{{readme}}\n\n{{license}}";
     const TEMPLATE_WITH_VERSION: &str = "{{readme}}\n\n{{version}}";
+    const TEMPLATE_WITH_INSTALLATION: &str = "{{installation}}\n\n{{readme}}";
+    const TEMPLATE_WITH_KEYWORDS: &str = "{{keywords}}\n{{categories}}\n\n{{readme}}";
     const TEMPLATE_FULL: &str =
         "{{badges}}\n\n# {{crate}}\n\n{{readme}}\n\n{{license}}\n\n{{version}}";
 
     // process template
     #[test]
     fn template_without_readme_should_fail() {
-        let result = super::process_template(String::new(), String::new(), "", &[], None, "");
-        assert!(result.is_err());
-        assert_eq!("Missing `{{readme}}` in template", result.unwrap_err());
+        let result =
+            super::process_template(String::new(), String::new(), &Substitutions::default());
+        assert!(matches!(result, Err(Error::TemplateMissingReadmeTag)));
+        assert_eq!(
+            "Missing `{{readme}}` in template",
+            result.unwrap_err().to_string()
+        );
     }
 
     #[test]
@@ -171,15 +291,12 @@ mod tests {
         let result = super::process_template(
             TEMPLATE_WITH_BADGES.to_owned(),
             String::new(),
-            "",
-            &[],
-            None,
-            "",
+            &Substitutions::default(),
         );
-        assert!(result.is_err());
+        assert!(matches!(result, Err(Error::MissingBadges)));
         assert_eq!(
             "`{{badges}}` was found in template but no badges were provided",
-            result.unwrap_err()
+            result.unwrap_err().to_string()
         );
     }
 
@@ -188,15 +305,12 @@ mod tests {
         let result = super::process_template(
             TEMPLATE_WITH_LICENSE.to_owned(),
             String::new(),
-            "",
-            &[],
-            None,
-            "",
+            &Substitutions::default(),
         );
-        assert!(result.is_err());
+        assert!(matches!(result, Err(Error::MissingLicense)));
         assert_eq!(
             "`{{license}}` was found in template but no license was provided",
-            result.unwrap_err()
+            result.unwrap_err().to_string()
         );
     }
 
@@ -205,10 +319,7 @@ mod tests {
         let result = super::process_template(
             TEMPLATE_MINIMAL.to_owned(),
             "readme".to_owned(),
-            "",
-            &[],
-            None,
-            "",
+            &Substitutions::default(),
         );
         assert!(result.is_ok());
         assert_eq!("readme", result.unwrap());
@@ -219,10 +330,10 @@ mod tests {
         let result = super::process_template(
             TEMPLATE_WITH_TITLE.to_owned(),
             "readme".to_owned(),
-            "title",
-            &[],
-            None,
-            "",
+            &Substitutions {
+                title: "title",
+                ..Default::default()
+            },
         );
         assert!(result.is_ok());
         assert_eq!("# title\n\nreadme", result.unwrap());
@@ -233,10 +344,10 @@ mod tests {
         let result = super::process_template(
             TEMPLATE_WITH_BADGES.to_owned(),
             "readme".to_owned(),
-            "",
-            &["badge1", "badge2"],
-            None,
-            "",
+            &Substitutions {
+                badges: &["badge1", "badge2"],
+                ..Default::default()
+            },
         );
         assert!(result.is_ok());
         assert_eq!("badge1\nbadge2\n\nreadme", result.unwrap());
@@ -247,10 +358,10 @@ mod tests {
         let result = super::process_template(
             TEMPLATE_WITH_LICENSE.to_owned(),
             "readme".to_owned(),
-            "",
-            &[],
-            Some("license"),
-            "",
+            &Substitutions {
+                license: Some("license"),
+                ..Default::default()
+            },
         );
         assert!(result.is_ok());
         assert_eq!("readme\n\nlicense", result.unwrap());
@@ -261,24 +372,56 @@ mod tests {
         let result = super::process_template(
             TEMPLATE_WITH_VERSION.to_owned(),
             "readme".to_owned(),
-            "",
-            &[],
-            None,
-            "3.0.1",
+            &Substitutions {
+                version: "3.0.1",
+                ..Default::default()
+            },
         );
         assert!(result.is_ok());
         assert_eq!("readme\n\n3.0.1", result.unwrap());
     }
 
+    #[test]
+    fn template_with_installation() {
+        let result = super::process_template(
+            TEMPLATE_WITH_INSTALLATION.to_owned(),
+            "readme".to_owned(),
+            &Substitutions {
+                installation: "installation",
+                ..Default::default()
+            },
+        );
+        assert!(result.is_ok());
+        assert_eq!("installation\n\nreadme", result.unwrap());
+    }
+
+    #[test]
+    fn template_with_keywords() {
+        let result = super::process_template(
+            TEMPLATE_WITH_KEYWORDS.to_owned(),
+            "readme".to_owned(),
+            &Substitutions {
+                keywords: "keyword1\nkeyword2",
+                categories: "category",
+                ..Default::default()
+            },
+        );
+        assert!(result.is_ok());
+        assert_eq!("keyword1\nkeyword2\ncategory\n\nreadme", result.unwrap());
+    }
+
     #[test]
     fn template_full() {
         let result = super::process_template(
             TEMPLATE_FULL.to_owned(),
             "readme".to_owned(),
-            "title",
-            &["badge1", "badge2"],
-            Some("license"),
-            "3.0.2",
+            &Substitutions {
+                title: "title",
+                badges: &["badge1", "badge2"],
+                license: Some("license"),
+                version: "3.0.2",
+                ..Default::default()
+            },
         );
         assert!(result.is_ok());
         assert_eq!(
@@ -404,6 +547,19 @@ mod tests {
         assert_eq!("# title", result);
     }
 
+    // remove title heading
+    #[test]
+    fn remove_title_heading_with_crate_name() {
+        let result = super::remove_title_heading("## My-Crate\n\nreadme".into(), "my-crate");
+        assert_eq!("readme", result);
+    }
+
+    #[test]
+    fn remove_title_heading_with_other_heading() {
+        let result = super::remove_title_heading("# Other\n\nreadme".into(), "my-crate");
+        assert_eq!("# Other\n\nreadme", result);
+    }
+
     // append license
     #[test]
     fn append_license_with_filled_readme() {
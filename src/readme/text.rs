@@ -0,0 +1,156 @@
+//! Convert the processed readme from markdown to plain text
+//!
+//! The markdown syntax is stripped: the heading markers, the emphasis, the code span backticks,
+//! the HTML tags and comments, and the images alone in their paragraph, like badges. The links are
+//! written as their text followed by their target, the code blocks are indented by four spaces
+//! and the block quotes by two. The lists and the tables are written as in markdown, as they read
+//! well as text.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::markdown::{image_paragraph, indent, join_blocks, parse, Block, Inline};
+
+lazy_static! {
+    // HTML comments and tags, like `<!-- comment -->` or `<div class="warning">`
+    static ref RE_HTML: Regex =
+        Regex::new(r"(?s)<!--.*?-->|</?[A-Za-z][A-Za-z0-9-]*(?:\s[^<>]*)?/?>").unwrap();
+}
+
+/// Convert the markdown `readme` to plain text
+pub fn markdown_to_text(readme: &str) -> String {
+    blocks(&parse(readme), false).join("\n").trim().to_owned()
+}
+
+/// Convert the markdown `blocks` to the lines of text, with a blank line between them unless
+/// `tight`
+fn blocks(blocks: &[Block], tight: bool) -> Vec<String> {
+    join_blocks(blocks.iter().map(block), tight)
+}
+
+/// Convert a markdown block to the lines of text
+fn block(block: &Block) -> Vec<String> {
+    match block {
+        Block::Heading { text, .. } => lines(&inline(text)),
+        Block::Paragraph(text) => match image_paragraph(text) {
+            Some(_) => Vec::new(),
+            None => lines(&inline(text)),
+        },
+        Block::Code { code, .. } => indent(lines(code), 4, None),
+        Block::Quote { alert, blocks } => {
+            let mut lines = Vec::new();
+            if let Some(alert) = alert {
+                lines.push(format!("{}:", alert.title()));
+            }
+            lines.extend(self::blocks(blocks, false));
+            indent(lines, 2, None)
+        }
+        Block::List {
+            start,
+            tight,
+            items,
+        } => join_blocks(
+            items.iter().enumerate().map(|(i, item)| {
+                let marker = match start {
+                    Some(start) => format!("{}. ", start + i as u64),
+                    None => "- ".to_owned(),
+                };
+                indent(self::blocks(item, *tight), marker.len(), Some(&marker))
+            }),
+            *tight,
+        ),
+        Block::Table { header, rows, .. } => {
+            let row = |cells: &[Vec<Inline>]| {
+                let cells: Vec<String> = cells.iter().map(|cell| inline(cell)).collect();
+                format!("| {} |", cells.join(" | "))
+            };
+            let mut lines = vec![row(header), format!("|{}", " --- |".repeat(header.len()))];
+            lines.extend(rows.iter().map(|cells| row(cells)));
+            lines
+        }
+        // the text of the HTML, without its tags, is markdown again
+        Block::Html(html) => blocks(&parse(&RE_HTML.replace_all(html, "")), false),
+        Block::Rule => vec!["---".to_owned()],
+    }
+}
+
+/// Split `text` into lines, without their trailing spaces
+fn lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|line| line.trim_end().to_owned())
+        .collect()
+}
+
+/// Strip the inline markup of `text`: the code spans, the emphasis, the links and the HTML
+fn inline(text: &[Inline]) -> String {
+    text.iter()
+        .map(|inline| match inline {
+            Inline::Text(text) | Inline::Code(text) => text.clone(),
+            Inline::Emphasis(text) | Inline::Strong(text) | Inline::Strikethrough(text) => {
+                self::inline(text)
+            }
+            // the links are written with their target, and the images as their alternative text
+            Inline::Link { target, text, .. } => match self::inline(text) {
+                label if label.is_empty() || label == *target => target.clone(),
+                label => format!("{} ({})", label, target),
+            },
+            Inline::Image { alt, .. } => alt.clone(),
+            Inline::Html(_) => String::new(),
+            Inline::SoftBreak | Inline::HardBreak => "\n".to_owned(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::markdown_to_text;
+
+    #[test]
+    fn convert_readme() {
+        let readme = "\
+[![Crates.io](https://img.shields.io/crates/v/a.svg)](https://crates.io/crates/a)
+
+# my-crate
+
+Use `cargo add`, **now**, see [the guide][guide] and <https://example.com>.
+<div class=\"warning\">
+Not *stable* \\*yet\\*.
+</div>
+
+> [!NOTE]
+> Be careful.
+
+## Usage
+
+```rust,no_run
+# not a heading
+let a = 1;
+```
+
+* one
+  + two
+
+[guide]: https://example.com/guide";
+
+        assert_eq!(
+            markdown_to_text(readme),
+            "\
+my-crate
+
+Use cargo add, now, see the guide (https://example.com/guide) and https://example.com.
+
+Not stable *yet*.
+
+  Note:
+  Be careful.
+
+Usage
+
+    # not a heading
+    let a = 1;
+
+- one
+  - two"
+        );
+    }
+}
@@ -0,0 +1,218 @@
+//! Generate a table of contents for a markdown document
+//!
+//! The anchors are the ones the target platform gives to headings, GitHub by default: the text in
+//! lowercase, without punctuation, with the spaces replaced by `-`, and with a `-1`, `-2`... suffix
+//! for headings found several times. GitLab also collapses the runs of `-`, and rustdoc, like the
+//! other pulldown-cmark based renderers such as mdBook, only lowercases the ASCII letters.
+
+use std::collections::HashMap;
+
+use super::links::replace_links;
+use super::markers::replace_between;
+use super::sections::Headings;
+use crate::Error;
+
+/// Comment marking the start of the table of contents
+pub const TOC_START_MARKER: &str = "<!-- cargo-readme toc start -->";
+/// Comment marking the end of the table of contents
+pub const TOC_END_MARKER: &str = "<!-- cargo-readme toc end -->";
+
+/// Rules giving the anchors of the headings, which differ between platforms
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AnchorStyle {
+    /// The anchors of GitHub, which crates.io also uses
+    #[default]
+    Github,
+    /// The anchors of GitLab
+    Gitlab,
+    /// The anchors of rustdoc and mdBook, rendered with pulldown-cmark
+    Rustdoc,
+}
+
+/// Get the table of contents of `markdown`, with the headings from `min_level` to `max_level`
+///
+/// The table of contents is a nested list of links to the headings, indented from `min_level`,
+/// whose anchors follow `style`.
+pub fn table_of_contents(
+    markdown: &str,
+    min_level: usize,
+    max_level: usize,
+    style: AnchorStyle,
+) -> String {
+    let mut headings = Headings::default();
+    let mut slugs = Slugs::new(style);
+    let mut toc = String::new();
+
+    for line in markdown.lines() {
+        if let Some((level, text)) = headings.heading(line) {
+            let text = link_texts(&text);
+            // every heading gets an anchor, so the suffixes match the ones of the whole document
+            let anchor = slugs.anchor(&text);
+            if level >= min_level && level <= max_level {
+                toc.push_str(&format!(
+                    "{}- [{}](#{})\n",
+                    "  ".repeat(level - min_level),
+                    text,
+                    anchor
+                ));
+            }
+        }
+    }
+
+    toc
+}
+
+/// Replace the table of contents between the toc markers of `existing` with `toc`
+///
+/// An error is returned if the markers are missing or out of order.
+pub fn update_table_of_contents(existing: &str, toc: &str) -> Result<String, Error> {
+    replace_between(existing, TOC_START_MARKER, TOC_END_MARKER, toc)
+}
+
+/// Give unique anchors to the headings of a document
+#[derive(Default)]
+pub(crate) struct Slugs {
+    style: AnchorStyle,
+    seen: HashMap<String, usize>,
+}
+
+impl Slugs {
+    /// Give the anchors following `style`
+    fn new(style: AnchorStyle) -> Self {
+        Slugs {
+            style,
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Get the anchor of the heading `text`, whose links are replaced by their text
+    pub(crate) fn anchor(&mut self, text: &str) -> String {
+        let slug = slug(&link_texts(text), self.style);
+        let count = self.seen.entry(slug.clone()).or_insert(0);
+        let anchor = match *count {
+            0 => slug,
+            n => format!("{}-{}", slug, n),
+        };
+        *count += 1;
+        anchor
+    }
+}
+
+/// Get the slug of the heading `text` following `style`
+fn slug(text: &str, style: AnchorStyle) -> String {
+    let text = text.trim();
+    match style {
+        AnchorStyle::Github => text
+            .to_lowercase()
+            .chars()
+            .filter_map(|c| match c {
+                ' ' => Some('-'),
+                '-' | '_' => Some(c),
+                c if c.is_alphanumeric() => Some(c),
+                _ => None,
+            })
+            .collect(),
+        AnchorStyle::Gitlab => {
+            let slug: String = text
+                .to_lowercase()
+                .chars()
+                .filter_map(|c| match c {
+                    c if c.is_whitespace() => Some('-'),
+                    '-' | '_' => Some(c),
+                    c if c.is_alphanumeric() => Some(c),
+                    _ => None,
+                })
+                .collect();
+            // the runs of `-` are collapsed into one
+            let mut collapsed = String::with_capacity(slug.len());
+            for c in slug.chars() {
+                if !(c == '-' && collapsed.ends_with('-')) {
+                    collapsed.push(c);
+                }
+            }
+            collapsed
+        }
+        AnchorStyle::Rustdoc => text
+            .chars()
+            .filter_map(|c| match c {
+                c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c.to_ascii_lowercase()),
+                c if c.is_ascii_whitespace() => Some('-'),
+                _ => None,
+            })
+            .collect(),
+    }
+}
+
+/// Replace the inline links and images of `text`, like `[text](target)`, by their text
+fn link_texts(text: &str) -> String {
+    replace_links(text, |link| Some(link_texts(link.text)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{slug, table_of_contents, update_table_of_contents, AnchorStyle};
+
+    #[test]
+    fn github_slugs() {
+        let github = |text| slug(text, AnchorStyle::Github);
+        assert_eq!(github("Getting Started"), "getting-started");
+        assert_eq!(github("What's `new`?"), "whats-new");
+        assert_eq!(github("snake_case & kebab-case"), "snake_case--kebab-case");
+    }
+
+    #[test]
+    fn gitlab_slugs() {
+        let gitlab = |text| slug(text, AnchorStyle::Gitlab);
+        assert_eq!(gitlab("Getting Started"), "getting-started");
+        assert_eq!(gitlab("snake_case & kebab-case"), "snake_case-kebab-case");
+        assert_eq!(gitlab("This - is - a - heading"), "this-is-a-heading");
+    }
+
+    #[test]
+    fn rustdoc_slugs() {
+        let rustdoc = |text| slug(text, AnchorStyle::Rustdoc);
+        assert_eq!(rustdoc("What's `new`?"), "whats-new");
+        assert_eq!(rustdoc("snake_case & kebab-case"), "snake_case--kebab-case");
+        assert_eq!(rustdoc("Über Ärger"), "Über-Ärger");
+    }
+
+    #[test]
+    fn nested_table_of_contents() {
+        let markdown = "\
+# crate
+
+## Usage
+
+```
+## not a heading
+```
+
+### With [the CLI](docs/cli.md)
+
+#### Too deep
+
+## Usage
+";
+
+        assert_eq!(
+            table_of_contents(markdown, 2, 3, AnchorStyle::Github),
+            "\
+- [Usage](#usage)
+  - [With the CLI](#with-the-cli)
+- [Usage](#usage-1)
+"
+        );
+    }
+
+    #[test]
+    fn update_between_toc_markers() {
+        let existing =
+            "# Title\n\n<!-- cargo-readme toc start -->\nold\n<!-- cargo-readme toc end -->\n";
+
+        assert_eq!(
+            update_table_of_contents(existing, "- [A](#a)\n").unwrap(),
+            "# Title\n\n<!-- cargo-readme toc start -->\n\n- [A](#a)\n\n<!-- cargo-readme toc end -->\n"
+        );
+        assert!(update_table_of_contents("# Title", "- [A](#a)").is_err());
+    }
+}
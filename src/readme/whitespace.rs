@@ -0,0 +1,73 @@
+//! Normalize the whitespace of the output, so it passes common markdown linters
+//!
+//! Trailing whitespace is removed from every line. Outside code blocks, a hard line break made of
+//! trailing spaces is kept as a backslash, and runs of blank lines are collapsed into one.
+
+use super::fences::{code_fence, fenced};
+
+/// Remove the trailing whitespace of every line of `text`
+///
+/// A hard line break, two or more trailing spaces followed by more text, is turned into a
+/// backslash so it still renders as a line break.
+pub fn strip_trailing_whitespace(text: &str) -> String {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let mut result: Vec<String> = Vec::new();
+
+    for (i, (kind, line)) in fenced(&lines).enumerate() {
+        let stripped = line.trim_end();
+        if !kind.is_code() && !stripped.is_empty() && line.ends_with("  ") {
+            let continued = lines
+                .get(i + 1)
+                .is_some_and(|next| !next.trim().is_empty() && code_fence(next).is_none());
+            if continued {
+                result.push(format!("{}\\", stripped));
+                continue;
+            }
+        }
+        result.push(stripped.to_owned());
+    }
+
+    result.join("\n")
+}
+
+/// Collapse the runs of blank lines of `text` outside code blocks into a single blank line
+pub fn collapse_blank_lines(text: &str) -> String {
+    let mut result: Vec<&str> = Vec::new();
+
+    for (kind, line) in fenced(text.split('\n')) {
+        if !kind.is_code()
+            && line.trim().is_empty()
+            && result.last().is_some_and(|l| l.trim().is_empty())
+        {
+            continue;
+        }
+        result.push(line);
+    }
+
+    result.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collapse_blank_lines, strip_trailing_whitespace};
+
+    #[test]
+    fn strip_whitespace() {
+        let input = "# Title \n\nHard  \nbreak. \t\nEnd  \n\n```\nlet a = 1;  \nlet b = 2;\n```";
+
+        assert_eq!(
+            strip_trailing_whitespace(input),
+            "# Title\n\nHard\\\nbreak.\nEnd\n\n```\nlet a = 1;\nlet b = 2;\n```"
+        );
+    }
+
+    #[test]
+    fn collapse_blank_line_runs() {
+        let input = "Intro\n\n\n\nText\n \n\n```\na\n\n\nb\n```\n\n\nEnd";
+
+        assert_eq!(
+            collapse_blank_lines(input),
+            "Intro\n\nText\n \n```\na\n\n\nb\n```\n\nEnd"
+        );
+    }
+}
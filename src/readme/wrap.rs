@@ -0,0 +1,311 @@
+//! Wrap the paragraphs of the output at a given column
+//!
+//! The lines of a paragraph are joined and broken again before the words reaching the column, so
+//! the paragraphs are reflowed whatever the width of the doc comments. Only the prose is wrapped:
+//! the code blocks, tables, headings, HTML blocks and reference definitions are left as they are.
+//! The list items and the block quotes are wrapped with their indentation or `>` on every line,
+//! and the hard line breaks are kept. Words longer than the width, like URLs, are not broken, and
+//! neither are code spans nor the alt texts of images.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::fences::{code_fence, fenced, LineKind};
+use super::links::{code_span_len, link_text_len};
+
+lazy_static! {
+    // a list item, like `- item` or `1. item`
+    static ref RE_LIST_ITEM: Regex =
+        Regex::new(r"^(?P<marker>\s*(?:[-*+]|\d{1,9}[.)])\s+)\S").unwrap();
+    // the delimiter row of a table, like `| --- | :-: |`
+    static ref RE_TABLE_DELIMITER: Regex =
+        Regex::new(r"^\s*\|?\s*:?-+:?\s*(?:\|\s*:?-+:?\s*)+\|?\s*$|^\s*\|\s*:?-+:?\s*\|\s*$")
+            .unwrap();
+    // the lines that are not prose: headings, HTML, reference definitions, thematic breaks and
+    // the markers of GitHub alerts, like `[!NOTE]`
+    static ref RE_BLOCK: Regex = Regex::new(
+        r"^\s{0,3}(?:#{1,6}(?:\s|$)|<|\[[^\]]+\]:\s|(?:[-*_]\s*){3,}$|=+\s*$|-+\s*$|\||\[![A-Z]+\]\s*$)"
+    )
+    .unwrap();
+    // a word that would start a block at the start of a line, like `-`, `1.` or `#`
+    static ref RE_BLOCK_START: Regex = Regex::new(r"^(?:[-*+>]|\d{1,9}[.)]|#{1,6}|=+|-+)$").unwrap();
+}
+
+/// A paragraph being collected, with the prefix of its first line and of the next ones
+struct Paragraph {
+    first: String,
+    rest: String,
+    /// The words of the lines, up to each hard line break, and the break
+    segments: Vec<(Vec<String>, String)>,
+    /// The lines as written, kept when the paragraph turns out to be a setext heading
+    original: Vec<String>,
+}
+
+impl Paragraph {
+    fn new(first: String, rest: String) -> Self {
+        Paragraph {
+            first,
+            rest,
+            segments: vec![(Vec::new(), String::new())],
+            original: Vec::new(),
+        }
+    }
+
+    /// Add the text of a line, without its prefix, to the paragraph
+    fn push(&mut self, line: &str, text: &str) {
+        self.original.push(line.to_owned());
+        // a hard line break, two trailing spaces or a backslash, ends the segment
+        let hard_break = match (text.ends_with("  "), text.trim_end().ends_with('\\')) {
+            (true, _) => "  ",
+            (false, true) => "\\",
+            _ => "",
+        };
+        let text = text.trim_end();
+        let text = match hard_break {
+            "\\" => &text[..text.len() - 1],
+            _ => text,
+        };
+        if let Some((words, end)) = self.segments.last_mut() {
+            words.extend(split_words(text));
+            *end = hard_break.to_owned();
+        }
+        if !hard_break.is_empty() {
+            self.segments.push((Vec::new(), String::new()));
+        }
+    }
+
+    /// Wrap the words of the paragraph at `width`
+    fn wrap(self, width: usize) -> Vec<String> {
+        let mut lines: Vec<String> = Vec::new();
+        for (words, end) in self.segments {
+            if words.is_empty() {
+                continue;
+            }
+            let mut line = String::new();
+            for word in words {
+                let prefix = match lines.is_empty() {
+                    true => &self.first,
+                    false => &self.rest,
+                };
+                if line.is_empty() {
+                    line = format!("{}{}", prefix, word);
+                } else if len(&line) + 1 + len(&word) <= width || RE_BLOCK_START.is_match(&word) {
+                    line.push(' ');
+                    line.push_str(&word);
+                } else {
+                    lines.push(line);
+                    line = format!("{}{}", self.rest, word);
+                }
+            }
+            lines.push(format!("{}{}", line, end));
+        }
+        lines
+    }
+}
+
+/// Wrap the prose paragraphs of the markdown `text` at `width` columns
+pub fn wrap_paragraphs(text: &str, width: usize) -> String {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let mut table = false;
+    let mut paragraph: Option<Paragraph> = None;
+    // the indentation of the content of the last list item
+    let mut list_indent: Option<usize> = None;
+    let mut result: Vec<String> = Vec::new();
+
+    let flush = |paragraph: &mut Option<Paragraph>, result: &mut Vec<String>| {
+        if let Some(p) = paragraph.take() {
+            result.extend(p.wrap(width));
+        }
+    };
+
+    for (i, (kind, line)) in fenced(&lines).enumerate() {
+        if kind == LineKind::Open {
+            flush(&mut paragraph, &mut result);
+        }
+        if kind.is_code() {
+            result.push(line.to_string());
+            continue;
+        }
+
+        let indent = line.len() - line.trim_start().len();
+        let is_table_header = lines
+            .get(i + 1)
+            .is_some_and(|next| line.contains('|') && RE_TABLE_DELIMITER.is_match(next));
+        table = (table || is_table_header) && !line.trim().is_empty() && line.contains('|');
+
+        if line.trim().is_empty() {
+            flush(&mut paragraph, &mut result);
+            result.push(line.to_string());
+            continue;
+        }
+        if table || RE_BLOCK.is_match(line) {
+            // the underline of a setext heading keeps the heading as it is
+            if let Some(p) = paragraph.take() {
+                match line.trim_start().starts_with(['=', '-']) && RE_BLOCK.is_match(line) {
+                    true => result.extend(p.original),
+                    false => result.extend(p.wrap(width)),
+                }
+            }
+            result.push(line.to_string());
+            continue;
+        }
+        if let Some(cap) = RE_LIST_ITEM.captures(line) {
+            flush(&mut paragraph, &mut result);
+            let marker = &cap["marker"];
+            list_indent = Some(marker.len());
+            let mut item = Paragraph::new(marker.to_owned(), " ".repeat(marker.len()));
+            item.push(line, &line[marker.len()..]);
+            paragraph = Some(item);
+            continue;
+        }
+        if let Some(quote) = line.trim_start().strip_prefix('>') {
+            let prefix = format!("{}> ", &line[..indent]);
+            let text = quote.strip_prefix(' ').unwrap_or(quote);
+            // the quotes holding other blocks, like lists or code, are left as they are
+            if RE_BLOCK.is_match(text) || RE_LIST_ITEM.is_match(text) || code_fence(text).is_some()
+            {
+                flush(&mut paragraph, &mut result);
+                result.push(line.to_string());
+                continue;
+            }
+            match paragraph {
+                Some(ref mut p) if p.rest == prefix => p.push(line, text),
+                _ => {
+                    flush(&mut paragraph, &mut result);
+                    let mut quote = Paragraph::new(prefix.clone(), prefix);
+                    quote.push(line, text);
+                    paragraph = Some(quote);
+                }
+            }
+            continue;
+        }
+
+        match paragraph {
+            // a continuation line, lazy or indented
+            Some(ref mut p) => p.push(line, line.trim_start()),
+            None => {
+                // an indented code block, unless the line continues a list item
+                let content_indent = list_indent.filter(|_| indent > 0).unwrap_or(0);
+                if indent >= content_indent + 4 {
+                    result.push(line.to_string());
+                    continue;
+                }
+                if indent == 0 {
+                    list_indent = None;
+                }
+                let prefix = " ".repeat(indent);
+                let mut p = Paragraph::new(prefix.clone(), prefix);
+                p.push(line, line.trim_start());
+                paragraph = Some(p);
+            }
+        }
+    }
+    flush(&mut paragraph, &mut result);
+
+    result.join("\n")
+}
+
+/// Split `text` into words at its spaces, outside code spans
+fn split_words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let len = match c {
+            '`' => code_span_len(rest),
+            // the alt text of an image, like a badge, is kept on one line
+            '!' if rest[1..].starts_with('[') => link_text_len(&rest[1..]).map_or(1, |len| len + 1),
+            c => c.len_utf8(),
+        };
+        if c.is_whitespace() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+        } else {
+            word.push_str(&rest[..len]);
+        }
+        rest = &rest[len..];
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// Get the width of `text`, in characters
+fn len(text: &str) -> usize {
+    text.chars().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::wrap_paragraphs;
+
+    #[test]
+    fn wrap_prose() {
+        let markdown = "\
+# A heading that is much longer than the width
+
+Some prose that goes on, with `a code span` and
+a [link](https://example.com/a/long/url/to/wrap).
+
+- A list item that is too long - to fit
+  on a line.
+1. Short.
+
+> A quote that is long enough to wrap.
+
+> [!NOTE]
+> An alert.
+
+| A table | that is long enough to wrap |
+| ------- | --------------------------- |
+
+```
+code that is long enough to wrap, but is not wrapped
+```
+
+A line with a hard break\\
+and the next one.
+
+Setext heading that is not wrapped
+---";
+
+        assert_eq!(
+            wrap_paragraphs(markdown, 20),
+            "\
+# A heading that is much longer than the width
+
+Some prose that goes
+on, with
+`a code span` and a
+[link](https://example.com/a/long/url/to/wrap).
+
+- A list item that
+  is too long - to
+  fit on a line.
+1. Short.
+
+> A quote that is
+> long enough to
+> wrap.
+
+> [!NOTE]
+> An alert.
+
+| A table | that is long enough to wrap |
+| ------- | --------------------------- |
+
+```
+code that is long enough to wrap, but is not wrapped
+```
+
+A line with a hard
+break\\
+and the next one.
+
+Setext heading that is not wrapped
+---"
+        );
+    }
+}
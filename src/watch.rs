@@ -0,0 +1,171 @@
+//! Regenerate the readme each time its sources change
+//!
+//! The watched files are the ones the last generation read: the source file and its modules, the
+//! files included with `include_str!`, the template, and the `Cargo.toml` of the crate and of its
+//! facade. The directories of the files are watched, so the files replaced by editors, or created
+//! later, are seen too. The changes are debounced, so saving several files at once regenerates the
+//! readme once.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use cargo_readme::Error;
+use notify_debouncer_full::new_debouncer;
+use notify_debouncer_full::notify::{Event, RecursiveMode};
+
+use crate::messages;
+
+/// Time the changes are gathered for, before the readme is regenerated
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Files read since the last generation started
+static READ: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+/// Record that the file at `path` was read to generate the readme, so it is watched
+pub fn record(path: &Path) {
+    READ.lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(absolute(path));
+}
+
+/// Run `generate`, then run it again each time one of the files it read, or of `paths`, changes
+///
+/// The errors of `generate` are shown without stopping to watch. As a failed generation may stop
+/// before reading all its files, the files of the generation before are still watched then.
+pub fn watch<F>(paths: &[PathBuf], mut generate: F) -> Result<(), Error>
+where
+    F: FnMut() -> Result<(), Error>,
+{
+    let (sender, receiver) = mpsc::channel();
+    let mut debouncer =
+        new_debouncer(DEBOUNCE, None, sender).map_err(|e| Error::io(".", io::Error::other(e)))?;
+    let mut files = BTreeSet::new();
+    let mut dirs = BTreeSet::new();
+
+    loop {
+        let (result, read) = run(&mut generate);
+        if result.is_ok() {
+            files.clear();
+        }
+        files.extend(read);
+        files.extend(paths.iter().map(|path| absolute(path)));
+        report(result);
+
+        let wanted: BTreeSet<PathBuf> = files
+            .iter()
+            .filter_map(|file| file.parent())
+            .map(Path::to_path_buf)
+            .collect();
+        for dir in dirs.difference(&wanted) {
+            // the directory may be gone already
+            let _ = debouncer.unwatch(dir);
+        }
+        for dir in wanted.difference(&dirs) {
+            if let Err(e) = debouncer.watch(dir, RecursiveMode::NonRecursive) {
+                messages::warn(format_args!(
+                    "Cannot watch '{}': {}",
+                    dir.to_string_lossy(),
+                    e
+                ));
+            }
+        }
+        dirs = wanted;
+
+        // wait for a change to one of the files, their reads, and the other files of their
+        // directories, like the readme written, being left out
+        loop {
+            match receiver.recv() {
+                Ok(Ok(events)) if events.iter().any(|event| changes(event, &files)) => break,
+                Ok(Ok(_)) => {}
+                Ok(Err(errors)) => {
+                    for e in errors {
+                        messages::warn(format_args!("Cannot watch the files: {}", e));
+                    }
+                }
+                Err(_) => return Ok(()),
+            }
+        }
+    }
+}
+
+/// Check if `event` is a change to one of `files`
+fn changes(event: &Event, files: &BTreeSet<PathBuf>) -> bool {
+    !event.kind.is_access() && event.paths.iter().any(|path| files.contains(path))
+}
+
+/// Run `generate`, getting the files it read
+fn run<F>(generate: &mut F) -> (Result<(), Error>, BTreeSet<PathBuf>)
+where
+    F: FnMut() -> Result<(), Error>,
+{
+    READ.lock().unwrap_or_else(PoisonError::into_inner).clear();
+    let result = generate();
+    let read = mem::take(&mut *READ.lock().unwrap_or_else(PoisonError::into_inner));
+    (result, read)
+}
+
+/// Show the result of a generation
+fn report(result: Result<(), Error>) {
+    match result {
+        Ok(()) => messages::info(format_args!("README generated, watching for changes...")),
+        Err(e) => messages::error(&e),
+    }
+}
+
+/// Get the canonical path of `path`, as given by the events, or of its directory when the file
+/// does not exist yet
+fn absolute(path: &Path) -> PathBuf {
+    if let Ok(path) = fs::canonicalize(path) {
+        return path;
+    }
+    match (path.parent().map(fs::canonicalize), path.file_name()) {
+        (Some(Ok(dir)), Some(name)) => dir.join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{absolute, record, run};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn files_read_by_generation() {
+        let (result, read) = run(&mut || {
+            record(Path::new("tests/check/Cargo.toml"));
+            record(Path::new("tests/check/src/../src/lib.rs"));
+            Ok(())
+        });
+
+        assert!(result.is_ok());
+        assert_eq!(
+            read.into_iter().collect::<Vec<_>>(),
+            [
+                absolute(Path::new("tests/check/Cargo.toml")),
+                absolute(Path::new("tests/check/src/lib.rs")),
+            ]
+        );
+        assert!(run(&mut || Ok(())).1.is_empty());
+    }
+
+    #[test]
+    fn absolute_path_of_missing_file() {
+        let dir = absolute(Path::new("tests/check"));
+
+        assert!(dir.is_absolute());
+        assert_eq!(
+            absolute(Path::new("tests/check/README.tpl")),
+            dir.join("README.tpl")
+        );
+        assert_eq!(
+            absolute(Path::new("missing/README.tpl")),
+            PathBuf::from("missing/README.tpl")
+        );
+    }
+}
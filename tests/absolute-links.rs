@@ -0,0 +1,49 @@
+use assert_cli::Assert;
+
+const EXPECTED: &str = r#"
+# absolute-links
+
+Read the [contributing guide](https://github.com/owner/absolute-links/blob/main/tests/absolute-links/CONTRIBUTING.md) and the [design](https://github.com/owner/absolute-links/blob/main/tests/absolute-links/docs/design.md#goals).
+
+![architecture](https://github.com/owner/absolute-links/raw/main/tests/absolute-links/docs/architecture.png)
+
+More on [the website](https://example.com).
+
+License: MIT
+"#;
+
+#[test]
+fn absolute_links() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/absolute-links",
+        "--absolute-links",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
+
+#[test]
+fn absolute_links_without_repository() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--absolute-links",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .is("Error: Cannot rewrite relative links, `package.repository` is not set in Cargo.toml")
+        .unwrap();
+}
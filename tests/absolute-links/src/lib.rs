@@ -0,0 +1,5 @@
+//! Read the [contributing guide](CONTRIBUTING.md) and the [design](./docs/design.md#goals).
+//!
+//! ![architecture](docs/architecture.png)
+//!
+//! More on [the website](https://example.com).
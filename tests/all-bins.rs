@@ -0,0 +1,31 @@
+use assert_cli::Assert;
+
+const EXPECTED: &str = r#"
+# multiple-bin
+
+## first
+
+first binary
+
+## second
+
+second binary
+"#;
+
+#[test]
+fn all_bins() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/multiple-bin",
+        "--all-bins",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
@@ -62,3 +62,36 @@ fn alternate_template() {
         .is(EXPECTED)
         .unwrap();
 }
+
+#[test]
+fn alternate_template_missing() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--template",
+        "MISSING.tpl",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails_with(3)
+        .and()
+        .stderr()
+        .contains("MISSING.tpl' does not exist")
+        .unwrap();
+}
+
+#[test]
+fn default_template_missing() {
+    // tests/check has no README.tpl, so the README is generated without template
+    let args = ["readme", "--project-root", "tests/check", "-vv"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stderr()
+        .contains("No template found at")
+        .unwrap();
+}
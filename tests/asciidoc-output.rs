@@ -0,0 +1,73 @@
+use assert_cli::Assert;
+use std::fs;
+
+const EXPECTED: &str = r#"= check
+
+Check the README.
+
+The docs changed.
+
+License: MIT
+"#;
+
+#[test]
+fn asciidoc_output() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output-format",
+        "asciidoc",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
+
+#[test]
+fn asciidoc_output_file() {
+    let _ = fs::remove_file("tests/check/README.adoc");
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output-format",
+        "asciidoc",
+        "--output",
+        "README.adoc",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+    // the file starts with the AsciiDoc marker comment, so it is replaced as generated
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let adoc = fs::read_to_string("tests/check/README.adoc").unwrap();
+    fs::remove_file("tests/check/README.adoc").unwrap();
+    assert_eq!(adoc, format!("// generated by cargo-readme\n{}", EXPECTED));
+}
+
+#[test]
+fn asciidoc_output_with_front_matter() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output-format",
+        "asciidoc",
+        "--front-matter",
+        "yaml",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails_with(3)
+        .and()
+        .stderr()
+        .contains("`--footer` and `--front-matter` cannot be used with `--output-format asciidoc`")
+        .unwrap();
+}
@@ -0,0 +1,134 @@
+use assert_cli::Assert;
+use std::fs;
+use std::path::Path;
+
+const EXPECTED_REBASED: &str = r#"
+# assets
+
+Crate with images next to its source.
+
+![diagram](src/assets/diagram.svg)
+
+![missing](assets/missing.svg)
+
+License: MIT
+"#;
+
+const EXPECTED_COPIED: &str = r#"
+# assets
+
+Crate with images next to its source.
+
+![diagram](assets/diagram.svg)
+
+![missing](assets/missing.svg)
+
+License: MIT
+"#;
+
+// a single test, the copied assets change the output of the default mode
+#[test]
+fn rebase_and_copy_assets() {
+    let copied = Path::new("tests/assets/assets");
+    let _ = fs::remove_dir_all(copied);
+
+    let args = ["readme", "--project-root", "tests/assets"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED_REBASED)
+        .unwrap();
+
+    // the images are only copied when the readme is written to a file
+    let args = ["readme", "--project-root", "tests/assets", "--copy-assets"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED_COPIED)
+        .unwrap();
+
+    assert!(!copied.exists());
+
+    let output = "tests/assets/COPIED.md";
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/assets",
+        "--copy-assets",
+        "--output",
+        "COPIED.md",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    assert!(fs::read_to_string(output)
+        .unwrap()
+        .ends_with(EXPECTED_COPIED));
+    assert_eq!(
+        fs::read_to_string(copied.join("diagram.svg")).unwrap(),
+        fs::read_to_string("tests/assets/src/assets/diagram.svg").unwrap()
+    );
+
+    fs::remove_file(output).unwrap();
+    fs::remove_dir_all(copied).unwrap();
+}
+
+const EXPECTED_REBASED_OUT: &str = r#"
+# assets
+
+Crate with images next to its source.
+
+![diagram](../src/assets/diagram.svg)
+
+![missing](assets/missing.svg)
+
+License: MIT
+"#;
+
+// the links work from the directory of the output file, where the images are copied to
+#[test]
+fn rebase_and_copy_assets_next_to_output() {
+    let out = Path::new("tests/assets/out");
+    let _ = fs::remove_dir_all(out);
+    fs::create_dir(out).unwrap();
+
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/assets",
+        "--output",
+        "out/R.md",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let rebased = fs::read_to_string(out.join("R.md")).unwrap();
+
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/assets",
+        "--copy-assets",
+        "--output",
+        "out/COPIED.md",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let copied = fs::read_to_string(out.join("COPIED.md")).unwrap();
+    let diagram = fs::read_to_string(out.join("assets/diagram.svg"));
+    fs::remove_dir_all(out).unwrap();
+
+    assert!(rebased.ends_with(EXPECTED_REBASED_OUT));
+    assert!(copied.ends_with(EXPECTED_COPIED));
+    assert_eq!(
+        diagram.unwrap(),
+        fs::read_to_string("tests/assets/src/assets/diagram.svg").unwrap()
+    );
+}
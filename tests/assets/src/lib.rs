@@ -0,0 +1,5 @@
+//! Crate with images next to its source.
+//!
+//! ![diagram](assets/diagram.svg)
+//!
+//! ![missing](assets/missing.svg)
@@ -0,0 +1,78 @@
+use assert_cli::Assert;
+use std::fs::{self, File};
+use std::time::{Duration, SystemTime};
+
+#[test]
+fn failed_generation_keeps_output() {
+    let output = "tests/check/KEPT.md";
+    fs::write(output, "# Kept\n").unwrap();
+
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output",
+        "KEPT.md",
+        "--only-section",
+        "Missing",
+    ];
+
+    Assert::main_binary().with_args(&args).fails().unwrap();
+
+    assert_eq!(fs::read_to_string(output).unwrap(), "# Kept\n");
+    fs::remove_file(output).unwrap();
+}
+
+#[test]
+fn output_is_replaced() {
+    let output = "tests/check/REPLACED.md";
+    fs::write(output, "<!-- generated by cargo-readme -->\n# Old\n").unwrap();
+
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output",
+        "REPLACED.md",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let readme = fs::read_to_string(output).unwrap();
+    assert_eq!(readme, fs::read_to_string("tests/check/README.md").unwrap());
+    fs::remove_file(output).unwrap();
+
+    // no temporary file is left behind
+    let leftovers = fs::read_dir("tests/check")
+        .unwrap()
+        .filter(|entry| entry.as_ref().unwrap().path().extension() == Some("tmp".as_ref()))
+        .count();
+    assert_eq!(leftovers, 0);
+}
+
+#[test]
+fn unchanged_output_is_not_written() {
+    let output = "tests/check/UNCHANGED.md";
+    fs::copy("tests/check/README.md", output).unwrap();
+    let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+    File::options()
+        .write(true)
+        .open(output)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output",
+        "UNCHANGED.md",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let metadata = fs::metadata(output).unwrap();
+    fs::remove_file(output).unwrap();
+    assert_eq!(metadata.modified().unwrap(), modified);
+}
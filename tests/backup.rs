@@ -0,0 +1,68 @@
+use assert_cli::Assert;
+use std::fs;
+
+#[test]
+fn backup_default_suffix() {
+    let output = "tests/check/BACKUP.md";
+    fs::write(output, "# Hand written\n").unwrap();
+
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output",
+        "BACKUP.md",
+        "--backup",
+        "--force",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let backup = fs::read_to_string("tests/check/BACKUP.md.bak").unwrap();
+    fs::remove_file("tests/check/BACKUP.md.bak").unwrap();
+    fs::remove_file(output).unwrap();
+    assert_eq!(backup, "# Hand written\n");
+}
+
+#[test]
+fn backup_custom_suffix() {
+    let output = "tests/check/SUFFIX.md";
+    fs::write(output, "# Hand written\n").unwrap();
+
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output",
+        "SUFFIX.md",
+        "--backup=~",
+        "--force",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let backup = fs::read_to_string("tests/check/SUFFIX.md~").unwrap();
+    fs::remove_file("tests/check/SUFFIX.md~").unwrap();
+    fs::remove_file(output).unwrap();
+    assert_eq!(backup, "# Hand written\n");
+}
+
+#[test]
+fn no_backup_of_new_file() {
+    let output = "tests/check/FRESH.md";
+    let _ = fs::remove_file(output);
+
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output",
+        "FRESH.md",
+        "--backup",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    fs::remove_file(output).unwrap();
+    assert!(fs::metadata("tests/check/FRESH.md.bak").is_err());
+}
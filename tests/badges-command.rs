@@ -0,0 +1,34 @@
+use assert_cli::Assert;
+
+#[test]
+fn badges_published() {
+    let args = ["readme", "badges", "--project-root", "tests/check"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is("\
+[![Crates.io](https://img.shields.io/crates/v/check.svg)](https://crates.io/crates/check)
+[![Docs.rs](https://docs.rs/check/badge.svg)](https://docs.rs/check)
+[![License](https://img.shields.io/crates/l/check.svg)](https://crates.io/crates/check)
+")
+        .unwrap();
+}
+
+#[test]
+fn badges_unpublished() {
+    let args = ["readme", "badges", "--project-root", "tests/badges-command"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is("\
+![License](https://img.shields.io/badge/license-MIT%20OR%20Apache--2.0-blue.svg)
+![MSRV](https://img.shields.io/badge/MSRV-1.70-blue.svg)
+")
+        .unwrap();
+}
@@ -0,0 +1 @@
+//! Print the badges.
@@ -0,0 +1,57 @@
+use assert_cli::Assert;
+
+const EXPECTED_DEFAULT: &str = r#"
+# cfg-attr
+
+Crate with feature gated docs.
+
+Always documented.
+
+License: MIT
+"#;
+
+#[test]
+fn cfg_attr_default() {
+    let args = ["readme", "--project-root", "tests/cfg-attr"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED_DEFAULT)
+        .unwrap();
+}
+
+const EXPECTED_ACTIVE_CFGS: &str = r#"
+# cfg-attr
+
+Crate with feature gated docs.
+
+Always documented.
+Values can be serialized with `serde`.
+Built on docs.rs.
+
+License: MIT
+"#;
+
+#[test]
+fn cfg_attr_active_cfgs() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/cfg-attr",
+        "--cfg",
+        "docsrs",
+        "--cfg",
+        "feature=\"serde\"",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED_ACTIVE_CFGS)
+        .unwrap();
+}
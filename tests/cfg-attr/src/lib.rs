@@ -0,0 +1,11 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
+//! Crate with feature gated docs.
+//!
+#![cfg_attr(doc, doc = "Always documented.")]
+#![cfg_attr(
+    feature = "serde",
+    doc = "Values can be serialized with `serde`."
+)]
+#![cfg_attr(docsrs, doc = "Built on docs.rs.")]
+
+pub struct Value;
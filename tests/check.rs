@@ -0,0 +1,171 @@
+use assert_cli::Assert;
+
+#[test]
+fn check_up_to_date() {
+    let args = ["readme", "--project-root", "tests/check", "--check"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is("")
+        .unwrap();
+}
+
+#[test]
+fn check_stale() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--check",
+        "--output",
+        "STALE.md",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails_with(2)
+        .and()
+        .stderr()
+        .contains("STALE.md' is not up to date, the first difference is at line 5")
+        .unwrap();
+}
+
+#[test]
+fn check_in_place() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--check",
+        "--in-place",
+        "--output",
+        "MARKED.md",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+}
+
+#[test]
+fn check_missing_file() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--check",
+        "--output",
+        "MISSING.md",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("Could not read")
+        .unwrap();
+}
+
+#[test]
+fn check_stdout() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--check",
+        "--output",
+        "-",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("`--check` and `--diff` cannot compare with stdout")
+        .unwrap();
+}
+
+#[test]
+fn check_shows_diff() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--check",
+        "--output",
+        "STALE.md",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stdout()
+        .contains(
+            "@@ -2,4 +2,6 @@\n \n Check the README.\n \n+The docs changed.\n+\n License: MIT\n",
+        )
+        .unwrap();
+}
+
+#[test]
+fn diff_stale() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--diff",
+        "--color",
+        "always",
+        "--output",
+        "STALE.md",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("\u{1b}[32m+The docs changed.\u{1b}[0m\n")
+        .unwrap();
+}
+
+#[test]
+fn diff_up_to_date() {
+    let args = ["readme", "--project-root", "tests/check", "--diff"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is("")
+        .unwrap();
+}
+
+#[test]
+fn check_stale_json() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--check",
+        "--output",
+        "STALE.md",
+        "--message-format",
+        "json",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains(r#""kind":"outdated-readme","level":"error","line":5,"#)
+        .and()
+        .stderr()
+        .contains(r#"/tests/check/STALE.md","#)
+        .unwrap();
+}
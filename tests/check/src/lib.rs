@@ -0,0 +1,3 @@
+//! Check the README.
+//!
+//! The docs changed.
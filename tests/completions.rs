@@ -0,0 +1,37 @@
+use assert_cli::Assert;
+
+#[test]
+fn bash_completions() {
+    let args = ["readme", "completions", "bash"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("COMPREPLY=($(compgen -W \"lf crlf native\" -- \"${cur}\"))\n")
+        .and()
+        .stdout()
+        .contains("complete -F _cargo__readme -o bashdefault -o default cargo-readme\n")
+        .unwrap();
+}
+
+#[test]
+fn fish_completions() {
+    let args = ["readme", "completions", "fish"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("__fish_seen_subcommand_from init\" -l metadata")
+        .unwrap();
+}
+
+#[test]
+fn unknown_shell() {
+    let args = ["readme", "completions", "tcsh"];
+
+    Assert::main_binary().with_args(&args).fails().unwrap();
+}
@@ -0,0 +1,64 @@
+use assert_cli::Assert;
+use std::fs;
+
+#[test]
+fn confluence_output() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output-format",
+        "confluence",
+    ];
+
+    let expected = r#"h1. check
+
+Check the README.
+
+The docs changed.
+
+License: MIT
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn confluence_output_file_needs_force() {
+    let _ = fs::remove_file("tests/check/README.confluence");
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output-format",
+        "confluence",
+        "--output",
+        "README.confluence",
+    ];
+    let forced = [&args[..], &["--force"]].concat();
+
+    // the wiki markup has no comment telling it was generated, so it is only replaced when it is
+    // up to date, or with --force
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+    let mut edited = fs::read_to_string("tests/check/README.confluence").unwrap();
+    edited.push_str("Hand written\n");
+    fs::write("tests/check/README.confluence", edited).unwrap();
+    let kept = Assert::main_binary().with_args(&args).fails().execute();
+    let replaced = Assert::main_binary()
+        .with_args(&forced)
+        .succeeds()
+        .execute();
+
+    let wiki = fs::read_to_string("tests/check/README.confluence").unwrap();
+    fs::remove_file("tests/check/README.confluence").unwrap();
+    kept.unwrap();
+    replaced.unwrap();
+    assert!(wiki.starts_with("h1. check\n"));
+}
@@ -0,0 +1,55 @@
+use assert_cli::Assert;
+
+#[test]
+fn crates_io_safe() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/crates-io",
+        "--no-badges",
+        "--no-license",
+        "--crates-io",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(r#"# shapes
+
+  <img src="https://example.com/logo.svg" alt="shapes">
+
+Draw shapes, see the guide and the examples.
+
+```rust
+let circle = shapes::Circle::new(10);
+```
+
+```
+graph LR;
+```"#)
+        .unwrap();
+}
+
+#[test]
+fn crates_io_keeps_markdown_without_option() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/crates-io",
+        "--no-badges",
+        "--no-license",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("[the guide](docs/GUIDE.md)")
+        .and()
+        .stdout()
+        .contains("```mermaid")
+        .unwrap();
+}
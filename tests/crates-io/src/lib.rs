@@ -0,0 +1,20 @@
+//! <picture>
+//!   <source srcset="assets/logo-dark.svg" media="(prefers-color-scheme: dark)">
+//!   <img src="https://example.com/logo.svg" alt="shapes">
+//! </picture>
+//!
+//! Draw shapes, see [the guide](docs/GUIDE.md) and [the examples][examples].
+//!
+//! <svg width="20" height="20">
+//!   <circle cx="10" cy="10" r="10" />
+//! </svg>
+//!
+//! ```
+//! let circle = shapes::Circle::new(10);
+//! ```
+//!
+//! ```mermaid
+//! graph LR;
+//! ```
+//!
+//! [examples]: examples/README.md
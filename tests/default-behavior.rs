@@ -58,3 +58,36 @@ fn default_behavior() {
         .is(EXPECTED)
         .unwrap();
 }
+
+#[test]
+fn default_behavior_from_subdirectory() {
+    // the project root is found above the current directory, and the default paths are resolved
+    // from it. The binary is run directly, as `cargo run` would find the manifest of the test
+    // project instead
+    Assert::command(&[env!("CARGO_BIN_EXE_cargo-readme"), "readme"])
+        .current_dir("tests/test-project/src")
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
+
+#[test]
+fn default_behavior_markdown_output_format() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--output-format",
+        "markdown",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
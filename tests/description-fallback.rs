@@ -0,0 +1,46 @@
+use assert_cli::Assert;
+
+const EXPECTED: &str = r#"
+# description-fallback
+
+A thin wrapper crate without doc comments.
+
+License: MIT
+"#;
+
+#[test]
+fn description_fallback() {
+    let args = ["readme", "--project-root", "tests/description-fallback"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .and()
+        .stderr()
+        .is("Warning: no doc comments found, using `package.description` instead")
+        .unwrap();
+}
+
+#[test]
+fn description_fallback_quiet() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/description-fallback",
+        "--quiet",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .and()
+        .stderr()
+        .is("")
+        .unwrap();
+}
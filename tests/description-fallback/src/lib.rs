@@ -0,0 +1 @@
+pub use std::collections::HashMap as Map;
@@ -0,0 +1,95 @@
+use assert_cli::Assert;
+
+#[test]
+fn diagrams_from_cargo_toml() {
+    let args = ["readme", "--project-root", "tests/diagrams", "--no-license"];
+
+    let expected = r#"
+# diagrams
+
+How the parts fit together:
+
+```mermaid
+graph LR;
+    A-->B;
+```
+
+And the formula:
+
+```math
+# \sum_{i=1}^n i = \frac{n(n+1)}{2}
+```
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn diagrams_passthrough() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/diagrams",
+        "--diagrams",
+        "passthrough",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("$$\n# \\sum_{i=1}^n i = \\frac{n(n+1)}{2}\n$$")
+        .unwrap();
+}
+
+#[test]
+fn diagrams_image() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/diagrams",
+        "--diagrams",
+        "image",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("[![diagram](https://mermaid.ink/svg/Z3JhcGggTFI7CiAgICBBLS0-Qjs=)](https://mermaid.live/view#base64:")
+        .and()
+        .stdout()
+        .contains("[![formula](https://latex.codecogs.com/svg.image?%23%20%5Csum%5F%7Bi%3D1%7D")
+        .and()
+        .stdout()
+        .doesnt_contain("```mermaid")
+        .unwrap();
+}
+
+#[test]
+fn diagrams_passthrough_normalized() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/diagrams",
+        "--diagrams",
+        "passthrough",
+        "--normalize-markdown",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("$$\n# \\sum_{i=1}^n i = \\frac{n(n+1)}{2}\n$$")
+        .unwrap();
+}
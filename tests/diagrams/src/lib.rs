@@ -0,0 +1,12 @@
+//! How the parts fit together:
+//!
+//! ```mermaid
+//! graph LR;
+//!     A-->B;
+//! ```
+//!
+//! And the formula:
+//!
+//! $$
+//! # \sum_{i=1}^n i = \frac{n(n+1)}{2}
+//! $$
@@ -0,0 +1,63 @@
+use assert_cli::Assert;
+
+const EXPECTED_KEEP: &str = r#"
+```rust,no_run
+// This should output as ```rust too
+```
+
+```rust,should_panic
+// And also this should output as ```rust
+```
+"#;
+
+#[test]
+fn keep_doctest_attributes() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-template",
+        "--doctest-attributes",
+        "keep",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains(EXPECTED_KEEP)
+        .unwrap();
+}
+
+const EXPECTED_COMMENT: &str = r#"
+<!-- no_run -->
+```rust
+// This should output as ```rust too
+```
+
+<!-- should_panic -->
+```rust
+// And also this should output as ```rust
+```
+"#;
+
+#[test]
+fn comment_doctest_attributes() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-template",
+        "--doctest-attributes",
+        "comment",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains(EXPECTED_COMMENT)
+        .unwrap();
+}
@@ -0,0 +1,81 @@
+use assert_cli::Assert;
+use std::fs;
+
+#[test]
+fn dry_run_up_to_date() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output",
+        "README.md",
+        "--dry-run",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("README.md' unchanged, it is up to date")
+        .unwrap();
+}
+
+#[test]
+fn dry_run_stale() {
+    let before = fs::read_to_string("tests/check/STALE.md").unwrap();
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output",
+        "STALE.md",
+        "--dry-run",
+        "--force",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("STALE.md', the first difference is at line 1")
+        .unwrap();
+
+    assert_eq!(fs::read_to_string("tests/check/STALE.md").unwrap(), before);
+}
+
+#[test]
+fn dry_run_new_file() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output",
+        "NEW.md",
+        "--dry-run",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("Would create '")
+        .unwrap();
+
+    assert!(fs::metadata("tests/check/NEW.md").is_err());
+}
+
+#[test]
+fn dry_run_stdout() {
+    let args = ["readme", "--project-root", "tests/check", "--dry-run"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is("Would write 7 lines to stdout")
+        .unwrap();
+}
@@ -0,0 +1,50 @@
+use assert_cli::Assert;
+
+#[test]
+fn skip_duplicate_title() {
+    let args = ["readme", "--project-root", "tests/duplicate-title"];
+
+    let expected = r#"
+# duplicate-title
+
+A crate whose docs start with its name.
+
+License: MIT
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn always_add_title() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/duplicate-title",
+        "--always-add-title",
+    ];
+
+    let expected = r#"
+# duplicate-title
+
+## Duplicate-Title
+
+A crate whose docs start with its name.
+
+License: MIT
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
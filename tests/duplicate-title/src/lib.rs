@@ -0,0 +1,3 @@
+//! # Duplicate-Title
+//!
+//! A crate whose docs start with its name.
@@ -0,0 +1,57 @@
+use assert_cli::Assert;
+
+#[test]
+fn emoji_unicode() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/emoji",
+        "--no-license",
+        "--emoji",
+        "unicode",
+    ];
+
+    let expected = r#"
+# emoji
+
+Fast 🚀 and safe 🦀, but `:crab:` in code and :unknown: stay.
+
+```rust
+let s = ":tada:";
+```
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn emoji_kept_by_default() {
+    let args = ["readme", "--project-root", "tests/emoji"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("Fast :rocket: and safe :crab:")
+        .unwrap();
+}
+
+#[test]
+fn emoji_invalid_mode() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/emoji",
+        "--emoji",
+        "ascii",
+    ];
+
+    Assert::main_binary().with_args(&args).fails().unwrap();
+}
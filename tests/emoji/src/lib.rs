@@ -0,0 +1,5 @@
+//! Fast :rocket: and safe :crab:, but `:crab:` in code and :unknown: stay.
+//!
+//! ```
+//! let s = ":tada:";
+//! ```
@@ -75,3 +75,22 @@ fn entrypoint_resolution_cargo_bin() {
         .is("cargo bin")
         .unwrap();
 }
+
+#[test]
+fn entrypoint_resolution_custom_lib() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/entrypoint-resolution/custom-lib",
+        "--no-title",
+        "--no-license",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is("custom lib")
+        .unwrap();
+}
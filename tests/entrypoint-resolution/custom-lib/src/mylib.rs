@@ -0,0 +1 @@
+//! custom lib
@@ -0,0 +1,83 @@
+use assert_cli::Assert;
+
+const EXPECTED: &str = r#"
+# entrypoint
+
+Sub-library docs.
+
+License: MIT
+"#;
+
+#[test]
+fn entrypoint() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/entrypoint",
+        "--entrypoint",
+        "src/sub.rs",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
+
+const EXPECTED_MODULES: &str = r#"
+# entrypoint
+
+Sub-library docs.
+
+## child
+
+Child module of the sub-library.
+
+## bindings
+
+Bindings loaded with a `#[path]` attribute.
+
+## bindings::extra
+
+Module next to the bindings.
+
+License: MIT
+"#;
+
+#[test]
+fn entrypoint_include_modules() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/entrypoint",
+        "--entrypoint",
+        "src/sub.rs",
+        "--include-modules",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED_MODULES)
+        .unwrap();
+}
+
+#[test]
+fn entrypoint_conflicts_with_input() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/entrypoint",
+        "--entrypoint",
+        "src/sub.rs",
+        "--input",
+        "src/lib.rs",
+    ];
+
+    Assert::main_binary().with_args(&args).fails().unwrap();
+}
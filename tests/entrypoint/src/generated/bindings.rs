@@ -0,0 +1,3 @@
+//! Bindings loaded with a `#[path]` attribute.
+
+pub mod extra;
@@ -0,0 +1 @@
+//! Module next to the bindings.
@@ -0,0 +1,3 @@
+//! Crate root docs, not in the README.
+
+pub mod sub;
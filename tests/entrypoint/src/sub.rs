@@ -0,0 +1,5 @@
+//! Sub-library docs.
+
+pub mod child;
+#[path = "generated/bindings.rs"]
+pub mod bindings;
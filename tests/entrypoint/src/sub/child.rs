@@ -0,0 +1 @@
+//! Child module of the sub-library.
@@ -0,0 +1,63 @@
+use assert_cli::Assert;
+
+const EXPECTED_CONFIG: &str = r#"
+# exclude-section
+
+Crate with sections left out of the README.
+
+## Usage
+
+Use the crate.
+
+## Examples
+
+```rust
+let answer = 42;
+```
+
+License: MIT
+"#;
+
+#[test]
+fn exclude_section_from_config() {
+    let args = ["readme", "--project-root", "tests/exclude-section"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED_CONFIG)
+        .unwrap();
+}
+
+const EXPECTED_ARGS: &str = r#"
+# exclude-section
+
+Crate with sections left out of the README.
+
+## Usage
+
+Use the crate.
+
+License: MIT
+"#;
+
+#[test]
+fn exclude_section_from_args() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/exclude-section",
+        "--exclude-section",
+        "examples",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED_ARGS)
+        .unwrap();
+}
@@ -0,0 +1,15 @@
+//! Crate with sections left out of the README.
+//!
+//! # Usage
+//!
+//! Use the crate.
+//!
+//! ## Implementation notes
+//!
+//! Internal details, only for rustdoc.
+//!
+//! # Examples
+//!
+//! ```
+//! let answer = 42;
+//! ```
@@ -0,0 +1,78 @@
+use assert_cli::Assert;
+
+#[test]
+fn keep_tabs() {
+    let args = ["readme", "--project-root", "tests/expand-tabs"];
+
+    let expected = "
+# expand-tabs
+
+Aligned\twith tabs.
+
+```rust
+let a\t= 1;
+let abcd\t= 2;
+if a == 1 {
+\tprintln!(\"{}\", abcd);
+}
+```
+
+License: MIT
+";
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn expand_tabs() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/expand-tabs",
+        "--expand-tabs",
+        "4",
+    ];
+
+    let expected = "
+# expand-tabs
+
+Aligned\twith tabs.
+
+```rust
+let a   = 1;
+let abcd    = 2;
+if a == 1 {
+    println!(\"{}\", abcd);
+}
+```
+
+License: MIT
+";
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn expand_tabs_zero_fails() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/expand-tabs",
+        "--expand-tabs",
+        "0",
+    ];
+
+    Assert::main_binary().with_args(&args).fails().unwrap();
+}
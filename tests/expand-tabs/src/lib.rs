@@ -0,0 +1,9 @@
+//! Aligned	with tabs.
+//!
+//! ```
+//! let a	= 1;
+//! let abcd	= 2;
+//! if a == 1 {
+//! 	println!("{}", abcd);
+//! }
+//! ```
@@ -0,0 +1,49 @@
+use assert_cli::Assert;
+
+#[test]
+fn facade_crate() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/facade/macros",
+        "--no-license",
+    ];
+
+    let expected = r#"
+# facade-macros
+
+The API users actually import.
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn facade_entrypoint() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/facade/entrypoint",
+        "--no-license",
+    ];
+
+    let expected = r#"
+# facade-entrypoint
+
+Documentation from an alternative entry file.
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
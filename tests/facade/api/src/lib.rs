@@ -0,0 +1,3 @@
+//! The API users actually import.
+
+pub use facade_macros::*;
@@ -0,0 +1 @@
+//! Documentation from an alternative entry file.
@@ -0,0 +1 @@
+//! Internal macros, see the facade crate.
@@ -0,0 +1,53 @@
+use assert_cli::Assert;
+
+const EXPECTED_BARE: &str = r#"
+```
+// This is standard doc test and should be output as ```rust
+let condition = true;
+"#;
+
+#[test]
+fn bare_fence_lang() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-template",
+        "--fence-lang",
+        "",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains(EXPECTED_BARE)
+        .unwrap();
+}
+
+const EXPECTED_CUSTOM: &str = r#"
+```rust,ignore
+// This is standard doc test and should be output as ```rust
+let condition = true;
+"#;
+
+#[test]
+fn custom_fence_lang() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-template",
+        "--fence-lang",
+        "rust,ignore",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains(EXPECTED_CUSTOM)
+        .unwrap();
+}
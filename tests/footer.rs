@@ -0,0 +1,65 @@
+use assert_cli::Assert;
+
+#[test]
+fn footer() {
+    let args = ["readme", "--project-root", "tests/check", "--footer"];
+    let expected = format!(
+        "\
+# check
+
+Check the README.
+
+The docs changed.
+
+License: MIT
+
+<!-- cargo-readme {} source-hash fnv1a-64:5c4761f45d763db5 -->
+",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected.as_str())
+        .unwrap();
+}
+
+#[test]
+fn footer_changes_with_source() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--input",
+        "-",
+        "--footer",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .stdin("//! Check the README.\n//!\n//! The docs changed.\n\nfn main() {}\n")
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("The docs changed.")
+        .and()
+        .stdout()
+        .doesnt_contain("fnv1a-64:5c4761f45d763db5")
+        .unwrap();
+}
+
+#[test]
+fn no_footer() {
+    let args = ["readme", "--project-root", "tests/check"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .doesnt_contain("source-hash")
+        .unwrap();
+}
@@ -0,0 +1,87 @@
+use assert_cli::Assert;
+
+const EXPECTED: &str = r#"
+# footnotes
+
+Crate with footnotes[^note].
+
+## Notes
+
+Details about the crate.
+
+[^note]: Footnotes are supported by rustdoc.
+
+License: MIT
+"#;
+
+#[test]
+fn footnotes() {
+    let args = ["readme", "--project-root", "tests/footnotes"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
+
+const EXPECTED_EXCLUDE_SECTION: &str = r#"
+# footnotes
+
+Crate with footnotes[^note].
+
+[^note]: Footnotes are supported by rustdoc.
+
+License: MIT
+"#;
+
+#[test]
+fn footnotes_in_excluded_section() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/footnotes",
+        "--exclude-section",
+        "Notes",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED_EXCLUDE_SECTION)
+        .unwrap();
+}
+
+const EXPECTED_INLINE: &str = r#"
+# footnotes
+
+Crate with footnotes (Footnotes are supported by rustdoc.).
+
+## Notes
+
+Details about the crate.
+
+License: MIT
+"#;
+
+#[test]
+fn inline_footnotes() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/footnotes",
+        "--inline-footnotes",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED_INLINE)
+        .unwrap();
+}
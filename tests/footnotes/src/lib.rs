@@ -0,0 +1,7 @@
+//! Crate with footnotes[^note].
+//!
+//! # Notes
+//!
+//! Details about the crate.
+//!
+//! [^note]: Footnotes are supported by rustdoc.
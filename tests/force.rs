@@ -0,0 +1,71 @@
+use assert_cli::Assert;
+use std::fs;
+
+#[test]
+fn hand_written_output_is_kept() {
+    let output = "tests/check/HAND.md";
+    fs::write(output, "# Hand written\n").unwrap();
+
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output",
+        "HAND.md",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("HAND.md' was not generated by cargo-readme, use `--force` to replace it")
+        .unwrap();
+
+    let readme = fs::read_to_string(output).unwrap();
+    fs::remove_file(output).unwrap();
+    assert_eq!(readme, "# Hand written\n");
+}
+
+#[test]
+fn hand_written_output_is_replaced_with_force() {
+    let output = "tests/check/FORCED.md";
+    fs::write(output, "# Hand written\n").unwrap();
+
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output",
+        "FORCED.md",
+        "--force",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let readme = fs::read_to_string(output).unwrap();
+    fs::remove_file(output).unwrap();
+    assert_eq!(readme, fs::read_to_string("tests/check/README.md").unwrap());
+}
+
+#[test]
+fn unmarked_output_with_the_same_content_is_replaced() {
+    let output = "tests/check/UNMARKED.md";
+    let generated = fs::read_to_string("tests/check/README.md").unwrap();
+    let (_, unmarked) = generated.split_once('\n').unwrap();
+    fs::write(output, unmarked).unwrap();
+
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output",
+        "UNMARKED.md",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let readme = fs::read_to_string(output).unwrap();
+    fs::remove_file(output).unwrap();
+    assert_eq!(readme, generated);
+}
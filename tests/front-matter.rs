@@ -0,0 +1,127 @@
+use assert_cli::Assert;
+use std::fs;
+
+#[test]
+fn front_matter_yaml() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--front-matter",
+        "yaml",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("---\ntitle: \"readme-test\"\ndate: ")
+        .and()
+        .stdout()
+        .contains("\ntags: [\"readme\", \"doc_comments\"]\ngenerator: \"cargo-readme\"\n---\n\n[![Build Status]")
+        .unwrap();
+}
+
+#[test]
+fn front_matter_toml_file() {
+    let _ = fs::remove_file("tests/check/PAGE.md");
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--front-matter",
+        "toml",
+        "--output",
+        "PAGE.md",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+    // the file starts with the front matter instead of the marker comment, but is still replaced
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let page = fs::read_to_string("tests/check/PAGE.md").unwrap();
+    fs::remove_file("tests/check/PAGE.md").unwrap();
+    assert!(page.starts_with("+++\ntitle = \"check\"\ndate = "));
+    assert!(page.contains("\ntags = []\ngenerator = \"cargo-readme\"\n+++\n\n# check\n"));
+}
+
+#[test]
+fn front_matter_date_is_kept() {
+    let _ = fs::remove_file("tests/check/DATED.md");
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--front-matter",
+        "yaml",
+        "--output",
+        "DATED.md",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .with_args(&["--date", "2024-01-31"])
+        .succeeds()
+        .unwrap();
+    // the page is up to date on another day, and keeps its date when it is generated again
+    Assert::main_binary()
+        .with_args(&args)
+        .with_args(&["--check"])
+        .succeeds()
+        .unwrap();
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let page = fs::read_to_string("tests/check/DATED.md").unwrap();
+    fs::remove_file("tests/check/DATED.md").unwrap();
+    assert!(page.starts_with("---\ntitle: \"check\"\ndate: 2024-01-31\n"));
+}
+
+#[test]
+fn invalid_date() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--front-matter",
+        "toml",
+        "--date",
+        "yesterday",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("`--date` must be a date like `2024-01-31`, not `yesterday`")
+        .unwrap();
+}
+
+#[test]
+fn generator_outside_front_matter() {
+    let output = "tests/check/GENERATOR.md";
+    fs::write(
+        output,
+        "# Notes\n\n```yaml\ngenerator: \"cargo-readme\"\n```\n",
+    )
+    .unwrap();
+
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output",
+        "GENERATOR.md",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("was not generated by cargo-readme")
+        .unwrap();
+
+    fs::remove_file(output).unwrap();
+}
@@ -0,0 +1,25 @@
+use assert_cli::Assert;
+
+const EXPECTED: &str = r#"
+# html
+
+Crate with raw HTML.
+
+> [!WARNING]
+> Use with <em>care</em>.
+
+License: MIT
+"#;
+
+#[test]
+fn github_alerts() {
+    let args = ["readme", "--project-root", "tests/html", "--github-alerts"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
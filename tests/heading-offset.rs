@@ -0,0 +1,61 @@
+use assert_cli::Assert;
+
+#[test]
+fn demote_headings() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-template",
+        "--heading-offset",
+        "2",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("\n### Level 1 heading should become level 2\n")
+        .and()
+        .stdout()
+        .contains("\n##### Level 3 heading should become level 4\n")
+        .unwrap();
+}
+
+#[test]
+fn promote_headings() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-template",
+        "--heading-offset",
+        "-1",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("\n# Level 2 heading should become level 3\n")
+        .and()
+        .stdout()
+        .contains("\n## Level 3 heading should become level 4\n")
+        .unwrap();
+}
+
+#[test]
+fn heading_offset_conflicts_with_no_indent_headings() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-indent-headings",
+        "--heading-offset",
+        "2",
+    ];
+
+    Assert::main_binary().with_args(&args).fails().unwrap();
+}
@@ -0,0 +1,66 @@
+use assert_cli::Assert;
+
+const EXPECTED_SHOW: &str = r#"
+```rust
+// This is standard doc test and should be output as ```rust
+# This should NOT be on the output
+let condition = true;
+"#;
+
+#[test]
+fn show_hidden_lines() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-template",
+        "--show-hidden-lines",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains(EXPECTED_SHOW)
+        .unwrap();
+}
+
+const EXPECTED_STRIP: &str = r#"
+```rust
+// This is standard doc test and should be output as ```rust
+This should NOT be on the output
+let condition = true;
+"#;
+
+#[test]
+fn strip_hidden_marks() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-template",
+        "--show-hidden-lines",
+        "--strip-hidden-marks",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains(EXPECTED_STRIP)
+        .unwrap();
+}
+
+#[test]
+fn strip_hidden_marks_requires_show_hidden_lines() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--strip-hidden-marks",
+    ];
+
+    Assert::main_binary().with_args(&args).fails().unwrap();
+}
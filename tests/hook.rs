@@ -0,0 +1,103 @@
+use assert_cli::Assert;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Create a git repository with a crate in `crates/hook`, outside this repository
+fn repository(name: &str) -> PathBuf {
+    let root = env::temp_dir().join(format!("cargo-readme-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("crates/hook/src")).unwrap();
+    fs::write(
+        root.join("crates/hook/Cargo.toml"),
+        "[package]\nname = \"hook\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::write(root.join("crates/hook/src/lib.rs"), "//! Hook.\n").unwrap();
+
+    let status = Command::new("git")
+        .arg("init")
+        .arg("--quiet")
+        .arg(&root)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    root
+}
+
+#[test]
+fn install_and_uninstall_hook() {
+    let root = repository("hook");
+    let project_root = root.join("crates/hook");
+    let project_root = project_root.to_str().unwrap();
+    let hook = root.join(".git/hooks/pre-commit");
+
+    let args = ["readme", "install-hook", "--project-root", project_root];
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+    let script = fs::read_to_string(&hook).unwrap();
+    assert!(script.ends_with("\ncargo readme --project-root 'crates/hook' --check\n"));
+
+    // the hook installed by cargo-readme can be replaced
+    let args = [
+        "readme",
+        "install-hook",
+        "--project-root",
+        project_root,
+        "--regenerate",
+    ];
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+    let script = fs::read_to_string(&hook).unwrap();
+    assert!(script.ends_with(
+        "\ncargo readme --project-root 'crates/hook' --output README.md && \
+         git add 'crates/hook/README.md'\n"
+    ));
+
+    let args = ["readme", "uninstall-hook", "--project-root", project_root];
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+    assert!(!hook.exists());
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("does not exist")
+        .unwrap();
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn keep_other_hook() {
+    let root = repository("other-hook");
+    let project_root = root.join("crates/hook");
+    let project_root = project_root.to_str().unwrap();
+    let hook = root.join(".git/hooks/pre-commit");
+    fs::write(&hook, "#!/bin/sh\ncargo fmt --check\n").unwrap();
+
+    let args = ["readme", "install-hook", "--project-root", project_root];
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("was not installed by cargo-readme, use `--force` to replace it")
+        .unwrap();
+
+    let args = ["readme", "uninstall-hook", "--project-root", project_root];
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("was not installed by cargo-readme")
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(&hook).unwrap(),
+        "#!/bin/sh\ncargo fmt --check\n"
+    );
+
+    fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,62 @@
+use assert_cli::Assert;
+use std::fs;
+
+#[test]
+fn html_output() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--output-format",
+        "html",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("<!DOCTYPE html>")
+        .and()
+        .stdout()
+        .contains("<title>readme-test</title>")
+        .and()
+        .stdout()
+        .contains(r#"<h1 id="readme-test">readme-test</h1>"#)
+        .and()
+        .stdout()
+        .contains(r#"<span class="kw">let</span> condition = <span class="kw">true</span>;"#)
+        .and()
+        .stdout()
+        .contains(
+            r#"<pre><code class="language-python"># This should be on the output</code></pre>"#,
+        )
+        .unwrap();
+}
+
+#[test]
+fn html_output_file() {
+    let _ = fs::remove_file("tests/check/index.html");
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output-format",
+        "html",
+        "--output",
+        "index.html",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+    // the page has no marker comment, but is still replaced as generated
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let html = fs::read_to_string("tests/check/index.html").unwrap();
+    fs::remove_file("tests/check/index.html").unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>\n"));
+    assert!(html.contains(&format!(
+        "<meta name=\"generator\" content=\"cargo-readme {}\">",
+        env!("CARGO_PKG_VERSION")
+    )));
+    assert!(html.contains("<p>Check the README.</p>\n<p>The docs changed.</p>"));
+}
@@ -0,0 +1,7 @@
+//! Crate with raw HTML.
+//!
+//! <div class="warning">
+//!
+//! Use with <em>care</em>.
+//!
+//! </div>
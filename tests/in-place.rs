@@ -0,0 +1,58 @@
+use assert_cli::Assert;
+use std::fs;
+
+const EXPECTED: &str = r#"Hand-written header.
+
+<!-- cargo-readme start -->
+
+# in-place
+
+Generated docs.
+
+License: MIT
+
+<!-- cargo-readme end -->
+
+Hand-written footer.
+"#;
+
+#[test]
+fn in_place() {
+    fs::copy("tests/in-place/README.orig.md", "tests/in-place/README.md").unwrap();
+
+    let args = ["readme", "--project-root", "tests/in-place", "--in-place"];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let readme = fs::read_to_string("tests/in-place/README.md").unwrap();
+    assert_eq!(readme, EXPECTED);
+
+    // updating again gives the same result
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let readme = fs::read_to_string("tests/in-place/README.md").unwrap();
+    assert_eq!(readme, EXPECTED);
+}
+
+#[test]
+fn in_place_without_markers() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/in-place",
+        "--in-place",
+        "--output",
+        "NO-MARKERS.md",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("Missing `<!-- cargo-readme start -->` marker")
+        .unwrap();
+
+    let content = fs::read_to_string("tests/in-place/NO-MARKERS.md").unwrap();
+    assert_eq!(content, "Hand-written content without markers.\n");
+}
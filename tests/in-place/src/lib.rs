@@ -0,0 +1 @@
+//! Generated docs.
@@ -0,0 +1,64 @@
+use assert_cli::Assert;
+
+const EXPECTED: &str = r#"
+# modules
+
+Crate with docs spread across modules.
+
+## alpha
+
+Alpha module.
+
+### Usage
+
+Use the alpha module.
+
+## alpha::nested
+
+Nested module inside alpha.
+
+## beta
+
+Beta module, in its own directory.
+
+License: MIT
+"#;
+
+#[test]
+fn include_modules() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/modules",
+        "--include-modules",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
+
+const EXPECTED_WITHOUT_MODULES: &str = r#"
+# modules
+
+Crate with docs spread across modules.
+
+License: MIT
+"#;
+
+#[test]
+fn without_include_modules() {
+    let args = ["readme", "--project-root", "tests/modules"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED_WITHOUT_MODULES)
+        .unwrap();
+}
@@ -0,0 +1,35 @@
+use assert_cli::Assert;
+
+const EXPECTED: &str = r#"
+# include-str
+
+Docs shared between rustdoc and the readme.
+
+## Getting started
+
+Read the [guide](docs/guide.md) and look at the ![diagram](docs/images/diagram.png).
+
+```rust
+// [not a link](guide.md)
+let answer = include_str::answer();
+```
+
+[changelog]: CHANGELOG.md
+
+See the [usage guide](https://docs.rs/include-str) for more.
+
+License: MIT
+"#;
+
+#[test]
+fn include_str() {
+    let args = ["readme", "--project-root", "tests/include-str"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
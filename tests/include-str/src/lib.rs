@@ -0,0 +1,7 @@
+#![doc = include_str!("../docs/intro.md")]
+//!
+//! See the [usage guide](https://docs.rs/include-str) for more.
+
+pub fn answer() -> u32 {
+    42
+}
@@ -0,0 +1,116 @@
+use assert_cli::Assert;
+
+#[test]
+fn init_template() {
+    let args = [
+        "readme",
+        "init",
+        "--project-root",
+        "tests/init",
+        "--template",
+        "INIT.tpl",
+        "--force",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stderr()
+        .contains("INIT.tpl'")
+        .unwrap();
+
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/init",
+        "--template",
+        "INIT.tpl",
+    ];
+
+    let expected = r#"
+[![Crates.io](https://img.shields.io/crates/v/init.svg)](https://crates.io/crates/init)
+[![Docs.rs](https://docs.rs/init/badge.svg)](https://docs.rs/init)
+
+# init
+
+Set up the readme.
+
+## License
+
+MIT
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn init_existing_template() {
+    let args = [
+        "readme",
+        "init",
+        "--project-root",
+        "tests/init",
+        "--template",
+        "EXISTING.tpl",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("EXISTING.tpl' already exists, use `--force` to replace it")
+        .unwrap();
+}
+
+#[test]
+fn init_quietly() {
+    let args = [
+        "readme",
+        "--quiet",
+        "init",
+        "--project-root",
+        "tests/init",
+        "--template",
+        "QUIET.tpl",
+        "--force",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stderr()
+        .is("")
+        .unwrap();
+}
+
+#[test]
+fn init_with_json_messages() {
+    let args = [
+        "readme",
+        "--message-format",
+        "json",
+        "init",
+        "--project-root",
+        "tests/init",
+        "--template",
+        "JSON.tpl",
+        "--force",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stderr()
+        .contains(r#""kind":"info","level":"info","line":null,"message":"Created '"#)
+        .unwrap();
+}
@@ -0,0 +1,10 @@
+[![Crates.io](https://img.shields.io/crates/v/{{crate}}.svg)](https://crates.io/crates/{{crate}})
+[![Docs.rs](https://docs.rs/{{crate}}/badge.svg)](https://docs.rs/{{crate}})
+
+# {{crate}}
+
+{{readme}}
+
+## License
+
+{{license}}
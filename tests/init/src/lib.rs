@@ -0,0 +1 @@
+//! Set up the readme.
@@ -0,0 +1,65 @@
+use assert_cli::Assert;
+
+#[test]
+fn installation_library() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--input",
+        "src/single_line.rs",
+        "--template",
+        "INSTALLATION.tpl",
+    ];
+
+    let expected = r#"
+# readme-test
+
+## Installation
+
+```sh
+cargo add readme-test@0.1.0
+```
+
+Test crate for cargo-readme
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn installation_binary() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/entrypoint-resolution/main",
+        "--template",
+        "../../test-project/INSTALLATION.tpl",
+    ];
+
+    let expected = r#"
+# readme-test
+
+## Installation
+
+```sh
+cargo install readme-test
+```
+
+main
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
@@ -0,0 +1,77 @@
+use assert_cli::Assert;
+
+const EXPECTED_DOCS_RS: &str = r#"
+# intra-doc-links
+
+Parse values with [`Parser`](https://docs.rs/intra-doc-links/0.2.0/intra_doc_links/?search=Parser), or build them with [the builder](https://docs.rs/intra-doc-links/0.2.0/intra_doc_links/?search=builder%3A%3ABuilder).
+
+Errors are reported as [`std::io::Error`](https://doc.rust-lang.org/std/?search=io%3A%3AError).
+
+License: MIT
+"#;
+
+#[test]
+fn intra_doc_links_docs_rs() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/intra-doc-links",
+        "--intra-doc-links",
+        "docs-rs",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED_DOCS_RS)
+        .unwrap();
+}
+
+const EXPECTED_STRIP: &str = r#"
+# intra-doc-links
+
+Parse values with `Parser`, or build them with the builder.
+
+Errors are reported as `std::io::Error`.
+
+License: MIT
+"#;
+
+#[test]
+fn intra_doc_links_strip() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/intra-doc-links",
+        "--intra-doc-links",
+        "strip",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED_STRIP)
+        .unwrap();
+}
+
+#[test]
+fn strip_unresolved_links() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/intra-doc-links",
+        "--strip-unresolved-links",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED_STRIP)
+        .unwrap();
+}
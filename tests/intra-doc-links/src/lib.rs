@@ -0,0 +1,9 @@
+//! Parse values with [`Parser`], or build them with [the builder](crate::builder::Builder).
+//!
+//! Errors are reported as [`std::io::Error`].
+
+pub mod builder {
+    pub struct Builder;
+}
+
+pub struct Parser;
@@ -0,0 +1,43 @@
+use assert_cli::Assert;
+
+#[test]
+fn invalid_manifest_fails_with_config_error() {
+    let args = ["readme", "--project-root", "tests/invalid-manifest"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails_with(3)
+        .and()
+        .stderr()
+        .contains("invalid-manifest/Cargo.toml': TOML parse error at line 3")
+        .unwrap();
+}
+
+#[test]
+fn missing_entrypoint_fails_with_generic_error() {
+    let args = ["readme", "--project-root", "tests/no-entrypoint-fail"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails_with(1)
+        .unwrap();
+}
+
+#[test]
+fn missing_input_fails_with_generic_error() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--input",
+        "src/template.rs",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails_with(1)
+        .and()
+        .stderr()
+        .contains("src/template.rs': No such file or directory")
+        .unwrap();
+}
@@ -0,0 +1,24 @@
+use assert_cli::Assert;
+
+const EXPECTED: &str = "
+# invalid-utf8
+
+Docs of a file with invalid UTF-8 in caf\u{FFFD}.
+
+More docs.
+
+License: MIT
+";
+
+#[test]
+fn invalid_utf8() {
+    let args = ["readme", "--project-root", "tests/invalid-utf8"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
@@ -0,0 +1,5 @@
+//! Docs of a file with invalid UTF-8 in caf.
+//!
+//! More docs.
+
+pub const BYTES: &[u8] = b"";
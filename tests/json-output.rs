@@ -0,0 +1,102 @@
+use assert_cli::Assert;
+use std::fs;
+
+const EXPECTED: &str = r##"{
+  "code_blocks": [],
+  "crate": {
+    "badges": [],
+    "categories": [],
+    "description": null,
+    "keywords": [],
+    "license": "MIT",
+    "license_file": null,
+    "name": "check",
+    "repository": null,
+    "rust_version": null,
+    "version": "0.1.0"
+  },
+  "generator": "cargo-readme VERSION",
+  "readme": "# check\n\nCheck the README.\n\nThe docs changed.\n\nLicense: MIT",
+  "sections": [
+    {
+      "content": "Check the README.\n\nThe docs changed.\n\nLicense: MIT",
+      "heading": "check",
+      "level": 1
+    }
+  ],
+  "variables": {
+    "badges": null,
+    "categories": "",
+    "crate": "check",
+    "installation": "```sh\ncargo add check@0.1.0\n```",
+    "keywords": "",
+    "license": "MIT",
+    "version": "0.1.0"
+  }
+}
+"##;
+
+fn expected() -> String {
+    EXPECTED.replace("VERSION", env!("CARGO_PKG_VERSION"))
+}
+
+#[test]
+fn json_output() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output-format",
+        "json",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected().as_str())
+        .unwrap();
+}
+
+#[test]
+fn json_output_file() {
+    let _ = fs::remove_file("tests/check/README.json");
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output-format",
+        "json",
+        "--output",
+        "README.json",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+    // the file has no marker comment, but is still replaced as generated
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let json = fs::read_to_string("tests/check/README.json").unwrap();
+    fs::remove_file("tests/check/README.json").unwrap();
+    assert_eq!(json, expected());
+}
+
+#[test]
+fn json_output_with_footer() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output-format",
+        "json",
+        "--footer",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails_with(3)
+        .and()
+        .stderr()
+        .contains("`--footer` and `--front-matter` cannot be used with `--output-format json`")
+        .unwrap();
+}
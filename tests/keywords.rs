@@ -0,0 +1,32 @@
+use assert_cli::Assert;
+
+const EXPECTED: &str = r#"
+# readme-test
+
+[![readme](https://img.shields.io/badge/keyword-readme-blue.svg)](https://crates.io/keywords/readme)
+[![doc_comments](https://img.shields.io/badge/keyword-doc__comments-blue.svg)](https://crates.io/keywords/doc_comments)
+[![development-tools::cargo-plugins](https://img.shields.io/badge/category-development--tools::cargo--plugins-blue.svg)](https://crates.io/categories/development-tools::cargo-plugins)
+
+Test crate for cargo-readme
+"#;
+
+#[test]
+fn keywords_and_categories() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--input",
+        "src/single_line.rs",
+        "--template",
+        "KEYWORDS.tpl",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
@@ -0,0 +1,48 @@
+use assert_cli::Assert;
+
+const EXPECTED: &str = r#"
+# license-file
+
+A crate with a license file.
+
+License: [MIT](LICENSE)
+"#;
+
+#[test]
+fn license_file() {
+    let args = ["readme", "--project-root", "tests/license-file"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
+
+// the license is only a link in the markdown
+#[test]
+fn license_file_in_json() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/license-file",
+        "--output-format",
+        "json",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains(
+            r#""license": "MIT",
+    "license_file": "LICENSE","#,
+        )
+        .and()
+        .stdout()
+        .contains(r#""license": "[MIT](LICENSE)""#)
+        .unwrap();
+}
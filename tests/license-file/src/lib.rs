@@ -0,0 +1 @@
+//! A crate with a license file.
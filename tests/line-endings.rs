@@ -0,0 +1,48 @@
+use assert_cli::Assert;
+use std::fs;
+
+const EXPECTED: &str = "# line-endings
+
+Docs with Windows line endings.
+
+```rust
+let a = 1;
+```
+";
+
+#[test]
+fn read_crlf() {
+    let args = ["readme", "--project-root", "tests/line-endings"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
+
+#[test]
+fn write_line_endings() {
+    let output = "tests/line-endings/README.md";
+    let _ = fs::remove_file(output);
+    let expected = format!("<!-- generated by cargo-readme -->\n{}", EXPECTED);
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/line-endings",
+        "--output",
+        "README.md",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+    assert_eq!(fs::read_to_string(output).unwrap(), expected);
+
+    let args = [&args[..], &["--line-endings", "crlf"]].concat();
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+    assert_eq!(
+        fs::read_to_string(output).unwrap(),
+        expected.replace('\n', "\r\n")
+    );
+}
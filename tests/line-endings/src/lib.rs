@@ -0,0 +1,5 @@
+//! Docs with Windows line endings.
+//!
+//! ```
+//! let a = 1;
+//! ```
@@ -0,0 +1,103 @@
+use assert_cli::Assert;
+
+#[test]
+fn keep_links() {
+    let args = ["readme", "--project-root", "tests/link-style"];
+
+    let expected = r#"
+# link-style
+
+Read [the guide][guide] and [the docs](https://docs.rs/link-style).
+
+The [guide] and the [crate](https://crates.io/crates/link-style) are also linked from
+[`Parser`].
+
+```rust
+// [guide]
+```
+
+[guide]: https://example.com/guide
+
+License: MIT
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn inline_links() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/link-style",
+        "--link-style",
+        "inline",
+    ];
+
+    let expected = r#"
+# link-style
+
+Read [the guide](https://example.com/guide) and [the docs](https://docs.rs/link-style).
+
+The [guide](https://example.com/guide) and the [crate](https://crates.io/crates/link-style) are also linked from
+[`Parser`].
+
+```rust
+// [guide]
+```
+
+License: MIT
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn reference_links() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/link-style",
+        "--link-style",
+        "reference",
+    ];
+
+    let expected = r#"
+# link-style
+
+Read [the guide][1] and [the docs][2].
+
+The [guide][1] and the [crate][3] are also linked from
+[`Parser`].
+
+```rust
+// [guide]
+```
+
+License: MIT
+
+[1]: https://example.com/guide
+[2]: https://docs.rs/link-style
+[3]: https://crates.io/crates/link-style
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
@@ -0,0 +1,12 @@
+//! Read [the guide][guide] and [the docs](https://docs.rs/link-style).
+//!
+//! The [guide] and the [crate](https://crates.io/crates/link-style) are also linked from
+//! [`Parser`].
+//!
+//! ```
+//! // [guide]
+//! ```
+//!
+//! [guide]: https://example.com/guide
+
+pub struct Parser;
@@ -0,0 +1,72 @@
+use assert_cli::Assert;
+
+#[test]
+fn lint_reports_problems() {
+    let args = ["readme", "--project-root", "tests/lint", "lint"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .is("Error: broken link to `docs/guide.md` at line 7
+Warning: code block without language at line 9
+Error: missing section `Support`
+Error: The README has 2 problems")
+        .unwrap();
+}
+
+#[test]
+fn lint_deny_warnings() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/lint",
+        "lint",
+        "--deny-warnings",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("Error: The README has 3 problems")
+        .unwrap();
+}
+
+#[test]
+fn lint_clean_readme() {
+    let args = ["readme", "--project-root", "tests/check", "lint"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stderr()
+        .is("")
+        .unwrap();
+}
+
+#[test]
+fn lint_colors() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/lint",
+        "--color",
+        "always",
+        "lint",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("\x1b[1;31mError\x1b[0m: broken link to `docs/guide.md` at line 7")
+        .and()
+        .stderr()
+        .contains("\x1b[1;33mWarning\x1b[0m: code block without language at line 9")
+        .unwrap();
+}
@@ -0,0 +1,17 @@
+//! Lint the README.
+//!
+//! # Usage
+//!
+//! See [the guide](docs/guide.md) and [the manifest](Cargo.toml).
+//!
+//! ```text
+//! # not a heading
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! let a = 1;
+//! ```
+//!
+//! # Usage
@@ -0,0 +1,84 @@
+use assert_cli::{Assert, Environment};
+
+#[test]
+fn man_page_output() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/man-page",
+        "--output-format",
+        "man",
+    ];
+
+    // without `SOURCE_DATE_EPOCH`, the page is dated with the version, not the current date
+    Assert::main_binary()
+        .with_args(&args)
+        .with_env(Environment::inherit().insert("SOURCE_DATE_EPOCH", ""))
+        .succeeds()
+        .and()
+        .stdout()
+        .contains(".TH \"GREET\" 1 \"0.1.0\" \"greet 0.1.0\" \"User Commands\"\n")
+        .and()
+        .stdout()
+        .contains(
+            r#".SH "NAME"
+greet \- Greet people from the command line
+.PP
+Print a greeting for each name given.
+.SH "SYNOPSIS"
+.PP
+.RS 4
+.nf
+greet [OPTIONS] <NAME>...
+.fi
+.RE
+.SH "OPTIONS"
+.TP
+\fB\-l\fR, \fB\-\-loud\fR
+greet in uppercase
+.TP
+\fB\-\-greeting <TEXT>\fR
+use another greeting than \fIHello\fR
+"#,
+        )
+        .unwrap();
+}
+
+#[test]
+fn man_page_source_date() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/man-page",
+        "--output-format",
+        "man",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .with_env(Environment::inherit().insert("SOURCE_DATE_EPOCH", "1706659200"))
+        .succeeds()
+        .and()
+        .stdout()
+        .contains(r#".TH "GREET" 1 "2024\-01\-31" "greet 0.1.0" "User Commands""#)
+        .unwrap();
+}
+
+#[test]
+fn man_page_without_binary() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output-format",
+        "man",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails_with(3)
+        .and()
+        .stderr()
+        .contains("`--output-format man` needs a crate with a binary target")
+        .unwrap();
+}
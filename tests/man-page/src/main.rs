@@ -0,0 +1,14 @@
+//! Print a greeting for each name given.
+//!
+//! # Synopsis
+//!
+//! ```sh
+//! greet [OPTIONS] <NAME>...
+//! ```
+//!
+//! # Options
+//!
+//! - `-l`, `--loud`: greet in uppercase
+//! - `--greeting <TEXT>`: use another greeting than *Hello*
+
+fn main() {}
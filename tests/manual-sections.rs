@@ -0,0 +1,44 @@
+use assert_cli::Assert;
+use std::fs;
+
+const EXPECTED: &str = r#"<!-- generated by cargo-readme -->
+# manual-sections
+
+Generated intro.
+
+## Usage
+
+Generated usage.
+
+## Support <!-- cargo-readme manual -->
+
+Hand-written support.
+
+## Contributing <!-- cargo-readme manual -->
+
+Hand-written contributing.
+
+License: MIT
+"#;
+
+#[test]
+fn preserve_manual_sections() {
+    fs::copy(
+        "tests/manual-sections/README.orig.md",
+        "tests/manual-sections/README.md",
+    )
+    .unwrap();
+
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/manual-sections",
+        "--output",
+        "README.md",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let readme = fs::read_to_string("tests/manual-sections/README.md").unwrap();
+    assert_eq!(readme, EXPECTED);
+}
@@ -0,0 +1,9 @@
+//! Generated intro.
+//!
+//! # Usage
+//!
+//! Generated usage.
+//!
+//! # Contributing
+//!
+//! Generated contributing.
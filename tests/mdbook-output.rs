@@ -0,0 +1,79 @@
+use assert_cli::Assert;
+
+const EXPECTED: &str = r#"# readme-test
+
+Test crate for cargo-readme
+
+## Level 1 heading should become level 2
+
+```rust
+// This is standard doc test and should be output as ```rust
+# This should NOT be on the output
+let condition = true;
+"#;
+
+#[test]
+fn mdbook_chapter() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-template",
+        "--output-format",
+        "mdbook",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains(EXPECTED)
+        .and()
+        .stdout()
+        .contains("```rust,no_run\n")
+        .unwrap();
+}
+
+#[test]
+fn mdbook_chapter_without_title() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-template",
+        "--no-title",
+        "--output-format",
+        "mdbook",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("\n# Level 1 heading should become level 2\n")
+        .unwrap();
+}
+
+#[test]
+fn mdbook_chapter_doctest_attributes_dropped() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-template",
+        "--output-format",
+        "mdbook",
+        "--doctest-attributes",
+        "drop",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .doesnt_contain("```rust,no_run")
+        .unwrap();
+}
@@ -0,0 +1,7 @@
+//! Alpha module.
+//!
+//! # Usage
+//!
+//! Use the alpha module.
+
+pub mod nested;
@@ -0,0 +1 @@
+//! Nested module inside alpha.
@@ -0,0 +1 @@
+//! Beta module, in its own directory.
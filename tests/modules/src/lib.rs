@@ -0,0 +1,6 @@
+//! Crate with docs spread across modules.
+
+pub mod alpha;
+pub mod beta;
+mod private;
+pub mod undocumented;
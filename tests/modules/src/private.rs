@@ -0,0 +1 @@
+//! Private module, left out of the README.
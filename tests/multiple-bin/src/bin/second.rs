@@ -0,0 +1,3 @@
+//! second binary
+
+fn main() {}
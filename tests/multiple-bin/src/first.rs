@@ -0,0 +1,3 @@
+//! first binary
+
+fn main() {}
@@ -0,0 +1,78 @@
+use assert_cli::Assert;
+
+const EXPECTED: &str = r#"
+# readme-test
+
+Test crate for cargo-readme
+
+Test crate for cargo-readme
+
+## Level 1 heading should become level 2
+
+License: MIT
+"#;
+
+#[test]
+fn multiple_inputs() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-template",
+        "--no-badges",
+        "--input",
+        "src/single_line.rs",
+        "--input",
+        "src/no_docs.rs",
+        "--input",
+        "src/other.rs",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
+
+#[test]
+fn multiple_inputs_comma_list() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-template",
+        "--no-badges",
+        "--input",
+        "src/single_line.rs,src/other.rs",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
+
+#[test]
+fn multiple_inputs_missing_file() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--input",
+        "src/single_line.rs,src/missing.rs",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("src/missing.rs")
+        .unwrap();
+}
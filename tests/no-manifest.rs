@@ -0,0 +1,79 @@
+use assert_cli::Assert;
+
+#[test]
+fn no_manifest_script() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/no-manifest",
+        "--input",
+        "script.rs",
+        "--no-manifest",
+        "--crate-name",
+        "script",
+        "--license",
+        "MIT OR Apache-2.0",
+    ];
+
+    let expected = r#"
+# script
+
+Script outside a Cargo project.
+
+```rust
+let script = true;
+```
+
+License: MIT OR Apache-2.0
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn license_override() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--license",
+        "Apache-2.0",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("License: Apache-2.0")
+        .and()
+        .stdout()
+        .doesnt_contain("License: MIT")
+        .unwrap();
+}
+
+#[test]
+fn no_manifest_requires_crate_name() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/no-manifest",
+        "--input",
+        "script.rs",
+        "--no-manifest",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("--crate-name")
+        .unwrap();
+}
@@ -0,0 +1,7 @@
+//! Script outside a Cargo project.
+//!
+//! ```
+//! let script = true;
+//! ```
+
+fn main() {}
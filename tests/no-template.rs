@@ -62,3 +62,24 @@ fn no_template() {
         .is(EXPECTED)
         .unwrap();
 }
+
+#[test]
+fn no_template_with_template() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-template",
+        "--template",
+        "OTHER.tpl",
+    ];
+
+    // invalid arguments are a configuration error, not an outdated README
+    Assert::main_binary()
+        .with_args(&args)
+        .fails_with(3)
+        .and()
+        .stderr()
+        .contains("cannot be used with")
+        .unwrap();
+}
@@ -0,0 +1,59 @@
+use assert_cli::Assert;
+
+#[test]
+fn normalize_markdown() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/normalize-markdown",
+        "--normalize-markdown",
+    ];
+
+    let expected = r#"
+# normalize
+
+Parse **everything**, *quickly*.
+
+# Usage
+
+- add the crate
+- call `parse_all_the_things`
+
+```sh
+cargo add normalize
+```
+
+---
+
+## Options
+
+1. first
+2. second
+
+License: MIT
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn keep_markdown_without_normalize() {
+    let args = ["readme", "--project-root", "tests/normalize-markdown"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("Parse __everything__, _quickly_.")
+        .and()
+        .stdout()
+        .contains("~~~sh")
+        .unwrap();
+}
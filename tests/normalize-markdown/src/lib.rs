@@ -0,0 +1,19 @@
+//! Parse __everything__, _quickly_.
+//!
+//! Usage
+//! =====
+//!
+//! * add the crate
+//! * call `parse_all_the_things`
+//!
+//! ~~~sh
+//! cargo add normalize
+//! ~~~
+//!
+//! ***
+//!
+//! Options
+//! ----------
+//!
+//! 1) first
+//! 2) second
@@ -0,0 +1,45 @@
+use assert_cli::Assert;
+
+const EXPECTED: &str = r#"
+Use the crate.
+"#;
+
+#[test]
+fn only_section() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/exclude-section",
+        "--only-section",
+        "usage",
+        "--no-title",
+        "--no-license",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
+
+#[test]
+fn only_missing_section() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/exclude-section",
+        "--only-section",
+        "Implementation notes",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("Section `Implementation notes` not found in the docs")
+        .unwrap();
+}
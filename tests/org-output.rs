@@ -0,0 +1,52 @@
+use assert_cli::Assert;
+use std::fs;
+
+const EXPECTED: &str = r#"* check
+
+Check the README.
+
+The docs changed.
+
+License: MIT
+"#;
+
+#[test]
+fn org_output() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output-format",
+        "org",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
+
+#[test]
+fn org_output_file() {
+    let _ = fs::remove_file("tests/check/README.org");
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output-format",
+        "org",
+        "--output",
+        "README.org",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+    // the file starts with the Org marker comment, so it is replaced as generated
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let org = fs::read_to_string("tests/check/README.org").unwrap();
+    fs::remove_file("tests/check/README.org").unwrap();
+    assert_eq!(org, format!("# generated by cargo-readme\n{}", EXPECTED));
+}
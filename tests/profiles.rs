@@ -0,0 +1,105 @@
+use assert_cli::Assert;
+use std::fs;
+
+const WEBSITE: &str = "\
+# profiles 0.1.0
+
+Crate with several profiles.
+
+# Usage
+
+Use it.
+";
+
+#[test]
+fn profile_with_command_line_output() {
+    // the `--output` of the command line overrides the one of the profile
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/profiles",
+        "--profile",
+        "website",
+        "--output",
+        "-",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(WEBSITE)
+        .unwrap();
+}
+
+#[test]
+fn all_profiles() {
+    let _ = fs::remove_file("tests/profiles/README.md");
+    let _ = fs::remove_file("tests/profiles/WEBSITE.md");
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/profiles",
+        "--all-profiles",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let readme = fs::read_to_string("tests/profiles/README.md").unwrap();
+    let website = fs::read_to_string("tests/profiles/WEBSITE.md").unwrap();
+    fs::remove_file("tests/profiles/README.md").unwrap();
+    fs::remove_file("tests/profiles/WEBSITE.md").unwrap();
+
+    assert_eq!(
+        readme,
+        "\
+<!-- generated by cargo-readme -->
+# profiles
+
+Crate with several profiles.
+
+## Usage
+
+Use it.
+
+License: MIT
+"
+    );
+    assert_eq!(
+        website,
+        format!("<!-- generated by cargo-readme -->\n{}", WEBSITE)
+    );
+}
+
+#[test]
+fn unknown_profile() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/profiles",
+        "--profile",
+        "missing",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails_with(3)
+        .and()
+        .stderr()
+        .contains("No profile named `missing` in `[package.metadata.readme.profiles]`")
+        .unwrap();
+}
+
+#[test]
+fn no_profiles() {
+    let args = ["readme", "--project-root", "tests/check", "--all-profiles"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails_with(3)
+        .and()
+        .stderr()
+        .contains("No profile set in `[package.metadata.readme.profiles]`")
+        .unwrap();
+}
@@ -0,0 +1,3 @@
+# {{crate}} {{version}}
+
+{{readme}}
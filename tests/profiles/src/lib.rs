@@ -0,0 +1,5 @@
+//! Crate with several profiles.
+//!
+//! # Usage
+//!
+//! Use it.
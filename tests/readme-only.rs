@@ -0,0 +1,26 @@
+use assert_cli::Assert;
+
+#[test]
+fn readme_and_docs_only_regions() {
+    let args = ["readme", "--project-root", "tests/readme-only"];
+
+    let expected = r#"
+# readme-only
+
+[![CI](https://example.com/ci.svg)](https://example.com/ci)
+
+Parse things.
+
+The end.
+
+License: MIT
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
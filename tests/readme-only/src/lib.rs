@@ -0,0 +1,13 @@
+//! <!-- cargo-readme readme-only-start
+//! [![CI](https://example.com/ci.svg)](https://example.com/ci)
+//! cargo-readme readme-only-end -->
+//!
+//! Parse things.
+//!
+//! <!-- cargo-readme docs-only-start -->
+//! See the [`parser`] module for the details.
+//! <!-- cargo-readme docs-only-end -->
+//!
+//! The end.
+
+pub mod parser {}
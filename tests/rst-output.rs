@@ -0,0 +1,74 @@
+use assert_cli::Assert;
+use std::fs;
+
+const EXPECTED: &str = r#"check
+=====
+
+Check the README.
+
+The docs changed.
+
+License: MIT
+"#;
+
+#[test]
+fn rst_output() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output-format",
+        "rst",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
+
+#[test]
+fn rst_output_file() {
+    let _ = fs::remove_file("tests/check/README.rst");
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output-format",
+        "rst",
+        "--output",
+        "README.rst",
+    ];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+    // the file starts with the rst marker comment, so it is replaced as generated
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let rst = fs::read_to_string("tests/check/README.rst").unwrap();
+    fs::remove_file("tests/check/README.rst").unwrap();
+    assert_eq!(rst, format!(".. generated by cargo-readme\n{}", EXPECTED));
+}
+
+#[test]
+fn rst_output_with_front_matter() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output-format",
+        "rst",
+        "--front-matter",
+        "yaml",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails_with(3)
+        .and()
+        .stderr()
+        .contains("`--footer` and `--front-matter` cannot be used with `--output-format rst`")
+        .unwrap();
+}
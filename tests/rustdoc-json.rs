@@ -0,0 +1,59 @@
+use assert_cli::Assert;
+
+const EXPECTED: &str = r#"
+# rustdoc-json
+
+Docs generated by a macro.
+
+The line ends with a hard break  
+before this one.
+
+## Examples
+
+```rust
+let answer = 42;
+```
+
+License: MIT
+"#;
+
+#[test]
+#[ignore = "requires the nightly toolchain"]
+fn rustdoc_json_backend() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/rustdoc-json",
+        "--backend",
+        "rustdoc-json",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
+
+#[test]
+fn rustdoc_json_backend_conflicts_with_input() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/rustdoc-json",
+        "--backend",
+        "rustdoc-json",
+        "--input",
+        "src/lib.rs",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("`--backend rustdoc-json` can only read")
+        .unwrap();
+}
@@ -0,0 +1,14 @@
+#![doc = concat!("Docs generated by ", "a macro.")]
+//!
+//! The line ends with a hard break  
+//! before this one.
+//!
+//! # Examples
+//!
+//! ```
+//! # let hidden = true;
+//! let answer = 42;
+//! ```
+
+/// Item docs, not in the README
+pub fn item() {}
@@ -0,0 +1,90 @@
+use assert_cli::Assert;
+
+const EXPECTED_PASSTHROUGH: &str = r#"
+# html
+
+Crate with raw HTML.
+
+<div class="warning">
+
+Use with <em>care</em>.
+
+</div>
+
+License: MIT
+"#;
+
+#[test]
+fn passthrough_html() {
+    let args = ["readme", "--project-root", "tests/html"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED_PASSTHROUGH)
+        .unwrap();
+}
+
+const EXPECTED_STRIP: &str = r#"
+# html
+
+Crate with raw HTML.
+
+Use with care.
+
+License: MIT
+"#;
+
+#[test]
+fn strip_html() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/html",
+        "--sanitize-html",
+        "strip",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED_STRIP)
+        .unwrap();
+}
+
+const EXPECTED_ESCAPE: &str = r#"
+# html
+
+Crate with raw HTML.
+
+&lt;div class="warning"&gt;
+
+Use with &lt;em&gt;care&lt;/em&gt;.
+
+&lt;/div&gt;
+
+License: MIT
+"#;
+
+#[test]
+fn escape_html() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/html",
+        "--sanitize-html",
+        "escape",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED_ESCAPE)
+        .unwrap();
+}
@@ -0,0 +1,60 @@
+use assert_cli::Assert;
+
+#[test]
+fn select_bin_declared_path() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/multiple-bin",
+        "--bin",
+        "first",
+        "--no-title",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is("first binary")
+        .unwrap();
+}
+
+#[test]
+fn select_bin_inferred_path() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/multiple-bin",
+        "--bin",
+        "second",
+        "--no-title",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is("second binary")
+        .unwrap();
+}
+
+#[test]
+fn select_bin_not_found() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/multiple-bin",
+        "--bin",
+        "third",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .is("Error: No binary target named `third` found")
+        .unwrap();
+}
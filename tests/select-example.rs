@@ -0,0 +1,55 @@
+use assert_cli::Assert;
+
+#[test]
+fn select_example() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-template",
+        "--no-badges",
+        "--example",
+        "basic",
+    ];
+
+    let expected = r#"
+# readme-test
+
+Basic example for cargo-readme
+
+## Running
+
+```sh
+cargo run --example basic
+```
+
+License: MIT
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn select_example_not_found() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--example",
+        "missing",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .is("Error: No example target named `missing` found")
+        .unwrap();
+}
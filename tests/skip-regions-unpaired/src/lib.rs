@@ -0,0 +1,6 @@
+//! Parse things.
+//!
+//! <!-- cargo-readme skip-start -->
+//! See the [`parser`] module for the details.
+
+pub mod parser {}
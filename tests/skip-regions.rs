@@ -0,0 +1,41 @@
+use assert_cli::Assert;
+
+#[test]
+fn skip_regions() {
+    let args = ["readme", "--project-root", "tests/skip-regions"];
+
+    let expected = r#"
+# skip-regions
+
+Parse things.
+
+## Example
+
+```rust
+// <!-- cargo-readme skip-start -->
+```
+
+License: MIT
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn unpaired_skip_marker() {
+    let args = ["readme", "--project-root", "tests/skip-regions-unpaired"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("Missing `<!-- cargo-readme skip-end -->` marker")
+        .unwrap();
+}
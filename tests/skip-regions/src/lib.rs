@@ -0,0 +1,13 @@
+//! Parse things.
+//!
+//! <!-- cargo-readme skip-start -->
+//! See the [`parser`] module for the details.
+//! <!-- cargo-readme skip-end -->
+//!
+//! # Example
+//!
+//! ```
+//! // <!-- cargo-readme skip-start -->
+//! ```
+
+pub mod parser {}
@@ -0,0 +1,43 @@
+use assert_cli::Assert;
+
+#[test]
+fn smart_punctuation() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/smart-punctuation",
+        "--no-license",
+        "--smart-punctuation",
+    ];
+
+    let expected = r#"
+# quotes
+
+The “best” crate – it’s fast… and it’s safe — see `"strings"`.
+
+```rust
+let s = "it's --- code...";
+```
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn straight_punctuation_by_default() {
+    let args = ["readme", "--project-root", "tests/smart-punctuation"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains(r#"The "best" crate -- it's fast..."#)
+        .unwrap();
+}
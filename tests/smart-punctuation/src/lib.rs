@@ -0,0 +1,5 @@
+//! The "best" crate -- it's fast... and it's safe --- see `"strings"`.
+//!
+//! ```
+//! let s = "it's --- code...";
+//! ```
@@ -0,0 +1,35 @@
+use assert_cli::Assert;
+
+const EXPECTED: &str = "\
+Doc lines:   11
+Code blocks: 2 (1 ignored)
+Headings:    1
+Links:       1
+Words:       7
+";
+
+#[test]
+fn stats() {
+    let args = ["readme", "--project-root", "tests/stats", "stats"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(EXPECTED)
+        .unwrap();
+}
+
+#[test]
+fn stats_all_bins_fails() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/stats",
+        "--all-bins",
+        "stats",
+    ];
+
+    Assert::main_binary().with_args(&args).fails().unwrap();
+}
@@ -0,0 +1,11 @@
+//! Count the docs, see [the guide](docs/guide.md).
+//!
+//! # Examples
+//!
+//! ```
+//! let a = 1;
+//! ```
+//!
+//! ```ignore
+//! let b = unknown();
+//! ```
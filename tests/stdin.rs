@@ -0,0 +1,101 @@
+use assert_cli::Assert;
+
+const SOURCE: &str = "//! Docs read from stdin.
+//!
+//! # Usage
+//!
+//! ```
+//! let stdin = true;
+//! ```
+fn main() {}
+";
+
+#[test]
+fn stdin_input() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-template",
+        "--no-badges",
+        "--input",
+        "-",
+    ];
+
+    let expected = r#"
+# readme-test
+
+Docs read from stdin.
+
+## Usage
+
+```rust
+let stdin = true;
+```
+
+License: MIT
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .stdin(SOURCE)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn stdin_input_without_cargo_toml() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests",
+        "--input",
+        "-",
+        "--no-cargo-toml",
+        "--crate-name",
+        "buffer",
+    ];
+
+    let expected = r#"
+# buffer
+
+Docs read from stdin.
+
+## Usage
+
+```rust
+let stdin = true;
+```
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .stdin(SOURCE)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn stdin_input_not_first() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--input",
+        "src/lib.rs,-",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .is("Error: Only the first `--input` can be `-`, to read from stdin")
+        .unwrap();
+}
@@ -0,0 +1,50 @@
+use assert_cli::Assert;
+use std::path::Path;
+
+#[test]
+fn explicit_stdout() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-template",
+        "--no-badges",
+        "--input",
+        "src/single_line.rs",
+        "--output",
+        "-",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is("# readme-test\n\nTest crate for cargo-readme\n\nLicense: MIT")
+        .and()
+        .stderr()
+        .is("")
+        .unwrap();
+
+    assert!(!Path::new("tests/test-project/-").exists());
+}
+
+#[test]
+fn explicit_stdout_in_place() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--in-place",
+        "--output",
+        "-",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .is("Error: `--in-place` cannot update stdout")
+        .unwrap();
+}
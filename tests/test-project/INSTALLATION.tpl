@@ -0,0 +1,7 @@
+# {{crate}}
+
+## Installation
+
+{{installation}}
+
+{{readme}}
@@ -0,0 +1,6 @@
+# {{crate}}
+
+{{keywords}}
+{{categories}}
+
+{{readme}}
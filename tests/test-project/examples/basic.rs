@@ -0,0 +1,9 @@
+//! Basic example for cargo-readme
+//!
+//! # Running
+//!
+//! ```sh
+//! cargo run --example basic
+//! ```
+
+fn main() {}
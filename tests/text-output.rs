@@ -0,0 +1,42 @@
+use assert_cli::Assert;
+
+#[test]
+fn text_output() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--output-format",
+        "text",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is("check\n\nCheck the README.\n\nThe docs changed.\n\nLicense: MIT\n")
+        .unwrap();
+}
+
+#[test]
+fn text_output_code_blocks() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--output-format",
+        "text",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains("Level 1 heading should become level 2\n\n    // This is standard doc test")
+        .and()
+        .stdout()
+        .doesnt_contain("```python")
+        .unwrap();
+}
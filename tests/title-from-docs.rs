@@ -0,0 +1,80 @@
+use assert_cli::Assert;
+
+#[test]
+fn title_from_docs() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/title-from-docs",
+        "--title-from-docs",
+    ];
+
+    let expected = r#"
+# Title From Docs
+
+A crate whose docs start with a title.
+
+## Usage
+
+Use it.
+
+License: MIT
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn title_from_docs_without_title() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--no-template",
+        "--no-badges",
+        "--input",
+        "src/single_line.rs",
+        "--title-from-docs",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is("# readme-test\n\nTest crate for cargo-readme\n\nLicense: MIT")
+        .unwrap();
+}
+
+#[test]
+fn crate_name_title() {
+    let args = ["readme", "--project-root", "tests/title-from-docs"];
+
+    let expected = r#"
+# title-from-docs
+
+## Title From Docs
+
+A crate whose docs start with a title.
+
+## Usage
+
+Use it.
+
+License: MIT
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
@@ -0,0 +1,7 @@
+//! # Title From Docs
+//!
+//! A crate whose docs start with a title.
+//!
+//! # Usage
+//!
+//! Use it.
@@ -0,0 +1,116 @@
+use assert_cli::Assert;
+use std::fs;
+
+const TOC: &str = "\
+- [Getting started](#getting-started)
+  - [Installing the `tool`](#installing-the-tool)
+- [FAQ](#faq)
+  - [What's new?](#whats-new)
+";
+
+#[test]
+fn toc_stdout() {
+    let args = ["readme", "toc", "tests/toc/GUIDE.orig.md"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(TOC)
+        .unwrap();
+}
+
+#[test]
+fn toc_levels() {
+    let args = [
+        "readme",
+        "toc",
+        "tests/toc/GUIDE.orig.md",
+        "--min-level",
+        "3",
+        "--max-level",
+        "4",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is("\
+- [Installing the `tool`](#installing-the-tool)
+  - [Too deep](#too-deep)
+- [What's new?](#whats-new)
+")
+        .unwrap();
+}
+
+#[test]
+fn toc_in_place() {
+    fs::copy("tests/toc/GUIDE.orig.md", "tests/toc/GUIDE.md").unwrap();
+
+    let args = ["readme", "toc", "tests/toc/GUIDE.md", "--in-place"];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let expected = fs::read_to_string("tests/toc/GUIDE.orig.md")
+        .unwrap()
+        .replacen(
+            "<!-- cargo-readme toc start -->\n",
+            &format!("<!-- cargo-readme toc start -->\n\n{}\n", TOC),
+            1,
+        );
+    assert_eq!(fs::read_to_string("tests/toc/GUIDE.md").unwrap(), expected);
+
+    // updating again gives the same result
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+    assert_eq!(fs::read_to_string("tests/toc/GUIDE.md").unwrap(), expected);
+}
+
+#[test]
+fn toc_in_place_without_markers() {
+    let args = ["readme", "toc", "tests/check/README.md", "--in-place"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("Missing `<!-- cargo-readme toc start -->` marker")
+        .unwrap();
+}
+
+#[test]
+fn toc_anchor_styles() {
+    for (style, toc) in [
+        (
+            "github",
+            "- [Build & test](#build--test)\n- [Über uns](#über-uns)\n",
+        ),
+        (
+            "gitlab",
+            "- [Build & test](#build-test)\n- [Über uns](#über-uns)\n",
+        ),
+        (
+            "rustdoc",
+            "- [Build & test](#build--test)\n- [Über uns](#Über-uns)\n",
+        ),
+    ] {
+        let args = [
+            "readme",
+            "toc",
+            "tests/toc/ANCHORS.md",
+            "--anchor-style",
+            style,
+        ];
+
+        Assert::main_binary()
+            .with_args(&args)
+            .succeeds()
+            .and()
+            .stdout()
+            .is(toc)
+            .unwrap();
+    }
+}
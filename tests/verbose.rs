@@ -0,0 +1,66 @@
+use assert_cli::Assert;
+
+#[test]
+fn verbose_reports_files() {
+    let args = ["readme", "--project-root", "tests/check", "-v"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stderr()
+        .contains("/tests/check/src/lib.rs'")
+        .and()
+        .stderr()
+        .contains("Info: Reading '")
+        .unwrap();
+}
+
+#[test]
+fn verbose_does_not_report_transformations() {
+    let args = ["readme", "--project-root", "tests/check", "-v"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stderr()
+        .doesnt_contain("Trace:")
+        .unwrap();
+}
+
+#[test]
+fn very_verbose_reports_transformations() {
+    let args = ["readme", "--project-root", "tests/check", "-vv"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stderr()
+        .contains("Trace: Found 3 lines of docs")
+        .and()
+        .stderr()
+        .contains("Trace: No template found at '")
+        .unwrap();
+}
+
+#[test]
+fn quiet_by_default() {
+    let args = ["readme", "--project-root", "tests/check"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stderr()
+        .is("")
+        .unwrap();
+}
+
+#[test]
+fn quiet_conflicts_with_verbose() {
+    let args = ["readme", "--project-root", "tests/check", "-q", "-v"];
+
+    Assert::main_binary().with_args(&args).fails().unwrap();
+}
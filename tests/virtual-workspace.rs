@@ -0,0 +1,52 @@
+use assert_cli::Assert;
+
+#[test]
+fn virtual_workspace_fail() {
+    let args = ["readme", "--project-root", "tests/virtual-workspace"];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("is a virtual manifest, use `--workspace` to generate the README of every member or `--package NAME` to select one")
+        .unwrap();
+}
+
+#[test]
+fn virtual_workspace_select_package() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/virtual-workspace",
+        "--package",
+        "second",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is("# second\n\nsecond member")
+        .unwrap();
+}
+
+#[test]
+fn virtual_workspace_package_not_found() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/virtual-workspace",
+        "-p",
+        "third",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .is("Error: No package named `third` found in the workspace")
+        .unwrap();
+}
@@ -0,0 +1 @@
+//! first member
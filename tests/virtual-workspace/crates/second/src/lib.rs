@@ -0,0 +1 @@
+//! second member
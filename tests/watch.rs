@@ -0,0 +1,34 @@
+use assert_cli::Assert;
+
+#[test]
+fn watch_stdin() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--watch",
+        "--input",
+        "-",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("`--watch` cannot read the source from stdin")
+        .unwrap();
+}
+
+#[test]
+fn watch_conflicts_with_check() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/check",
+        "--watch",
+        "--check",
+    ];
+
+    Assert::main_binary().with_args(&args).fails().unwrap();
+}
@@ -0,0 +1,63 @@
+use assert_cli::Assert;
+
+#[test]
+fn collapse_blank_lines() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/whitespace",
+        "--collapse-blank-lines",
+    ];
+
+    let expected = r#"
+# whitespace
+
+Line with a hard
+break.
+
+```rust
+let a = 1;
+
+
+let b = 2;
+```
+
+License: MIT
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn strip_trailing_whitespace() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/whitespace",
+        "--template",
+        "WHITESPACE.tpl",
+        "--strip-trailing-whitespace",
+    ];
+
+    let expected = r#"# whitespace
+Hard line\
+break.
+
+Line with a hard
+break.
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .contains(expected)
+        .unwrap();
+}
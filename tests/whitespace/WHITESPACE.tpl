@@ -0,0 +1,5 @@
+# {{crate}} 
+Hard line  
+break.  
+
+{{readme}}
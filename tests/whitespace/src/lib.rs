@@ -0,0 +1,11 @@
+//! Line with a hard  
+//! break.   
+//!
+//!
+//!
+//! ```
+//! let a = 1;  
+//!
+//!
+//! let b = 2;
+//! ```
@@ -0,0 +1 @@
+//! An internal tool.
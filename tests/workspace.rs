@@ -0,0 +1,72 @@
+use assert_cli::Assert;
+use std::fs;
+
+#[test]
+fn workspace_unpublished_template() {
+    let _ = fs::remove_file("tests/workspace/crates/public/README.md");
+    let _ = fs::remove_file("tests/workspace/crates/internal/README.md");
+
+    let args = ["readme", "--project-root", "tests/workspace", "--workspace"];
+
+    Assert::main_binary().with_args(&args).succeeds().unwrap();
+
+    let public = fs::read_to_string("tests/workspace/crates/public/README.md").unwrap();
+    assert_eq!(
+        "<!-- generated by cargo-readme -->\n# public\n\nA published crate.\n\nLicense: MIT\n",
+        public
+    );
+
+    let internal = fs::read_to_string("tests/workspace/crates/internal/README.md").unwrap();
+    assert_eq!(
+        "<!-- generated by cargo-readme -->\n# internal (internal)\n\nAn internal tool.\n",
+        internal
+    );
+}
+
+#[test]
+fn workspace_skip_unpublished() {
+    let internal_readme = "tests/workspace-skip/crates/internal/README.md";
+    let _ = fs::remove_file(internal_readme);
+    let _ = fs::remove_file("tests/workspace-skip/crates/public/README.md");
+
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/workspace-skip",
+        "--workspace",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stderr()
+        .is("Skipping `internal`: crate is not published")
+        .unwrap();
+
+    let public = fs::read_to_string("tests/workspace-skip/crates/public/README.md").unwrap();
+    assert_eq!(
+        "<!-- generated by cargo-readme -->\n# public\n\nA published crate.\n\nLicense: MIT\n",
+        public
+    );
+
+    assert!(fs::metadata(internal_readme).is_err());
+}
+
+#[test]
+fn workspace_not_a_workspace() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--workspace",
+    ];
+
+    Assert::main_binary()
+        .with_args(&args)
+        .fails()
+        .and()
+        .stderr()
+        .contains("is not a workspace root")
+        .unwrap();
+}
@@ -0,0 +1 @@
+//! A published crate.
@@ -0,0 +1,46 @@
+use assert_cli::Assert;
+
+#[test]
+fn wrap_paragraphs() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/wrap",
+        "--no-license",
+        "--wrap",
+        "40",
+    ];
+
+    let expected = r#"
+# wrap
+
+A crate whose docs are written with very
+long lines, which the style guide of the
+project asks to wrap.
+
+- A list item that is also too long to
+  fit on a single line of forty columns.
+
+```rust
+let a_line_of_code_that_is_long_and_is_never_wrapped = "because it is code, not prose";
+```
+
+| Option | Description that is too long but is in a table |
+| ------ | ----------------------------------------------- |
+"#;
+
+    Assert::main_binary()
+        .with_args(&args)
+        .succeeds()
+        .and()
+        .stdout()
+        .is(expected)
+        .unwrap();
+}
+
+#[test]
+fn wrap_needs_a_width() {
+    let args = ["readme", "--project-root", "tests/wrap", "--wrap", "0"];
+
+    Assert::main_binary().with_args(&args).fails().unwrap();
+}
@@ -0,0 +1,10 @@
+//! A crate whose docs are written with very long lines, which the style guide of the project asks to wrap.
+//!
+//! - A list item that is also too long to fit on a single line of forty columns.
+//!
+//! ```
+//! let a_line_of_code_that_is_long_and_is_never_wrapped = "because it is code, not prose";
+//! ```
+//!
+//! | Option | Description that is too long but is in a table |
+//! | ------ | ----------------------------------------------- |